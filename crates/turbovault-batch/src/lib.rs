@@ -62,6 +62,7 @@
 //! [`BatchExecutor`] manages batch execution with:
 //! - Validation before execution
 //! - Conflict detection between operations
+//! - Note-schema checks for created and written notes
 //! - Atomic execution with proper sequencing
 //! - Transaction ID tracking
 //! - Detailed result reporting
@@ -114,6 +115,8 @@
 //!
 //! Errors stop batch execution:
 //! - Validation errors prevent any execution
+//! - Note-schema violations prevent execution in `reject` mode and are
+//!   reported in [`BatchResult::warnings`] in `warn` mode
 //! - Operation errors stop the batch
 //! - Previous operations are recorded but not rolled back
 //! - Error details provided in result
@@ -132,7 +135,7 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Arc;
 use turbovault_core::prelude::*;
-use turbovault_core::{PathValidator, TransactionBuilder};
+use turbovault_core::{PathValidator, SchemaMode, TransactionBuilder};
 use turbovault_vault::VaultManager;
 
 /// Individual batch operation to execute
@@ -235,6 +238,9 @@ pub struct BatchResult {
     pub changes: Vec<String>,
     /// Errors encountered
     pub errors: Vec<String>,
+    /// Note-schema violations that did not block the batch (warn mode)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
     /// Execution records for each operation
     pub records: Vec<OperationRecord>,
     /// Unique transaction ID
@@ -258,6 +264,11 @@ impl BatchExecutor {
 
     /// Validate batch operations before execution
     pub async fn validate(&self, ops: &[BatchOperation]) -> Result<()> {
+        self.validate_with_warnings(ops).await.map(|_| ())
+    }
+
+    /// Validate operations, returning non-blocking note-schema warnings
+    async fn validate_with_warnings(&self, ops: &[BatchOperation]) -> Result<Vec<String>> {
         if ops.is_empty() {
             return Err(Error::config_error("Batch cannot be empty".to_string()));
         }
@@ -274,7 +285,53 @@ impl BatchExecutor {
             }
         }
 
-        Ok(())
+        self.check_note_schemas(ops).await
+    }
+
+    /// Check written notes against the vault's note-type schemas.
+    ///
+    /// In reject mode any violation fails the whole batch before anything is
+    /// written; in warn mode violations are returned as warnings.
+    async fn check_note_schemas(&self, ops: &[BatchOperation]) -> Result<Vec<String>> {
+        let Some(schemas) = self.manager.note_schemas().await else {
+            return Ok(Vec::new());
+        };
+
+        let mut warnings = Vec::new();
+        for (idx, op) in ops.iter().enumerate() {
+            let (BatchOperation::CreateNote { path, content }
+            | BatchOperation::WriteNote { path, content }) = op
+            else {
+                continue;
+            };
+
+            let report = self
+                .manager
+                .validate_note_schema(std::path::Path::new(path), content)
+                .await?;
+            let violations: Vec<&str> = report
+                .issues
+                .iter()
+                .filter(|i| i.severity.is_failure())
+                .map(|i| i.message.as_str())
+                .collect();
+            if violations.is_empty() {
+                continue;
+            }
+
+            let message = format!(
+                "Operation {} ({}) violates its note schema: {}",
+                idx,
+                path,
+                violations.join("; ")
+            );
+            if schemas.mode == SchemaMode::Reject {
+                return Err(Error::validation_error(message));
+            }
+            warnings.push(message);
+        }
+
+        Ok(warnings)
     }
 
     /// Execute batch operations atomically
@@ -282,19 +339,23 @@ impl BatchExecutor {
         let transaction = TransactionBuilder::new();
 
         // 1. Validate
-        if let Err(e) = self.validate(&ops).await {
-            return Ok(BatchResult {
-                success: false,
-                executed: 0,
-                total: ops.len(),
-                failed_at: None,
-                changes: vec![],
-                errors: vec![e.to_string()],
-                records: vec![],
-                transaction_id: transaction.transaction_id().to_string(),
-                duration_ms: transaction.elapsed_ms(),
-            });
-        }
+        let warnings = match self.validate_with_warnings(&ops).await {
+            Ok(warnings) => warnings,
+            Err(e) => {
+                return Ok(BatchResult {
+                    success: false,
+                    executed: 0,
+                    total: ops.len(),
+                    failed_at: None,
+                    changes: vec![],
+                    errors: vec![e.to_string()],
+                    warnings: vec![],
+                    records: vec![],
+                    transaction_id: transaction.transaction_id().to_string(),
+                    duration_ms: transaction.elapsed_ms(),
+                });
+            }
+        };
 
         let mut changes = Vec::new();
         let mut records = Vec::new();
@@ -335,6 +396,7 @@ impl BatchExecutor {
                         failed_at: Some(idx),
                         changes,
                         errors,
+                        warnings,
                         records,
                         transaction_id: transaction.transaction_id().to_string(),
                         duration_ms: transaction.elapsed_ms(),
@@ -351,6 +413,7 @@ impl BatchExecutor {
             failed_at: None,
             changes,
             errors,
+            warnings,
             records,
             transaction_id: transaction.transaction_id().to_string(),
             duration_ms: transaction.elapsed_ms(),
@@ -381,6 +444,7 @@ impl BatchExecutor {
                 tokio::fs::remove_file(&full_path).await.map_err(|e| {
                     Error::config_error(format!("Failed to delete {}: {}", path, e))
                 })?;
+                self.manager.forget_file(&full_path).await?;
 
                 Ok(format!("Deleted: {}", path))
             }
@@ -409,6 +473,7 @@ impl BatchExecutor {
                 tokio::fs::rename(&from_path, &to_path).await.map_err(|e| {
                     Error::config_error(format!("Failed to move {} to {}: {}", from, to, e))
                 })?;
+                self.manager.forget_file(&from_path).await?;
 
                 Ok(format!("Moved: {} → {}", from, to))
             }
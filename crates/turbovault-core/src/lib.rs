@@ -17,6 +17,7 @@
 //! - [`error`] - Comprehensive error types and Result aliases
 //! - [`config`] - Server and vault configuration structures
 //! - [`validation`] - Content validation framework
+//! - [`schema`] - Note-type schemas for frontmatter validation
 //! - [`metrics`] - Performance monitoring and statistics
 //! - [`multi_vault`] - Multi-vault management support
 //! - [`profiles`] - Configuration profiles for different environments
@@ -82,6 +83,7 @@ pub mod models;
 pub mod multi_vault;
 pub mod profiles;
 pub mod resilience;
+pub mod schema;
 pub mod utils;
 pub mod validation;
 
@@ -91,6 +93,10 @@ pub use metrics::{Counter, Histogram, HistogramStats, HistogramTimer, MetricsCon
 pub use models::*;
 pub use multi_vault::{MultiVaultManager, VaultInfo};
 pub use profiles::ConfigProfile;
+pub use schema::{
+    NOTE_SCHEMA_FILE, NoteSchemas, NoteTypeSchema, PropertyRule, PropertyType, SchemaMode,
+    SchemaValidator,
+};
 pub use utils::{CSVBuilder, PathValidator, TransactionBuilder, to_json_string};
 pub use validation::{
     CompositeValidator, ContentValidator, FrontmatterValidator, LinkValidator, Severity,
@@ -109,6 +115,7 @@ pub mod prelude {
     };
     pub use crate::multi_vault::{MultiVaultManager, VaultInfo};
     pub use crate::profiles::ConfigProfile;
+    pub use crate::schema::{NoteSchemas, SchemaMode};
     pub use crate::validation::{
         CompositeValidator, ContentValidator, FrontmatterValidator, LinkValidator, Severity,
         ValidationIssue, ValidationReport, Validator,
@@ -76,6 +76,11 @@ impl SourcePosition {
             length,
         }
     }
+
+    /// Byte range covered, from `offset` for `length` bytes
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.offset..self.offset + self.length
+    }
 }
 
 /// Pre-computed line starts for O(log n) line/column lookup.
@@ -141,6 +146,29 @@ impl LineIndex {
     pub fn line_count(&self) -> usize {
         self.line_starts.len()
     }
+
+    /// Update the index for `range` of the content being replaced by
+    /// `new_text`, without rescanning the rest of the document.
+    ///
+    /// Line starts after the edit are shifted; only `new_text` is scanned.
+    pub fn apply_edit(&mut self, range: std::ops::Range<usize>, new_text: &str) {
+        let first = self
+            .line_starts
+            .partition_point(|&start| start <= range.start);
+        let last = self
+            .line_starts
+            .partition_point(|&start| start <= range.end);
+        let inserted = new_text
+            .match_indices('\n')
+            .map(|(i, _)| range.start + i + 1);
+        let shifted: Vec<usize> = self.line_starts[last..]
+            .iter()
+            .map(|&start| start - range.end + range.start + new_text.len())
+            .collect();
+        self.line_starts.truncate(first);
+        self.line_starts.extend(inserted);
+        self.line_starts.extend(shifted);
+    }
 }
 
 /// Type of link in Obsidian content
@@ -221,12 +249,20 @@ pub struct TaskItem {
 }
 
 /// Type of callout block
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Obsidian's built-in aliases map to their canonical type (`summary` and
+/// `tldr` are [`CalloutType::Abstract`], `hint` is [`CalloutType::Tip`], ...).
+/// Any other identifier, such as a CSS-snippet `[!decision]`, is kept as
+/// [`CalloutType::Custom`] with its lowercased name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CalloutType {
     Note,
+    Abstract,
     Tip,
     Info,
     Todo,
+    /// Never produced by the parser; `[!important]` is an alias of [`CalloutType::Tip`]
+    #[deprecated(since = "1.2.6", note = "`important` is parsed as CalloutType::Tip")]
     Important,
     Success,
     Question,
@@ -236,16 +272,120 @@ pub enum CalloutType {
     Bug,
     Example,
     Quote,
+    Custom(String),
+}
+
+impl CalloutType {
+    /// Resolve a callout identifier (case-insensitive) to its type
+    pub fn from_identifier(identifier: &str) -> Self {
+        let identifier = identifier.trim().to_lowercase();
+        match identifier.as_str() {
+            "note" => Self::Note,
+            "abstract" | "summary" | "tldr" => Self::Abstract,
+            "tip" | "hint" | "important" => Self::Tip,
+            "info" => Self::Info,
+            "todo" => Self::Todo,
+            "success" | "check" | "done" => Self::Success,
+            "question" | "help" | "faq" => Self::Question,
+            "warning" | "caution" | "attention" => Self::Warning,
+            "failure" | "fail" | "missing" => Self::Failure,
+            "danger" | "error" => Self::Danger,
+            "bug" => Self::Bug,
+            "example" => Self::Example,
+            "quote" | "cite" => Self::Quote,
+            _ => Self::Custom(identifier),
+        }
+    }
+
+    /// Canonical identifier (`abstract` for `[!tldr]`, the name itself for custom types)
+    #[allow(deprecated)]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Note => "note",
+            Self::Abstract => "abstract",
+            Self::Tip => "tip",
+            Self::Info => "info",
+            Self::Todo => "todo",
+            Self::Important => "important",
+            Self::Success => "success",
+            Self::Question => "question",
+            Self::Warning => "warning",
+            Self::Failure => "failure",
+            Self::Danger => "danger",
+            Self::Bug => "bug",
+            Self::Example => "example",
+            Self::Quote => "quote",
+            Self::Custom(name) => name,
+        }
+    }
+
+    /// Whether this is a vault-specific type rather than an Obsidian built-in
+    pub fn is_custom(&self) -> bool {
+        matches!(self, Self::Custom(_))
+    }
+}
+
+impl std::fmt::Display for CalloutType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// A callout block in vault content
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Callout {
     pub type_: CalloutType,
+    /// Identifier as written, e.g. `TLDR` in `> [!TLDR]`
+    #[serde(default)]
+    pub identifier: String,
     pub title: Option<String>,
     pub content: String,
     pub position: SourcePosition,
     pub is_foldable: bool,
+    /// Collapsed by default (`-` fold marker)
+    #[serde(default)]
+    pub is_collapsed: bool,
+    /// Blockquote nesting level: 1 for `> [!note]`, 2 for `> > [!note]`
+    #[serde(default)]
+    pub depth: usize,
+}
+
+/// An Obsidian comment (`%%hidden%%`), only visible in editing view
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub content: String,
+    pub position: SourcePosition,
+    pub is_block: bool, // spans lines
+}
+
+/// Highlighted text (`==text==`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Highlight {
+    pub text: String,
+    pub position: SourcePosition,
+}
+
+/// A footnote reference (`[^label]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FootnoteReference {
+    pub label: String,
+    pub position: SourcePosition,
+}
+
+/// A footnote definition (`[^label]: text`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FootnoteDefinition {
+    pub label: String,
+    pub content: String,
+    pub position: SourcePosition,
+}
+
+/// A LaTeX math expression (`$inline$` or `$$display$$`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Math {
+    pub expression: String,
+    pub position: SourcePosition,
+    pub is_display: bool,
 }
 
 /// A block in vault content (Obsidian block reference with ^id)
@@ -274,11 +414,17 @@ pub enum ContentBlock {
         content: String,
         inline: Vec<InlineElement>,
         anchor: Option<String>,
+        /// Where the block is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
     },
     /// A paragraph of text
     Paragraph {
         content: String,
         inline: Vec<InlineElement>,
+        /// Where the block is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
     },
     /// A fenced or indented code block
     Code {
@@ -286,33 +432,80 @@ pub enum ContentBlock {
         content: String,
         start_line: usize,
         end_line: usize,
+        /// Where the block is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
     },
     /// An ordered or unordered list
-    List { ordered: bool, items: Vec<ListItem> },
+    List {
+        ordered: bool,
+        items: Vec<ListItem>,
+        /// Where the block is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
     /// A blockquote (> text)
     Blockquote {
         content: String,
         blocks: Vec<ContentBlock>,
+        /// Where the block is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
     },
     /// A table with headers and rows
     Table {
         headers: Vec<String>,
         alignments: Vec<TableAlignment>,
         rows: Vec<Vec<String>>,
+        /// Where the block is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
     },
     /// An image (standalone, not inline)
     Image {
         alt: String,
         src: String,
         title: Option<String>,
+        /// Where the block is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
     },
     /// A horizontal rule (---, ***, ___)
-    HorizontalRule,
+    HorizontalRule {
+        /// Where the block is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
     /// HTML <details><summary> block
     Details {
         summary: String,
         content: String,
         blocks: Vec<ContentBlock>,
+        /// Where the block is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
+    /// A display math block ($$...$$)
+    Math {
+        content: String,
+        /// Where the block is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
+    /// A footnote definition ([^label]: text)
+    FootnoteDefinition {
+        label: String,
+        blocks: Vec<ContentBlock>,
+        /// Where the block is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
+    /// An Obsidian comment on its own lines (%%...%%)
+    Comment {
+        content: String,
+        /// Where the block is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
     },
 }
 
@@ -334,10 +527,12 @@ impl ContentBlock {
     ///             url: "#overview".to_string(),
     ///             title: None,
     ///             line_offset: None,
+    ///             span: None,
     ///         },
-    ///         InlineElement::Text { value: " and ".to_string() },
-    ///         InlineElement::Strong { value: "bold".to_string() },
+    ///         InlineElement::Text { value: " and ".to_string(), span: None },
+    ///         InlineElement::Strong { value: "bold".to_string(), span: None },
     ///     ],
+    ///     span: None,
     /// };
     /// assert_eq!(block.to_plain_text(), "Overview and bold");
     /// ```
@@ -368,7 +563,7 @@ impl ContentBlock {
                 }
             }
             Self::Image { alt, .. } => alt.clone(),
-            Self::HorizontalRule => String::new(),
+            Self::HorizontalRule { .. } => String::new(),
             Self::Details {
                 summary, blocks, ..
             } => {
@@ -383,8 +578,78 @@ impl ContentBlock {
                     format!("{}\n{}", summary, blocks_text)
                 }
             }
+            Self::Math { content, .. } => content.clone(),
+            Self::FootnoteDefinition { blocks, .. } => blocks
+                .iter()
+                .map(Self::to_plain_text)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Self::Comment { .. } => String::new(),
+        }
+    }
+
+    /// Where this block is in the source, if it was parsed from text
+    pub fn span(&self) -> Option<&SourcePosition> {
+        match self {
+            Self::Heading { span, .. }
+            | Self::Paragraph { span, .. }
+            | Self::Code { span, .. }
+            | Self::List { span, .. }
+            | Self::Blockquote { span, .. }
+            | Self::Table { span, .. }
+            | Self::Image { span, .. }
+            | Self::HorizontalRule { span }
+            | Self::Details { span, .. }
+            | Self::Math { span, .. }
+            | Self::FootnoteDefinition { span, .. }
+            | Self::Comment { span, .. } => span.as_ref(),
         }
     }
+
+    /// Call `f` on the span of this block and of every list item, inline
+    /// element and block nested in it.
+    pub fn for_each_span_mut(&mut self, f: &mut impl FnMut(&mut Option<SourcePosition>)) {
+        match self {
+            Self::Heading { inline, span, .. } | Self::Paragraph { inline, span, .. } => {
+                inline.iter_mut().for_each(|e| f(e.span_mut()));
+                f(span);
+            }
+            Self::List { items, span, .. } => {
+                for item in items {
+                    item.for_each_span_mut(f);
+                }
+                f(span);
+            }
+            Self::Blockquote { blocks, span, .. }
+            | Self::Details { blocks, span, .. }
+            | Self::FootnoteDefinition { blocks, span, .. } => {
+                for block in blocks {
+                    block.for_each_span_mut(f);
+                }
+                f(span);
+            }
+            Self::Code { span, .. }
+            | Self::Table { span, .. }
+            | Self::Image { span, .. }
+            | Self::HorizontalRule { span }
+            | Self::Math { span, .. }
+            | Self::Comment { span, .. } => f(span),
+        }
+    }
+
+    /// Remove all source spans, e.g. to compare blocks parsed from different text.
+    ///
+    /// # Example
+    /// ```
+    /// use turbovault_core::ContentBlock;
+    ///
+    /// let mut block = ContentBlock::HorizontalRule { span: None };
+    /// block.clear_spans();
+    /// assert!(block.span().is_none());
+    /// ```
+    pub fn clear_spans(&mut self) {
+        self.for_each_span_mut(&mut |span| *span = None);
+    }
 }
 
 /// An inline element within a block.
@@ -394,13 +659,33 @@ impl ContentBlock {
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum InlineElement {
     /// Plain text
-    Text { value: String },
+    Text {
+        value: String,
+        /// Where the element is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
     /// Bold text (**text** or __text__)
-    Strong { value: String },
+    Strong {
+        value: String,
+        /// Where the element is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
     /// Italic text (*text* or _text_)
-    Emphasis { value: String },
+    Emphasis {
+        value: String,
+        /// Where the element is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
     /// Inline code (`code`)
-    Code { value: String },
+    Code {
+        value: String,
+        /// Where the element is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
     /// A link [text](url)
     Link {
         text: String,
@@ -409,6 +694,9 @@ pub enum InlineElement {
         /// Relative line offset within parent block (for nested list items)
         #[serde(default, skip_serializing_if = "Option::is_none")]
         line_offset: Option<usize>,
+        /// Where the element is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
     },
     /// An inline image ![alt](src)
     Image {
@@ -418,9 +706,46 @@ pub enum InlineElement {
         /// Relative line offset within parent block (for nested list items)
         #[serde(default, skip_serializing_if = "Option::is_none")]
         line_offset: Option<usize>,
+        /// Where the element is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
     },
     /// Strikethrough text (~~text~~)
-    Strikethrough { value: String },
+    Strikethrough {
+        value: String,
+        /// Where the element is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
+    /// Highlighted text (==text==)
+    Highlight {
+        value: String,
+        /// Where the element is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
+    /// Math expression ($x$, or $$x$$ within a paragraph)
+    Math {
+        value: String,
+        display: bool,
+        /// Where the element is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
+    /// Footnote reference ([^label])
+    FootnoteReference {
+        label: String,
+        /// Where the element is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
+    /// Obsidian comment (%%text%%)
+    Comment {
+        value: String,
+        /// Where the element is in the source
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<SourcePosition>,
+    },
 }
 
 impl InlineElement {
@@ -429,6 +754,7 @@ impl InlineElement {
     /// Returns only the visible text content, stripping markdown syntax.
     /// For links, returns the link text (not the URL).
     /// For images, returns the alt text.
+    /// Math returns its source; footnote references and comments are empty.
     ///
     /// # Example
     /// ```
@@ -439,19 +765,57 @@ impl InlineElement {
     ///     url: "#overview".to_string(),
     ///     title: None,
     ///     line_offset: None,
+    ///     span: None,
     /// };
     /// assert_eq!(link.to_plain_text(), "Overview");
     /// ```
     #[must_use]
     pub fn to_plain_text(&self) -> &str {
         match self {
-            Self::Text { value }
-            | Self::Strong { value }
-            | Self::Emphasis { value }
-            | Self::Code { value }
-            | Self::Strikethrough { value } => value,
+            Self::Text { value, .. }
+            | Self::Strong { value, .. }
+            | Self::Emphasis { value, .. }
+            | Self::Code { value, .. }
+            | Self::Strikethrough { value, .. }
+            | Self::Highlight { value, .. }
+            | Self::Math { value, .. } => value,
             Self::Link { text, .. } => text,
             Self::Image { alt, .. } => alt,
+            Self::FootnoteReference { .. } | Self::Comment { .. } => "",
+        }
+    }
+
+    /// Where this element is in the source, if it was parsed from text
+    pub fn span(&self) -> Option<&SourcePosition> {
+        match self {
+            Self::Text { span, .. }
+            | Self::Strong { span, .. }
+            | Self::Emphasis { span, .. }
+            | Self::Code { span, .. }
+            | Self::Link { span, .. }
+            | Self::Image { span, .. }
+            | Self::Strikethrough { span, .. }
+            | Self::Highlight { span, .. }
+            | Self::Math { span, .. }
+            | Self::FootnoteReference { span, .. }
+            | Self::Comment { span, .. } => span.as_ref(),
+        }
+    }
+
+    /// Mutable access to the span of this element
+    pub fn span_mut(&mut self) -> &mut Option<SourcePosition> {
+        match self {
+            Self::Text { span, .. }
+            | Self::Strong { span, .. }
+            | Self::Emphasis { span, .. }
+            | Self::Code { span, .. }
+            | Self::Link { span, .. }
+            | Self::Image { span, .. }
+            | Self::Strikethrough { span, .. }
+            | Self::Highlight { span, .. }
+            | Self::Math { span, .. }
+            | Self::FootnoteReference { span, .. }
+            | Self::Comment { span, .. } => span,
         }
     }
 }
@@ -468,6 +832,9 @@ pub struct ListItem {
     /// Nested blocks (e.g., code blocks, sub-lists inside list items)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub blocks: Vec<ContentBlock>,
+    /// Where the item is in the source, from its marker to its last nested block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SourcePosition>,
 }
 
 impl ListItem {
@@ -483,8 +850,9 @@ impl ListItem {
     /// let item = ListItem {
     ///     checked: Some(false),
     ///     content: "Todo item".to_string(),
-    ///     inline: vec![InlineElement::Text { value: "Todo item".to_string() }],
+    ///     inline: vec![InlineElement::Text { value: "Todo item".to_string(), span: None }],
     ///     blocks: vec![],
+    ///     span: None,
     /// };
     /// assert_eq!(item.to_plain_text(), "Todo item");
     /// ```
@@ -507,6 +875,15 @@ impl ListItem {
 
         result
     }
+
+    /// Call `f` on the span of this item and of everything nested in it.
+    pub fn for_each_span_mut(&mut self, f: &mut impl FnMut(&mut Option<SourcePosition>)) {
+        self.inline.iter_mut().for_each(|e| f(e.span_mut()));
+        for block in &mut self.blocks {
+            block.for_each_span_mut(f);
+        }
+        f(&mut self.span);
+    }
 }
 
 /// Table column alignment.
@@ -716,6 +1093,24 @@ mod tests {
         assert_eq!(index.line_start(4), None); // Beyond content
     }
 
+    #[test]
+    fn test_line_index_apply_edit() {
+        let edits: [(&str, std::ops::Range<usize>, &str); 5] = [
+            ("Line 1\nLine 2\nLine 3", 7..13, "Two\nlines"),
+            ("Line 1\nLine 2\nLine 3", 5..15, ""),
+            ("Line 1\nLine 2\nLine 3", 0..0, "\n\n"),
+            ("Line 1\nLine 2\nLine 3", 20..20, "\nLine 4\n"),
+            ("a\nb\n", 1..2, ""),
+        ];
+        for (content, range, text) in edits {
+            let mut index = LineIndex::new(content);
+            index.apply_edit(range.clone(), text);
+            let mut edited = content.to_string();
+            edited.replace_range(range, text);
+            assert_eq!(index.line_starts, LineIndex::new(&edited).line_starts);
+        }
+    }
+
     #[test]
     fn test_source_position_from_offset() {
         let content = "Line 1\nLine 2 [[Link]] here\nLine 3";
@@ -768,4 +1163,72 @@ mod tests {
         assert_eq!(index.line_col(6), (1, 7)); // The newline itself
         assert_eq!(index.line_col(7), (2, 1)); // After newline
     }
+
+    #[test]
+    fn test_source_position_range() {
+        let content = "Line 1\nLine 2";
+        let index = LineIndex::new(content);
+        let position = SourcePosition::from_offset_indexed(&index, 3, 7);
+        assert_eq!((position.line, position.column), (1, 4));
+        assert_eq!(position.range(), 3..10);
+        assert_eq!(&content[position.range()], "e 1\nLin");
+        assert_eq!(index.line_col(position.range().end), (2, 4));
+    }
+
+    #[test]
+    fn test_content_block_span_is_optional_in_serde() {
+        let rule: ContentBlock = serde_json::from_str(r#"{"type":"horizontalrule"}"#).unwrap();
+        assert_eq!(rule, ContentBlock::HorizontalRule { span: None });
+
+        let paragraph = ContentBlock::Paragraph {
+            content: "Hi".to_string(),
+            inline: vec![InlineElement::Text {
+                value: "Hi".to_string(),
+                span: None,
+            }],
+            span: None,
+        };
+        let json = serde_json::to_string(&paragraph).unwrap();
+        assert!(!json.contains("span"));
+        assert_eq!(
+            serde_json::from_str::<ContentBlock>(&json).unwrap(),
+            paragraph
+        );
+    }
+
+    #[test]
+    fn test_callout_type_aliases() {
+        assert_eq!(CalloutType::from_identifier("TLDR"), CalloutType::Abstract);
+        assert_eq!(
+            CalloutType::from_identifier("summary"),
+            CalloutType::Abstract
+        );
+        assert_eq!(CalloutType::from_identifier("hint"), CalloutType::Tip);
+        assert_eq!(CalloutType::from_identifier("important"), CalloutType::Tip);
+        assert_eq!(CalloutType::from_identifier("faq"), CalloutType::Question);
+        assert_eq!(CalloutType::from_identifier("cite"), CalloutType::Quote);
+        assert_eq!(CalloutType::from_identifier("done"), CalloutType::Success);
+        assert_eq!(
+            CalloutType::from_identifier("caution"),
+            CalloutType::Warning
+        );
+    }
+
+    #[test]
+    fn test_callout_deserializes_without_new_fields() {
+        let json = r#"{"type_":"Note","title":null,"content":"x","position":{"line":1,"column":1,"offset":0,"length":9},"is_foldable":false}"#;
+        let callout: Callout = serde_json::from_str(json).unwrap();
+        assert_eq!(callout.identifier, "");
+        assert!(!callout.is_collapsed);
+        assert_eq!(callout.depth, 0);
+    }
+
+    #[test]
+    fn test_callout_type_custom() {
+        let type_ = CalloutType::from_identifier("Decision");
+        assert_eq!(type_, CalloutType::Custom("decision".to_string()));
+        assert!(type_.is_custom());
+        assert_eq!(type_.as_str(), "decision");
+        assert_eq!(CalloutType::Abstract.to_string(), "abstract");
+    }
 }
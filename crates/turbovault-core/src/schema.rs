@@ -0,0 +1,660 @@
+//! Note-type schemas for frontmatter validation.
+//!
+//! A vault can declare note types in [`NOTE_SCHEMA_FILE`]. Each type lists
+//! required and optional properties with an expected type, allowed values
+//! and/or a regex pattern. A note belongs to a type through its `type`
+//! property (configurable) or, failing that, through the folder it lives in.
+//!
+//! ```yaml
+//! mode: reject          # or `warn` (default)
+//! type_property: type   # frontmatter key naming the note type
+//! types:
+//!   meeting:
+//!     folders: [Meetings]
+//!     required:
+//!       date: date
+//!       attendees: list
+//!     optional:
+//!       status:
+//!         type: text
+//!         enum: [draft, final]
+//!       ticket:
+//!         pattern: "^[A-Z]+-[0-9]+$"
+//!     strict: true      # warn about properties not listed above
+//! ```
+
+use crate::error::{Error, Result};
+use crate::models::VaultFile;
+use crate::validation::{Severity, ValidationIssue, ValidationReport, Validator};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Location of the schema file, relative to the vault root
+pub const NOTE_SCHEMA_FILE: &str = ".turbovault/schemas.yaml";
+
+/// What happens when a write violates a schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaMode {
+    /// Write the note and report the violations
+    #[default]
+    Warn,
+    /// Refuse the write
+    Reject,
+}
+
+/// Expected type of a property value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PropertyType {
+    #[serde(alias = "string")]
+    Text,
+    Number,
+    #[serde(alias = "checkbox", alias = "bool")]
+    Boolean,
+    /// `YYYY-MM-DD`
+    Date,
+    /// Date with optional time (`YYYY-MM-DDTHH:MM[:SS]`)
+    DateTime,
+    #[serde(
+        alias = "multitext",
+        alias = "tags",
+        alias = "aliases",
+        alias = "array"
+    )]
+    List,
+    Object,
+    Any,
+}
+
+impl PropertyType {
+    /// Whether a value has this type
+    pub fn accepts(self, value: &Value) -> bool {
+        match self {
+            PropertyType::Text => value.is_string(),
+            PropertyType::Number => value.is_number(),
+            PropertyType::Boolean => value.is_boolean(),
+            PropertyType::Date => value.as_str().is_some_and(is_date),
+            PropertyType::DateTime => value.as_str().is_some_and(|s| is_date(s) || is_datetime(s)),
+            PropertyType::List => value.is_array(),
+            PropertyType::Object => value.is_object(),
+            PropertyType::Any => true,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            PropertyType::Text => "text",
+            PropertyType::Number => "number",
+            PropertyType::Boolean => "boolean",
+            PropertyType::Date => "date",
+            PropertyType::DateTime => "datetime",
+            PropertyType::List => "list",
+            PropertyType::Object => "object",
+            PropertyType::Any => "any",
+        }
+    }
+}
+
+fn is_date(s: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").is_ok()
+}
+
+fn is_datetime(s: &str) -> bool {
+    let s = s.trim();
+    chrono::DateTime::parse_from_rfc3339(s).is_ok()
+        || [
+            "%Y-%m-%dT%H:%M:%S",
+            "%Y-%m-%dT%H:%M",
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%d %H:%M",
+        ]
+        .iter()
+        .any(|fmt| chrono::NaiveDateTime::parse_from_str(s, fmt).is_ok())
+}
+
+/// Constraints on a single property
+#[derive(Debug, Clone)]
+pub struct PropertyRule {
+    pub kind: PropertyType,
+    /// Allowed values (checked per element for lists)
+    pub allowed: Vec<Value>,
+    /// Pattern every scalar value (or list element) must match
+    pub pattern: Option<Regex>,
+}
+
+/// Schema for one note type
+#[derive(Debug, Clone)]
+pub struct NoteTypeSchema {
+    pub name: String,
+    /// Vault-relative folders whose notes default to this type
+    pub folders: Vec<String>,
+    pub required: BTreeMap<String, PropertyRule>,
+    pub optional: BTreeMap<String, PropertyRule>,
+    /// Warn about properties that are neither required nor optional
+    pub strict: bool,
+}
+
+/// All note-type schemas of a vault
+#[derive(Debug, Clone)]
+pub struct NoteSchemas {
+    pub mode: SchemaMode,
+    /// Frontmatter key naming the note type
+    pub type_property: String,
+    pub types: Vec<NoteTypeSchema>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawSchemaFile {
+    #[serde(default)]
+    mode: SchemaMode,
+    #[serde(default = "default_type_property")]
+    type_property: String,
+    #[serde(default)]
+    types: BTreeMap<String, RawNoteType>,
+}
+
+fn default_type_property() -> String {
+    "type".to_string()
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawNoteType {
+    #[serde(default)]
+    folders: Vec<String>,
+    #[serde(default)]
+    required: BTreeMap<String, RawRule>,
+    #[serde(default)]
+    optional: BTreeMap<String, RawRule>,
+    #[serde(default)]
+    strict: bool,
+}
+
+/// A rule is either a bare type name or a map with `type`, `enum` and `pattern`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawRule {
+    Type(PropertyType),
+    Full {
+        #[serde(rename = "type")]
+        kind: Option<PropertyType>,
+        #[serde(rename = "enum", default)]
+        allowed: Vec<Value>,
+        pattern: Option<String>,
+    },
+}
+
+impl RawRule {
+    fn compile(self, type_name: &str, property: &str) -> Result<PropertyRule> {
+        match self {
+            RawRule::Type(kind) => Ok(PropertyRule {
+                kind,
+                allowed: Vec::new(),
+                pattern: None,
+            }),
+            RawRule::Full {
+                kind,
+                allowed,
+                pattern,
+            } => {
+                let pattern = pattern
+                    .map(|p| {
+                        Regex::new(&p).map_err(|e| {
+                            Error::config_error(format!(
+                                "Invalid pattern for '{}.{}': {}",
+                                type_name, property, e
+                            ))
+                        })
+                    })
+                    .transpose()?;
+                Ok(PropertyRule {
+                    kind: kind.unwrap_or(PropertyType::Any),
+                    allowed,
+                    pattern,
+                })
+            }
+        }
+    }
+}
+
+impl NoteSchemas {
+    /// Parse schemas from the YAML schema file format
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let raw: RawSchemaFile = serde_yaml::from_str(yaml)
+            .map_err(|e| Error::config_error(format!("Invalid note schema file: {}", e)))?;
+
+        let mut types = Vec::with_capacity(raw.types.len());
+        for (name, raw_type) in raw.types {
+            let compile_all = |rules: BTreeMap<String, RawRule>| {
+                rules
+                    .into_iter()
+                    .map(|(key, rule)| rule.compile(&name, &key).map(|r| (key, r)))
+                    .collect::<Result<BTreeMap<_, _>>>()
+            };
+            types.push(NoteTypeSchema {
+                folders: raw_type
+                    .folders
+                    .iter()
+                    .map(|f| f.trim_matches('/').to_string())
+                    .collect(),
+                required: compile_all(raw_type.required)?,
+                optional: compile_all(raw_type.optional)?,
+                strict: raw_type.strict,
+                name,
+            });
+        }
+
+        Ok(Self {
+            mode: raw.mode,
+            type_property: raw.type_property,
+            types,
+        })
+    }
+
+    /// Find the schema for a note.
+    ///
+    /// The type property wins; otherwise the deepest matching folder decides.
+    pub fn schema_for(
+        &self,
+        relative_path: &str,
+        properties: Option<&HashMap<String, Value>>,
+    ) -> Option<&NoteTypeSchema> {
+        if let Some(type_name) = properties
+            .and_then(|p| p.get(&self.type_property))
+            .and_then(Value::as_str)
+        {
+            return self
+                .types
+                .iter()
+                .find(|t| t.name.eq_ignore_ascii_case(type_name.trim()));
+        }
+
+        let path = relative_path.replace('\\', "/");
+        self.types
+            .iter()
+            .flat_map(|t| t.folders.iter().map(move |f| (t, f)))
+            .filter(|(_, folder)| folder.is_empty() || path.starts_with(&format!("{}/", folder)))
+            .max_by_key(|(_, folder)| folder.len())
+            .map(|(t, _)| t)
+    }
+
+    /// Validate a note's frontmatter against its schema.
+    ///
+    /// `properties` is `None` when the note has no (parseable) frontmatter.
+    /// Notes without a matching schema produce an empty report.
+    pub fn validate(
+        &self,
+        relative_path: &str,
+        properties: Option<&HashMap<String, Value>>,
+    ) -> ValidationReport {
+        let mut report = ValidationReport::new();
+
+        if let Some(type_name) = properties
+            .and_then(|p| p.get(&self.type_property))
+            .and_then(Value::as_str)
+            && self.schema_for(relative_path, properties).is_none()
+        {
+            report.add_issue(
+                ValidationIssue::new(
+                    Severity::Warning,
+                    "schema",
+                    format!("Unknown note type '{}'", type_name),
+                )
+                .with_suggestion(format!(
+                    "Declare '{}' in {} or use one of: {}",
+                    type_name,
+                    NOTE_SCHEMA_FILE,
+                    self.types
+                        .iter()
+                        .map(|t| t.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            );
+            return report;
+        }
+
+        let Some(schema) = self.schema_for(relative_path, properties) else {
+            return report;
+        };
+        let empty = HashMap::new();
+        let properties = properties.unwrap_or(&empty);
+
+        for (key, rule) in &schema.required {
+            match properties.get(key) {
+                None | Some(Value::Null) => report.add_issue(
+                    ValidationIssue::new(
+                        Severity::Error,
+                        "schema",
+                        format!(
+                            "Missing required property '{}' for type '{}'",
+                            key, schema.name
+                        ),
+                    )
+                    .with_suggestion(format!(
+                        "Add '{}:' ({}) to frontmatter",
+                        key,
+                        rule.kind.name()
+                    )),
+                ),
+                Some(value) => check_rule(&mut report, &schema.name, key, rule, value),
+            }
+        }
+
+        for (key, rule) in &schema.optional {
+            if let Some(value) = properties.get(key)
+                && !value.is_null()
+            {
+                check_rule(&mut report, &schema.name, key, rule, value);
+            }
+        }
+
+        if schema.strict {
+            let mut unknown: Vec<&String> = properties
+                .keys()
+                .filter(|k| {
+                    **k != self.type_property
+                        && !schema.required.contains_key(*k)
+                        && !schema.optional.contains_key(*k)
+                })
+                .collect();
+            unknown.sort();
+            for key in unknown {
+                report.add_issue(ValidationIssue::new(
+                    Severity::Warning,
+                    "schema",
+                    format!(
+                        "Property '{}' is not declared for type '{}'",
+                        key, schema.name
+                    ),
+                ));
+            }
+        }
+
+        report
+    }
+
+    /// Validator adapter for use in a [`crate::CompositeValidator`]
+    pub fn validator(&self, vault_root: impl Into<PathBuf>) -> SchemaValidator {
+        SchemaValidator {
+            schemas: self.clone(),
+            vault_root: vault_root.into(),
+        }
+    }
+}
+
+fn check_rule(
+    report: &mut ValidationReport,
+    type_name: &str,
+    key: &str,
+    rule: &PropertyRule,
+    value: &Value,
+) {
+    if !rule.kind.accepts(value) {
+        report.add_issue(ValidationIssue::new(
+            Severity::Error,
+            "schema",
+            format!(
+                "Property '{}' of type '{}' should be {}, found {}",
+                key,
+                type_name,
+                rule.kind.name(),
+                value
+            ),
+        ));
+        return;
+    }
+
+    let elements: Vec<&Value> = match value {
+        Value::Array(items) => items.iter().collect(),
+        other => vec![other],
+    };
+
+    if !rule.allowed.is_empty() {
+        for element in &elements {
+            if !rule.allowed.iter().any(|a| scalar_eq(a, element)) {
+                report.add_issue(
+                    ValidationIssue::new(
+                        Severity::Error,
+                        "schema",
+                        format!("Property '{}' has disallowed value {}", key, element),
+                    )
+                    .with_suggestion(format!(
+                        "Use one of: {}",
+                        rule.allowed
+                            .iter()
+                            .map(scalar_text)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )),
+                );
+            }
+        }
+    }
+
+    if let Some(pattern) = &rule.pattern {
+        for element in &elements {
+            if !pattern.is_match(&scalar_text(element)) {
+                report.add_issue(ValidationIssue::new(
+                    Severity::Error,
+                    "schema",
+                    format!(
+                        "Property '{}' value {} does not match pattern '{}'",
+                        key,
+                        element,
+                        pattern.as_str()
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn scalar_eq(a: &Value, b: &Value) -> bool {
+    a == b || scalar_text(a) == scalar_text(b)
+}
+
+/// [`Validator`] that checks vault files against note-type schemas
+#[derive(Debug, Clone)]
+pub struct SchemaValidator {
+    schemas: NoteSchemas,
+    vault_root: PathBuf,
+}
+
+impl Validator for SchemaValidator {
+    fn validate(&self, file: &VaultFile) -> ValidationReport {
+        if !is_markdown(&file.path) {
+            return ValidationReport::new();
+        }
+        let relative = file
+            .path
+            .strip_prefix(&self.vault_root)
+            .unwrap_or(&file.path)
+            .to_string_lossy();
+        self.schemas
+            .validate(&relative, file.frontmatter.as_ref().map(|fm| &fm.data))
+    }
+
+    fn name(&self) -> &str {
+        "SchemaValidator"
+    }
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("md"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SCHEMA: &str = r#"
+mode: reject
+types:
+  meeting:
+    folders: [Meetings]
+    required:
+      date: date
+      attendees: list
+    optional:
+      status:
+        type: text
+        enum: [draft, final]
+      ticket:
+        pattern: "^[A-Z]+-[0-9]+$"
+  person:
+    folders: [People/]
+    required:
+      name: text
+    strict: true
+"#;
+
+    fn props(value: Value) -> HashMap<String, Value> {
+        value
+            .as_object()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    fn errors(report: &ValidationReport) -> Vec<String> {
+        report.issues.iter().map(|i| i.message.clone()).collect()
+    }
+
+    #[test]
+    fn test_parse_schema_file() {
+        let schemas = NoteSchemas::from_yaml(SCHEMA).unwrap();
+        assert_eq!(schemas.mode, SchemaMode::Reject);
+        assert_eq!(schemas.type_property, "type");
+        assert_eq!(schemas.types.len(), 2);
+        let person = schemas.types.iter().find(|t| t.name == "person").unwrap();
+        assert_eq!(person.folders, ["People"]);
+        assert!(person.strict);
+    }
+
+    #[test]
+    fn test_invalid_schema_file() {
+        assert!(NoteSchemas::from_yaml("types: [1, 2]").is_err());
+        assert!(NoteSchemas::from_yaml("types:\n  a:\n    required:\n      x: colour").is_err());
+        assert!(
+            NoteSchemas::from_yaml("types:\n  a:\n    optional:\n      x:\n        pattern: '('")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_type_property_and_folder_matching() {
+        let schemas = NoteSchemas::from_yaml(SCHEMA).unwrap();
+        let meeting = props(json!({"type": "Meeting"}));
+        assert_eq!(
+            schemas
+                .schema_for("notes/x.md", Some(&meeting))
+                .unwrap()
+                .name,
+            "meeting"
+        );
+        assert_eq!(
+            schemas.schema_for("People/ada.md", None).unwrap().name,
+            "person"
+        );
+        assert!(schemas.schema_for("Peoples/ada.md", None).is_none());
+
+        // The type property overrides the folder
+        let explicit = props(json!({"type": "meeting"}));
+        assert_eq!(
+            schemas
+                .schema_for("People/sync.md", Some(&explicit))
+                .unwrap()
+                .name,
+            "meeting"
+        );
+    }
+
+    #[test]
+    fn test_valid_note_passes() {
+        let schemas = NoteSchemas::from_yaml(SCHEMA).unwrap();
+        let note = props(json!({
+            "date": "2024-05-01",
+            "attendees": ["ada", "bob"],
+            "status": "draft",
+            "ticket": "OPS-12"
+        }));
+        let report = schemas.validate("Meetings/standup.md", Some(&note));
+        assert!(report.passed, "{:?}", errors(&report));
+        assert_eq!(report.total_issues(), 0);
+    }
+
+    #[test]
+    fn test_violations_reported() {
+        let schemas = NoteSchemas::from_yaml(SCHEMA).unwrap();
+        let note = props(json!({
+            "date": "next tuesday",
+            "status": "done",
+            "ticket": "ops-12"
+        }));
+        let report = schemas.validate("Meetings/standup.md", Some(&note));
+        assert!(!report.passed);
+        let messages = errors(&report);
+        assert_eq!(messages.len(), 4, "{:?}", messages);
+        assert!(messages.iter().any(|m| m.contains("'attendees'")));
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.contains("'date'") && m.contains("date"))
+        );
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.contains("disallowed value \"done\""))
+        );
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.contains("does not match pattern"))
+        );
+    }
+
+    #[test]
+    fn test_missing_frontmatter_in_typed_folder() {
+        let schemas = NoteSchemas::from_yaml(SCHEMA).unwrap();
+        let report = schemas.validate("People/ada.md", None);
+        assert_eq!(report.summary.error_count, 1);
+    }
+
+    #[test]
+    fn test_strict_and_unknown_type_warn() {
+        let schemas = NoteSchemas::from_yaml(SCHEMA).unwrap();
+        let person = props(json!({"name": "Ada", "nickname": "A"}));
+        let report = schemas.validate("People/ada.md", Some(&person));
+        assert!(report.passed);
+        assert_eq!(report.summary.warning_count, 1);
+
+        let unknown = props(json!({"type": "recipe"}));
+        let report = schemas.validate("x.md", Some(&unknown));
+        assert!(report.passed);
+        assert!(errors(&report)[0].contains("Unknown note type 'recipe'"));
+    }
+
+    #[test]
+    fn test_untyped_notes_ignored() {
+        let schemas = NoteSchemas::from_yaml(SCHEMA).unwrap();
+        let report = schemas.validate("inbox/idea.md", Some(&props(json!({"a": 1}))));
+        assert_eq!(report.total_issues(), 0);
+    }
+}
@@ -0,0 +1,862 @@
+//! Link graph export for Gephi, Graphviz, Mermaid and JSON Graph tooling
+//!
+//! [`GraphExporter`] takes a snapshot of a [`LinkGraph`] — notes with their
+//! folder, tags, word count and centrality, and typed edges weighted by how
+//! many links of that kind connect the two notes — optionally restricted to a
+//! tag, a folder or the ego network around one note. The snapshot renders to
+//! GraphML, GEXF, DOT, Mermaid, JSON Graph Format, plain JSON or a CSV edge
+//! list.
+
+use crate::ExportFormat;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use turbovault_core::prelude::*;
+use turbovault_core::to_json_string;
+use turbovault_graph::{CentralityConfig, EdgeKind, LinkGraph, PathDirection};
+
+/// Default number of notes drawn in Mermaid output
+pub const DEFAULT_MERMAID_MAX_NODES: usize = 50;
+
+/// Per-note attributes that the link graph doesn't track
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NoteAttributes {
+    pub tags: Vec<String>,
+    pub word_count: usize,
+}
+
+/// Which part of the vault to export
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SubgraphSelection {
+    /// Every note
+    #[default]
+    All,
+    /// Notes with a tag or one of its `tag/child` descendants (case-insensitive)
+    Tag(String),
+    /// Notes in a folder (vault-relative) or its subfolders
+    Folder(String),
+    /// A note and everything within `hops` links of it, in either direction
+    Ego { center: PathBuf, hops: usize },
+}
+
+/// Options for a graph export
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphExportOptions {
+    pub selection: SubgraphSelection,
+    /// Edge kinds to export (empty = all)
+    pub kinds: Vec<EdgeKind>,
+    /// Notes drawn in Mermaid output; the highest-PageRank notes are kept
+    pub mermaid_max_nodes: usize,
+}
+
+impl Default for GraphExportOptions {
+    fn default() -> Self {
+        Self {
+            selection: SubgraphSelection::All,
+            kinds: Vec::new(),
+            mermaid_max_nodes: DEFAULT_MERMAID_MAX_NODES,
+        }
+    }
+}
+
+/// A note in an exported graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphNode {
+    /// Vault-relative path with `/` separators
+    pub id: String,
+    /// File name without extension
+    pub label: String,
+    /// Vault-relative folder (empty for the root)
+    pub folder: String,
+    pub tags: Vec<String>,
+    pub word_count: usize,
+    pub pagerank: f64,
+    pub betweenness: f64,
+    pub closeness: f64,
+}
+
+/// Links of one kind from one note to another
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub kind: EdgeKind,
+    /// Frontmatter property, for `property` edges
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
+    /// Number of links
+    pub weight: usize,
+}
+
+/// Nodes and edges selected for export
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphSnapshot {
+    /// Nodes sorted by id
+    pub nodes: Vec<GraphNode>,
+    /// Edges sorted by source, target and kind
+    pub edges: Vec<GraphEdge>,
+    /// Notes in the whole vault
+    pub total_nodes: usize,
+}
+
+/// Builds [`GraphSnapshot`]s from a link graph
+pub struct GraphExporter<'a> {
+    graph: &'a LinkGraph,
+    vault_root: &'a Path,
+    attributes: Option<&'a HashMap<PathBuf, NoteAttributes>>,
+}
+
+impl<'a> GraphExporter<'a> {
+    /// Create an exporter; node ids are made relative to `vault_root`
+    pub fn new(graph: &'a LinkGraph, vault_root: &'a Path) -> Self {
+        Self {
+            graph,
+            vault_root,
+            attributes: None,
+        }
+    }
+
+    /// Tags and word counts, keyed by the graph's note paths
+    pub fn with_attributes(mut self, attributes: &'a HashMap<PathBuf, NoteAttributes>) -> Self {
+        self.attributes = Some(attributes);
+        self
+    }
+
+    /// Select nodes and edges for export
+    pub fn snapshot(&self, options: &GraphExportOptions) -> Result<GraphSnapshot> {
+        let all = self.graph.all_files();
+        let selected: Vec<PathBuf> = match &options.selection {
+            SubgraphSelection::All => all.clone(),
+            SubgraphSelection::Tag(tag) => {
+                let tag = tag.trim_start_matches('#').to_lowercase();
+                let prefix = format!("{}/", tag);
+                all.iter()
+                    .filter(|path| {
+                        self.attributes_of(path).tags.iter().any(|t| {
+                            let t = t.trim_start_matches('#').to_lowercase();
+                            t == tag || t.starts_with(&prefix)
+                        })
+                    })
+                    .cloned()
+                    .collect()
+            }
+            SubgraphSelection::Folder(folder) => {
+                let folder = folder.trim_matches('/');
+                let prefix = format!("{}/", folder);
+                all.iter()
+                    .filter(|path| {
+                        let note_folder = self.folder_of(path);
+                        folder.is_empty()
+                            || note_folder == folder
+                            || note_folder.starts_with(&prefix)
+                    })
+                    .cloned()
+                    .collect()
+            }
+            SubgraphSelection::Ego { center, hops } => {
+                let ego = self.graph.ego_network(
+                    center,
+                    *hops,
+                    PathDirection::Undirected,
+                    &options.kinds,
+                )?;
+                std::iter::once(ego.center)
+                    .chain(ego.nodes.into_iter().map(|node| node.path))
+                    .collect()
+            }
+        };
+        let members: HashSet<&PathBuf> = selected.iter().collect();
+
+        let centrality = self.graph.centrality(&CentralityConfig::default());
+        let mut nodes: Vec<GraphNode> = selected
+            .iter()
+            .map(|path| {
+                let attributes = self.attributes_of(path);
+                GraphNode {
+                    id: self.id_of(path),
+                    label: path
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    folder: self.folder_of(path),
+                    tags: attributes.tags.clone(),
+                    word_count: attributes.word_count,
+                    pagerank: centrality.pagerank.get(path).copied().unwrap_or(0.0),
+                    betweenness: centrality.betweenness.get(path).copied().unwrap_or(0.0),
+                    closeness: centrality.closeness.get(path).copied().unwrap_or(0.0),
+                }
+            })
+            .collect();
+        nodes.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut weights: BTreeMap<(String, String, EdgeKind, Option<String>), usize> =
+            BTreeMap::new();
+        for source in &selected {
+            for (target, edges) in self.graph.forward_links_by_kind(source, &options.kinds)? {
+                if !members.contains(&target) {
+                    continue;
+                }
+                for edge in edges {
+                    let key = (
+                        self.id_of(source),
+                        self.id_of(&target),
+                        edge.kind,
+                        edge.property,
+                    );
+                    *weights.entry(key).or_insert(0) += 1;
+                }
+            }
+        }
+        let edges = weights
+            .into_iter()
+            .map(|((source, target, kind, property), weight)| GraphEdge {
+                source,
+                target,
+                kind,
+                property,
+                weight,
+            })
+            .collect();
+
+        Ok(GraphSnapshot {
+            nodes,
+            edges,
+            total_nodes: all.len(),
+        })
+    }
+
+    /// Snapshot and render in one step
+    pub fn export(&self, format: ExportFormat, options: &GraphExportOptions) -> Result<String> {
+        self.snapshot(options)?
+            .render(format, options.mermaid_max_nodes)
+    }
+
+    fn attributes_of(&self, path: &Path) -> NoteAttributes {
+        self.attributes
+            .and_then(|attributes| attributes.get(path))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn id_of(&self, path: &Path) -> String {
+        slash_path(path.strip_prefix(self.vault_root).unwrap_or(path))
+    }
+
+    fn folder_of(&self, path: &Path) -> String {
+        path.strip_prefix(self.vault_root)
+            .unwrap_or(path)
+            .parent()
+            .map(slash_path)
+            .unwrap_or_default()
+    }
+}
+
+impl GraphSnapshot {
+    /// Render in any export format; `Json` is this snapshot, `Csv` an edge list
+    pub fn render(&self, format: ExportFormat, mermaid_max_nodes: usize) -> Result<String> {
+        match format {
+            ExportFormat::Json => to_json_string(self, "graph"),
+            ExportFormat::Csv => Ok(self.to_csv()),
+            ExportFormat::GraphMl => Ok(self.to_graphml()),
+            ExportFormat::Gexf => Ok(self.to_gexf()),
+            ExportFormat::Dot => Ok(self.to_dot()),
+            ExportFormat::Mermaid => Ok(self.to_mermaid(mermaid_max_nodes)),
+            ExportFormat::JsonGraph => to_json_string(&self.to_json_graph(), "JSON graph"),
+        }
+    }
+
+    /// Whether Mermaid output with this cap drops notes
+    pub fn exceeds(&self, max_nodes: usize) -> bool {
+        self.nodes.len() > max_nodes
+    }
+
+    /// Edge list: `source,target,kind,property,weight`
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("source,target,kind,property,weight\n");
+        for edge in &self.edges {
+            let _ = writeln!(
+                csv,
+                "\"{}\",\"{}\",{},\"{}\",{}",
+                edge.source.replace('"', "\"\""),
+                edge.target.replace('"', "\"\""),
+                edge.kind.as_str(),
+                edge.property.as_deref().unwrap_or("").replace('"', "\"\""),
+                edge.weight
+            );
+        }
+        csv
+    }
+
+    /// GraphML for Gephi, yEd and NetworkX
+    pub fn to_graphml(&self) -> String {
+        let mut out = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        );
+        for (id, domain, name, kind) in [
+            ("label", "node", "label", "string"),
+            ("folder", "node", "folder", "string"),
+            ("tags", "node", "tags", "string"),
+            ("word_count", "node", "word_count", "int"),
+            ("pagerank", "node", "pagerank", "double"),
+            ("betweenness", "node", "betweenness", "double"),
+            ("closeness", "node", "closeness", "double"),
+            ("kind", "edge", "kind", "string"),
+            ("property", "edge", "property", "string"),
+            ("weight", "edge", "weight", "int"),
+        ] {
+            let _ = writeln!(
+                out,
+                "  <key id=\"{id}\" for=\"{domain}\" attr.name=\"{name}\" attr.type=\"{kind}\"/>"
+            );
+        }
+        out.push_str("  <graph id=\"vault\" edgedefault=\"directed\">\n");
+
+        for node in &self.nodes {
+            let _ = writeln!(out, "    <node id=\"{}\">", xml_escape(&node.id));
+            for (key, value) in [
+                ("label", xml_escape(&node.label)),
+                ("folder", xml_escape(&node.folder)),
+                ("tags", xml_escape(&node.tags.join(";"))),
+                ("word_count", node.word_count.to_string()),
+                ("pagerank", node.pagerank.to_string()),
+                ("betweenness", node.betweenness.to_string()),
+                ("closeness", node.closeness.to_string()),
+            ] {
+                let _ = writeln!(out, "      <data key=\"{key}\">{value}</data>");
+            }
+            out.push_str("    </node>\n");
+        }
+
+        for (i, edge) in self.edges.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">",
+                i,
+                xml_escape(&edge.source),
+                xml_escape(&edge.target)
+            );
+            let _ = writeln!(
+                out,
+                "      <data key=\"kind\">{}</data>",
+                edge.kind.as_str()
+            );
+            if let Some(property) = &edge.property {
+                let _ = writeln!(
+                    out,
+                    "      <data key=\"property\">{}</data>",
+                    xml_escape(property)
+                );
+            }
+            let _ = writeln!(out, "      <data key=\"weight\">{}</data>", edge.weight);
+            out.push_str("    </edge>\n");
+        }
+
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    /// GEXF 1.3 for Gephi
+    pub fn to_gexf(&self) -> String {
+        let mut out = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n\
+             \x20 <graph defaultedgetype=\"directed\" mode=\"static\">\n\
+             \x20   <attributes class=\"node\">\n",
+        );
+        for (i, (name, kind)) in [
+            ("folder", "string"),
+            ("tags", "liststring"),
+            ("word_count", "integer"),
+            ("pagerank", "double"),
+            ("betweenness", "double"),
+            ("closeness", "double"),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let _ = writeln!(
+                out,
+                "      <attribute id=\"{i}\" title=\"{name}\" type=\"{kind}\"/>"
+            );
+        }
+        out.push_str(
+            "    </attributes>\n\
+             \x20   <attributes class=\"edge\">\n\
+             \x20     <attribute id=\"0\" title=\"kind\" type=\"string\"/>\n\
+             \x20     <attribute id=\"1\" title=\"property\" type=\"string\"/>\n\
+             \x20   </attributes>\n\
+             \x20   <nodes>\n",
+        );
+
+        for node in &self.nodes {
+            let _ = writeln!(
+                out,
+                "      <node id=\"{}\" label=\"{}\">\n        <attvalues>",
+                xml_escape(&node.id),
+                xml_escape(&node.label)
+            );
+            let tags = format!("[{}]", node.tags.join(", "));
+            for (i, value) in [
+                xml_escape(&node.folder),
+                xml_escape(&tags),
+                node.word_count.to_string(),
+                node.pagerank.to_string(),
+                node.betweenness.to_string(),
+                node.closeness.to_string(),
+            ]
+            .iter()
+            .enumerate()
+            {
+                let _ = writeln!(out, "          <attvalue for=\"{i}\" value=\"{value}\"/>");
+            }
+            out.push_str("        </attvalues>\n      </node>\n");
+        }
+        out.push_str("    </nodes>\n    <edges>\n");
+
+        for (i, edge) in self.edges.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "      <edge id=\"{}\" source=\"{}\" target=\"{}\" weight=\"{}\">\n        <attvalues>\n          <attvalue for=\"0\" value=\"{}\"/>",
+                i,
+                xml_escape(&edge.source),
+                xml_escape(&edge.target),
+                edge.weight,
+                edge.kind.as_str()
+            );
+            if let Some(property) = &edge.property {
+                let _ = writeln!(
+                    out,
+                    "          <attvalue for=\"1\" value=\"{}\"/>",
+                    xml_escape(property)
+                );
+            }
+            out.push_str("        </attvalues>\n      </edge>\n");
+        }
+
+        out.push_str("    </edges>\n  </graph>\n</gexf>\n");
+        out
+    }
+
+    /// Graphviz DOT
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph vault {\n  node [shape=box];\n");
+        for node in &self.nodes {
+            let _ = writeln!(
+                out,
+                "  \"{}\" [label=\"{}\", folder=\"{}\", tags=\"{}\", word_count={}, pagerank={:.6}, betweenness={:.6}, closeness={:.6}];",
+                dot_escape(&node.id),
+                dot_escape(&node.label),
+                dot_escape(&node.folder),
+                dot_escape(&node.tags.join(";")),
+                node.word_count,
+                node.pagerank,
+                node.betweenness,
+                node.closeness
+            );
+        }
+        for edge in &self.edges {
+            let style = match edge.kind {
+                EdgeKind::Embed => ", style=dashed",
+                EdgeKind::Property => ", style=dotted",
+                _ => "",
+            };
+            let label = match &edge.property {
+                Some(property) => format!(", label=\"{}\"", dot_escape(property)),
+                None => String::new(),
+            };
+            let _ = writeln!(
+                out,
+                "  \"{}\" -> \"{}\" [kind=\"{}\", weight={}{}{}];",
+                dot_escape(&edge.source),
+                dot_escape(&edge.target),
+                edge.kind.as_str(),
+                edge.weight,
+                style,
+                label
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Mermaid flowchart, keeping the `max_nodes` notes with the highest PageRank
+    pub fn to_mermaid(&self, max_nodes: usize) -> String {
+        let mut kept: Vec<&GraphNode> = self.nodes.iter().collect();
+        if kept.len() > max_nodes {
+            kept.sort_by(|a, b| {
+                b.pagerank
+                    .total_cmp(&a.pagerank)
+                    .then_with(|| a.id.cmp(&b.id))
+            });
+            kept.truncate(max_nodes);
+            kept.sort_by(|a, b| a.id.cmp(&b.id));
+        }
+        let ids: HashMap<&str, usize> = kept
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id.as_str(), i))
+            .collect();
+
+        let mut out = String::from("flowchart LR\n");
+        if kept.len() < self.nodes.len() {
+            let _ = writeln!(
+                out,
+                "  %% Showing {} of {} notes (highest PageRank)",
+                kept.len(),
+                self.nodes.len()
+            );
+        }
+        for (i, node) in kept.iter().enumerate() {
+            let _ = writeln!(out, "  n{}[\"{}\"]", i, mermaid_escape(&node.label));
+        }
+        for edge in &self.edges {
+            let (Some(source), Some(target)) =
+                (ids.get(edge.source.as_str()), ids.get(edge.target.as_str()))
+            else {
+                continue;
+            };
+            let arrow = match edge.kind {
+                EdgeKind::Embed | EdgeKind::Property => "-.->",
+                _ => "-->",
+            };
+            let label = match (&edge.property, edge.kind) {
+                (Some(property), _) => format!("|{}|", mermaid_escape(property)),
+                (None, EdgeKind::Embed) => "|embed|".to_string(),
+                _ => String::new(),
+            };
+            let _ = writeln!(out, "  n{source} {arrow}{label} n{target}");
+        }
+        out
+    }
+
+    /// JSON Graph Format (jsongraphformat.info) v2 document
+    pub fn to_json_graph(&self) -> serde_json::Value {
+        let nodes: serde_json::Map<String, serde_json::Value> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                (
+                    node.id.clone(),
+                    json!({
+                        "label": node.label,
+                        "metadata": {
+                            "folder": node.folder,
+                            "tags": node.tags,
+                            "word_count": node.word_count,
+                            "pagerank": node.pagerank,
+                            "betweenness": node.betweenness,
+                            "closeness": node.closeness,
+                        }
+                    }),
+                )
+            })
+            .collect();
+        let edges: Vec<serde_json::Value> = self
+            .edges
+            .iter()
+            .map(|edge| {
+                let mut metadata = json!({ "weight": edge.weight });
+                if let Some(property) = &edge.property {
+                    metadata["property"] = json!(property);
+                }
+                json!({
+                    "source": edge.source,
+                    "target": edge.target,
+                    "relation": edge.kind.as_str(),
+                    "directed": true,
+                    "metadata": metadata,
+                })
+            })
+            .collect();
+
+        json!({
+            "graph": {
+                "id": "vault",
+                "type": "turbovault-links",
+                "directed": true,
+                "nodes": nodes,
+                "edges": edges,
+            }
+        })
+    }
+}
+
+fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(s: &str) -> String {
+    s.replace('"', "#quot;").replace('|', "#124;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(path: &str, links: &[&str]) -> VaultFile {
+        let path = PathBuf::from("/vault").join(path);
+        let mut file = VaultFile::new(
+            path.clone(),
+            String::new(),
+            FileMetadata {
+                path: path.clone(),
+                size: 0,
+                created_at: 0.0,
+                modified_at: 0.0,
+                checksum: String::new(),
+                is_attachment: false,
+            },
+        );
+        file.links = links
+            .iter()
+            .enumerate()
+            .map(|(i, target)| {
+                let type_ = if target.starts_with('!') {
+                    LinkType::Embed
+                } else {
+                    LinkType::WikiLink
+                };
+                Link::new(
+                    type_,
+                    path.clone(),
+                    target.trim_start_matches('!').to_string(),
+                    SourcePosition::new(i + 1, 0, 0, 0),
+                )
+            })
+            .collect();
+        file
+    }
+
+    /// projects/alpha -> hub (twice), projects/beta -> hub, hub embeds "a & b", lone
+    fn vault() -> (LinkGraph, HashMap<PathBuf, NoteAttributes>) {
+        let files = [
+            note("projects/alpha.md", &["hub", "hub"]),
+            note("projects/beta.md", &["hub"]),
+            note("hub.md", &["!a & b"]),
+            note("a & b.md", &[]),
+            note("lone.md", &[]),
+        ];
+        let mut graph = LinkGraph::new();
+        for file in &files {
+            graph.add_file(file).unwrap();
+        }
+        for file in &files {
+            graph.update_links(file).unwrap();
+        }
+
+        let mut attributes = HashMap::new();
+        attributes.insert(
+            PathBuf::from("/vault/projects/alpha.md"),
+            NoteAttributes {
+                tags: vec!["project/active".to_string()],
+                word_count: 120,
+            },
+        );
+        attributes.insert(
+            PathBuf::from("/vault/hub.md"),
+            NoteAttributes {
+                tags: vec!["Project".to_string(), "moc".to_string()],
+                word_count: 40,
+            },
+        );
+        (graph, attributes)
+    }
+
+    #[test]
+    fn test_snapshot_nodes_and_weighted_edges() {
+        let (graph, attributes) = vault();
+        let root = PathBuf::from("/vault");
+        let snapshot = GraphExporter::new(&graph, &root)
+            .with_attributes(&attributes)
+            .snapshot(&GraphExportOptions::default())
+            .unwrap();
+
+        assert_eq!(snapshot.total_nodes, 5);
+        let ids: Vec<&str> = snapshot.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "a & b.md",
+                "hub.md",
+                "lone.md",
+                "projects/alpha.md",
+                "projects/beta.md"
+            ]
+        );
+        let alpha = &snapshot.nodes[3];
+        assert_eq!(alpha.label, "alpha");
+        assert_eq!(alpha.folder, "projects");
+        assert_eq!(alpha.word_count, 120);
+        assert!(snapshot.nodes[1].pagerank > alpha.pagerank);
+
+        assert_eq!(snapshot.edges.len(), 3);
+        let to_hub = snapshot
+            .edges
+            .iter()
+            .find(|e| e.source == "projects/alpha.md")
+            .unwrap();
+        assert_eq!(to_hub.target, "hub.md");
+        assert_eq!(to_hub.weight, 2);
+        assert_eq!(snapshot.edges[0].kind, EdgeKind::Embed);
+    }
+
+    #[test]
+    fn test_subgraph_selection() {
+        let (graph, attributes) = vault();
+        let root = PathBuf::from("/vault");
+        let exporter = GraphExporter::new(&graph, &root).with_attributes(&attributes);
+        let ids = |selection: SubgraphSelection| -> Vec<String> {
+            exporter
+                .snapshot(&GraphExportOptions {
+                    selection,
+                    ..Default::default()
+                })
+                .unwrap()
+                .nodes
+                .into_iter()
+                .map(|n| n.id)
+                .collect()
+        };
+
+        // Nested tags count and matching ignores case
+        assert_eq!(
+            ids(SubgraphSelection::Tag("#project".to_string())),
+            vec!["hub.md", "projects/alpha.md"]
+        );
+        assert_eq!(
+            ids(SubgraphSelection::Folder("projects/".to_string())),
+            vec!["projects/alpha.md", "projects/beta.md"]
+        );
+        assert_eq!(
+            ids(SubgraphSelection::Ego {
+                center: PathBuf::from("/vault/a & b.md"),
+                hops: 1,
+            }),
+            vec!["a & b.md", "hub.md"]
+        );
+        assert!(
+            exporter
+                .snapshot(&GraphExportOptions {
+                    selection: SubgraphSelection::Ego {
+                        center: PathBuf::from("/vault/missing.md"),
+                        hops: 1,
+                    },
+                    ..Default::default()
+                })
+                .is_err()
+        );
+
+        // Edges leaving the selection are dropped
+        let folder = exporter
+            .snapshot(&GraphExportOptions {
+                selection: SubgraphSelection::Folder("projects".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(folder.edges.is_empty());
+
+        let embeds = exporter
+            .snapshot(&GraphExportOptions {
+                kinds: vec![EdgeKind::Embed],
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(embeds.edges.len(), 1);
+    }
+
+    #[test]
+    fn test_render_formats_escape_names() {
+        let (graph, attributes) = vault();
+        let root = PathBuf::from("/vault");
+        let snapshot = GraphExporter::new(&graph, &root)
+            .with_attributes(&attributes)
+            .snapshot(&GraphExportOptions::default())
+            .unwrap();
+
+        let graphml = snapshot.render(ExportFormat::GraphMl, 50).unwrap();
+        assert!(graphml.contains("<node id=\"a &amp; b.md\">"));
+        assert!(graphml.contains("<data key=\"kind\">embed</data>"));
+        assert!(!graphml.contains("a & b"));
+
+        let gexf = snapshot.render(ExportFormat::Gexf, 50).unwrap();
+        assert!(gexf.contains("version=\"1.3\""));
+        assert!(gexf.contains("weight=\"2\""));
+        assert_eq!(gexf.matches("<node ").count(), 5);
+
+        let dot = snapshot.render(ExportFormat::Dot, 50).unwrap();
+        assert!(dot.starts_with("digraph vault {"));
+        assert!(
+            dot.contains("\"projects/alpha.md\" -> \"hub.md\" [kind=\"wiki_link\", weight=2];")
+        );
+        assert!(dot.contains("style=dashed"));
+
+        let jgf: serde_json::Value =
+            serde_json::from_str(&snapshot.render(ExportFormat::JsonGraph, 50).unwrap()).unwrap();
+        assert_eq!(jgf["graph"]["nodes"]["projects/alpha.md"]["label"], "alpha");
+        assert_eq!(jgf["graph"]["edges"].as_array().unwrap().len(), 3);
+
+        let csv = snapshot.render(ExportFormat::Csv, 50).unwrap();
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.starts_with("source,target,kind,property,weight"));
+    }
+
+    #[test]
+    fn test_mermaid_caps_by_pagerank() {
+        let (graph, attributes) = vault();
+        let root = PathBuf::from("/vault");
+        let snapshot = GraphExporter::new(&graph, &root)
+            .with_attributes(&attributes)
+            .snapshot(&GraphExportOptions::default())
+            .unwrap();
+
+        let full = snapshot.to_mermaid(50);
+        assert!(full.starts_with("flowchart LR\n"));
+        assert!(!full.contains("%%"));
+        assert_eq!(full.matches("-->").count(), 2);
+        assert_eq!(full.matches("-.->|embed|").count(), 1);
+
+        assert!(snapshot.exceeds(2));
+        let capped = snapshot.to_mermaid(2);
+        assert!(capped.contains("%% Showing 2 of 5 notes"));
+        assert!(capped.contains("[\"hub\"]"));
+        assert!(capped.contains("[\"a & b\"]"));
+        assert!(!capped.contains("alpha"));
+        assert_eq!(capped.lines().count(), 5);
+        assert_eq!(capped.matches("-.->|embed|").count(), 1);
+    }
+
+    #[test]
+    fn test_export_format_names() {
+        assert_eq!(
+            ExportFormat::from_name("GraphML"),
+            Some(ExportFormat::GraphMl)
+        );
+        assert_eq!(
+            ExportFormat::from_name("jgf"),
+            Some(ExportFormat::JsonGraph)
+        );
+        assert_eq!(ExportFormat::from_name("graphviz"), Some(ExportFormat::Dot));
+        assert_eq!(ExportFormat::from_name("svg"), None);
+        assert!(ExportFormat::Mermaid.is_graph());
+        assert!(!ExportFormat::Csv.is_graph());
+        assert_eq!(ExportFormat::JsonGraph.as_str(), "json_graph");
+    }
+}
@@ -1,6 +1,8 @@
 //! # Export System
 //!
-//! Provides data export functionality for vault analysis in multiple formats (JSON, CSV).
+//! Provides data export functionality for vault analysis in multiple formats (JSON, CSV),
+//! and exports the link graph itself as GraphML, GEXF, DOT, Mermaid or JSON Graph
+//! (see [`graph_export`]).
 //! Enables downstream processing and reporting of vault metrics and analysis.
 //!
 //! ## Quick Start
@@ -161,18 +163,69 @@
 //! - No I/O operations within exporters
 //! - Suitable for batch processing large datasets
 
+pub mod graph_export;
+
+pub use graph_export::{
+    DEFAULT_MERMAID_MAX_NODES, GraphEdge, GraphExportOptions, GraphExporter, GraphNode,
+    GraphSnapshot, NoteAttributes, SubgraphSelection,
+};
+
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use turbovault_core::prelude::*;
 use turbovault_core::to_json_string;
 
 /// Export format options
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExportFormat {
     /// JSON format (pretty-printed)
     Json,
     /// CSV format (flattened)
     Csv,
+    /// GraphML (Gephi, yEd, NetworkX)
+    GraphMl,
+    /// GEXF 1.3 (Gephi)
+    Gexf,
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+    /// JSON Graph Format
+    JsonGraph,
+}
+
+impl ExportFormat {
+    /// Parse a format name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace('-', "_").as_str() {
+            "json" => Some(ExportFormat::Json),
+            "csv" => Some(ExportFormat::Csv),
+            "graphml" => Some(ExportFormat::GraphMl),
+            "gexf" => Some(ExportFormat::Gexf),
+            "dot" | "graphviz" => Some(ExportFormat::Dot),
+            "mermaid" => Some(ExportFormat::Mermaid),
+            "jgf" | "json_graph" => Some(ExportFormat::JsonGraph),
+            _ => None,
+        }
+    }
+
+    /// Canonical format name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::GraphMl => "graphml",
+            ExportFormat::Gexf => "gexf",
+            ExportFormat::Dot => "dot",
+            ExportFormat::Mermaid => "mermaid",
+            ExportFormat::JsonGraph => "json_graph",
+        }
+    }
+
+    /// Whether the format only makes sense for graphs
+    pub fn is_graph(&self) -> bool {
+        !matches!(self, ExportFormat::Json | ExportFormat::Csv)
+    }
 }
 
 /// Health report for export
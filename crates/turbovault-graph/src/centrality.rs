@@ -0,0 +1,498 @@
+//! Centrality metrics over the link graph
+//!
+//! - **PageRank**: random-surfer importance with damping; the rank of notes
+//!   without outgoing links is spread evenly over the whole vault.
+//! - **Betweenness**: Brandes' algorithm over shortest directed paths,
+//!   estimated from evenly spaced source notes on large vaults.
+//! - **Closeness**: harmonic closeness, the mean of `1 / distance` from every
+//!   other note, so unreachable notes contribute zero instead of infinity.
+//!
+//! Parallel edges (a note linking another several times, or through
+//! different link kinds) and self-links count once.
+
+use petgraph::graph::{DiGraph, NodeIndex};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// A centrality metric
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CentralityMetric {
+    PageRank,
+    Betweenness,
+    Closeness,
+}
+
+impl CentralityMetric {
+    /// Every metric, in reporting order
+    pub const ALL: [CentralityMetric; 3] = [
+        CentralityMetric::PageRank,
+        CentralityMetric::Betweenness,
+        CentralityMetric::Closeness,
+    ];
+
+    /// Parse a metric name (case-insensitive, `-`/`_` optional)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase().replace(['-', '_', ' '], "");
+        match name.as_str() {
+            "pagerank" | "pr" => Some(Self::PageRank),
+            "betweenness" => Some(Self::Betweenness),
+            "closeness" | "harmonic" | "harmoniccloseness" => Some(Self::Closeness),
+            _ => None,
+        }
+    }
+
+    /// Name used in tool output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PageRank => "pagerank",
+            Self::Betweenness => "betweenness",
+            Self::Closeness => "closeness",
+        }
+    }
+}
+
+/// Parameters for centrality computation
+#[derive(Debug, Clone, PartialEq)]
+pub struct CentralityConfig {
+    /// PageRank damping factor (probability of following a link)
+    pub damping: f64,
+    /// PageRank stops once the L1 change per note drops below this
+    pub tolerance: f64,
+    /// Upper bound on PageRank iterations
+    pub max_iterations: usize,
+    /// Vaults with more notes than this get sampled betweenness
+    pub betweenness_sample_threshold: usize,
+    /// Number of source notes used when sampling betweenness
+    pub betweenness_samples: usize,
+}
+
+impl Default for CentralityConfig {
+    fn default() -> Self {
+        Self {
+            damping: 0.85,
+            tolerance: 1e-6,
+            max_iterations: 100,
+            betweenness_sample_threshold: 2000,
+            betweenness_samples: 256,
+        }
+    }
+}
+
+/// Centrality scores for every note in the graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Centrality {
+    /// PageRank; sums to 1 over the vault
+    pub pagerank: HashMap<PathBuf, f64>,
+    /// Betweenness normalized by `(n - 1)(n - 2)`, in 0.0-1.0
+    pub betweenness: HashMap<PathBuf, f64>,
+    /// Harmonic closeness normalized by `n - 1`, in 0.0-1.0
+    pub closeness: HashMap<PathBuf, f64>,
+    /// Number of source notes betweenness was computed from
+    pub betweenness_sources: usize,
+    /// Number of notes scored
+    pub node_count: usize,
+    /// PageRank iterations until convergence
+    pub pagerank_iterations: usize,
+}
+
+impl Centrality {
+    /// Compute all metrics for a graph whose nodes are note paths
+    pub fn compute<E>(graph: &DiGraph<PathBuf, E>, config: &CentralityConfig) -> Self {
+        let adjacency = Adjacency::new(graph);
+        let n = adjacency.len();
+        let (pagerank, pagerank_iterations) = pagerank(&adjacency, config);
+
+        let sources = if n > config.betweenness_sample_threshold && config.betweenness_samples > 0 {
+            sample_sources(graph, config.betweenness_samples)
+        } else {
+            (0..n).collect()
+        };
+        let betweenness = betweenness(&adjacency, &sources);
+        let closeness = harmonic_closeness(&adjacency);
+
+        let by_path = |scores: Vec<f64>| -> HashMap<PathBuf, f64> {
+            graph
+                .node_indices()
+                .map(|idx| (graph[idx].clone(), scores[idx.index()]))
+                .collect()
+        };
+
+        Self {
+            pagerank: by_path(pagerank),
+            betweenness: by_path(betweenness),
+            closeness: by_path(closeness),
+            betweenness_sources: sources.len(),
+            node_count: n,
+            pagerank_iterations,
+        }
+    }
+
+    /// Scores for one metric
+    pub fn scores(&self, metric: CentralityMetric) -> &HashMap<PathBuf, f64> {
+        match metric {
+            CentralityMetric::PageRank => &self.pagerank,
+            CentralityMetric::Betweenness => &self.betweenness,
+            CentralityMetric::Closeness => &self.closeness,
+        }
+    }
+
+    /// Score of one note (0.0 if it isn't in the graph)
+    pub fn score(&self, metric: CentralityMetric, path: &Path) -> f64 {
+        self.scores(metric).get(path).copied().unwrap_or(0.0)
+    }
+
+    /// Notes sorted by a metric, highest first (ties by path)
+    pub fn ranking(&self, metric: CentralityMetric) -> Vec<(PathBuf, f64)> {
+        let mut ranked: Vec<(PathBuf, f64)> = self
+            .scores(metric)
+            .iter()
+            .map(|(path, &score)| (path.clone(), score))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked
+    }
+
+    /// Whether betweenness is an estimate from a sample of source notes
+    pub fn is_sampled(&self) -> bool {
+        self.betweenness_sources < self.node_count
+    }
+}
+
+/// Deduplicated adjacency lists indexed by node position
+struct Adjacency {
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+}
+
+impl Adjacency {
+    fn new<E>(graph: &DiGraph<PathBuf, E>) -> Self {
+        let n = graph.node_count();
+        let mut outgoing = vec![Vec::new(); n];
+        let mut incoming = vec![Vec::new(); n];
+
+        for edge in graph.raw_edges() {
+            let (source, target) = (edge.source().index(), edge.target().index());
+            if source != target {
+                outgoing[source].push(target);
+                incoming[target].push(source);
+            }
+        }
+        for list in outgoing.iter_mut().chain(incoming.iter_mut()) {
+            list.sort_unstable();
+            list.dedup();
+        }
+
+        Self { outgoing, incoming }
+    }
+
+    fn len(&self) -> usize {
+        self.outgoing.len()
+    }
+}
+
+/// Power-iteration PageRank; returns the scores and the iteration count
+fn pagerank(adjacency: &Adjacency, config: &CentralityConfig) -> (Vec<f64>, usize) {
+    let n = adjacency.len();
+    if n == 0 {
+        return (Vec::new(), 0);
+    }
+
+    let nf = n as f64;
+    let damping = config.damping.clamp(0.0, 1.0);
+    let mut rank = vec![1.0 / nf; n];
+    let mut iterations = 0;
+
+    while iterations < config.max_iterations {
+        iterations += 1;
+
+        // Notes without outgoing links teleport their rank uniformly
+        let dangling: f64 = (0..n)
+            .filter(|&u| adjacency.outgoing[u].is_empty())
+            .map(|u| rank[u])
+            .sum();
+        let base = (1.0 - damping) / nf + damping * dangling / nf;
+
+        let mut next = vec![base; n];
+        for (u, targets) in adjacency.outgoing.iter().enumerate() {
+            if targets.is_empty() {
+                continue;
+            }
+            let share = damping * rank[u] / targets.len() as f64;
+            for &v in targets {
+                next[v] += share;
+            }
+        }
+
+        let change: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if change < config.tolerance * nf {
+            break;
+        }
+    }
+
+    (rank, iterations)
+}
+
+/// Brandes betweenness from the given sources, scaled up when sampled
+fn betweenness(adjacency: &Adjacency, sources: &[usize]) -> Vec<f64> {
+    let n = adjacency.len();
+    let mut centrality = vec![0.0; n];
+    if n < 3 || sources.is_empty() {
+        return centrality;
+    }
+
+    let mut stack = Vec::with_capacity(n);
+    let mut queue = VecDeque::with_capacity(n);
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut paths = vec![0.0f64; n];
+    let mut distance = vec![usize::MAX; n];
+    let mut dependency = vec![0.0f64; n];
+
+    for &source in sources {
+        stack.clear();
+        for v in 0..n {
+            predecessors[v].clear();
+            paths[v] = 0.0;
+            distance[v] = usize::MAX;
+            dependency[v] = 0.0;
+        }
+        paths[source] = 1.0;
+        distance[source] = 0;
+        queue.push_back(source);
+
+        while let Some(v) = queue.pop_front() {
+            stack.push(v);
+            for &w in &adjacency.outgoing[v] {
+                if distance[w] == usize::MAX {
+                    distance[w] = distance[v] + 1;
+                    queue.push_back(w);
+                }
+                if distance[w] == distance[v] + 1 {
+                    paths[w] += paths[v];
+                    predecessors[w].push(v);
+                }
+            }
+        }
+
+        while let Some(w) = stack.pop() {
+            for &v in &predecessors[w] {
+                dependency[v] += paths[v] / paths[w] * (1.0 + dependency[w]);
+            }
+            if w != source {
+                centrality[w] += dependency[w];
+            }
+        }
+    }
+
+    let nf = n as f64;
+    let scale = (nf / sources.len() as f64) / ((nf - 1.0) * (nf - 2.0));
+    for value in &mut centrality {
+        *value *= scale;
+    }
+    centrality
+}
+
+/// Harmonic closeness over incoming shortest paths
+fn harmonic_closeness(adjacency: &Adjacency) -> Vec<f64> {
+    let n = adjacency.len();
+    if n < 2 {
+        return vec![0.0; n];
+    }
+
+    let mut distance = vec![usize::MAX; n];
+    let mut queue = VecDeque::with_capacity(n);
+    let mut closeness = vec![0.0; n];
+
+    for (target, score) in closeness.iter_mut().enumerate() {
+        distance.fill(usize::MAX);
+        distance[target] = 0;
+        queue.push_back(target);
+
+        // Walk links backwards: distances from every note to `target`
+        while let Some(v) = queue.pop_front() {
+            for &w in &adjacency.incoming[v] {
+                if distance[w] == usize::MAX {
+                    distance[w] = distance[v] + 1;
+                    *score += 1.0 / distance[w] as f64;
+                    queue.push_back(w);
+                }
+            }
+        }
+
+        *score /= (n - 1) as f64;
+    }
+
+    closeness
+}
+
+/// Evenly spaced source notes in path order, so samples are reproducible
+fn sample_sources<E>(graph: &DiGraph<PathBuf, E>, samples: usize) -> Vec<usize> {
+    let mut nodes: Vec<usize> = graph.node_indices().map(|idx| idx.index()).collect();
+    nodes.sort_by(|&a, &b| graph[NodeIndex::new(a)].cmp(&graph[NodeIndex::new(b)]));
+
+    let step = nodes.len() as f64 / samples as f64;
+    (0..samples.min(nodes.len()))
+        .map(|i| nodes[(i as f64 * step) as usize])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(nodes: &[&str], edges: &[(usize, usize)]) -> DiGraph<PathBuf, ()> {
+        let mut graph = DiGraph::new();
+        let idx: Vec<_> = nodes
+            .iter()
+            .map(|n| graph.add_node(PathBuf::from(format!("{n}.md"))))
+            .collect();
+        for &(a, b) in edges {
+            graph.add_edge(idx[a], idx[b], ());
+        }
+        graph
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn test_pagerank_cycle_is_uniform() {
+        let g = graph(&["a", "b", "c"], &[(0, 1), (1, 2), (2, 0)]);
+        let c = Centrality::compute(&g, &CentralityConfig::default());
+        for score in c.pagerank.values() {
+            assert!(close(*score, 1.0 / 3.0));
+        }
+    }
+
+    #[test]
+    fn test_pagerank_handles_dangling_notes() {
+        // a -> b, c -> b; b links nowhere
+        let g = graph(&["a", "b", "c"], &[(0, 1), (2, 1), (2, 1)]);
+        let c = Centrality::compute(&g, &CentralityConfig::default());
+
+        let total: f64 = c.pagerank.values().sum();
+        assert!(close(total, 1.0));
+
+        // Matches networkx.pagerank on the same graph
+        assert!(close(
+            c.score(CentralityMetric::PageRank, Path::new("b.md")),
+            0.57447
+        ));
+        assert!(close(
+            c.score(CentralityMetric::PageRank, Path::new("a.md")),
+            0.21277
+        ));
+        assert_eq!(
+            c.ranking(CentralityMetric::PageRank)[0].0,
+            PathBuf::from("b.md")
+        );
+    }
+
+    #[test]
+    fn test_betweenness_on_a_path() {
+        // a -> b -> c -> d: b and c sit on every through-path
+        let g = graph(&["a", "b", "c", "d"], &[(0, 1), (1, 2), (2, 3)]);
+        let c = Centrality::compute(&g, &CentralityConfig::default());
+
+        // b lies on a->c and a->d; normalized by (n-1)(n-2) = 6
+        assert!(close(
+            c.score(CentralityMetric::Betweenness, Path::new("b.md")),
+            2.0 / 6.0
+        ));
+        assert!(close(
+            c.score(CentralityMetric::Betweenness, Path::new("c.md")),
+            2.0 / 6.0
+        ));
+        assert_eq!(
+            c.score(CentralityMetric::Betweenness, Path::new("a.md")),
+            0.0
+        );
+        assert_eq!(
+            c.score(CentralityMetric::Betweenness, Path::new("d.md")),
+            0.0
+        );
+        assert!(!c.is_sampled());
+    }
+
+    #[test]
+    fn test_betweenness_splits_between_equal_paths() {
+        // a -> b -> d and a -> c -> d
+        let g = graph(&["a", "b", "c", "d"], &[(0, 1), (0, 2), (1, 3), (2, 3)]);
+        let c = Centrality::compute(&g, &CentralityConfig::default());
+        assert!(close(
+            c.score(CentralityMetric::Betweenness, Path::new("b.md")),
+            0.5 / 6.0
+        ));
+        assert!(close(
+            c.score(CentralityMetric::Betweenness, Path::new("c.md")),
+            0.5 / 6.0
+        ));
+    }
+
+    #[test]
+    fn test_sampled_betweenness() {
+        let names: Vec<String> = (0..20).map(|i| format!("n{i:02}")).collect();
+        let refs: Vec<&str> = names.iter().map(String::as_str).collect();
+        let edges: Vec<(usize, usize)> = (0..20).map(|i| (i, (i + 1) % 20)).collect();
+        let g = graph(&refs, &edges);
+
+        let config = CentralityConfig {
+            betweenness_sample_threshold: 10,
+            betweenness_samples: 5,
+            ..CentralityConfig::default()
+        };
+        let c = Centrality::compute(&g, &config);
+        assert!(c.is_sampled());
+        assert_eq!(c.betweenness_sources, 5);
+
+        // A directed ring is symmetric, so the estimate's mean is exact
+        let exact = Centrality::compute(&g, &CentralityConfig::default());
+        let mean = |c: &Centrality| c.betweenness.values().sum::<f64>() / 20.0;
+        assert!(close(mean(&c), mean(&exact)));
+    }
+
+    #[test]
+    fn test_harmonic_closeness() {
+        // a -> b -> c; nothing reaches a
+        let g = graph(&["a", "b", "c"], &[(0, 1), (1, 2)]);
+        let c = Centrality::compute(&g, &CentralityConfig::default());
+        assert_eq!(c.score(CentralityMetric::Closeness, Path::new("a.md")), 0.0);
+        assert!(close(
+            c.score(CentralityMetric::Closeness, Path::new("b.md")),
+            0.5
+        ));
+        assert!(close(
+            c.score(CentralityMetric::Closeness, Path::new("c.md")),
+            0.75
+        ));
+    }
+
+    #[test]
+    fn test_metric_names() {
+        assert_eq!(
+            CentralityMetric::from_name("PageRank"),
+            Some(CentralityMetric::PageRank)
+        );
+        assert_eq!(
+            CentralityMetric::from_name("page_rank"),
+            Some(CentralityMetric::PageRank)
+        );
+        assert_eq!(
+            CentralityMetric::from_name("harmonic"),
+            Some(CentralityMetric::Closeness)
+        );
+        assert_eq!(CentralityMetric::from_name("eigenvector"), None);
+        assert_eq!(CentralityMetric::Betweenness.as_str(), "betweenness");
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let g = graph(&[], &[]);
+        let c = Centrality::compute(&g, &CentralityConfig::default());
+        assert_eq!(c.node_count, 0);
+        assert!(c.ranking(CentralityMetric::PageRank).is_empty());
+    }
+}
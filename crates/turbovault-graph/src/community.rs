@@ -0,0 +1,732 @@
+//! Community detection: topic clusters in a well-linked vault
+//!
+//! Uses the Louvain method on the undirected view of the link graph, where
+//! a pair of notes linking each other weighs twice as much as a one-way
+//! link. Notes are visited in path order so results are reproducible.
+//!
+//! Each community is described by its most central notes (PageRank), its
+//! dominant tags and its folder distribution, which makes it possible to
+//! flag notes filed in a different folder than the rest of their cluster.
+
+use crate::centrality::CentralityConfig;
+use crate::graph::LinkGraph;
+use petgraph::graph::NodeIndex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Parameters for community detection
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommunityConfig {
+    /// Modularity resolution; higher values give smaller communities
+    pub resolution: f64,
+    /// Upper bound on local-moving sweeps per level
+    pub max_sweeps: usize,
+    /// Communities smaller than this are reported as unclustered notes
+    pub min_size: usize,
+    /// Central notes listed per community
+    pub central_notes: usize,
+    /// Dominant tags listed per community
+    pub top_tags: usize,
+}
+
+impl Default for CommunityConfig {
+    fn default() -> Self {
+        Self {
+            resolution: 1.0,
+            max_sweeps: 32,
+            min_size: 2,
+            central_notes: 5,
+            top_tags: 5,
+        }
+    }
+}
+
+/// Number of notes in a community with a given tag
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+/// Number of notes in a community filed in a given folder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FolderCount {
+    /// Folder relative to the vault root (empty for the root itself)
+    pub folder: String,
+    pub count: usize,
+}
+
+/// A cluster of densely linked notes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Community {
+    /// Index in [`CommunityReport::communities`] (largest first)
+    pub id: usize,
+    pub size: usize,
+    /// Member notes in path order
+    pub notes: Vec<PathBuf>,
+    /// Members with the highest PageRank, most central first
+    pub central_notes: Vec<PathBuf>,
+    /// Most common tags among members, by number of notes
+    pub dominant_tags: Vec<TagCount>,
+    /// Folders of members, most common first
+    pub folders: Vec<FolderCount>,
+    /// Links between members
+    pub internal_links: usize,
+    /// Links between a member and a note outside the community
+    pub external_links: usize,
+}
+
+impl Community {
+    /// The folder holding most members and its share of the community
+    pub fn dominant_folder(&self) -> Option<(&str, f64)> {
+        self.folders
+            .first()
+            .map(|f| (f.folder.as_str(), f.count as f64 / self.size.max(1) as f64))
+    }
+}
+
+/// A note filed outside the folder most of its community lives in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderMismatch {
+    pub path: PathBuf,
+    /// The note's folder relative to the vault root
+    pub folder: String,
+    pub community: usize,
+    /// Dominant folder of the community
+    pub expected_folder: String,
+    /// Share of the community in the dominant folder
+    pub share: f64,
+}
+
+/// Result of community detection over the vault
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommunityReport {
+    /// Communities of at least `min_size` notes, largest first
+    pub communities: Vec<Community>,
+    /// Notes in communities below `min_size` (usually orphans)
+    pub unclustered: Vec<PathBuf>,
+    /// Newman modularity of the partition (-0.5 to 1.0)
+    pub modularity: f64,
+    pub note_count: usize,
+    /// Root that folders are relative to
+    #[serde(skip)]
+    pub vault_root: PathBuf,
+}
+
+impl CommunityReport {
+    /// Community id of a note, if it belongs to a reported community
+    pub fn community_of(&self, path: &Path) -> Option<usize> {
+        self.communities
+            .iter()
+            .find(|c| c.notes.iter().any(|n| n == path))
+            .map(|c| c.id)
+    }
+
+    /// Notes whose folder disagrees with their community.
+    ///
+    /// Only communities of at least three notes whose dominant folder holds
+    /// `min_share` (0.0-1.0) of their members are considered.
+    pub fn folder_mismatches(&self, min_share: f64) -> Vec<FolderMismatch> {
+        let mut mismatches = Vec::new();
+        for community in &self.communities {
+            if community.size < 3 {
+                continue;
+            }
+            let Some((expected, share)) = community.dominant_folder() else {
+                continue;
+            };
+            if share < min_share || share >= 1.0 {
+                continue;
+            }
+            for note in &community.notes {
+                let folder = note_folder(note, &self.vault_root);
+                if folder != expected {
+                    mismatches.push(FolderMismatch {
+                        path: note.clone(),
+                        folder,
+                        community: community.id,
+                        expected_folder: expected.to_string(),
+                        share,
+                    });
+                }
+            }
+        }
+        mismatches
+    }
+}
+
+/// Detects communities in a link graph
+pub struct CommunityDetector<'a> {
+    graph: &'a LinkGraph,
+    tags: Option<&'a HashMap<PathBuf, Vec<String>>>,
+    vault_root: Option<&'a Path>,
+    config: CommunityConfig,
+}
+
+impl<'a> CommunityDetector<'a> {
+    /// Create a detector with the default config
+    pub fn new(graph: &'a LinkGraph) -> Self {
+        Self {
+            graph,
+            tags: None,
+            vault_root: None,
+            config: CommunityConfig::default(),
+        }
+    }
+
+    /// Tags of each note, used for [`Community::dominant_tags`]
+    pub fn with_tags(mut self, tags: &'a HashMap<PathBuf, Vec<String>>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Vault root that folders are reported relative to
+    pub fn with_vault_root(mut self, vault_root: &'a Path) -> Self {
+        self.vault_root = Some(vault_root);
+        self
+    }
+
+    /// Override detection parameters
+    pub fn with_config(mut self, config: CommunityConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Partition the vault into communities
+    pub fn detect(&self) -> CommunityReport {
+        let graph = self.graph.digraph();
+        let root = self.vault_root.unwrap_or(Path::new(""));
+
+        // Visit notes in path order so the partition is reproducible
+        let mut paths: Vec<(PathBuf, NodeIndex)> = graph
+            .node_indices()
+            .map(|idx| (graph[idx].clone(), idx))
+            .collect();
+        paths.sort();
+        let position: HashMap<NodeIndex, usize> = paths
+            .iter()
+            .enumerate()
+            .map(|(pos, (_, idx))| (*idx, pos))
+            .collect();
+        let paths: Vec<PathBuf> = paths.into_iter().map(|(path, _)| path).collect();
+
+        // Distinct directed links; a mutual pair becomes one edge of weight 2
+        let mut directed: HashSet<(usize, usize)> = HashSet::new();
+        let mut pairs: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+        for edge in graph.raw_edges() {
+            let (a, b) = (position[&edge.source()], position[&edge.target()]);
+            if a != b && directed.insert((a, b)) {
+                *pairs.entry((a.min(b), a.max(b))).or_insert(0.0) += 1.0;
+            }
+        }
+        let network = Network::from_pairs(paths.len(), &pairs);
+        let labels = louvain(&network, &self.config);
+        let modularity = network.modularity(&labels, self.config.resolution);
+
+        let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (node, &label) in labels.iter().enumerate() {
+            members.entry(label).or_default().push(node);
+        }
+        let mut groups: Vec<Vec<usize>> = members.into_values().collect();
+        groups.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a[0].cmp(&b[0])));
+
+        // (internal, external) links per community
+        let mut link_counts: HashMap<usize, (usize, usize)> = HashMap::new();
+        for &(a, b) in &directed {
+            if labels[a] == labels[b] {
+                link_counts.entry(labels[a]).or_default().0 += 1;
+            } else {
+                link_counts.entry(labels[a]).or_default().1 += 1;
+                link_counts.entry(labels[b]).or_default().1 += 1;
+            }
+        }
+
+        let centrality = self.graph.centrality(&CentralityConfig::default());
+        let mut communities = Vec::new();
+        let mut unclustered = Vec::new();
+        for group in groups {
+            if group.len() < self.config.min_size.max(1) {
+                unclustered.extend(group.iter().map(|&n| paths[n].clone()));
+                continue;
+            }
+
+            let (internal_links, external_links) = link_counts
+                .get(&labels[group[0]])
+                .copied()
+                .unwrap_or((0, 0));
+
+            let notes: Vec<PathBuf> = group.iter().map(|&n| paths[n].clone()).collect();
+            let mut central: Vec<&PathBuf> = notes.iter().collect();
+            central.sort_by(|a, b| {
+                centrality
+                    .pagerank
+                    .get(*b)
+                    .unwrap_or(&0.0)
+                    .total_cmp(centrality.pagerank.get(*a).unwrap_or(&0.0))
+                    .then_with(|| a.cmp(b))
+            });
+
+            communities.push(Community {
+                id: communities.len(),
+                size: notes.len(),
+                central_notes: central
+                    .into_iter()
+                    .take(self.config.central_notes)
+                    .cloned()
+                    .collect(),
+                dominant_tags: self.dominant_tags(&notes),
+                folders: self.folders(&notes, root),
+                notes,
+                internal_links,
+                external_links,
+            });
+        }
+
+        CommunityReport {
+            communities,
+            unclustered,
+            modularity,
+            note_count: paths.len(),
+            vault_root: root.to_path_buf(),
+        }
+    }
+
+    /// Tags shared by the most members (case-insensitive, first spelling wins)
+    fn dominant_tags(&self, notes: &[PathBuf]) -> Vec<TagCount> {
+        let Some(tags) = self.tags else {
+            return Vec::new();
+        };
+
+        let mut counts: BTreeMap<String, TagCount> = BTreeMap::new();
+        for note in notes {
+            let mut seen: Vec<String> = Vec::new();
+            for tag in tags.get(note).into_iter().flatten() {
+                let key = tag.to_lowercase();
+                if seen.contains(&key) {
+                    continue;
+                }
+                seen.push(key.clone());
+                counts
+                    .entry(key)
+                    .or_insert_with(|| TagCount {
+                        tag: tag.clone(),
+                        count: 0,
+                    })
+                    .count += 1;
+            }
+        }
+
+        let mut counts: Vec<TagCount> = counts.into_values().collect();
+        counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        counts.truncate(self.config.top_tags);
+        counts
+    }
+
+    /// Folder distribution, most common first
+    fn folders(&self, notes: &[PathBuf], root: &Path) -> Vec<FolderCount> {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for note in notes {
+            *counts.entry(note_folder(note, root)).or_insert(0) += 1;
+        }
+
+        let mut folders: Vec<FolderCount> = counts
+            .into_iter()
+            .map(|(folder, count)| FolderCount { folder, count })
+            .collect();
+        folders.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.folder.cmp(&b.folder)));
+        folders
+    }
+}
+
+/// Folder of a note relative to the vault root, with `/` separators
+fn note_folder(note: &Path, root: &Path) -> String {
+    let relative = note.strip_prefix(root).unwrap_or(note);
+    relative
+        .parent()
+        .map(|p| {
+            p.components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default()
+}
+
+/// Undirected weighted graph used by Louvain
+pub(crate) struct Network {
+    /// Neighbours and edge weights, excluding self-loops
+    neighbours: Vec<Vec<(usize, f64)>>,
+    /// Weight of edges folded into each node by aggregation
+    self_loops: Vec<f64>,
+    /// Total edge weight (m)
+    total_weight: f64,
+}
+
+impl Network {
+    pub(crate) fn from_pairs(n: usize, pairs: &BTreeMap<(usize, usize), f64>) -> Self {
+        let mut neighbours = vec![Vec::new(); n];
+        let mut total_weight = 0.0;
+        for (&(a, b), &weight) in pairs {
+            neighbours[a].push((b, weight));
+            neighbours[b].push((a, weight));
+            total_weight += weight;
+        }
+        Self {
+            neighbours,
+            self_loops: vec![0.0; n],
+            total_weight,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    /// Weighted degree; a self-loop counts twice
+    fn degree(&self, node: usize) -> f64 {
+        2.0 * self.self_loops[node] + self.neighbours[node].iter().map(|(_, w)| w).sum::<f64>()
+    }
+
+    /// Collapse each community into one node
+    fn aggregate(&self, labels: &[usize], communities: usize) -> Self {
+        let mut weights: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); communities];
+        let mut self_loops = vec![0.0; communities];
+        for node in 0..self.len() {
+            let c = labels[node];
+            self_loops[c] += self.self_loops[node];
+            for &(other, weight) in &self.neighbours[node] {
+                let d = labels[other];
+                if c == d {
+                    // Each internal edge is seen from both ends
+                    self_loops[c] += weight / 2.0;
+                } else {
+                    *weights[c].entry(d).or_insert(0.0) += weight;
+                }
+            }
+        }
+        Self {
+            neighbours: weights
+                .into_iter()
+                .map(|w| w.into_iter().collect())
+                .collect(),
+            self_loops,
+            total_weight: self.total_weight,
+        }
+    }
+
+    /// Newman modularity of a partition of this network
+    pub(crate) fn modularity(&self, labels: &[usize], resolution: f64) -> f64 {
+        if self.total_weight == 0.0 {
+            return 0.0;
+        }
+        let communities = labels.iter().copied().max().map_or(0, |m| m + 1);
+        let mut internal = vec![0.0; communities];
+        let mut degree = vec![0.0; communities];
+        for node in 0..self.len() {
+            let c = labels[node];
+            internal[c] += self.self_loops[node];
+            degree[c] += self.degree(node);
+            for &(other, weight) in &self.neighbours[node] {
+                if labels[other] == c {
+                    internal[c] += weight / 2.0;
+                }
+            }
+        }
+        let m = self.total_weight;
+        internal
+            .iter()
+            .zip(&degree)
+            .map(|(l, d)| l / m - resolution * (d / (2.0 * m)).powi(2))
+            .sum()
+    }
+}
+
+/// Louvain community detection; returns a dense community label per node
+pub(crate) fn louvain(network: &Network, config: &CommunityConfig) -> Vec<usize> {
+    let mut labels: Vec<usize> = (0..network.len()).collect();
+    if network.total_weight == 0.0 {
+        return labels;
+    }
+
+    let mut level = None;
+    loop {
+        let current = level.as_ref().unwrap_or(network);
+        let (moved, local) = local_moving(current, config);
+        if !moved {
+            break;
+        }
+        let (local, count) = relabel(&local);
+        for label in labels.iter_mut() {
+            *label = local[*label];
+        }
+        if count == current.len() {
+            break;
+        }
+        level = Some(current.aggregate(&local, count));
+    }
+
+    relabel(&labels).0
+}
+
+/// One Louvain level: greedily move nodes to the neighbouring community
+/// with the best modularity gain until nothing moves
+fn local_moving(network: &Network, config: &CommunityConfig) -> (bool, Vec<usize>) {
+    let n = network.len();
+    let two_m = 2.0 * network.total_weight;
+    let degree: Vec<f64> = (0..n).map(|node| network.degree(node)).collect();
+    let mut community: Vec<usize> = (0..n).collect();
+    let mut totals = degree.clone();
+    let mut moved_any = false;
+
+    let mut links_to: Vec<f64> = vec![0.0; n];
+    let mut touched: Vec<usize> = Vec::new();
+
+    for _ in 0..config.max_sweeps {
+        let mut moved = false;
+        for node in 0..n {
+            let current = community[node];
+            totals[current] -= degree[node];
+
+            for &c in &touched {
+                links_to[c] = 0.0;
+            }
+            touched.clear();
+            for &(other, weight) in &network.neighbours[node] {
+                let c = community[other];
+                if links_to[c] == 0.0 {
+                    touched.push(c);
+                }
+                links_to[c] += weight;
+            }
+
+            let gain =
+                |c: usize| links_to[c] - config.resolution * totals[c] * degree[node] / two_m;
+            let mut best = current;
+            let mut best_gain = gain(current);
+            for &c in &touched {
+                let g = gain(c);
+                // Only strictly better moves, so ties keep the node in place
+                if g > best_gain + 1e-12 {
+                    best = c;
+                    best_gain = g;
+                }
+            }
+
+            totals[best] += degree[node];
+            if best != current {
+                community[node] = best;
+                moved = true;
+                moved_any = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    (moved_any, community)
+}
+
+/// Renumber labels densely in order of first appearance
+fn relabel(labels: &[usize]) -> (Vec<usize>, usize) {
+    let mut mapping: HashMap<usize, usize> = HashMap::new();
+    let relabelled = labels
+        .iter()
+        .map(|&label| {
+            let next = mapping.len();
+            *mapping.entry(label).or_insert(next)
+        })
+        .collect();
+    (relabelled, mapping.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use turbovault_core::prelude::*;
+
+    fn note(path: &str, links: &[&str]) -> VaultFile {
+        let mut file = VaultFile::new(
+            PathBuf::from(path),
+            String::new(),
+            FileMetadata {
+                path: PathBuf::from(path),
+                size: 0,
+                created_at: 0.0,
+                modified_at: 0.0,
+                checksum: String::new(),
+                is_attachment: false,
+            },
+        );
+        file.links = links
+            .iter()
+            .map(|target| {
+                Link::new(
+                    LinkType::WikiLink,
+                    PathBuf::from(path),
+                    target.to_string(),
+                    SourcePosition::start(),
+                )
+            })
+            .collect();
+        file
+    }
+
+    fn build(files: &[VaultFile]) -> LinkGraph {
+        let mut graph = LinkGraph::new();
+        for file in files {
+            graph.add_file(file).unwrap();
+        }
+        for file in files {
+            graph.update_links(file).unwrap();
+        }
+        graph
+    }
+
+    /// Two tightly linked topics joined by one link, plus an orphan
+    fn two_topics() -> Vec<VaultFile> {
+        vec![
+            note(
+                "/v/rust/ownership.md",
+                &["borrowing", "lifetimes", "traits"],
+            ),
+            note("/v/rust/borrowing.md", &["ownership", "lifetimes"]),
+            note("/v/rust/lifetimes.md", &["ownership", "borrowing"]),
+            note("/v/cooking/traits.md", &["ownership", "generics"]),
+            note("/v/rust/generics.md", &["traits", "ownership"]),
+            note("/v/cooking/bread.md", &["yeast", "flour", "oven"]),
+            note("/v/cooking/yeast.md", &["bread", "flour"]),
+            note("/v/cooking/flour.md", &["bread", "yeast", "oven"]),
+            note("/v/cooking/oven.md", &["bread", "flour", "ownership"]),
+            note("/v/inbox/orphan.md", &[]),
+        ]
+    }
+
+    #[test]
+    fn test_detects_topics_in_one_component() {
+        let graph = build(&two_topics());
+        let report = CommunityDetector::new(&graph)
+            .with_vault_root(Path::new("/v"))
+            .detect();
+
+        assert_eq!(report.note_count, 10);
+        assert_eq!(report.communities.len(), 2);
+        assert_eq!(
+            report.unclustered,
+            vec![PathBuf::from("/v/inbox/orphan.md")]
+        );
+        assert!(report.modularity > 0.3);
+
+        let rust = report
+            .community_of(Path::new("/v/rust/ownership.md"))
+            .unwrap();
+        let cooking = report
+            .community_of(Path::new("/v/cooking/bread.md"))
+            .unwrap();
+        assert_ne!(rust, cooking);
+        assert_eq!(
+            report.community_of(Path::new("/v/cooking/traits.md")),
+            Some(rust)
+        );
+        assert_eq!(report.communities[rust].size, 5);
+        assert_eq!(
+            report.communities[rust].central_notes[0],
+            PathBuf::from("/v/rust/ownership.md")
+        );
+        assert_eq!(report.communities[rust].external_links, 1);
+        assert_eq!(
+            report.communities[cooking].folders,
+            vec![FolderCount {
+                folder: "cooking".to_string(),
+                count: 4
+            }]
+        );
+    }
+
+    #[test]
+    fn test_folder_mismatches() {
+        let graph = build(&two_topics());
+        let report = CommunityDetector::new(&graph)
+            .with_vault_root(Path::new("/v"))
+            .detect();
+
+        let mismatches = report.folder_mismatches(0.6);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].path, PathBuf::from("/v/cooking/traits.md"));
+        assert_eq!(mismatches[0].folder, "cooking");
+        assert_eq!(mismatches[0].expected_folder, "rust");
+        assert!((mismatches[0].share - 0.8).abs() < 1e-9);
+
+        // A stricter threshold ignores mixed communities
+        assert!(report.folder_mismatches(0.9).is_empty());
+    }
+
+    #[test]
+    fn test_dominant_tags() {
+        let graph = build(&two_topics());
+        let mut tags = HashMap::new();
+        for (path, note_tags) in [
+            ("/v/cooking/bread.md", vec!["baking", "food"]),
+            ("/v/cooking/yeast.md", vec!["Baking"]),
+            ("/v/cooking/flour.md", vec!["baking", "baking"]),
+        ] {
+            tags.insert(
+                PathBuf::from(path),
+                note_tags.into_iter().map(String::from).collect(),
+            );
+        }
+        let report = CommunityDetector::new(&graph).with_tags(&tags).detect();
+        let cooking = report
+            .community_of(Path::new("/v/cooking/bread.md"))
+            .unwrap();
+        assert_eq!(
+            report.communities[cooking].dominant_tags,
+            vec![
+                TagCount {
+                    tag: "baking".to_string(),
+                    count: 3
+                },
+                TagCount {
+                    tag: "food".to_string(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_modularity_matches_definition() {
+        // Two triangles joined by one edge: Q = 2 * (3/7 - (7/14)^2) = 5/14
+        let network = Network::from_pairs(
+            6,
+            &[
+                ((0, 1), 1.0),
+                ((0, 2), 1.0),
+                ((1, 2), 1.0),
+                ((2, 3), 1.0),
+                ((3, 4), 1.0),
+                ((3, 5), 1.0),
+                ((4, 5), 1.0),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let labels = louvain(&network, &CommunityConfig::default());
+        assert_eq!(labels, vec![0, 0, 0, 1, 1, 1]);
+        assert!((network.modularity(&labels, 1.0) - 5.0 / 14.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_and_linkless_graphs() {
+        let graph = LinkGraph::new();
+        let report = CommunityDetector::new(&graph).detect();
+        assert!(report.communities.is_empty());
+        assert_eq!(report.modularity, 0.0);
+
+        let graph = build(&[note("/v/a.md", &[]), note("/v/b.md", &[])]);
+        let report = CommunityDetector::new(&graph).detect();
+        assert!(report.communities.is_empty());
+        assert_eq!(report.unclustered.len(), 2);
+    }
+}
@@ -1,18 +1,106 @@
 //! Link graph using petgraph for vault relationship analysis
 
+use crate::centrality::{Centrality, CentralityConfig};
+use crate::paths::PathDirection;
+use crate::resolve::{normalize, percent_decode};
 use petgraph::algo::kosaraju_scc;
 use petgraph::prelude::*;
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use turbovault_core::prelude::*;
 
 /// Node index type for graph
 type NodeIndex = petgraph::graph::NodeIndex;
 
+/// Kind of link an edge was created from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    /// `[[Note]]`
+    WikiLink,
+    /// `![[Note]]`
+    Embed,
+    /// `[text](folder/note.md)`
+    MarkdownLink,
+    /// `[[Note#Heading]]` or `[text](note.md#heading)`
+    HeadingRef,
+    /// `[[Note#^block]]`
+    BlockRef,
+    /// `"[[Note]]"` value of a frontmatter property
+    Property,
+}
+
+impl EdgeKind {
+    /// Every edge kind
+    pub const ALL: [EdgeKind; 6] = [
+        EdgeKind::WikiLink,
+        EdgeKind::Embed,
+        EdgeKind::MarkdownLink,
+        EdgeKind::HeadingRef,
+        EdgeKind::BlockRef,
+        EdgeKind::Property,
+    ];
+
+    /// Edge kind for a link in note content (anchors and URLs aren't edges)
+    pub fn from_link_type(type_: &LinkType) -> Option<Self> {
+        match type_ {
+            LinkType::WikiLink => Some(EdgeKind::WikiLink),
+            LinkType::Embed => Some(EdgeKind::Embed),
+            LinkType::MarkdownLink => Some(EdgeKind::MarkdownLink),
+            LinkType::HeadingRef => Some(EdgeKind::HeadingRef),
+            LinkType::BlockRef => Some(EdgeKind::BlockRef),
+            LinkType::Anchor | LinkType::ExternalLink => None,
+        }
+    }
+
+    /// Parse a kind name such as `wikilink`, `markdown_link` or `property`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace('-', "_").as_str() {
+            "wikilink" | "wiki_link" => Some(EdgeKind::WikiLink),
+            "embed" => Some(EdgeKind::Embed),
+            "markdown" | "markdown_link" => Some(EdgeKind::MarkdownLink),
+            "heading" | "heading_ref" => Some(EdgeKind::HeadingRef),
+            "block" | "block_ref" => Some(EdgeKind::BlockRef),
+            "property" | "frontmatter" => Some(EdgeKind::Property),
+            _ => None,
+        }
+    }
+
+    /// Canonical snake_case name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdgeKind::WikiLink => "wiki_link",
+            EdgeKind::Embed => "embed",
+            EdgeKind::MarkdownLink => "markdown_link",
+            EdgeKind::HeadingRef => "heading_ref",
+            EdgeKind::BlockRef => "block_ref",
+            EdgeKind::Property => "property",
+        }
+    }
+
+    /// Whether this kind passes a filter; an empty filter matches every kind
+    pub fn matches(&self, kinds: &[EdgeKind]) -> bool {
+        kinds.is_empty() || kinds.contains(self)
+    }
+}
+
+/// A typed edge between two notes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkEdge {
+    pub kind: EdgeKind,
+    pub link: Link,
+    /// Frontmatter property holding the link, for [`EdgeKind::Property`] edges
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
+}
+
 /// Link graph for analyzing vault relationships
 pub struct LinkGraph {
-    /// Directed graph: nodes are file paths, edges are links
-    graph: DiGraph<PathBuf, Link>,
+    /// Directed graph: nodes are file paths, edges are typed links
+    graph: DiGraph<PathBuf, LinkEdge>,
 
     /// Map from file name (stem) to node index
     file_index: HashMap<String, NodeIndex>,
@@ -22,6 +110,9 @@ pub struct LinkGraph {
 
     /// Map from full path to node index (for quick lookups)
     path_index: HashMap<PathBuf, NodeIndex>,
+
+    /// Centrality scores, cleared whenever nodes or edges change
+    centrality: Mutex<Option<(CentralityConfig, Arc<Centrality>)>>,
 }
 
 impl LinkGraph {
@@ -32,11 +123,13 @@ impl LinkGraph {
             file_index: HashMap::new(),
             alias_index: HashMap::new(),
             path_index: HashMap::new(),
+            centrality: Mutex::new(None),
         }
     }
 
     /// Add a file to the graph
     pub fn add_file(&mut self, file: &VaultFile) -> Result<()> {
+        self.invalidate_centrality();
         let path = file.path.clone();
 
         // Create node if not exists
@@ -67,6 +160,8 @@ impl LinkGraph {
     /// Remove a file from the graph
     pub fn remove_file(&mut self, path: &PathBuf) -> Result<()> {
         if let Some(&idx) = self.path_index.get(path) {
+            self.invalidate_centrality();
+
             // Remove from all indices
             self.path_index.remove(path);
 
@@ -84,8 +179,13 @@ impl LinkGraph {
         Ok(())
     }
 
-    /// Add links from a parsed file to the graph
+    /// Add links from a parsed file to the graph.
+    ///
+    /// Every internal link kind becomes a typed edge: wikilinks, embeds,
+    /// relative markdown links, heading and block references, and
+    /// `[[Note]]` values of frontmatter properties.
     pub fn update_links(&mut self, file: &VaultFile) -> Result<()> {
+        self.invalidate_centrality();
         let source_path = &file.path;
 
         // Get or create source node
@@ -103,19 +203,64 @@ impl LinkGraph {
             self.graph.remove_edge(edge_id);
         }
 
-        // Add edges for each internal link (wikilinks and embeds)
-        for link in &file.links {
-            if matches!(link.type_, LinkType::WikiLink | LinkType::Embed)
-                && let Some(target_idx) = self.resolve_link(&link.target)
-            {
-                // Add edge (both nodes already exist from add_file)
-                self.graph.add_edge(source_idx, target_idx, link.clone());
+        let content_links = file.links.iter().filter_map(|link| {
+            EdgeKind::from_link_type(&link.type_).map(|kind| LinkEdge {
+                kind,
+                link: link.clone(),
+                property: None,
+            })
+        });
+        let property_links = property_links(file)
+            .into_iter()
+            .map(|(property, link)| LinkEdge {
+                kind: EdgeKind::Property,
+                link,
+                property: Some(property),
+            });
+
+        for edge in content_links.chain(property_links).collect::<Vec<_>>() {
+            if let Some(target_idx) = self.resolve_edge_target(&edge.link.target, source_path) {
+                self.graph.add_edge(source_idx, target_idx, edge);
             }
         }
 
         Ok(())
     }
 
+    /// Resolve a link target to a note, trying paths relative to the source first
+    fn resolve_edge_target(&self, target: &str, source: &Path) -> Option<NodeIndex> {
+        let file_part = target.split('#').next()?.trim();
+        if file_part.is_empty() {
+            // Same-document references aren't edges
+            return None;
+        }
+
+        let decoded = percent_decode(file_part);
+        for candidate in [file_part, decoded.as_str()] {
+            if let Some(parent) = source.parent() {
+                let path = normalize(&parent.join(candidate));
+                if let Some(&idx) = self.path_index.get(&path) {
+                    return Some(idx);
+                }
+                let mut with_ext = path.into_os_string();
+                with_ext.push(".md");
+                if let Some(&idx) = self.path_index.get(&PathBuf::from(with_ext)) {
+                    return Some(idx);
+                }
+            }
+            if let Some(idx) = self.resolve_link(candidate) {
+                return Some(idx);
+            }
+        }
+
+        None
+    }
+
+    /// Resolve a wikilink target (stem, alias or folder path) to a file in the graph
+    pub fn resolve_target(&self, target: &str) -> Option<PathBuf> {
+        self.resolve_link(target).map(|idx| self.graph[idx].clone())
+    }
+
     /// Resolve a wikilink target to a file path and node index
     fn resolve_link(&self, target: &str) -> Option<NodeIndex> {
         // Remove block/heading references
@@ -154,60 +299,83 @@ impl LinkGraph {
 
     /// Get all backlinks to a file (files that link to this file)
     pub fn backlinks(&self, path: &PathBuf) -> Result<Vec<(PathBuf, Vec<Link>)>> {
-        if let Some(&target_idx) = self.path_index.get(path) {
-            let backlinks: Vec<_> = self
-                .graph
-                .edges_directed(target_idx, Incoming)
-                .map(|edge| {
-                    let source_idx = edge.source();
-                    let source_path = self.graph[source_idx].clone();
-                    (source_path, edge.weight().clone())
-                })
-                .fold(HashMap::new(), |mut acc, (path, link)| {
-                    acc.entry(path).or_insert_with(Vec::new).push(link);
-                    acc
-                })
-                .into_iter()
-                .collect();
-
-            Ok(backlinks)
-        } else {
-            Ok(vec![])
-        }
+        Ok(Self::edge_links(self.backlinks_by_kind(path, &[])?))
     }
 
     /// Get all forward links from a file (files this file links to)
     pub fn forward_links(&self, path: &PathBuf) -> Result<Vec<(PathBuf, Vec<Link>)>> {
-        if let Some(&source_idx) = self.path_index.get(path) {
-            let forward_links: Vec<_> = self
-                .graph
-                .edges(source_idx)
-                .map(|edge| {
-                    let target_idx = edge.target();
-                    let target_path = self.graph[target_idx].clone();
-                    (target_path, edge.weight().clone())
-                })
-                .fold(HashMap::new(), |mut acc, (path, link)| {
-                    acc.entry(path).or_insert_with(Vec::new).push(link);
-                    acc
-                })
-                .into_iter()
-                .collect();
+        Ok(Self::edge_links(self.forward_links_by_kind(path, &[])?))
+    }
 
-            Ok(forward_links)
-        } else {
-            Ok(vec![])
-        }
+    /// Backlinks restricted to the given edge kinds (empty = all kinds)
+    pub fn backlinks_by_kind(
+        &self,
+        path: &PathBuf,
+        kinds: &[EdgeKind],
+    ) -> Result<Vec<(PathBuf, Vec<LinkEdge>)>> {
+        Ok(self.grouped_edges(path, Incoming, kinds))
+    }
+
+    /// Forward links restricted to the given edge kinds (empty = all kinds)
+    pub fn forward_links_by_kind(
+        &self,
+        path: &PathBuf,
+        kinds: &[EdgeKind],
+    ) -> Result<Vec<(PathBuf, Vec<LinkEdge>)>> {
+        Ok(self.grouped_edges(path, Outgoing, kinds))
+    }
+
+    /// Edges of a node in one direction, grouped by the note at the other end
+    fn grouped_edges(
+        &self,
+        path: &PathBuf,
+        direction: Direction,
+        kinds: &[EdgeKind],
+    ) -> Vec<(PathBuf, Vec<LinkEdge>)> {
+        let Some(&idx) = self.path_index.get(path) else {
+            return vec![];
+        };
+
+        self.graph
+            .edges_directed(idx, direction)
+            .filter(|edge| edge.weight().kind.matches(kinds))
+            .map(|edge| {
+                let other = match direction {
+                    Incoming => edge.source(),
+                    Outgoing => edge.target(),
+                };
+                (self.graph[other].clone(), edge.weight().clone())
+            })
+            .fold(HashMap::new(), |mut acc, (path, edge)| {
+                acc.entry(path).or_insert_with(Vec::new).push(edge);
+                acc
+            })
+            .into_iter()
+            .collect()
+    }
+
+    fn edge_links(grouped: Vec<(PathBuf, Vec<LinkEdge>)>) -> Vec<(PathBuf, Vec<Link>)> {
+        grouped
+            .into_iter()
+            .map(|(path, edges)| (path, edges.into_iter().map(|e| e.link).collect()))
+            .collect()
     }
 
     /// Find all orphaned notes (no incoming or outgoing links)
     pub fn orphaned_notes(&self) -> Vec<PathBuf> {
+        self.orphaned_notes_by_kind(&[])
+    }
+
+    /// Orphaned notes, counting only edges of the given kinds (empty = all kinds)
+    pub fn orphaned_notes_by_kind(&self, kinds: &[EdgeKind]) -> Vec<PathBuf> {
         self.graph
             .node_indices()
             .filter(|&idx| {
-                let in_degree = self.graph.edges_directed(idx, Incoming).count();
-                let out_degree = self.graph.edges(idx).count();
-                in_degree == 0 && out_degree == 0
+                let mut edges = self
+                    .graph
+                    .edges_directed(idx, Incoming)
+                    .chain(self.graph.edges(idx));
+                !edges.any(|e| e.weight().kind.matches(kinds))
             })
             .map(|idx| self.graph[idx].clone())
             .collect()
@@ -215,40 +383,69 @@ impl LinkGraph {
 
     /// Find related notes within N hops (breadth-first search)
     pub fn related_notes(&self, path: &PathBuf, max_hops: usize) -> Result<Vec<PathBuf>> {
-        if let Some(&start_idx) = self.path_index.get(path) {
-            let mut visited = HashSet::new();
-            let mut queue = vec![(start_idx, 0)];
-            let mut related = Vec::new();
-
-            visited.insert(start_idx);
+        self.related_notes_by_kind(path, max_hops, &[])
+    }
 
-            while let Some((idx, hops)) = queue.pop() {
-                if hops > 0 {
-                    related.push(self.graph[idx].clone());
-                }
+    /// Related notes within N hops, following only edges of the given kinds
+    /// (empty = all kinds). Links are followed both ways; results are ordered
+    /// by distance, then path.
+    pub fn related_notes_by_kind(
+        &self,
+        path: &PathBuf,
+        max_hops: usize,
+        kinds: &[EdgeKind],
+    ) -> Result<Vec<PathBuf>> {
+        if !self.path_index.contains_key(path) {
+            return Ok(vec![]);
+        }
 
-                if hops < max_hops {
-                    // Add all neighbors
-                    for neighbor_idx in self.graph.neighbors(idx) {
-                        if visited.insert(neighbor_idx) {
-                            queue.push((neighbor_idx, hops + 1));
-                        }
-                    }
-
-                    // Also traverse incoming edges
-                    for neighbor_idx in self.graph.edges_directed(idx, Incoming).map(|e| e.source())
-                    {
-                        if visited.insert(neighbor_idx) {
-                            queue.push((neighbor_idx, hops + 1));
-                        }
-                    }
-                }
-            }
+        let ego = self.ego_network(path, max_hops, PathDirection::Undirected, kinds)?;
+        Ok(ego.nodes.into_iter().map(|node| node.path).collect())
+    }
 
-            Ok(related)
-        } else {
-            Ok(vec![])
+    /// PageRank, betweenness and harmonic closeness for every note.
+    ///
+    /// Computed on first use and cached until the graph changes; a call with
+    /// a different config recomputes and replaces the cached scores.
+    pub fn centrality(&self, config: &CentralityConfig) -> Arc<Centrality> {
+        let mut cache = self
+            .centrality
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((cached_config, scores)) = cache.as_ref()
+            && cached_config == config
+        {
+            return Arc::clone(scores);
         }
+
+        let scores = Arc::new(Centrality::compute(&self.graph, config));
+        *cache = Some((config.clone(), Arc::clone(&scores)));
+        scores
+    }
+
+    /// Whether centrality scores are cached for the current graph
+    pub fn has_cached_centrality(&self) -> bool {
+        self.centrality
+            .lock()
+            .map(|cache| cache.is_some())
+            .unwrap_or(false)
+    }
+
+    fn invalidate_centrality(&mut self) {
+        *self
+            .centrality
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    }
+
+    /// The underlying petgraph graph
+    pub(crate) fn digraph(&self) -> &DiGraph<PathBuf, LinkEdge> {
+        &self.graph
+    }
+
+    /// Node of a note path
+    pub(crate) fn node_index(&self, path: &Path) -> Option<NodeIndex> {
+        self.path_index.get(path).copied()
     }
 
     /// Find strongly connected components (cycles in the graph)
@@ -305,7 +502,7 @@ impl LinkGraph {
             let links: Vec<Link> = self
                 .graph
                 .edges_directed(target_idx, Incoming)
-                .map(|edge| edge.weight().clone())
+                .map(|edge| edge.weight().link.clone())
                 .collect();
             Ok(links)
         } else {
@@ -319,7 +516,7 @@ impl LinkGraph {
             let links: Vec<Link> = self
                 .graph
                 .edges(source_idx)
-                .map(|edge| edge.weight().clone())
+                .map(|edge| edge.weight().link.clone())
                 .collect();
             Ok(links)
         } else {
@@ -336,7 +533,7 @@ impl LinkGraph {
             let links: Vec<Link> = self
                 .graph
                 .edges(node_idx)
-                .map(|edge| edge.weight().clone())
+                .map(|edge| edge.weight().link.clone())
                 .collect();
 
             if !links.is_empty() {
@@ -368,6 +565,42 @@ impl LinkGraph {
     }
 }
 
+/// Extract `[[Note]]` links from frontmatter property values.
+///
+/// Obsidian stores note links in properties as quoted wikilinks, either as
+/// a single text value or inside a list. Returns `(property, link)` pairs
+/// in key order.
+pub fn property_links(file: &VaultFile) -> Vec<(String, Link)> {
+    let Some(frontmatter) = &file.frontmatter else {
+        return Vec::new();
+    };
+
+    let mut keys: Vec<&String> = frontmatter.data.keys().collect();
+    keys.sort();
+
+    let mut links = Vec::new();
+    for key in keys {
+        let values: Vec<&Value> = match &frontmatter.data[key] {
+            Value::Array(items) => items.iter().collect(),
+            value => vec![value],
+        };
+        for value in values {
+            let Value::String(text) = value else {
+                continue;
+            };
+            if !text.contains("[[") {
+                continue;
+            }
+            for mut link in turbovault_parser::parse_wikilinks(text) {
+                link.source_file = file.path.clone();
+                link.position = frontmatter.position;
+                links.push((key.clone(), link));
+            }
+        }
+    }
+    links
+}
+
 impl Default for LinkGraph {
     fn default() -> Self {
         Self::new()
@@ -469,6 +702,174 @@ mod tests {
         assert_eq!(orphans[0], PathBuf::from("orphan.md"));
     }
 
+    fn create_typed_file(path: &str, links: Vec<(LinkType, &str)>) -> VaultFile {
+        let mut file = create_test_file(path, vec![]);
+        file.links = links
+            .into_iter()
+            .map(|(type_, target)| {
+                Link::new(
+                    type_,
+                    PathBuf::from(path),
+                    target.to_string(),
+                    SourcePosition::start(),
+                )
+            })
+            .collect();
+        file
+    }
+
+    #[test]
+    fn test_all_internal_link_kinds_become_edges() {
+        let mut graph = LinkGraph::new();
+        let mut source = create_typed_file(
+            "notes/source.md",
+            vec![
+                (LinkType::MarkdownLink, "../projects/plan.md"),
+                (LinkType::MarkdownLink, "sibling%20note.md"),
+                (LinkType::HeadingRef, "plan#Goals"),
+                (LinkType::BlockRef, "plan#^abc"),
+                (LinkType::Anchor, "#Local"),
+                (LinkType::ExternalLink, "https://example.com"),
+            ],
+        );
+        let mut data = HashMap::new();
+        data.insert("project".to_string(), serde_json::json!("[[plan]]"));
+        data.insert(
+            "related".to_string(),
+            serde_json::json!(["[[sibling note|Sibling]]", "plain text"]),
+        );
+        source.frontmatter = Some(Frontmatter {
+            data,
+            position: SourcePosition::start(),
+        });
+        let plan = create_test_file("projects/plan.md", vec![]);
+        let sibling = create_test_file("notes/sibling note.md", vec![]);
+
+        graph.add_file(&source).unwrap();
+        graph.add_file(&plan).unwrap();
+        graph.add_file(&sibling).unwrap();
+        graph.update_links(&source).unwrap();
+
+        // Anchors and external URLs aren't edges
+        assert_eq!(graph.edge_count(), 6);
+
+        let plan_path = PathBuf::from("projects/plan.md");
+        let mut kinds: Vec<EdgeKind> = graph
+            .backlinks_by_kind(&plan_path, &[])
+            .unwrap()
+            .into_iter()
+            .flat_map(|(_, edges)| edges.into_iter().map(|e| e.kind))
+            .collect();
+        kinds.sort_by_key(|k| k.as_str());
+        assert_eq!(
+            kinds,
+            vec![
+                EdgeKind::BlockRef,
+                EdgeKind::HeadingRef,
+                EdgeKind::MarkdownLink,
+                EdgeKind::Property
+            ]
+        );
+
+        let property_edges = graph
+            .forward_links_by_kind(&PathBuf::from("notes/source.md"), &[EdgeKind::Property])
+            .unwrap();
+        let mut properties: Vec<_> = property_edges
+            .iter()
+            .flat_map(|(path, edges)| {
+                edges
+                    .iter()
+                    .map(move |e| (path.clone(), e.property.clone().unwrap()))
+            })
+            .collect();
+        properties.sort();
+        assert_eq!(
+            properties,
+            vec![
+                (
+                    PathBuf::from("notes/sibling note.md"),
+                    "related".to_string()
+                ),
+                (PathBuf::from("projects/plan.md"), "project".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edge_kind_filters() {
+        let mut graph = LinkGraph::new();
+        let hub = create_typed_file("hub.md", vec![(LinkType::WikiLink, "a")]);
+        let mut b = create_test_file("b.md", vec![]);
+        let mut data = HashMap::new();
+        data.insert("up".to_string(), serde_json::json!("[[hub]]"));
+        b.frontmatter = Some(Frontmatter {
+            data,
+            position: SourcePosition::start(),
+        });
+        let a = create_test_file("a.md", vec![]);
+
+        for file in [&hub, &a, &b] {
+            graph.add_file(file).unwrap();
+        }
+        for file in [&hub, &a, &b] {
+            graph.update_links(file).unwrap();
+        }
+
+        assert!(graph.orphaned_notes().is_empty());
+        assert_eq!(
+            graph.orphaned_notes_by_kind(&[EdgeKind::WikiLink]),
+            vec![PathBuf::from("b.md")]
+        );
+
+        let related = graph
+            .related_notes_by_kind(&PathBuf::from("a.md"), 2, &[EdgeKind::WikiLink])
+            .unwrap();
+        assert_eq!(related, vec![PathBuf::from("hub.md")]);
+        let related = graph
+            .related_notes_by_kind(&PathBuf::from("a.md"), 2, &[])
+            .unwrap();
+        assert_eq!(related.len(), 2);
+
+        assert_eq!(
+            EdgeKind::from_name("markdown-link"),
+            Some(EdgeKind::MarkdownLink)
+        );
+        assert_eq!(EdgeKind::from_name("Property"), Some(EdgeKind::Property));
+        assert_eq!(EdgeKind::from_name("tag"), None);
+    }
+
+    #[test]
+    fn test_centrality_cached_until_graph_changes() {
+        let mut graph = LinkGraph::new();
+        let hub = create_test_file("hub.md", vec![]);
+        let a = create_test_file("a.md", vec!["hub"]);
+        let b = create_test_file("b.md", vec!["hub"]);
+        for file in [&hub, &a, &b] {
+            graph.add_file(file).unwrap();
+        }
+        for file in [&hub, &a, &b] {
+            graph.update_links(file).unwrap();
+        }
+
+        let config = CentralityConfig::default();
+        assert!(!graph.has_cached_centrality());
+        let first = graph.centrality(&config);
+        assert!(graph.has_cached_centrality());
+        assert!(Arc::ptr_eq(&first, &graph.centrality(&config)));
+        assert_eq!(
+            first.ranking(crate::CentralityMetric::PageRank)[0].0,
+            PathBuf::from("hub.md")
+        );
+
+        let c = create_test_file("c.md", vec!["a"]);
+        graph.add_file(&c).unwrap();
+        graph.update_links(&c).unwrap();
+        assert!(!graph.has_cached_centrality());
+        let second = graph.centrality(&config);
+        assert_eq!(second.node_count, 4);
+        assert!(!Arc::ptr_eq(&first, &second));
+    }
+
     #[test]
     fn test_graph_stats() {
         let mut graph = LinkGraph::new();
@@ -4,11 +4,25 @@
 //! finding orphaned notes, and analyzing connectivity patterns.
 
 use crate::graph::LinkGraph;
+use crate::resolve::AnchorIndex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use turbovault_core::{Link, Result};
 
+/// What part of a broken link failed to resolve
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrokenLinkKind {
+    /// The linked note or attachment doesn't exist
+    #[default]
+    MissingTarget,
+    /// The note exists but has no matching `#Heading`
+    MissingHeading,
+    /// The note exists but has no matching `#^block` ID
+    MissingBlock,
+}
+
 /// A broken link in the vault
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BrokenLink {
@@ -20,6 +34,9 @@ pub struct BrokenLink {
     pub line: usize,
     /// Suggested fixes
     pub suggestions: Vec<String>,
+    /// Whether the note itself or only a heading/block fragment is missing
+    #[serde(default)]
+    pub kind: BrokenLinkKind,
 }
 
 /// Health analysis report for the vault
@@ -29,8 +46,11 @@ pub struct HealthReport {
     pub total_notes: usize,
     /// Total number of links
     pub total_links: usize,
-    /// Broken links found
+    /// Broken links found (missing notes or attachments)
     pub broken_links: Vec<BrokenLink>,
+    /// Links to existing notes whose heading or block fragment is missing
+    #[serde(default)]
+    pub broken_fragments: Vec<BrokenLink>,
     /// Orphaned notes (no incoming or outgoing links)
     pub orphaned_notes: Vec<PathBuf>,
     /// Isolated clusters (groups of notes not connected to main graph)
@@ -50,6 +70,7 @@ impl HealthReport {
             total_notes: 0,
             total_links: 0,
             broken_links: Vec::new(),
+            broken_fragments: Vec::new(),
             orphaned_notes: Vec::new(),
             isolated_clusters: Vec::new(),
             hub_notes: Vec::new(),
@@ -67,8 +88,9 @@ impl HealthReport {
 
         let mut score = 100;
 
-        // Penalize broken links (up to -30 points)
-        let broken_ratio = self.broken_links.len() as f32 / self.total_links.max(1) as f32;
+        // Penalize broken links and fragments (up to -30 points)
+        let broken = self.broken_links.len() + self.broken_fragments.len();
+        let broken_ratio = (broken as f32 / self.total_links.max(1) as f32).min(1.0);
         score -= (broken_ratio * 30.0) as u8;
 
         // Penalize orphaned notes (up to -20 points)
@@ -102,23 +124,38 @@ impl Default for HealthReport {
 pub struct HealthAnalyzer<'a> {
     graph: &'a LinkGraph,
     files: Option<&'a HashMap<PathBuf, Vec<Link>>>,
+    anchors: Option<&'a AnchorIndex>,
 }
 
 impl<'a> HealthAnalyzer<'a> {
     /// Create a new health analyzer
     pub fn new(graph: &'a LinkGraph) -> Self {
-        Self { graph, files: None }
+        Self {
+            graph,
+            files: None,
+            anchors: None,
+        }
     }
 
     /// Create a new health analyzer with access to file links
-    /// (needed for detecting broken links that aren't in the graph)
+    /// (needed for detecting broken links that aren't in the graph).
+    ///
+    /// The links should come from a [`LinkResolver`](crate::LinkResolver)
+    /// pass so that `is_valid` reflects whether each target exists.
     pub fn with_files(graph: &'a LinkGraph, files: &'a HashMap<PathBuf, Vec<Link>>) -> Self {
         Self {
             graph,
             files: Some(files),
+            anchors: None,
         }
     }
 
+    /// Use note headings and block IDs to suggest fixes for broken fragments
+    pub fn with_anchors(mut self, anchors: &'a AnchorIndex) -> Self {
+        self.anchors = Some(anchors);
+        self
+    }
+
     /// Run a comprehensive health analysis
     pub fn analyze(&self) -> Result<HealthReport> {
         let mut report = HealthReport::new();
@@ -127,8 +164,11 @@ impl<'a> HealthAnalyzer<'a> {
         report.total_notes = self.graph.node_count();
         report.total_links = self.graph.edge_count();
 
-        // Find broken links
-        report.broken_links = self.find_broken_links()?;
+        // Find broken links, keeping fragment-only breakage separate
+        (report.broken_fragments, report.broken_links) = self
+            .find_broken_links()?
+            .into_iter()
+            .partition(|b| b.kind != BrokenLinkKind::MissingTarget);
 
         // Find orphaned notes
         report.orphaned_notes = self.graph.orphaned_notes();
@@ -157,15 +197,7 @@ impl<'a> HealthAnalyzer<'a> {
             for (source, links) in files {
                 for link in links {
                     if !link.is_valid {
-                        // Try to find similar targets for suggestions
-                        let suggestions = self.suggest_targets(&link.target);
-
-                        broken.push(BrokenLink {
-                            source_file: source.clone(),
-                            target: link.target.clone(),
-                            line: link.position.line,
-                            suggestions,
-                        });
+                        broken.push(self.broken_link(source, link));
                     }
                 }
             }
@@ -174,22 +206,59 @@ impl<'a> HealthAnalyzer<'a> {
             for (source, links) in self.graph.all_links() {
                 for link in links {
                     if !link.is_valid {
-                        let suggestions = self.suggest_targets(&link.target);
-
-                        broken.push(BrokenLink {
-                            source_file: source.clone(),
-                            target: link.target.clone(),
-                            line: link.position.line,
-                            suggestions,
-                        });
+                        broken.push(self.broken_link(&source, &link));
                     }
                 }
             }
         }
 
+        broken.sort_by(|a, b| {
+            (&a.source_file, a.line, &a.target).cmp(&(&b.source_file, b.line, &b.target))
+        });
+
         Ok(broken)
     }
 
+    /// Describe an invalid link, classifying it and suggesting fixes.
+    ///
+    /// A link that resolved to a file but is still invalid has a broken
+    /// fragment; suggestions are then the closest headings or block IDs.
+    fn broken_link(&self, source: &Path, link: &Link) -> BrokenLink {
+        let (kind, suggestions) = match (&link.resolved_target, link.target.split_once('#')) {
+            (Some(resolved), Some((note, fragment))) => {
+                let kind = if fragment.starts_with('^') {
+                    BrokenLinkKind::MissingBlock
+                } else {
+                    BrokenLinkKind::MissingHeading
+                };
+                let suggestions = self
+                    .anchors
+                    .and_then(|anchors| anchors.get(resolved))
+                    .map(|anchors| {
+                        anchors
+                            .closest(fragment, 3)
+                            .into_iter()
+                            .map(|anchor| format!("{}#{}", note, anchor))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (kind, suggestions)
+            }
+            _ => (
+                BrokenLinkKind::MissingTarget,
+                self.suggest_targets(&link.target),
+            ),
+        };
+
+        BrokenLink {
+            source_file: source.to_path_buf(),
+            target: link.target.clone(),
+            line: link.position.line,
+            suggestions,
+            kind,
+        }
+    }
+
     /// Find notes with no outgoing links
     fn find_dead_end_notes(&self) -> Result<Vec<PathBuf>> {
         let mut dead_ends = Vec::new();
@@ -223,7 +292,7 @@ impl<'a> HealthAnalyzer<'a> {
         }
 
         // Sort by connection count (descending)
-        hubs.sort_by(|a, b| b.1.cmp(&a.1));
+        hubs.sort_by_key(|h| std::cmp::Reverse(h.1));
         hubs.truncate(limit);
 
         Ok(hubs)
@@ -247,7 +316,15 @@ impl<'a> HealthAnalyzer<'a> {
     /// Suggest similar targets for a broken link
     fn suggest_targets(&self, target: &str) -> Vec<String> {
         let mut suggestions = Vec::new();
-        let target_lower = target.to_lowercase();
+
+        // Compare against the bare note name: no fragment, folder or extension
+        let name = target.split('#').next().unwrap_or(target);
+        let name = name.rsplit('/').next().unwrap_or(name);
+        let name = name.strip_suffix(".md").unwrap_or(name);
+        let target_lower = name.trim().to_lowercase();
+        if target_lower.is_empty() {
+            return suggestions;
+        }
 
         for path in self.graph.all_files() {
             if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
@@ -274,7 +351,10 @@ impl<'a> HealthAnalyzer<'a> {
 
         report.total_notes = self.graph.node_count();
         report.total_links = self.graph.edge_count();
-        report.broken_links = self.find_broken_links()?;
+        (report.broken_fragments, report.broken_links) = self
+            .find_broken_links()?
+            .into_iter()
+            .partition(|b| b.kind != BrokenLinkKind::MissingTarget);
         report.orphaned_notes = self.graph.orphaned_notes();
 
         report.calculate_score();
@@ -361,6 +441,7 @@ mod tests {
                 target: "broken".to_string(),
                 line: 1,
                 suggestions: Vec::new(),
+                kind: BrokenLinkKind::MissingTarget,
             });
         }
         report.orphaned_notes.push(PathBuf::from("orphan.md"));
@@ -409,6 +490,57 @@ mod tests {
         assert_eq!(broken[0].target, "nonexistent");
     }
 
+    #[test]
+    fn test_broken_fragments_reported_separately() {
+        let mut graph = LinkGraph::new();
+
+        let mut target = create_test_file("guide.md");
+        target.headings = vec![turbovault_core::Heading {
+            text: "Installation".to_string(),
+            level: 2,
+            position: SourcePosition::start(),
+            anchor: Some("installation".to_string()),
+        }];
+        graph.add_file(&target).unwrap();
+
+        let mut renamed = create_test_link("notes.md", "guide#Instalation", false);
+        renamed.type_ = LinkType::HeadingRef;
+        renamed.resolved_target = Some(PathBuf::from("guide.md"));
+        let mut missing_block = create_test_link("notes.md", "guide#^gone", false);
+        missing_block.type_ = LinkType::BlockRef;
+        missing_block.resolved_target = Some(PathBuf::from("guide.md"));
+        let missing_note = create_test_link("notes.md", "nowhere", false);
+
+        let mut files = HashMap::new();
+        files.insert(
+            PathBuf::from("notes.md"),
+            vec![renamed, missing_block, missing_note],
+        );
+        let anchors = crate::AnchorIndex::from_files([&target]);
+
+        let report = HealthAnalyzer::with_files(&graph, &files)
+            .with_anchors(&anchors)
+            .quick_check()
+            .unwrap();
+
+        assert_eq!(report.broken_links.len(), 1);
+        assert_eq!(report.broken_links[0].kind, BrokenLinkKind::MissingTarget);
+        assert_eq!(report.broken_fragments.len(), 2);
+        assert_eq!(
+            report.broken_fragments[0].kind,
+            BrokenLinkKind::MissingHeading
+        );
+        assert_eq!(
+            report.broken_fragments[0].suggestions,
+            vec!["guide#Installation".to_string()]
+        );
+        assert_eq!(
+            report.broken_fragments[1].kind,
+            BrokenLinkKind::MissingBlock
+        );
+        assert!(report.broken_fragments[1].suggestions.is_empty());
+    }
+
     #[test]
     fn test_find_dead_end_notes() {
         let mut graph = LinkGraph::new();
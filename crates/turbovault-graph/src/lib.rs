@@ -7,11 +7,15 @@
 //! - Link resolution (wikilinks, aliases, folder paths)
 //! - Backlink queries
 //! - Related notes discovery (BFS)
+//! - Shortest paths, ego networks and common neighbors between notes
 //! - Orphan detection
 //! - Cycle detection
 //! - Graph statistics
 //! - Vault health analysis
-//! - Broken link detection
+//! - Community detection (Louvain) with central notes, tags and folders per cluster
+//! - Tag co-occurrence (PMI, Jaccard), near-duplicate tags and tag communities
+//! - Centrality (PageRank, betweenness, harmonic closeness), cached until the graph changes
+//! - Link resolution and broken link detection
 //!
 //! ## Quick Start
 //!
@@ -31,7 +35,9 @@
 //!
 //! ### Nodes and Edges
 //! - **Nodes**: Represent vault files (notes)
-//! - **Edges**: Represent links between files
+//! - **Edges**: Represent links between files, typed by [`EdgeKind`]
+//!   (wikilink, embed, markdown link, heading/block reference, or a
+//!   frontmatter property holding `[[Note]]`)
 //! - **Directed**: Links flow from source to target
 //!
 //! ### Graph Operations
@@ -41,6 +47,10 @@
 //! - **Related Notes**: Discover related notes through BFS traversal
 //! - **Orphans**: Find isolated notes with no links in or out
 //!
+//! The `*_by_kind` variants (`backlinks_by_kind`, `forward_links_by_kind`,
+//! `related_notes_by_kind`, `orphaned_notes_by_kind`) consider only the
+//! given edge kinds.
+//!
 //! ### Vault Health Metrics
 //!
 //! The health analyzer provides:
@@ -48,6 +58,8 @@
 //! - **Connectivity Rate**: Percentage of connected notes
 //! - **Link Density**: Ratio of existing links to possible links
 //! - **Broken Links**: Links to non-existent targets
+//! - **Broken Fragments**: `[[Note#Heading]]` / `[[Note#^block]]` links whose
+//!   heading or block no longer exists, with the closest existing headings
 //! - **Orphaned Notes**: Isolated notes with no relationships
 //!
 //! ## Advanced Usage
@@ -86,7 +98,11 @@
 //! ## Modules
 //!
 //! - [`graph`] - Main LinkGraph implementation
+//! - [`centrality`] - PageRank, Brandes betweenness and harmonic closeness
+//! - [`community`] - Louvain community detection and folder mismatches
 //! - [`health`] - Vault health analysis
+//! - [`paths`] - Shortest paths, ego networks and common neighbors
+//! - [`resolve`] - Link resolution against vault files
 //!
 //! ## Performance Characteristics
 //!
@@ -96,16 +112,43 @@
 //! - Orphan detection: O(n)
 //! - Cycle detection: O(n + m)
 //! - Health analysis: O(n + m)
+//! - Community detection: O(m) per Louvain sweep, a few sweeps per level
+//! - Centrality: O(n·(n + m)) for betweenness and closeness, cached per graph state
+//! - Tag co-occurrence: O(Σ tags per note²) to build, O(t²) for near-duplicate tags
 
+pub mod centrality;
+pub mod community;
 pub mod graph;
 pub mod health;
+pub mod paths;
+pub mod resolve;
+pub mod tags;
 
-pub use graph::{GraphStats, LinkGraph};
-pub use health::{BrokenLink, HealthAnalyzer, HealthReport};
+pub use centrality::{Centrality, CentralityConfig, CentralityMetric};
+pub use community::{
+    Community, CommunityConfig, CommunityDetector, CommunityReport, FolderCount, FolderMismatch,
+    TagCount,
+};
+pub use graph::{EdgeKind, GraphStats, LinkEdge, LinkGraph, property_links};
+pub use health::{BrokenLink, BrokenLinkKind, HealthAnalyzer, HealthReport};
+pub use paths::{
+    CommonNeighbor, EgoEdge, EgoNetwork, EgoNode, NeighborRelation, NotePath, PathDirection,
+    PathHop,
+};
+pub use resolve::{AnchorIndex, LinkResolver, LinkValidation, NoteAnchors};
+pub use tags::{
+    BridgingNote, NearDuplicateConfig, NearDuplicateTags, TagCommunities, TagCommunity, TagGraph,
+    TagPair,
+};
 pub use turbovault_core::prelude::*;
 
 pub mod prelude {
-    pub use crate::graph::{GraphStats, LinkGraph};
-    pub use crate::health::{BrokenLink, HealthAnalyzer, HealthReport};
+    pub use crate::centrality::{Centrality, CentralityConfig, CentralityMetric};
+    pub use crate::community::{Community, CommunityDetector, CommunityReport, FolderMismatch};
+    pub use crate::graph::{EdgeKind, GraphStats, LinkEdge, LinkGraph};
+    pub use crate::health::{BrokenLink, BrokenLinkKind, HealthAnalyzer, HealthReport};
+    pub use crate::paths::{CommonNeighbor, EgoNetwork, NotePath, PathDirection};
+    pub use crate::resolve::{AnchorIndex, LinkResolver, LinkValidation, NoteAnchors};
+    pub use crate::tags::{TagGraph, TagPair};
     pub use turbovault_core::prelude::*;
 }
@@ -0,0 +1,626 @@
+//! Path and neighborhood queries between notes
+//!
+//! - **Shortest paths**: every shortest path between two notes (up to a
+//!   limit), with the link followed at each hop
+//! - **Ego networks**: all notes within `k` hops of a note, with distances
+//!   and the links among them
+//! - **Common neighbors**: notes linked with both of two notes
+//!
+//! Queries either follow links in their written direction
+//! ([`PathDirection::Directed`]) or treat them as two-way
+//! ([`PathDirection::Undirected`]), and can be restricted to edge kinds.
+
+use crate::graph::{EdgeKind, LinkEdge, LinkGraph};
+use petgraph::Direction::{Incoming, Outgoing};
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use turbovault_core::prelude::*;
+
+/// Whether queries follow links only from source to target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathDirection {
+    /// Follow links from the note that wrote them to their target
+    Directed,
+    /// Follow links both ways
+    #[default]
+    Undirected,
+}
+
+/// One step of a path between notes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathHop {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    /// `false` when the hop walks a link backwards (`to` links to `from`)
+    pub forward: bool,
+    pub kind: EdgeKind,
+    /// Link target as written (`Note#Heading`, `../note.md`)
+    pub target: String,
+    /// Display text, or the target when the link has none
+    pub link_text: String,
+    /// Line of the link in the note that contains it
+    pub line: usize,
+}
+
+/// A path between two notes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotePath {
+    /// Notes along the path, both ends included
+    pub notes: Vec<PathBuf>,
+    pub hops: Vec<PathHop>,
+}
+
+impl NotePath {
+    /// Number of hops
+    pub fn len(&self) -> usize {
+        self.hops.len()
+    }
+
+    /// Whether the path starts and ends at the same note
+    pub fn is_empty(&self) -> bool {
+        self.hops.is_empty()
+    }
+}
+
+/// A note in an ego network
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EgoNode {
+    pub path: PathBuf,
+    /// Hops from the center
+    pub distance: usize,
+}
+
+/// A link between two notes of an ego network
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct EgoEdge {
+    pub source: PathBuf,
+    pub target: PathBuf,
+    pub kind: EdgeKind,
+}
+
+/// Notes within `k` hops of a center note
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EgoNetwork {
+    pub center: PathBuf,
+    /// Neighbors sorted by distance, then path (the center is not included)
+    pub nodes: Vec<EgoNode>,
+    /// Links among the center and its neighbors, one per source, target and kind
+    pub edges: Vec<EgoEdge>,
+}
+
+/// How a neighbor is linked with a note
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NeighborRelation {
+    /// The note links to the neighbor
+    LinksTo,
+    /// The neighbor links to the note
+    LinkedFrom,
+    /// Both
+    Mutual,
+}
+
+/// A note linked with both notes of a pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommonNeighbor {
+    pub path: PathBuf,
+    /// Relation to the first note
+    pub first: NeighborRelation,
+    /// Relation to the second note
+    pub second: NeighborRelation,
+}
+
+impl LinkGraph {
+    /// All shortest paths from `from` to `to`, up to `max_paths`.
+    ///
+    /// The order of paths is stable between calls. Returns an empty list when the
+    /// notes aren't connected, and a single zero-hop path when they are the
+    /// same note.
+    pub fn shortest_paths(
+        &self,
+        from: &Path,
+        to: &Path,
+        direction: PathDirection,
+        kinds: &[EdgeKind],
+        max_paths: usize,
+    ) -> Result<Vec<NotePath>> {
+        let source = self.require_node(from)?;
+        let target = self.require_node(to)?;
+        if source == target {
+            return Ok(vec![NotePath {
+                notes: vec![from.to_path_buf()],
+                hops: vec![],
+            }]);
+        }
+
+        // BFS recording every predecessor on a shortest path
+        let mut distance: HashMap<NodeIndex, usize> = HashMap::from([(source, 0)]);
+        let mut predecessors: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            let next = distance[&node] + 1;
+            if distance.get(&target).is_some_and(|&d| next > d) {
+                break;
+            }
+            for neighbor in self.neighbor_nodes(node, direction, kinds) {
+                match distance.get(&neighbor) {
+                    None => {
+                        distance.insert(neighbor, next);
+                        predecessors.entry(neighbor).or_default().push(node);
+                        queue.push_back(neighbor);
+                    }
+                    Some(&d) if d == next => {
+                        predecessors.entry(neighbor).or_default().push(node);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if !distance.contains_key(&target) {
+            return Ok(vec![]);
+        }
+
+        for list in predecessors.values_mut() {
+            list.sort_by(|a, b| self.digraph()[*a].cmp(&self.digraph()[*b]));
+        }
+
+        // Walk predecessors back from the target
+        let mut paths = Vec::new();
+        let mut stack = vec![vec![target]];
+        while let Some(partial) = stack.pop() {
+            if paths.len() >= max_paths {
+                break;
+            }
+            let last = *partial.last().expect("partial paths are never empty");
+            if last == source {
+                let nodes: Vec<NodeIndex> = partial.into_iter().rev().collect();
+                paths.push(self.note_path(&nodes, direction, kinds));
+                continue;
+            }
+            // Push in reverse so the first predecessor is explored first
+            for &pred in predecessors[&last].iter().rev() {
+                let mut extended = partial.clone();
+                extended.push(pred);
+                stack.push(extended);
+            }
+        }
+
+        Ok(paths)
+    }
+
+    /// Notes within `max_hops` of `center`, with distances and the links
+    /// among them
+    pub fn ego_network(
+        &self,
+        center: &Path,
+        max_hops: usize,
+        direction: PathDirection,
+        kinds: &[EdgeKind],
+    ) -> Result<EgoNetwork> {
+        let start = self.require_node(center)?;
+
+        let mut distance: HashMap<NodeIndex, usize> = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            let hops = distance[&node];
+            if hops >= max_hops {
+                continue;
+            }
+            for neighbor in self.neighbor_nodes(node, direction, kinds) {
+                if let Entry::Vacant(entry) = distance.entry(neighbor) {
+                    entry.insert(hops + 1);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        let graph = self.digraph();
+        let mut nodes: Vec<EgoNode> = distance
+            .iter()
+            .filter(|&(&idx, _)| idx != start)
+            .map(|(&idx, &distance)| EgoNode {
+                path: graph[idx].clone(),
+                distance,
+            })
+            .collect();
+        nodes.sort_by(|a, b| {
+            a.distance
+                .cmp(&b.distance)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+
+        let edges: BTreeSet<EgoEdge> = distance
+            .keys()
+            .flat_map(|&idx| graph.edges_directed(idx, Outgoing))
+            .filter(|edge| {
+                edge.source() != edge.target()
+                    && distance.contains_key(&edge.target())
+                    && edge.weight().kind.matches(kinds)
+            })
+            .map(|edge| EgoEdge {
+                source: graph[edge.source()].clone(),
+                target: graph[edge.target()].clone(),
+                kind: edge.weight().kind,
+            })
+            .collect();
+
+        Ok(EgoNetwork {
+            center: center.to_path_buf(),
+            nodes,
+            edges: edges.into_iter().collect(),
+        })
+    }
+
+    /// Notes linked with both `first` and `second`, in either direction
+    pub fn common_neighbors(
+        &self,
+        first: &Path,
+        second: &Path,
+        kinds: &[EdgeKind],
+    ) -> Result<Vec<CommonNeighbor>> {
+        let a = self.require_node(first)?;
+        let b = self.require_node(second)?;
+        let relations_a = self.neighbor_relations(a, kinds);
+        let relations_b = self.neighbor_relations(b, kinds);
+
+        let mut common: Vec<CommonNeighbor> = relations_a
+            .iter()
+            .filter(|(idx, _)| **idx != a && **idx != b)
+            .filter_map(|(idx, &relation_a)| {
+                relations_b.get(idx).map(|&relation_b| CommonNeighbor {
+                    path: self.digraph()[*idx].clone(),
+                    first: relation_a,
+                    second: relation_b,
+                })
+            })
+            .collect();
+        common.sort_by(|x, y| x.path.cmp(&y.path));
+        Ok(common)
+    }
+
+    fn require_node(&self, path: &Path) -> Result<NodeIndex> {
+        self.node_index(path)
+            .ok_or_else(|| Error::not_found(format!("Note not in link graph: {}", path.display())))
+    }
+
+    /// Distinct neighbors in path order, skipping self-links
+    fn neighbor_nodes(
+        &self,
+        node: NodeIndex,
+        direction: PathDirection,
+        kinds: &[EdgeKind],
+    ) -> Vec<NodeIndex> {
+        let graph = self.digraph();
+        let outgoing = graph
+            .edges_directed(node, Outgoing)
+            .filter(|e| e.weight().kind.matches(kinds))
+            .map(|e| e.target());
+        let incoming = graph
+            .edges_directed(node, Incoming)
+            .filter(|e| direction == PathDirection::Undirected && e.weight().kind.matches(kinds))
+            .map(|e| e.source());
+
+        let mut neighbors: Vec<NodeIndex> = outgoing
+            .chain(incoming)
+            .filter(|&other| other != node)
+            .collect();
+        neighbors.sort_by(|a, b| graph[*a].cmp(&graph[*b]));
+        neighbors.dedup();
+        neighbors
+    }
+
+    fn neighbor_relations(
+        &self,
+        node: NodeIndex,
+        kinds: &[EdgeKind],
+    ) -> HashMap<NodeIndex, NeighborRelation> {
+        let graph = self.digraph();
+        let mut relations: HashMap<NodeIndex, NeighborRelation> = HashMap::new();
+        let mut record = |other: NodeIndex, relation: NeighborRelation| {
+            relations
+                .entry(other)
+                .and_modify(|existing| {
+                    if *existing != relation {
+                        *existing = NeighborRelation::Mutual;
+                    }
+                })
+                .or_insert(relation);
+        };
+
+        for edge in graph.edges_directed(node, Outgoing) {
+            if edge.weight().kind.matches(kinds) {
+                record(edge.target(), NeighborRelation::LinksTo);
+            }
+        }
+        for edge in graph.edges_directed(node, Incoming) {
+            if edge.weight().kind.matches(kinds) {
+                record(edge.source(), NeighborRelation::LinkedFrom);
+            }
+        }
+        relations
+    }
+
+    /// Describe a node sequence, picking the link used for each hop
+    fn note_path(
+        &self,
+        nodes: &[NodeIndex],
+        direction: PathDirection,
+        kinds: &[EdgeKind],
+    ) -> NotePath {
+        let graph = self.digraph();
+        let hops = nodes
+            .windows(2)
+            .filter_map(|pair| {
+                let (from, to) = (pair[0], pair[1]);
+                // Prefer a link written in the walking direction
+                let forward = self.best_edge(from, to, kinds).map(|e| (e, true));
+                let hop = match direction {
+                    PathDirection::Directed => forward,
+                    PathDirection::Undirected => {
+                        forward.or_else(|| self.best_edge(to, from, kinds).map(|e| (e, false)))
+                    }
+                };
+                hop.map(|(edge, forward)| PathHop {
+                    from: graph[from].clone(),
+                    to: graph[to].clone(),
+                    forward,
+                    kind: edge.kind,
+                    target: edge.link.target.clone(),
+                    link_text: edge
+                        .link
+                        .display_text
+                        .clone()
+                        .unwrap_or_else(|| edge.link.target.clone()),
+                    line: edge.link.position.line,
+                })
+            })
+            .collect();
+
+        NotePath {
+            notes: nodes.iter().map(|&idx| graph[idx].clone()).collect(),
+            hops,
+        }
+    }
+
+    /// The earliest link from `source` to `target` (by line, then edge kind)
+    fn best_edge(
+        &self,
+        source: NodeIndex,
+        target: NodeIndex,
+        kinds: &[EdgeKind],
+    ) -> Option<&LinkEdge> {
+        self.digraph()
+            .edges_connecting(source, target)
+            .map(|e| e.weight())
+            .filter(|edge| edge.kind.matches(kinds))
+            .min_by_key(|edge| (edge.link.position.line, edge.kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(path: &str, links: &[(&str, Option<&str>, usize)]) -> VaultFile {
+        let mut file = VaultFile::new(
+            PathBuf::from(path),
+            String::new(),
+            FileMetadata {
+                path: PathBuf::from(path),
+                size: 0,
+                created_at: 0.0,
+                modified_at: 0.0,
+                checksum: String::new(),
+                is_attachment: false,
+            },
+        );
+        file.links = links
+            .iter()
+            .map(|&(target, display, line)| {
+                let type_ = if target.contains('#') {
+                    LinkType::HeadingRef
+                } else {
+                    LinkType::WikiLink
+                };
+                let mut link = Link::new(
+                    type_,
+                    PathBuf::from(path),
+                    target.to_string(),
+                    SourcePosition::new(line, 0, 0, 0),
+                );
+                link.display_text = display.map(String::from);
+                link
+            })
+            .collect();
+        file
+    }
+
+    /// a -> b -> d, a -> c -> d, e -> d, f isolated
+    fn diamond() -> LinkGraph {
+        let files = [
+            note("a.md", &[("b", Some("see B"), 3), ("c", None, 5)]),
+            note("b.md", &[("d", None, 1)]),
+            note("c.md", &[("d#Intro", None, 2)]),
+            note("d.md", &[]),
+            note("e.md", &[("d", None, 1)]),
+            note("f.md", &[]),
+        ];
+        let mut graph = LinkGraph::new();
+        for file in &files {
+            graph.add_file(file).unwrap();
+        }
+        for file in &files {
+            graph.update_links(file).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_all_shortest_paths_with_link_text() {
+        let graph = diamond();
+        let paths = graph
+            .shortest_paths(
+                Path::new("a.md"),
+                Path::new("d.md"),
+                PathDirection::Directed,
+                &[],
+                10,
+            )
+            .unwrap();
+
+        assert_eq!(paths.len(), 2);
+        assert_eq!(
+            paths[0].notes,
+            vec![
+                PathBuf::from("a.md"),
+                PathBuf::from("b.md"),
+                PathBuf::from("d.md")
+            ]
+        );
+        assert_eq!(paths[0].len(), 2);
+        assert_eq!(paths[0].hops[0].link_text, "see B");
+        assert_eq!(paths[0].hops[0].line, 3);
+        assert!(paths[0].hops[0].forward);
+
+        let via_c = &paths[1];
+        assert_eq!(via_c.notes[1], PathBuf::from("c.md"));
+        assert_eq!(via_c.hops[1].kind, EdgeKind::HeadingRef);
+        assert_eq!(via_c.hops[1].target, "d#Intro");
+        assert_eq!(via_c.hops[1].link_text, "d#Intro");
+
+        let limited = graph
+            .shortest_paths(
+                Path::new("a.md"),
+                Path::new("d.md"),
+                PathDirection::Directed,
+                &[],
+                1,
+            )
+            .unwrap();
+        assert_eq!(limited.len(), 1);
+    }
+
+    #[test]
+    fn test_directed_and_undirected_paths() {
+        let graph = diamond();
+        let e = Path::new("e.md");
+        let a = Path::new("a.md");
+
+        // Links only lead into d, so a is unreachable from e following links
+        assert!(
+            graph
+                .shortest_paths(e, a, PathDirection::Directed, &[], 10)
+                .unwrap()
+                .is_empty()
+        );
+
+        let paths = graph
+            .shortest_paths(e, a, PathDirection::Undirected, &[], 10)
+            .unwrap();
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].len(), 3);
+        assert!(paths[0].hops[0].forward);
+        assert!(!paths[0].hops[1].forward);
+        assert_eq!(paths[0].hops[1].from, PathBuf::from("d.md"));
+
+        // Kind filters drop the heading reference through c
+        let wikilinks = graph
+            .shortest_paths(e, a, PathDirection::Undirected, &[EdgeKind::WikiLink], 10)
+            .unwrap();
+        assert_eq!(wikilinks.len(), 1);
+        assert_eq!(wikilinks[0].notes[2], PathBuf::from("b.md"));
+    }
+
+    #[test]
+    fn test_same_note_and_missing_notes() {
+        let graph = diamond();
+        let same = graph
+            .shortest_paths(
+                Path::new("f.md"),
+                Path::new("f.md"),
+                PathDirection::Directed,
+                &[],
+                10,
+            )
+            .unwrap();
+        assert_eq!(same.len(), 1);
+        assert!(same[0].is_empty());
+
+        assert!(
+            graph
+                .shortest_paths(
+                    Path::new("a.md"),
+                    Path::new("f.md"),
+                    PathDirection::Undirected,
+                    &[],
+                    10
+                )
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            graph
+                .shortest_paths(
+                    Path::new("a.md"),
+                    Path::new("missing.md"),
+                    PathDirection::Directed,
+                    &[],
+                    10
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_ego_network() {
+        let graph = diamond();
+        let ego = graph
+            .ego_network(Path::new("b.md"), 2, PathDirection::Undirected, &[])
+            .unwrap();
+
+        let nodes: Vec<(&str, usize)> = ego
+            .nodes
+            .iter()
+            .map(|n| (n.path.to_str().unwrap(), n.distance))
+            .collect();
+        assert_eq!(
+            nodes,
+            vec![("a.md", 1), ("d.md", 1), ("c.md", 2), ("e.md", 2)]
+        );
+        assert_eq!(ego.edges.len(), 5);
+
+        let directed = graph
+            .ego_network(Path::new("b.md"), 2, PathDirection::Directed, &[])
+            .unwrap();
+        assert_eq!(directed.nodes.len(), 1);
+        assert_eq!(directed.edges.len(), 1);
+
+        let related = graph.related_notes(&PathBuf::from("b.md"), 2).unwrap();
+        assert_eq!(
+            related,
+            ego.nodes.into_iter().map(|n| n.path).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_common_neighbors() {
+        let graph = diamond();
+        let common = graph
+            .common_neighbors(Path::new("b.md"), Path::new("c.md"), &[])
+            .unwrap();
+        let paths: Vec<_> = common.iter().map(|c| c.path.clone()).collect();
+        assert_eq!(paths, vec![PathBuf::from("a.md"), PathBuf::from("d.md")]);
+        assert_eq!(common[0].first, NeighborRelation::LinkedFrom);
+        assert_eq!(common[1].first, NeighborRelation::LinksTo);
+
+        let common = graph
+            .common_neighbors(Path::new("a.md"), Path::new("e.md"), &[])
+            .unwrap();
+        assert!(common.is_empty());
+    }
+}
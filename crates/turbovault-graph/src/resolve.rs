@@ -0,0 +1,682 @@
+//! Link resolution and validation.
+//!
+//! The parser emits every link with `is_valid: true` and no resolved target,
+//! and the [`LinkGraph`] only stores edges for links that already resolved.
+//! [`LinkResolver`] resolves each wikilink, embed and relative markdown link
+//! against the files actually present in the vault, so broken links can be
+//! reported by the [`HealthAnalyzer`](crate::HealthAnalyzer).
+//!
+//! With an [`AnchorIndex`], `#Heading` and `#^block` fragments are checked
+//! too. Headings match Obsidian-style: exact text, case- and
+//! whitespace-insensitive text, or the same [`slugify`] slug, and nested
+//! references (`[[Note#Parent#Child]]`) must follow the heading outline.
+
+use crate::graph::LinkGraph;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use turbovault_core::{Heading, Link, LinkType, VaultFile};
+use turbovault_parser::slugify;
+
+/// Headings and block IDs of a note, used to check link fragments
+#[derive(Debug, Clone, Default)]
+pub struct NoteAnchors {
+    pub headings: Vec<Heading>,
+    pub block_ids: Vec<String>,
+}
+
+impl NoteAnchors {
+    /// Build from a parsed note
+    pub fn from_file(file: &VaultFile) -> Self {
+        Self {
+            headings: file.headings.clone(),
+            block_ids: file
+                .blocks
+                .iter()
+                .filter_map(|b| b.block_id.clone())
+                .collect(),
+        }
+    }
+
+    /// Check whether a fragment (`Heading`, `Parent#Child` or `^block`) exists
+    pub fn contains(&self, fragment: &str) -> bool {
+        if let Some(id) = fragment.strip_prefix('^') {
+            return self.block_ids.iter().any(|b| b.eq_ignore_ascii_case(id));
+        }
+        let empty = fragment.split('#').all(|s| s.trim().is_empty());
+        empty || self.heading(fragment).is_some()
+    }
+
+    /// Heading a `Heading` or `Parent#Child` fragment refers to
+    pub fn heading(&self, fragment: &str) -> Option<&Heading> {
+        let segments: Vec<&str> = fragment
+            .split('#')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        // Each segment must match a later, deeper heading than the previous one
+        let mut from = 0;
+        let mut parent_level = 0;
+        let mut found = None;
+        for segment in segments {
+            let offset = self.headings[from..]
+                .iter()
+                .position(|h| h.level > parent_level && heading_matches(&h.text, segment))?;
+            let heading = &self.headings[from + offset];
+            parent_level = heading.level;
+            from += offset + 1;
+            found = Some(heading);
+        }
+        found
+    }
+
+    /// Existing headings (or block IDs) closest to a broken fragment, best first
+    pub fn closest(&self, fragment: &str, limit: usize) -> Vec<String> {
+        let (wanted, candidates, prefix): (&str, Vec<&str>, &str) = match fragment.strip_prefix('^')
+        {
+            Some(id) => (id, self.block_ids.iter().map(String::as_str).collect(), "^"),
+            None => (
+                fragment.rsplit('#').next().unwrap_or(fragment),
+                self.headings.iter().map(|h| h.text.as_str()).collect(),
+                "",
+            ),
+        };
+
+        let wanted = normalize_heading(wanted);
+        let mut scored: Vec<(f64, &str)> = candidates
+            .into_iter()
+            .map(|candidate| {
+                let score = strsim::normalized_levenshtein(&wanted, &normalize_heading(candidate));
+                (score, candidate)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let mut closest: Vec<String> = Vec::new();
+        for (_, candidate) in scored {
+            let suggestion = format!("{}{}", prefix, candidate);
+            if !closest.contains(&suggestion) {
+                closest.push(suggestion);
+            }
+            if closest.len() >= limit {
+                break;
+            }
+        }
+        closest
+    }
+}
+
+/// Headings and block IDs for every indexed note
+#[derive(Debug, Clone, Default)]
+pub struct AnchorIndex {
+    notes: HashMap<PathBuf, NoteAnchors>,
+}
+
+impl AnchorIndex {
+    /// Create an empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index the anchors of several parsed notes
+    pub fn from_files<'f>(files: impl IntoIterator<Item = &'f VaultFile>) -> Self {
+        let mut index = Self::new();
+        for file in files {
+            index.insert(file);
+        }
+        index
+    }
+
+    /// Add or replace a note's anchors
+    pub fn insert(&mut self, file: &VaultFile) {
+        self.notes
+            .insert(file.path.clone(), NoteAnchors::from_file(file));
+    }
+
+    /// Drop a note's anchors
+    pub fn remove(&mut self, path: &Path) {
+        self.notes.remove(path);
+    }
+
+    /// Anchors of a note, if it was indexed
+    pub fn get(&self, path: &Path) -> Option<&NoteAnchors> {
+        self.notes.get(path)
+    }
+}
+
+/// Validated links of every note, with the anchors used to check fragments
+#[derive(Debug, Clone, Default)]
+pub struct LinkValidation {
+    /// Links keyed by source note, with `resolved_target`/`is_valid` set
+    pub links: HashMap<PathBuf, Vec<Link>>,
+    /// Headings and block IDs of every validated note
+    pub anchors: AnchorIndex,
+}
+
+/// Obsidian heading match: text ignoring case and spacing, or equal slugs
+fn heading_matches(heading: &str, wanted: &str) -> bool {
+    if normalize_heading(heading) == normalize_heading(wanted) {
+        return true;
+    }
+    let slug = slugify(heading);
+    !slug.is_empty() && slug == slugify(wanted)
+}
+
+fn normalize_heading(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Resolves link targets against the files of a vault
+pub struct LinkResolver<'a> {
+    graph: &'a LinkGraph,
+    anchors: Option<&'a AnchorIndex>,
+    vault_root: PathBuf,
+    /// Every known file (notes and attachments), as absolute paths
+    files: HashSet<PathBuf>,
+    /// Lowercased file name (with extension) to matching paths, sorted
+    by_name: HashMap<String, Vec<PathBuf>>,
+}
+
+impl<'a> LinkResolver<'a> {
+    /// Create a resolver for a vault.
+    ///
+    /// `files` should list every file in the vault, including attachments
+    /// that aren't part of the graph, so embeds like `![[image.png]]` resolve.
+    pub fn new(
+        graph: &'a LinkGraph,
+        vault_root: impl Into<PathBuf>,
+        files: impl IntoIterator<Item = PathBuf>,
+    ) -> Self {
+        let mut known: HashSet<PathBuf> = files.into_iter().collect();
+        known.extend(graph.all_files());
+
+        let mut by_name: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        for path in &known {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                by_name
+                    .entry(name.to_lowercase())
+                    .or_default()
+                    .push(path.clone());
+            }
+        }
+        for paths in by_name.values_mut() {
+            paths.sort();
+        }
+
+        Self {
+            graph,
+            anchors: None,
+            vault_root: vault_root.into(),
+            files: known,
+            by_name,
+        }
+    }
+
+    /// Also check `#Heading` and `#^block` fragments against indexed notes.
+    ///
+    /// Fragments pointing at notes missing from the index (attachments,
+    /// `file.pdf#page=3`) are not checked.
+    pub fn with_anchors(mut self, anchors: &'a AnchorIndex) -> Self {
+        self.anchors = Some(anchors);
+        self
+    }
+
+    /// Resolve a link to the file it points at.
+    ///
+    /// Same-document anchors resolve to the source file. External links
+    /// are never resolved.
+    pub fn resolve(&self, link: &Link) -> Option<PathBuf> {
+        self.resolve_from(link, &link.source_file)
+    }
+
+    /// Return the file's links with `resolved_target` and `is_valid` populated.
+    ///
+    /// External links are left valid since they can't be checked offline.
+    /// A link whose file resolved but whose fragment didn't keeps its
+    /// `resolved_target` and is marked invalid.
+    pub fn validate(&self, file: &VaultFile) -> Vec<Link> {
+        self.validate_links(&file.path, &file.links)
+    }
+
+    /// [`Self::validate`] for links parsed from the note at `source`
+    pub fn validate_links(&self, source: &Path, links: &[Link]) -> Vec<Link> {
+        links
+            .iter()
+            .map(|link| {
+                let mut link = link.clone();
+                if link.type_ == LinkType::ExternalLink {
+                    link.resolved_target = None;
+                    link.is_valid = true;
+                } else {
+                    link.resolved_target = self.resolve_from(&link, source);
+                    link.is_valid = link
+                        .resolved_target
+                        .as_ref()
+                        .is_some_and(|target| self.fragment_exists(&link.target, target));
+                }
+                link
+            })
+            .collect()
+    }
+
+    /// Validate the links of several files, keyed by source path
+    pub fn validate_all<'f>(
+        &self,
+        files: impl IntoIterator<Item = &'f VaultFile>,
+    ) -> HashMap<PathBuf, Vec<Link>> {
+        files
+            .into_iter()
+            .map(|file| (file.path.clone(), self.validate(file)))
+            .collect()
+    }
+
+    fn resolve_from(&self, link: &Link, source: &Path) -> Option<PathBuf> {
+        match link.type_ {
+            LinkType::ExternalLink => return None,
+            LinkType::Anchor => return Some(source.to_path_buf()),
+            _ => {}
+        }
+
+        let target = link.target.split('#').next().unwrap_or("").trim();
+        if target.is_empty() {
+            // `[[#^block]]` and similar refer to the source document
+            return Some(source.to_path_buf());
+        }
+
+        self.resolve_path(target, source).or_else(|| {
+            let decoded = percent_decode(target);
+            if decoded != target {
+                self.resolve_path(&decoded, source)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Check the `#...` part of a link target against the resolved note
+    pub fn fragment_exists(&self, target: &str, resolved: &Path) -> bool {
+        let Some((_, fragment)) = target.split_once('#') else {
+            return true;
+        };
+        match self.anchors.and_then(|anchors| anchors.get(resolved)) {
+            Some(anchors) => anchors.contains(&percent_decode(fragment)),
+            None => true,
+        }
+    }
+
+    fn resolve_path(&self, target: &str, source: &Path) -> Option<PathBuf> {
+        // Relative to the linking note, then relative to the vault root
+        let bases = [
+            source.parent().unwrap_or(&self.vault_root),
+            &self.vault_root,
+        ];
+        for base in bases {
+            let candidate = normalize(&base.join(target));
+            if let Some(found) = self.existing(&candidate) {
+                return Some(found);
+            }
+        }
+
+        // Obsidian-style resolution: stem, alias or folder suffix
+        if let Some(found) = self.graph.resolve_target(target) {
+            return Some(found);
+        }
+
+        self.by_file_name(target)
+    }
+
+    /// Check a candidate path, with and without an implicit `.md` extension
+    fn existing(&self, candidate: &Path) -> Option<PathBuf> {
+        if self.files.contains(candidate) {
+            return Some(candidate.to_path_buf());
+        }
+        let mut with_ext = candidate.as_os_str().to_owned();
+        with_ext.push(".md");
+        let with_ext = PathBuf::from(with_ext);
+        self.files.contains(&with_ext).then_some(with_ext)
+    }
+
+    /// Case-insensitive match on file name, checking any folder prefix
+    fn by_file_name(&self, target: &str) -> Option<PathBuf> {
+        let target = target.trim_start_matches("./").to_lowercase();
+        let name = target.rsplit('/').next()?;
+
+        for key in [name.to_string(), format!("{}.md", name)] {
+            let Some(paths) = self.by_name.get(&key) else {
+                continue;
+            };
+            let suffix = if key == name {
+                target.clone()
+            } else {
+                format!("{}.md", target)
+            };
+            let found = paths.iter().find(|path| {
+                let relative = path.strip_prefix(&self.vault_root).unwrap_or(path);
+                let relative = relative.to_string_lossy().replace('\\', "/").to_lowercase();
+                relative == suffix || relative.ends_with(&format!("/{}", suffix))
+            });
+            if let Some(found) = found {
+                return Some(found.clone());
+            }
+        }
+
+        None
+    }
+}
+
+/// Lexically resolve `.` and `..` components
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                result.pop();
+            }
+            other => result.push(other),
+        }
+    }
+    result
+}
+
+/// Decode `%XX` escapes used in markdown link destinations
+pub(crate) fn percent_decode(input: &str) -> String {
+    if !input.contains('%') {
+        return input.to_string();
+    }
+
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(hex) = input.get(i + 1..i + 3)
+            && hex.bytes().all(|b| b.is_ascii_hexdigit())
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use turbovault_core::{Block, FileMetadata, SourcePosition};
+
+    fn note(path: &str, links: Vec<(LinkType, &str)>) -> VaultFile {
+        let mut file = VaultFile::new(
+            PathBuf::from(path),
+            String::new(),
+            FileMetadata {
+                path: PathBuf::from(path),
+                size: 0,
+                created_at: 0.0,
+                modified_at: 0.0,
+                checksum: String::new(),
+                is_attachment: false,
+            },
+        );
+        file.links = links
+            .into_iter()
+            .map(|(type_, target)| {
+                Link::new(
+                    type_,
+                    PathBuf::from(path),
+                    target.to_string(),
+                    SourcePosition::start(),
+                )
+            })
+            .collect();
+        file
+    }
+
+    fn graph_with(notes: &[&VaultFile]) -> LinkGraph {
+        let mut graph = LinkGraph::new();
+        for file in notes {
+            graph.add_file(file).unwrap();
+        }
+        graph
+    }
+
+    fn validity(links: &[Link]) -> Vec<(&str, bool)> {
+        links
+            .iter()
+            .map(|l| (l.target.as_str(), l.is_valid))
+            .collect()
+    }
+
+    #[test]
+    fn test_wikilinks_and_embeds() {
+        let source = note(
+            "/vault/notes/source.md",
+            vec![
+                (LinkType::WikiLink, "Target"),
+                (LinkType::WikiLink, "Missing"),
+                (LinkType::HeadingRef, "Target#Intro"),
+                (LinkType::Embed, "diagram.png"),
+                (LinkType::Embed, "gone.png"),
+            ],
+        );
+        let target = note("/vault/Target.md", vec![]);
+        let graph = graph_with(&[&source, &target]);
+        let resolver = LinkResolver::new(
+            &graph,
+            "/vault",
+            vec![PathBuf::from("/vault/assets/diagram.png")],
+        );
+
+        let links = resolver.validate(&source);
+        assert_eq!(
+            validity(&links),
+            vec![
+                ("Target", true),
+                ("Missing", false),
+                ("Target#Intro", true),
+                ("diagram.png", true),
+                ("gone.png", false),
+            ]
+        );
+        assert_eq!(
+            links[0].resolved_target,
+            Some(PathBuf::from("/vault/Target.md"))
+        );
+        assert_eq!(
+            links[3].resolved_target,
+            Some(PathBuf::from("/vault/assets/diagram.png"))
+        );
+        assert_eq!(links[1].resolved_target, None);
+    }
+
+    #[test]
+    fn test_relative_markdown_links() {
+        let source = note(
+            "/vault/notes/source.md",
+            vec![
+                (LinkType::MarkdownLink, "../Other%20Note.md"),
+                (LinkType::MarkdownLink, "./sibling.md"),
+                (LinkType::HeadingRef, "sibling.md#Usage"),
+                (LinkType::MarkdownLink, "../missing.md"),
+                (LinkType::ExternalLink, "https://example.com"),
+                (LinkType::Anchor, "#local"),
+            ],
+        );
+        let other = note("/vault/Other Note.md", vec![]);
+        let sibling = note("/vault/notes/sibling.md", vec![]);
+        let graph = graph_with(&[&source, &other, &sibling]);
+        let resolver = LinkResolver::new(&graph, "/vault", Vec::new());
+
+        let links = resolver.validate(&source);
+        assert_eq!(
+            validity(&links),
+            vec![
+                ("../Other%20Note.md", true),
+                ("./sibling.md", true),
+                ("sibling.md#Usage", true),
+                ("../missing.md", false),
+                ("https://example.com", true),
+                ("#local", true),
+            ]
+        );
+        assert_eq!(
+            links[5].resolved_target,
+            Some(PathBuf::from("/vault/notes/source.md"))
+        );
+        assert_eq!(links[4].resolved_target, None);
+    }
+
+    #[test]
+    fn test_folder_paths_and_case() {
+        let source = note(
+            "/vault/source.md",
+            vec![
+                (LinkType::WikiLink, "projects/Plan"),
+                (LinkType::WikiLink, "other/Plan"),
+                (LinkType::Embed, "Assets/Photo.JPG"),
+            ],
+        );
+        let plan = note("/vault/projects/plan.md", vec![]);
+        let graph = graph_with(&[&source, &plan]);
+        let resolver = LinkResolver::new(
+            &graph,
+            "/vault",
+            vec![PathBuf::from("/vault/assets/photo.jpg")],
+        );
+
+        let links = resolver.validate(&source);
+        assert_eq!(
+            validity(&links),
+            vec![
+                ("projects/Plan", true),
+                ("other/Plan", false),
+                ("Assets/Photo.JPG", true),
+            ]
+        );
+    }
+
+    fn with_anchors(mut file: VaultFile, headings: &[(u8, &str)], blocks: &[&str]) -> VaultFile {
+        file.headings = headings
+            .iter()
+            .map(|(level, text)| Heading {
+                text: text.to_string(),
+                level: *level,
+                position: SourcePosition::start(),
+                anchor: Some(slugify(text)),
+            })
+            .collect();
+        file.blocks = blocks
+            .iter()
+            .map(|id| Block {
+                content: String::new(),
+                block_id: Some(id.to_string()),
+                position: SourcePosition::start(),
+                type_: "paragraph".to_string(),
+            })
+            .collect();
+        file
+    }
+
+    #[test]
+    fn test_note_anchors_heading_rules() {
+        let guide = with_anchors(
+            note("/vault/Guide.md", vec![]),
+            &[
+                (1, "Guide"),
+                (2, "Getting Started"),
+                (3, "Install: macOS & Linux"),
+                (2, "FAQ"),
+            ],
+            &["summary-1"],
+        );
+        let anchors = NoteAnchors::from_file(&guide);
+
+        assert!(anchors.contains("Getting Started"));
+        assert!(anchors.contains("getting   started"));
+        assert!(anchors.contains("getting-started"));
+        assert!(anchors.contains("Install macOS  Linux"));
+        assert!(anchors.contains("Getting Started#Install: macOS & Linux"));
+        assert!(anchors.contains("^summary-1"));
+        assert!(anchors.contains("^SUMMARY-1"));
+
+        assert!(!anchors.contains("Setup"));
+        assert!(!anchors.contains("FAQ#Install: macOS & Linux"));
+        assert!(!anchors.contains("^summary-2"));
+
+        assert_eq!(
+            anchors.closest("Getting Startd", 1),
+            vec!["Getting Started"]
+        );
+        assert_eq!(anchors.closest("^summary-2", 3), vec!["^summary-1"]);
+    }
+
+    #[test]
+    fn test_fragment_validation() {
+        let source = note(
+            "/vault/source.md",
+            vec![
+                (LinkType::HeadingRef, "Guide#Getting Started"),
+                (LinkType::HeadingRef, "Guide#Old Heading"),
+                (LinkType::BlockRef, "Guide#^summary-1"),
+                (LinkType::BlockRef, "Guide#^gone"),
+                (LinkType::HeadingRef, "Guide.md#getting-started"),
+                (LinkType::HeadingRef, "Guide.md#Getting%20Started"),
+                (LinkType::Anchor, "#Local"),
+                (LinkType::Anchor, "#Elsewhere"),
+                (LinkType::Embed, "manual.pdf#page=3"),
+            ],
+        );
+        let source = with_anchors(source, &[(1, "Local")], &[]);
+        let guide = with_anchors(
+            note("/vault/Guide.md", vec![]),
+            &[(2, "Getting Started")],
+            &["summary-1"],
+        );
+        let graph = graph_with(&[&source, &guide]);
+        let anchors = AnchorIndex::from_files([&source, &guide]);
+        let resolver =
+            LinkResolver::new(&graph, "/vault", vec![PathBuf::from("/vault/manual.pdf")])
+                .with_anchors(&anchors);
+
+        let links = resolver.validate(&source);
+        assert_eq!(
+            validity(&links),
+            vec![
+                ("Guide#Getting Started", true),
+                ("Guide#Old Heading", false),
+                ("Guide#^summary-1", true),
+                ("Guide#^gone", false),
+                ("Guide.md#getting-started", true),
+                ("Guide.md#Getting%20Started", true),
+                ("#Local", true),
+                ("#Elsewhere", false),
+                ("manual.pdf#page=3", true),
+            ]
+        );
+        // The note resolved; only the fragment is broken
+        assert_eq!(
+            links[1].resolved_target,
+            Some(PathBuf::from("/vault/Guide.md"))
+        );
+
+        // Without an anchor index fragments aren't checked
+        let plain = LinkResolver::new(&graph, "/vault", vec![PathBuf::from("/vault/manual.pdf")]);
+        assert!(plain.validate(&source).iter().all(|l| l.is_valid));
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("My%20Note.md"), "My Note.md");
+        assert_eq!(percent_decode("caf%C3%A9"), "café");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+}
@@ -0,0 +1,687 @@
+//! Tag co-occurrence analysis
+//!
+//! [`TagGraph`] is the bipartite tag-to-note graph folded into a weighted
+//! tag co-occurrence graph: two tags are connected by the number of notes
+//! carrying both. Pairs are scored with Jaccard similarity and (normalized)
+//! pointwise mutual information, so rare tags that always appear together
+//! rank above common tags that happen to overlap.
+//!
+//! Tags are compared case-insensitively without the leading `#`, and nested
+//! `#parent/child` tags form a hierarchy. Ancestors and descendants are never
+//! reported as related or duplicate tags, since nesting already relates them.
+
+use crate::community::{CommunityConfig, Network, louvain};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use turbovault_core::prelude::*;
+
+/// Co-occurrence statistics for two tags
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagPair {
+    pub tag: String,
+    pub other: String,
+    /// Notes carrying both tags
+    pub shared_notes: usize,
+    /// Shared notes over notes carrying either tag
+    pub jaccard: f64,
+    /// ln(P(a, b) / (P(a) P(b)))
+    pub pmi: f64,
+    /// PMI scaled to [-1, 1]; 1 means the tags only ever appear together
+    pub npmi: f64,
+}
+
+/// Thresholds for near-duplicate tag detection
+#[derive(Debug, Clone, PartialEq)]
+pub struct NearDuplicateConfig {
+    /// Minimum combined score (0.6 × string + 0.4 × context similarity)
+    pub min_score: f64,
+    /// Pairs with names less similar than this are never reported
+    pub min_string_similarity: f64,
+}
+
+impl Default for NearDuplicateConfig {
+    fn default() -> Self {
+        Self {
+            min_score: 0.5,
+            min_string_similarity: 0.3,
+        }
+    }
+}
+
+/// Two tags that probably mean the same thing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NearDuplicateTags {
+    /// The more widely used tag, suggested as the one to keep
+    pub keep: String,
+    pub merge: String,
+    pub keep_notes: usize,
+    pub merge_notes: usize,
+    /// Notes carrying both tags
+    pub shared_notes: usize,
+    /// Name similarity in [0, 1], ignoring separators, plurals and acronyms
+    pub string_similarity: f64,
+    /// Cosine similarity of the tags they co-occur with, in [0, 1]
+    pub context_similarity: f64,
+    pub score: f64,
+    /// One tag is the acronym of the other (`ml` / `machine-learning`)
+    pub acronym: bool,
+}
+
+/// Tags that tend to be used together
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagCommunity {
+    /// Index in [`TagCommunities::communities`] (largest first)
+    pub id: usize,
+    /// Member tags, most used first
+    pub tags: Vec<String>,
+    /// Notes carrying at least one member tag
+    pub note_count: usize,
+}
+
+/// Partition of the tag co-occurrence graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCommunities {
+    pub communities: Vec<TagCommunity>,
+    pub modularity: f64,
+    /// Community of every tag
+    #[serde(skip)]
+    labels: HashMap<String, usize>,
+}
+
+impl TagCommunities {
+    /// Community of a tag
+    pub fn community_of(&self, tag: &str) -> Option<usize> {
+        self.labels.get(&normalize_tag(tag)).copied()
+    }
+}
+
+/// A note whose tags span several tag communities
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BridgingNote {
+    pub path: PathBuf,
+    /// Communities touched, in id order
+    pub communities: Vec<usize>,
+    /// The note's tags, grouped by community
+    pub tags: Vec<String>,
+}
+
+/// Tag co-occurrence graph with the nested-tag hierarchy
+#[derive(Debug, Clone, Default)]
+pub struct TagGraph {
+    /// Notes carrying exactly each tag
+    notes_by_tag: BTreeMap<String, BTreeSet<PathBuf>>,
+    /// Tags of each note
+    tags_by_note: BTreeMap<PathBuf, BTreeSet<String>>,
+    /// Shared-note counts, stored in both directions
+    cooccurrence: HashMap<String, BTreeMap<String, usize>>,
+    /// Direct children of each tag, including parents only used through children
+    children: BTreeMap<String, BTreeSet<String>>,
+    /// Every note passed in, tagged or not
+    note_count: usize,
+}
+
+impl TagGraph {
+    /// Build from each note's tags (with or without `#`, any case)
+    pub fn new(note_tags: &HashMap<PathBuf, Vec<String>>) -> Self {
+        let mut graph = Self {
+            note_count: note_tags.len(),
+            ..Self::default()
+        };
+
+        for (path, tags) in note_tags {
+            let tags: BTreeSet<String> = tags
+                .iter()
+                .map(|t| normalize_tag(t))
+                .filter(|t| !t.is_empty())
+                .collect();
+            if tags.is_empty() {
+                continue;
+            }
+            for tag in &tags {
+                graph
+                    .notes_by_tag
+                    .entry(tag.clone())
+                    .or_default()
+                    .insert(path.clone());
+            }
+            for a in &tags {
+                for b in &tags {
+                    if a != b {
+                        *graph
+                            .cooccurrence
+                            .entry(a.clone())
+                            .or_default()
+                            .entry(b.clone())
+                            .or_insert(0) += 1;
+                    }
+                }
+            }
+            graph.tags_by_note.insert(path.clone(), tags);
+        }
+
+        for tag in graph.notes_by_tag.keys() {
+            let mut child = tag.as_str();
+            while let Some((parent, _)) = child.rsplit_once('/') {
+                graph
+                    .children
+                    .entry(parent.to_string())
+                    .or_default()
+                    .insert(child.to_string());
+                child = parent;
+            }
+        }
+
+        graph
+    }
+
+    /// Notes the graph was built from, tagged or not
+    pub fn note_count(&self) -> usize {
+        self.note_count
+    }
+
+    /// Tags used on at least one note, sorted
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.notes_by_tag.keys().map(String::as_str)
+    }
+
+    /// Notes carrying exactly this tag
+    pub fn notes_with(&self, tag: &str) -> Vec<PathBuf> {
+        self.notes_by_tag
+            .get(&normalize_tag(tag))
+            .map(|notes| notes.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Tags of one note, normalized
+    pub fn tags_of(&self, path: &Path) -> Vec<String> {
+        self.tags_by_note
+            .get(path)
+            .map(|tags| tags.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Notes carrying this tag or any descendant
+    pub fn notes_under(&self, tag: &str) -> Vec<PathBuf> {
+        let tag = normalize_tag(tag);
+        let prefix = format!("{}/", tag);
+        let notes: BTreeSet<&PathBuf> = self
+            .notes_by_tag
+            .iter()
+            .filter(|(t, _)| **t == tag || t.starts_with(&prefix))
+            .flat_map(|(_, notes)| notes)
+            .collect();
+        notes.into_iter().cloned().collect()
+    }
+
+    /// Parent of a nested tag (`a/b` → `a`)
+    pub fn parent(&self, tag: &str) -> Option<String> {
+        normalize_tag(tag)
+            .rsplit_once('/')
+            .map(|(parent, _)| parent.to_string())
+    }
+
+    /// Direct children of a tag
+    pub fn children(&self, tag: &str) -> Vec<String> {
+        self.children
+            .get(&normalize_tag(tag))
+            .map(|children| children.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Top-level tags, including parents only used through children
+    pub fn roots(&self) -> Vec<String> {
+        let roots: BTreeSet<&str> = self
+            .notes_by_tag
+            .keys()
+            .map(|tag| tag.split('/').next().unwrap_or(tag))
+            .collect();
+        roots.into_iter().map(String::from).collect()
+    }
+
+    /// Co-occurrence statistics for two tags, if both are used
+    pub fn pair(&self, tag: &str, other: &str) -> Option<TagPair> {
+        let (tag, other) = (normalize_tag(tag), normalize_tag(other));
+        if tag == other {
+            return None;
+        }
+        let a = self.notes_by_tag.get(&tag)?.len();
+        let b = self.notes_by_tag.get(&other)?.len();
+        let shared = self.shared(&tag, &other);
+        Some(self.score_pair(tag, other, a, b, shared))
+    }
+
+    /// Tags co-occurring with `tag` on at least `min_shared` notes, by NPMI
+    ///
+    /// Ancestors and descendants of `tag` are left out.
+    pub fn related_tags(&self, tag: &str, min_shared: usize) -> Result<Vec<TagPair>> {
+        let tag = normalize_tag(tag);
+        let count = self
+            .notes_by_tag
+            .get(&tag)
+            .map(BTreeSet::len)
+            .ok_or_else(|| Error::not_found(format!("Tag not found: #{}", tag)))?;
+
+        let mut related: Vec<TagPair> = self
+            .cooccurrence
+            .get(&tag)
+            .into_iter()
+            .flatten()
+            .filter(|(other, shared)| **shared >= min_shared.max(1) && !nested(&tag, other))
+            .map(|(other, &shared)| {
+                let other_count = self.notes_by_tag[other].len();
+                self.score_pair(tag.clone(), other.clone(), count, other_count, shared)
+            })
+            .collect();
+        related.sort_by(|a, b| {
+            b.npmi
+                .total_cmp(&a.npmi)
+                .then_with(|| b.shared_notes.cmp(&a.shared_notes))
+                .then_with(|| a.other.cmp(&b.other))
+        });
+        Ok(related)
+    }
+
+    /// Pairs of tags that look like spellings of the same concept, best first
+    pub fn near_duplicates(&self, config: &NearDuplicateConfig) -> Vec<NearDuplicateTags> {
+        let tags: Vec<&String> = self.notes_by_tag.keys().collect();
+        let mut duplicates = Vec::new();
+
+        for (i, a) in tags.iter().enumerate() {
+            for b in &tags[i + 1..] {
+                if nested(a, b) {
+                    continue;
+                }
+                let (string_similarity, acronym) = name_similarity(a, b);
+                if string_similarity < config.min_string_similarity {
+                    continue;
+                }
+                let context_similarity = self.context_similarity(a, b);
+                let score = 0.6 * string_similarity + 0.4 * context_similarity;
+                if score < config.min_score {
+                    continue;
+                }
+
+                let (a_notes, b_notes) = (self.notes_by_tag[*a].len(), self.notes_by_tag[*b].len());
+                let (keep, merge, keep_notes, merge_notes) = if b_notes > a_notes {
+                    (b, a, b_notes, a_notes)
+                } else {
+                    (a, b, a_notes, b_notes)
+                };
+                duplicates.push(NearDuplicateTags {
+                    keep: (*keep).clone(),
+                    merge: (*merge).clone(),
+                    keep_notes,
+                    merge_notes,
+                    shared_notes: self.shared(a, b),
+                    string_similarity,
+                    context_similarity,
+                    score,
+                    acronym,
+                });
+            }
+        }
+
+        duplicates.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.keep.cmp(&b.keep))
+                .then_with(|| a.merge.cmp(&b.merge))
+        });
+        duplicates
+    }
+
+    /// Cluster tags with Louvain over shared-note weights
+    ///
+    /// Only `config.resolution` and `config.max_sweeps` apply; every tag
+    /// belongs to some community, singletons included.
+    pub fn communities(&self, config: &CommunityConfig) -> TagCommunities {
+        let tags: Vec<&String> = self.notes_by_tag.keys().collect();
+        let index: HashMap<&String, usize> =
+            tags.iter().enumerate().map(|(i, t)| (*t, i)).collect();
+
+        let mut pairs: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+        for (tag, others) in &self.cooccurrence {
+            for (other, &shared) in others {
+                let (a, b) = (index[tag], index[other]);
+                if a < b {
+                    pairs.insert((a, b), shared as f64);
+                }
+            }
+        }
+        let network = Network::from_pairs(tags.len(), &pairs);
+        let labels = louvain(&network, config);
+        let modularity = network.modularity(&labels, config.resolution);
+
+        let mut members: BTreeMap<usize, Vec<&String>> = BTreeMap::new();
+        for (i, &label) in labels.iter().enumerate() {
+            members.entry(label).or_default().push(tags[i]);
+        }
+        let mut groups: Vec<Vec<&String>> = members.into_values().collect();
+        for group in &mut groups {
+            group.sort_by(|a, b| {
+                self.notes_by_tag[*b]
+                    .len()
+                    .cmp(&self.notes_by_tag[*a].len())
+                    .then_with(|| a.cmp(b))
+            });
+        }
+        groups.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a[0].cmp(b[0])));
+
+        let mut communities = Vec::new();
+        let mut labels = HashMap::new();
+        for (id, group) in groups.into_iter().enumerate() {
+            let notes: BTreeSet<&PathBuf> = group
+                .iter()
+                .flat_map(|tag| &self.notes_by_tag[*tag])
+                .collect();
+            for tag in &group {
+                labels.insert((*tag).clone(), id);
+            }
+            communities.push(TagCommunity {
+                id,
+                tags: group.into_iter().cloned().collect(),
+                note_count: notes.len(),
+            });
+        }
+
+        TagCommunities {
+            communities,
+            modularity,
+            labels,
+        }
+    }
+
+    /// Notes whose tags touch at least `min_communities` tag communities,
+    /// most communities first
+    pub fn bridging_notes(
+        &self,
+        communities: &TagCommunities,
+        min_communities: usize,
+    ) -> Vec<BridgingNote> {
+        let mut bridges: Vec<BridgingNote> = self
+            .tags_by_note
+            .iter()
+            .filter_map(|(path, tags)| {
+                let mut by_community: BTreeMap<usize, Vec<&String>> = BTreeMap::new();
+                for tag in tags {
+                    if let Some(&id) = communities.labels.get(tag) {
+                        by_community.entry(id).or_default().push(tag);
+                    }
+                }
+                if by_community.len() < min_communities.max(2) {
+                    return None;
+                }
+                Some(BridgingNote {
+                    path: path.clone(),
+                    communities: by_community.keys().copied().collect(),
+                    tags: by_community.into_values().flatten().cloned().collect(),
+                })
+            })
+            .collect();
+        bridges.sort_by(|a, b| {
+            b.communities
+                .len()
+                .cmp(&a.communities.len())
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        bridges
+    }
+
+    fn shared(&self, tag: &str, other: &str) -> usize {
+        self.cooccurrence
+            .get(tag)
+            .and_then(|others| others.get(other))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn score_pair(&self, tag: String, other: String, a: usize, b: usize, shared: usize) -> TagPair {
+        let n = self.note_count.max(1) as f64;
+        let union = (a + b - shared) as f64;
+        let jaccard = if union > 0.0 {
+            shared as f64 / union
+        } else {
+            0.0
+        };
+        let (pmi, npmi) = if shared == 0 {
+            (f64::NEG_INFINITY, -1.0)
+        } else {
+            let p_ab = shared as f64 / n;
+            let pmi = (p_ab / ((a as f64 / n) * (b as f64 / n))).ln();
+            let npmi = if p_ab >= 1.0 { 1.0 } else { pmi / -p_ab.ln() };
+            (pmi, npmi)
+        };
+        TagPair {
+            tag,
+            other,
+            shared_notes: shared,
+            jaccard,
+            pmi,
+            npmi,
+        }
+    }
+
+    /// Cosine similarity of two tags' co-occurrence vectors, ignoring each other
+    fn context_similarity(&self, a: &str, b: &str) -> f64 {
+        let empty = BTreeMap::new();
+        let va = self.cooccurrence.get(a).unwrap_or(&empty);
+        let vb = self.cooccurrence.get(b).unwrap_or(&empty);
+        let keep = |tag: &String| tag != a && tag != b;
+
+        let dot: f64 = va
+            .iter()
+            .filter(|(tag, _)| keep(tag))
+            .filter_map(|(tag, &x)| vb.get(tag).map(|&y| (x * y) as f64))
+            .sum();
+        let norm = |v: &BTreeMap<String, usize>| {
+            v.iter()
+                .filter(|(tag, _)| keep(tag))
+                .map(|(_, &x)| (x * x) as f64)
+                .sum::<f64>()
+                .sqrt()
+        };
+        let denominator = norm(va) * norm(vb);
+        if denominator > 0.0 {
+            dot / denominator
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Lowercase without the leading `#`
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// Whether one tag is an ancestor of the other
+fn nested(a: &str, b: &str) -> bool {
+    let is_ancestor =
+        |p: &str, c: &str| c.len() > p.len() && c.starts_with(p) && c[p.len()..].starts_with('/');
+    is_ancestor(a, b) || is_ancestor(b, a)
+}
+
+/// Name similarity of two tags, and whether it comes from an acronym match
+///
+/// Compares whole tags and their last path segments with separators
+/// (`-`, `_`, spaces) removed: equal names score 1.0, singular/plural
+/// variants 0.95, an acronym of a multi-word tag 0.9, anything else its
+/// normalized Levenshtein similarity.
+fn name_similarity(a: &str, b: &str) -> (f64, bool) {
+    let last = |tag: &str| tag.rsplit('/').next().unwrap_or(tag).to_string();
+    let candidates = [(a.to_string(), b.to_string()), (last(a), last(b))];
+
+    let mut best = (0.0, false);
+    for (x, y) in candidates {
+        let score = segment_similarity(&x, &y);
+        if score.0 > best.0 {
+            best = score;
+        }
+    }
+    best
+}
+
+fn segment_similarity(a: &str, b: &str) -> (f64, bool) {
+    let squash = |s: &str| -> String {
+        s.chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' ' | '/'))
+            .collect()
+    };
+    let (sa, sb) = (squash(a), squash(b));
+    if sa.is_empty() || sb.is_empty() {
+        return (0.0, false);
+    }
+    if sa == sb {
+        return (1.0, false);
+    }
+    let singular = |s: &str| s.strip_suffix('s').unwrap_or(s).to_string();
+    if singular(&sa) == singular(&sb) {
+        return (0.95, false);
+    }
+    if acronym(a).is_some_and(|ac| ac == sb) || acronym(b).is_some_and(|ac| ac == sa) {
+        return (0.9, true);
+    }
+    (strsim::normalized_levenshtein(&sa, &sb), false)
+}
+
+/// First letters of a multi-word tag (`machine-learning` → `ml`)
+fn acronym(tag: &str) -> Option<String> {
+    let words: Vec<&str> = tag
+        .split(['-', '_', ' ', '/'])
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words.len() < 2 {
+        return None;
+    }
+    words.iter().map(|w| w.chars().next()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(notes: &[(&str, &[&str])]) -> TagGraph {
+        let map: HashMap<PathBuf, Vec<String>> = notes
+            .iter()
+            .map(|(path, tags)| {
+                (
+                    PathBuf::from(path),
+                    tags.iter().map(|t| t.to_string()).collect(),
+                )
+            })
+            .collect();
+        TagGraph::new(&map)
+    }
+
+    /// Two topics (ml/ai and cooking) with one note spanning both
+    fn vault() -> TagGraph {
+        graph(&[
+            ("a.md", &["#ML", "ai", "python"]),
+            ("b.md", &["machine-learning", "ai", "python"]),
+            ("c.md", &["ml", "ai"]),
+            ("d.md", &["machine-learning", "ai"]),
+            ("e.md", &["cooking", "recipes"]),
+            ("f.md", &["cooking", "recipe"]),
+            ("g.md", &["cooking/italian", "recipes"]),
+            ("h.md", &["python", "cooking"]),
+            ("i.md", &[]),
+        ])
+    }
+
+    #[test]
+    fn test_pair_scores() {
+        let graph = vault();
+        assert_eq!(graph.note_count(), 9);
+        assert_eq!(graph.notes_with("#ml").len(), 2);
+
+        // ai: a, b, c, d; python: a, b, h
+        let pair = graph.pair("ai", "python").unwrap();
+        assert_eq!(pair.shared_notes, 2);
+        assert!((pair.jaccard - 2.0 / 5.0).abs() < 1e-9);
+        let pmi = ((2.0f64 / 9.0) / ((4.0 / 9.0) * (3.0 / 9.0))).ln();
+        assert!((pair.pmi - pmi).abs() < 1e-9);
+        assert!((pair.npmi - pmi / -(2.0f64 / 9.0).ln()).abs() < 1e-9);
+
+        let apart = graph.pair("ai", "cooking").unwrap();
+        assert_eq!(apart.shared_notes, 0);
+        assert_eq!(apart.npmi, -1.0);
+        assert!(graph.pair("ai", "missing").is_none());
+    }
+
+    #[test]
+    fn test_related_tags_by_npmi() {
+        let graph = vault();
+        let related = graph.related_tags("AI", 1).unwrap();
+        let names: Vec<&str> = related.iter().map(|p| p.other.as_str()).collect();
+        assert_eq!(names, vec!["machine-learning", "ml", "python"]);
+        assert!(related[0].npmi > related[2].npmi);
+
+        assert!(graph.related_tags("ai", 3).unwrap().is_empty());
+        assert!(graph.related_tags("nope", 1).is_err());
+    }
+
+    #[test]
+    fn test_hierarchy() {
+        let graph = graph(&[
+            ("a.md", &["project/alpha/docs", "project"]),
+            ("b.md", &["project/beta"]),
+        ]);
+        assert_eq!(graph.roots(), vec!["project"]);
+        assert_eq!(
+            graph.children("project"),
+            vec!["project/alpha", "project/beta"]
+        );
+        assert_eq!(graph.children("project/alpha"), vec!["project/alpha/docs"]);
+        assert_eq!(graph.parent("project/alpha"), Some("project".to_string()));
+        assert_eq!(graph.notes_under("project").len(), 2);
+        assert_eq!(graph.notes_with("project").len(), 1);
+
+        // Nesting already relates these
+        assert!(graph.related_tags("project", 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_near_duplicates() {
+        let graph = vault();
+        let duplicates = graph.near_duplicates(&NearDuplicateConfig::default());
+        let pairs: Vec<(&str, &str)> = duplicates
+            .iter()
+            .map(|d| (d.keep.as_str(), d.merge.as_str()))
+            .collect();
+
+        assert!(pairs.contains(&("recipes", "recipe")));
+        assert!(
+            pairs.contains(&("machine-learning", "ml"))
+                || pairs.contains(&("ml", "machine-learning"))
+        );
+        let ml = duplicates.iter().find(|d| d.acronym).unwrap();
+        assert_eq!(ml.shared_notes, 0);
+        assert!(ml.context_similarity > 0.9);
+
+        assert!(!pairs.iter().any(|(a, b)| *a == "ai" || *b == "ai"));
+        assert!(!pairs.contains(&("cooking", "cooking/italian")));
+    }
+
+    #[test]
+    fn test_communities_and_bridging_notes() {
+        let graph = vault();
+        let communities = graph.communities(&CommunityConfig::default());
+        let ai = communities.community_of("ai").unwrap();
+        assert_eq!(communities.community_of("#ML"), Some(ai));
+        let cooking = communities.community_of("cooking").unwrap();
+        assert_ne!(ai, cooking);
+        assert_eq!(communities.community_of("recipes"), Some(cooking));
+        assert!(communities.modularity > 0.0);
+
+        let bridges = graph.bridging_notes(&communities, 2);
+        // python co-occurs more with ai than with cooking, so h.md is the bridge
+        assert_eq!(communities.community_of("python"), Some(ai));
+        assert_eq!(
+            bridges.iter().map(|b| b.path.clone()).collect::<Vec<_>>(),
+            vec![PathBuf::from("h.md")]
+        );
+        assert_eq!(bridges[0].tags, vec!["python", "cooking"]);
+        assert_eq!(bridges[0].communities.len(), 2);
+    }
+}
@@ -0,0 +1,190 @@
+//! Obsidian Bases (`.base`) view definitions.
+//!
+//! A base is YAML: global `filters`, `formulas`, per-property display
+//! settings under `properties`, and a list of `views` (table, cards, ...),
+//! each with its own `filters`, column `order`, `sort` and `limit`. Filters
+//! are expression strings such as `file.hasTag("book")` or
+//! `status != "done"`, combined with `and`, `or` and `not` lists. This module
+//! models the file; evaluating expressions is left to the caller. Keys the
+//! model does not know are kept in `extra`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use turbovault_core::{Error, Result};
+
+/// A `.base` file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Base {
+    /// Filters every view applies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<BaseFilter>,
+    /// Formula name to expression
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub formulas: Map<String, Value>,
+    /// Property (`note.status`, `file.name`, `formula.x`) to its settings
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub properties: Map<String, Value>,
+    #[serde(default)]
+    pub views: Vec<BaseView>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// A filter: an expression, or `and` / `or` / `not` over nested filters
+///
+/// `not` matches when none of its filters match.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BaseFilter {
+    Expression(String),
+    And { and: Vec<BaseFilter> },
+    Or { or: Vec<BaseFilter> },
+    Not { not: Vec<BaseFilter> },
+}
+
+/// One view of a base
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaseView {
+    /// `table`, `cards`, ...
+    #[serde(rename = "type")]
+    pub view_type: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<BaseFilter>,
+    /// Properties shown, in column order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sort: Vec<BaseSort>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Sort key of a view
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaseSort {
+    pub property: String,
+    #[serde(default)]
+    pub direction: SortDirection,
+}
+
+/// Sort direction, written `ASC` / `DESC`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl Base {
+    /// Parse a base; empty content is an empty base
+    pub fn parse(content: &str) -> Result<Self> {
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        serde_yaml::from_str(content)
+            .map_err(|e| Error::parse_error(format!("Invalid base: {}", e)))
+    }
+
+    /// View by name (case-insensitive), or the first view when `name` is `None`
+    pub fn view(&self, name: Option<&str>) -> Option<&BaseView> {
+        match name {
+            Some(name) => self
+                .views
+                .iter()
+                .find(|v| v.name.eq_ignore_ascii_case(name.trim())),
+            None => self.views.first(),
+        }
+    }
+
+    /// Display name configured for a property, if any
+    pub fn display_name(&self, property: &str) -> Option<&str> {
+        self.properties.get(property)?.get("displayName")?.as_str()
+    }
+}
+
+impl BaseFilter {
+    /// Every expression in the filter, depth first
+    pub fn expressions(&self) -> Vec<&str> {
+        match self {
+            BaseFilter::Expression(expression) => vec![expression.as_str()],
+            BaseFilter::And { and: filters }
+            | BaseFilter::Or { or: filters }
+            | BaseFilter::Not { not: filters } => {
+                filters.iter().flat_map(BaseFilter::expressions).collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOOKS: &str = r#"
+filters:
+  and:
+    - file.hasTag("book")
+    - 'file.inFolder("Library")'
+formulas:
+  pages_left: "pages - read"
+properties:
+  note.author:
+    displayName: Author
+views:
+  - type: table
+    name: Reading
+    filters:
+      or:
+        - status == "reading"
+        - not:
+            - status == "done"
+    order:
+      - file.name
+      - note.author
+      - formula.pages_left
+    sort:
+      - property: note.author
+        direction: DESC
+    limit: 10
+  - type: cards
+    name: All
+    image: note.cover
+"#;
+
+    #[test]
+    fn test_parse_base() {
+        let base = Base::parse(BOOKS).unwrap();
+        assert_eq!(
+            base.filters.as_ref().unwrap().expressions(),
+            vec!["file.hasTag(\"book\")", "file.inFolder(\"Library\")"]
+        );
+        assert_eq!(base.formulas["pages_left"], Value::from("pages - read"));
+        assert_eq!(base.display_name("note.author"), Some("Author"));
+        assert_eq!(base.views.len(), 2);
+
+        let reading = base.view(Some("reading")).unwrap();
+        assert_eq!(reading.view_type, "table");
+        assert_eq!(reading.order.len(), 3);
+        assert_eq!(reading.sort[0].direction, SortDirection::Desc);
+        assert_eq!(reading.limit, Some(10));
+        assert!(matches!(
+            &reading.filters,
+            Some(BaseFilter::Or { or }) if matches!(&or[1], BaseFilter::Not { .. })
+        ));
+
+        let cards = base.view(Some("All")).unwrap();
+        assert_eq!(cards.extra["image"], Value::from("note.cover"));
+        assert_eq!(base.view(None).unwrap().name, "Reading");
+    }
+
+    #[test]
+    fn test_parse_empty_and_invalid() {
+        assert_eq!(Base::parse("").unwrap(), Base::default());
+        assert!(Base::parse("views: 3").is_err());
+    }
+}
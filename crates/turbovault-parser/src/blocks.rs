@@ -6,16 +6,229 @@
 //! - Lists (ordered, unordered, task lists)
 //! - Tables, blockquotes, images
 //! - HTML details blocks
+//! - Display math, footnote definitions and `%%comments%%`
 //!
 //! The parser handles inline formatting within blocks, producing
-//! `InlineElement` vectors for text content.
+//! `InlineElement` vectors for text content, including `==highlights==`,
+//! `$math$`, footnote references and inline comments.
 
 use pulldown_cmark::{
     Alignment as CmarkAlignment, CodeBlockKind, Event, Options, Parser, Tag, TagEnd,
 };
 use regex::Regex;
+use std::ops::Range;
 use std::sync::LazyLock;
-use turbovault_core::{ContentBlock, InlineElement, ListItem, TableAlignment};
+use turbovault_core::{
+    ContentBlock, InlineElement, LineIndex, ListItem, SourcePosition, TableAlignment,
+};
+
+use crate::ParseOptions;
+use crate::engine::ParseEngine;
+
+// ============================================================================
+// Offset mapping (preprocessing rewrites the text pulldown-cmark sees)
+// ============================================================================
+
+/// Replacements made by one preprocessing step, used to map offsets in the
+/// rewritten text back to the text it was made from.
+#[derive(Debug, Default)]
+struct OffsetMap {
+    /// (range in the output, range in the input), in order
+    edits: Vec<(Range<usize>, Range<usize>)>,
+}
+
+impl OffsetMap {
+    fn push(&mut self, output: Range<usize>, input: Range<usize>) {
+        self.edits.push((output, input));
+    }
+
+    /// Range in the input for a range in the output. A range starting where
+    /// something was replaced includes it, one ending there does not, and
+    /// offsets strictly inside a replacement map to the start of what it
+    /// replaced.
+    fn map(&self, range: Range<usize>) -> Range<usize> {
+        self.map_offset(range.start, true)..self.map_offset(range.end, false)
+    }
+
+    fn map_offset(&self, offset: usize, is_start: bool) -> usize {
+        let idx = self.edits.partition_point(|(output, _)| {
+            output.start < offset || (is_start && output.start == offset)
+        });
+        match idx.checked_sub(1).map(|i| &self.edits[i]) {
+            None => offset,
+            Some((output, input)) if offset >= output.end => input.end + (offset - output.end),
+            Some((_, input)) => input.start,
+        }
+    }
+}
+
+/// Replace every match of `re` and record the replacements
+fn replace_all_mapped(
+    text: &str,
+    re: &Regex,
+    mut replacement: impl FnMut(&regex::Captures) -> String,
+) -> (String, OffsetMap) {
+    let mut result = String::with_capacity(text.len());
+    let mut map = OffsetMap::default();
+    let mut last = 0;
+    for caps in re.captures_iter(text) {
+        let matched = caps.get(0).unwrap();
+        let replaced = replacement(&caps);
+        result.push_str(&text[last..matched.start()]);
+        if replaced != matched.as_str() {
+            map.push(result.len()..result.len() + replaced.len(), matched.range());
+        }
+        result.push_str(&replaced);
+        last = matched.end();
+    }
+    result.push_str(&text[last..]);
+    (result, map)
+}
+
+/// Span of a byte range, to be given lines and columns by [`locate_spans`]
+fn raw_span(range: Range<usize>) -> Option<SourcePosition> {
+    Some(SourcePosition::new(
+        0,
+        0,
+        range.start,
+        range.end.saturating_sub(range.start),
+    ))
+}
+
+/// Move the byte ranges of spans, e.g. from nested markdown into its parent
+pub(crate) fn remap_spans(blocks: &mut [ContentBlock], map: impl Fn(Range<usize>) -> Range<usize>) {
+    for block in blocks {
+        block.for_each_span_mut(&mut |span| {
+            if let Some(span) = span {
+                let range = map(span.range());
+                span.offset = range.start;
+                span.length = range.end.saturating_sub(range.start);
+            }
+        });
+    }
+}
+
+/// Fill in lines and columns of raw spans. Trailing line breaks are left out
+/// of a span, and code blocks take their `start_line`/`end_line` from it.
+fn locate_spans(blocks: &mut [ContentBlock], markdown: &str, start_line: usize) {
+    let index = LineIndex::new(markdown);
+    for block in blocks.iter_mut() {
+        block.for_each_span_mut(&mut |span| {
+            if let Some(span) = span {
+                let mut range = span.range();
+                if let Some(text) = markdown.get(range.clone()) {
+                    let trimmed = text.trim_end_matches(['\n', '\r']).len();
+                    if trimmed > 0 {
+                        range.end = range.start + trimmed;
+                    }
+                }
+                *span = SourcePosition::from_offset_indexed(&index, range.start, range.len());
+                span.line += start_line;
+            }
+        });
+    }
+    set_code_lines(blocks, markdown);
+}
+
+fn set_code_lines(blocks: &mut [ContentBlock], markdown: &str) {
+    for block in blocks {
+        match block {
+            ContentBlock::Code {
+                start_line,
+                end_line,
+                span: Some(span),
+                ..
+            } => {
+                *start_line = span.line;
+                *end_line = span.line
+                    + markdown
+                        .get(span.range())
+                        .map_or(0, |text| text.matches('\n').count());
+            }
+            ContentBlock::List { items, .. } => {
+                for item in items {
+                    set_code_lines(&mut item.blocks, markdown);
+                }
+            }
+            ContentBlock::Blockquote { blocks, .. }
+            | ContentBlock::Details { blocks, .. }
+            | ContentBlock::FootnoteDefinition { blocks, .. } => set_code_lines(blocks, markdown),
+            _ => {}
+        }
+    }
+}
+
+// ============================================================================
+// Comment and highlight preprocessing (pulldown-cmark knows neither)
+// ============================================================================
+
+/// Placeholder a `%%comment%%` is replaced with, holding its index: an HTML
+/// comment when it fills its lines (an HTML block), an empty element when it
+/// shares a line with text (inline HTML)
+static COMMENT_PLACEHOLDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:<!--turbovault-comment:(\d+)-->|<turbovault-comment-(\d+)/>)\s*$").unwrap()
+});
+
+/// Replace comments with HTML comment placeholders and highlights with
+/// `<mark>` tags, outside code. Returns the rewritten markdown, the
+/// comment texts and the replacements made.
+fn preprocess_comments_and_highlights(markdown: &str) -> (String, Vec<String>, OffsetMap) {
+    if !markdown.contains("%%") && !markdown.contains("==") {
+        return (markdown.to_string(), Vec::new(), OffsetMap::default());
+    }
+
+    let options = ParseOptions {
+        parse_comments: true,
+        parse_highlights: true,
+        ..ParseOptions::none()
+    };
+    let parsed = ParseEngine::new(markdown).parse(&options);
+
+    let mut comments = Vec::new();
+    let mut edits: Vec<(usize, usize, String)> = Vec::new();
+    for comment in parsed.comments {
+        let start = comment.position.offset;
+        let end = start + comment.position.length;
+        let line_before = &markdown[markdown[..start].rfind('\n').map_or(0, |i| i + 1)..start];
+        let line_after = &markdown[end..markdown[end..]
+            .find('\n')
+            .map_or(markdown.len(), |i| end + i)];
+        let placeholder = if line_before.trim().is_empty() && line_after.trim().is_empty() {
+            format!("<!--turbovault-comment:{}-->", comments.len())
+        } else {
+            format!("<turbovault-comment-{}/>", comments.len())
+        };
+        edits.push((start, end, placeholder));
+        comments.push(comment.content);
+    }
+    for highlight in parsed.highlights {
+        let start = highlight.position.offset;
+        let end = start + highlight.position.length;
+        edits.push((start, start + 2, "<mark>".to_string()));
+        edits.push((end - 2, end, "</mark>".to_string()));
+    }
+    edits.sort_by_key(|(start, _, _)| *start);
+
+    let mut result = String::with_capacity(markdown.len());
+    let mut map = OffsetMap::default();
+    let mut last = 0;
+    for (start, end, replacement) in edits {
+        result.push_str(&markdown[last..start]);
+        map.push(result.len()..result.len() + replacement.len(), start..end);
+        result.push_str(&replacement);
+        last = end;
+    }
+    result.push_str(&markdown[last..]);
+    (result, comments, map)
+}
+
+/// Index of the comment a placeholder stands for
+fn comment_placeholder(html: &str) -> Option<usize> {
+    COMMENT_PLACEHOLDER_RE
+        .captures(html)
+        .and_then(|caps| caps.get(1).or_else(|| caps.get(2)))
+        .and_then(|idx| idx.as_str().parse().ok())
+}
 
 // ============================================================================
 // Wikilink preprocessing (converts [[x]] to [x](wikilink:x) for pulldown-cmark)
@@ -27,34 +240,34 @@ static WIKILINK_RE: LazyLock<Regex> =
 
 /// Preprocess wikilinks to standard markdown links with wikilink: prefix.
 /// This allows pulldown-cmark to parse them as regular links.
-fn preprocess_wikilinks(markdown: &str) -> String {
-    WIKILINK_RE
-        .replace_all(markdown, |caps: &regex::Captures| {
-            let target = caps.get(1).map(|m| m.as_str().trim()).unwrap_or("");
-            let alias = caps.get(2).map(|m| m.as_str().trim());
-            let display_text = alias.unwrap_or(target);
-            format!("[{}](wikilink:{})", display_text, target)
-        })
-        .to_string()
+fn preprocess_wikilinks(markdown: &str) -> (String, OffsetMap) {
+    replace_all_mapped(markdown, &WIKILINK_RE, |caps| {
+        let target = caps.get(1).map(|m| m.as_str().trim()).unwrap_or("");
+        let alias = caps.get(2).map(|m| m.as_str().trim());
+        let display_text = alias.unwrap_or(target);
+        format!("[{}](wikilink:{})", display_text, target)
+    })
 }
 
 /// Regex for links with spaces in URL (not valid CommonMark but common in wikis)
 static LINK_WITH_SPACES_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\(([^)<>]+\s[^)<>]*)\)").unwrap());
 
+/// A destination followed by a quoted title: `url "title"` is valid CommonMark
+static LINK_WITH_TITLE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\S+\s+(?:"[^"]*"|'[^']*')\s*$"#).unwrap());
+
 /// Preprocess links with spaces to angle bracket syntax.
-fn preprocess_links_with_spaces(markdown: &str) -> String {
-    LINK_WITH_SPACES_RE
-        .replace_all(markdown, |caps: &regex::Captures| {
-            let text = &caps[1];
-            let url = &caps[2];
-            if url.contains(' ') {
-                format!("[{}](<{}>)", text, url)
-            } else {
-                caps[0].to_string()
-            }
-        })
-        .to_string()
+fn preprocess_links_with_spaces(markdown: &str) -> (String, OffsetMap) {
+    replace_all_mapped(markdown, &LINK_WITH_SPACES_RE, |caps| {
+        let text = &caps[1];
+        let url = &caps[2];
+        if url.contains(' ') && !LINK_WITH_TITLE_RE.is_match(url) {
+            format!("[{}](<{}>)", text, url)
+        } else {
+            caps[0].to_string()
+        }
+    })
 }
 
 // ============================================================================
@@ -62,9 +275,10 @@ fn preprocess_links_with_spaces(markdown: &str) -> String {
 // ============================================================================
 
 /// Extract HTML <details> blocks and replace with placeholders.
-fn extract_details_blocks(markdown: &str) -> (String, Vec<ContentBlock>) {
+fn extract_details_blocks(markdown: &str) -> (String, Vec<ContentBlock>, OffsetMap) {
     let mut details_blocks = Vec::new();
     let mut result = String::new();
+    let mut map = OffsetMap::default();
     let mut current_pos = 0;
 
     while current_pos < markdown.len() {
@@ -80,30 +294,39 @@ fn extract_details_blocks(markdown: &str) -> (String, Vec<ContentBlock>) {
             let summary = extract_summary(details_content);
 
             // Extract content after </summary>
-            let content_start = if let Some(summary_end_pos) = details_content.find("</summary>") {
-                let summary_tag_end = summary_end_pos + "</summary>".len();
-                &details_content[summary_tag_end..]
-            } else {
-                details_content
-            };
+            let content_start = details_content
+                .find("</summary>")
+                .map_or(0, |pos| pos + "</summary>".len());
+            let content_start = &details_content[content_start..];
 
             let content_trimmed = content_start.trim();
+            let content_offset = details_end - content_start.trim_start().len();
 
             // Parse nested content
-            let nested_blocks = if !content_trimmed.is_empty() {
-                parse_blocks(content_trimmed)
+            let mut nested_blocks = if !content_trimmed.is_empty() {
+                parse_blocks_raw(content_trimmed)
             } else {
                 Vec::new()
             };
+            remap_spans(&mut nested_blocks, |range| {
+                range.start + content_offset..range.end + content_offset
+            });
 
+            let block_end = details_end + "</details>".len();
             details_blocks.push(ContentBlock::Details {
                 summary,
                 content: content_trimmed.to_string(),
                 blocks: nested_blocks,
+                span: raw_span(current_pos..block_end),
             });
 
-            result.push_str(&format!("\n[DETAILS_BLOCK_{}]\n", details_blocks.len() - 1));
-            current_pos = details_end + "</details>".len();
+            let placeholder = format!("\n[DETAILS_BLOCK_{}]\n", details_blocks.len() - 1);
+            map.push(
+                result.len()..result.len() + placeholder.len(),
+                current_pos..block_end,
+            );
+            result.push_str(&placeholder);
+            current_pos = block_end;
             continue;
         }
 
@@ -115,7 +338,7 @@ fn extract_details_blocks(markdown: &str) -> (String, Vec<ContentBlock>) {
         }
     }
 
-    (result, details_blocks)
+    (result, details_blocks, map)
 }
 
 /// Extract summary text from details content.
@@ -137,8 +360,25 @@ fn extract_summary(details_content: &str) -> String {
 // Parser state machine
 // ============================================================================
 
+/// Byte ranges below are in the markdown given to the parser, before
+/// preprocessing.
 struct BlockParserState {
     current_line: usize,
+    comments: Vec<String>,
+    in_highlight: bool,
+    /// Label, index of its first block and range of the open footnote definition
+    footnote: Option<(String, usize, Range<usize>)>,
+    paragraph_range: Range<usize>,
+    list_range: Range<usize>,
+    item_range: Range<usize>,
+    code_range: Range<usize>,
+    quote_range: Range<usize>,
+    table_range: Range<usize>,
+    heading_range: Range<usize>,
+    /// Inline element count when each open strong/emphasis/strikethrough began
+    format_starts: Vec<usize>,
+    /// Inline element count and offset where the open highlight began
+    highlight_start: Option<(usize, usize)>,
     paragraph_buffer: String,
     inline_buffer: Vec<InlineElement>,
     list_items: Vec<ListItem>,
@@ -152,6 +392,7 @@ struct BlockParserState {
     code_language: Option<String>,
     code_start_line: usize,
     blockquote_buffer: String,
+    blockquote_blocks: Vec<ContentBlock>,
     table_headers: Vec<String>,
     table_alignments: Vec<TableAlignment>,
     table_rows: Vec<Vec<String>>,
@@ -171,6 +412,7 @@ struct BlockParserState {
     in_code_inline: bool,
     in_link: bool,
     link_url: String,
+    link_title: String,
     link_text: String,
     image_in_link: bool,
     in_image: bool,
@@ -180,9 +422,21 @@ struct BlockParserState {
 }
 
 impl BlockParserState {
-    fn new(start_line: usize) -> Self {
+    fn new(start_line: usize, comments: Vec<String>) -> Self {
         Self {
             current_line: start_line,
+            comments,
+            in_highlight: false,
+            footnote: None,
+            paragraph_range: 0..0,
+            list_range: 0..0,
+            item_range: 0..0,
+            code_range: 0..0,
+            quote_range: 0..0,
+            table_range: 0..0,
+            heading_range: 0..0,
+            format_starts: Vec::new(),
+            highlight_start: None,
             paragraph_buffer: String::new(),
             inline_buffer: Vec::new(),
             list_items: Vec::new(),
@@ -196,6 +450,7 @@ impl BlockParserState {
             code_language: None,
             code_start_line: 0,
             blockquote_buffer: String::new(),
+            blockquote_blocks: Vec::new(),
             table_headers: Vec::new(),
             table_alignments: Vec::new(),
             table_rows: Vec::new(),
@@ -215,6 +470,7 @@ impl BlockParserState {
             in_code_inline: false,
             in_link: false,
             link_url: String::new(),
+            link_title: String::new(),
             link_text: String::new(),
             image_in_link: false,
             in_image: false,
@@ -233,10 +489,7 @@ impl BlockParserState {
 
     fn flush_paragraph(&mut self, blocks: &mut Vec<ContentBlock>) {
         if self.in_paragraph && !self.paragraph_buffer.is_empty() {
-            blocks.push(ContentBlock::Paragraph {
-                content: self.paragraph_buffer.clone(),
-                inline: self.inline_buffer.clone(),
-            });
+            blocks.push(self.paragraph_block());
             self.paragraph_buffer.clear();
             self.inline_buffer.clear();
             self.in_paragraph = false;
@@ -248,6 +501,7 @@ impl BlockParserState {
             blocks.push(ContentBlock::List {
                 ordered: self.list_ordered,
                 items: self.list_items.clone(),
+                span: raw_span(self.list_range.clone()),
             });
             self.list_items.clear();
             self.in_list = false;
@@ -256,12 +510,7 @@ impl BlockParserState {
 
     fn flush_code(&mut self, blocks: &mut Vec<ContentBlock>) {
         if self.in_code && !self.code_buffer.is_empty() {
-            blocks.push(ContentBlock::Code {
-                language: self.code_language.clone(),
-                content: self.code_buffer.trim_end().to_string(),
-                start_line: self.code_start_line,
-                end_line: self.current_line,
-            });
+            blocks.push(self.code_block());
             self.code_buffer.clear();
             self.code_language = None;
             self.in_code = false;
@@ -270,10 +519,10 @@ impl BlockParserState {
 
     fn flush_blockquote(&mut self, blocks: &mut Vec<ContentBlock>) {
         if self.in_blockquote && !self.blockquote_buffer.is_empty() {
-            let nested_blocks = parse_blocks(&self.blockquote_buffer);
             blocks.push(ContentBlock::Blockquote {
                 content: self.blockquote_buffer.clone(),
-                blocks: nested_blocks,
+                blocks: std::mem::take(&mut self.blockquote_blocks),
+                span: raw_span(self.quote_range.clone()),
             });
             self.blockquote_buffer.clear();
             self.in_blockquote = false;
@@ -286,6 +535,7 @@ impl BlockParserState {
                 headers: self.table_headers.clone(),
                 alignments: self.table_alignments.clone(),
                 rows: self.table_rows.clone(),
+                span: raw_span(self.table_range.clone()),
             });
             self.table_headers.clear();
             self.table_alignments.clear();
@@ -297,31 +547,99 @@ impl BlockParserState {
         }
     }
 
-    fn add_inline_text(&mut self, text: &str) {
+    /// The buffered paragraph; one that is only display math is a math block
+    fn paragraph_block(&self) -> ContentBlock {
+        let mut elements = self
+            .inline_buffer
+            .iter()
+            .filter(|e| !matches!(e, InlineElement::Text { value, .. } if value.trim().is_empty()));
+        if let (
+            Some(InlineElement::Math {
+                value,
+                display: true,
+                ..
+            }),
+            None,
+        ) = (elements.next(), elements.next())
+        {
+            return ContentBlock::Math {
+                content: value.clone(),
+                span: raw_span(self.paragraph_range.clone()),
+            };
+        }
+        ContentBlock::Paragraph {
+            content: self.paragraph_buffer.clone(),
+            inline: self.inline_buffer.clone(),
+            span: raw_span(self.paragraph_range.clone()),
+        }
+    }
+
+    fn code_block(&self) -> ContentBlock {
+        ContentBlock::Code {
+            language: self.code_language.clone(),
+            content: self.code_buffer.trim_end().to_string(),
+            start_line: self.code_start_line,
+            end_line: self.current_line,
+            span: raw_span(self.code_range.clone()),
+        }
+    }
+
+    /// Inline elements currently being collected
+    fn inline_target(&mut self) -> &mut Vec<InlineElement> {
+        if self.in_heading {
+            &mut self.heading_inline
+        } else {
+            &mut self.inline_buffer
+        }
+    }
+
+    /// Stretch the span of the only element collected since `start` over the
+    /// whole formatted range, delimiters included
+    fn widen_span(&mut self, start: usize, range: Range<usize>) {
+        let target = self.inline_target();
+        if target.len() == start + 1 {
+            *target[start].span_mut() = raw_span(range);
+        }
+    }
+
+    /// Add a non-text inline element (math, footnote reference, comment)
+    fn add_inline_element(
+        &mut self,
+        mut element: InlineElement,
+        source: &str,
+        range: Range<usize>,
+    ) {
+        *element.span_mut() = raw_span(range);
+        if self.in_heading {
+            self.heading_buffer.push_str(source);
+            self.heading_inline.push(element);
+        } else if self.in_link || self.in_image {
+            self.link_text.push_str(source);
+        } else {
+            self.inline_buffer.push(element);
+            self.paragraph_buffer.push_str(source);
+        }
+    }
+
+    fn add_inline_text(&mut self, text: &str, range: Range<usize>) {
         if text.is_empty() {
             return;
         }
 
+        let value = text.to_string();
+        let span = raw_span(range);
         let element = if self.in_code_inline {
-            InlineElement::Code {
-                value: text.to_string(),
-            }
+            InlineElement::Code { value, span }
         } else if self.in_strong {
-            InlineElement::Strong {
-                value: text.to_string(),
-            }
+            InlineElement::Strong { value, span }
         } else if self.in_emphasis {
-            InlineElement::Emphasis {
-                value: text.to_string(),
-            }
+            InlineElement::Emphasis { value, span }
         } else if self.in_strikethrough {
-            InlineElement::Strikethrough {
-                value: text.to_string(),
-            }
+            InlineElement::Strikethrough { value, span }
+        } else if self.in_highlight {
+            InlineElement::Highlight { value, span }
         } else {
-            InlineElement::Text {
-                value: text.to_string(),
-            }
+            InlineElement::Text { value, span }
         };
 
         self.inline_buffer.push(element);
@@ -334,17 +652,20 @@ impl BlockParserState {
 // ============================================================================
 
 #[allow(clippy::too_many_lines)]
-fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<ContentBlock>) {
+fn process_event(
+    event: Event,
+    range: Range<usize>,
+    state: &mut BlockParserState,
+    blocks: &mut Vec<ContentBlock>,
+) {
     match event {
         Event::Start(Tag::Paragraph) => {
             state.in_paragraph = true;
+            state.paragraph_range = range;
         }
         Event::End(TagEnd::Paragraph) => {
             if state.item_depth >= 1 && state.in_paragraph && !state.paragraph_buffer.is_empty() {
-                state.item_blocks.push(ContentBlock::Paragraph {
-                    content: state.paragraph_buffer.clone(),
-                    inline: state.inline_buffer.clone(),
-                });
+                state.item_blocks.push(state.paragraph_block());
                 state.paragraph_buffer.clear();
                 state.inline_buffer.clear();
                 state.in_paragraph = false;
@@ -355,6 +676,7 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
         Event::Start(Tag::CodeBlock(kind)) => {
             state.in_code = true;
             state.code_start_line = state.current_line;
+            state.code_range = range;
             state.code_language = match kind {
                 CodeBlockKind::Fenced(lang) => {
                     if lang.is_empty() {
@@ -368,12 +690,7 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
         }
         Event::End(TagEnd::CodeBlock) => {
             if state.item_depth >= 1 && state.in_code && !state.code_buffer.is_empty() {
-                state.item_blocks.push(ContentBlock::Code {
-                    language: state.code_language.clone(),
-                    content: state.code_buffer.trim_end().to_string(),
-                    start_line: state.code_start_line,
-                    end_line: state.current_line,
-                });
+                state.item_blocks.push(state.code_block());
                 state.code_buffer.clear();
                 state.code_language = None;
                 state.in_code = false;
@@ -386,6 +703,7 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
             if state.list_depth == 1 {
                 state.in_list = true;
                 state.list_ordered = start_number.is_some();
+                state.list_range = range;
             }
         }
         Event::End(TagEnd::List(_)) => {
@@ -405,6 +723,7 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
                 state.inline_buffer.clear();
                 state.item_blocks.clear();
                 state.nested_line_offset = 0;
+                state.item_range = range;
             }
         }
         Event::End(TagEnd::Item) => {
@@ -422,8 +741,9 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
                         state.inline_buffer.clone(),
                         all_blocks,
                     )
-                } else if let Some(ContentBlock::Paragraph { content, inline }) =
-                    state.item_blocks.first().cloned()
+                } else if let Some(ContentBlock::Paragraph {
+                    content, inline, ..
+                }) = state.item_blocks.first().cloned()
                 {
                     let remaining: Vec<ContentBlock> = state.item_blocks.drain(1..).collect();
                     (content, inline, remaining)
@@ -440,6 +760,7 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
                     content,
                     inline,
                     blocks: remaining_blocks,
+                    span: raw_span(state.item_range.clone()),
                 });
                 state.paragraph_buffer.clear();
                 state.inline_buffer.clear();
@@ -455,10 +776,17 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
             state.in_blockquote = true;
         }
         Event::End(TagEnd::BlockQuote(_)) => {
-            state.flush_blockquote(blocks);
+            if state.item_depth >= 1 {
+                let mut quote = Vec::new();
+                state.flush_blockquote(&mut quote);
+                state.item_blocks.extend(quote);
+            } else {
+                state.flush_blockquote(blocks);
+            }
         }
         Event::Start(Tag::Table(alignments)) => {
             state.in_table = true;
+            state.table_range = range;
             state.table_alignments = alignments
                 .iter()
                 .map(|a| match a {
@@ -493,28 +821,45 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
         }
         Event::Start(Tag::Strong) => {
             state.in_strong = true;
+            let start = state.inline_target().len();
+            state.format_starts.push(start);
         }
         Event::End(TagEnd::Strong) => {
             state.in_strong = false;
+            if let Some(start) = state.format_starts.pop() {
+                state.widen_span(start, range);
+            }
         }
         Event::Start(Tag::Emphasis) => {
             state.in_emphasis = true;
+            let start = state.inline_target().len();
+            state.format_starts.push(start);
         }
         Event::End(TagEnd::Emphasis) => {
             state.in_emphasis = false;
+            if let Some(start) = state.format_starts.pop() {
+                state.widen_span(start, range);
+            }
         }
         Event::Start(Tag::Strikethrough) => {
             state.in_strikethrough = true;
+            let start = state.inline_target().len();
+            state.format_starts.push(start);
         }
         Event::End(TagEnd::Strikethrough) => {
             state.in_strikethrough = false;
+            if let Some(start) = state.format_starts.pop() {
+                state.widen_span(start, range);
+            }
         }
         Event::Code(text) => {
             state.in_code_inline = true;
-            state.add_inline_text(&text);
+            state.add_inline_text(&text, range);
             state.in_code_inline = false;
         }
-        Event::Start(Tag::Link { dest_url, .. }) => {
+        Event::Start(Tag::Link {
+            dest_url, title, ..
+        }) => {
             // For nested list items, add newline and indent before the link
             // (same logic as in Event::Text for nested items)
             if state.in_list && state.item_depth > 1 {
@@ -533,6 +878,7 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
             }
             state.in_link = true;
             state.link_url = dest_url.to_string();
+            state.link_title = title.to_string();
             state.link_text.clear();
         }
         Event::End(TagEnd::Link) => {
@@ -551,6 +897,7 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
                     url: state.saved_link_url.clone(),
                     title: None,
                     line_offset,
+                    span: raw_span(range),
                 });
                 state
                     .paragraph_buffer
@@ -559,8 +906,9 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
                 state.inline_buffer.push(InlineElement::Link {
                     text: state.link_text.clone(),
                     url: state.link_url.clone(),
-                    title: None,
+                    title: (!state.link_title.is_empty()).then(|| state.link_title.clone()),
                     line_offset,
+                    span: raw_span(range),
                 });
                 state
                     .paragraph_buffer
@@ -569,6 +917,7 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
 
             state.link_text.clear();
             state.link_url.clear();
+            state.link_title.clear();
             state.saved_link_url.clear();
             state.image_in_link = false;
         }
@@ -610,6 +959,7 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
                         src: state.link_url.clone(),
                         title,
                         line_offset,
+                        span: raw_span(range),
                     });
                     // Add image placeholder to paragraph content
                     state
@@ -621,6 +971,7 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
                         alt: state.link_text.clone(),
                         src: state.link_url.clone(),
                         title,
+                        span: raw_span(range),
                     });
                     state.paragraph_buffer.clear();
                 }
@@ -632,26 +983,20 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
         Event::Text(text) => {
             if state.in_code {
                 state.code_buffer.push_str(&text);
-            } else if state.in_blockquote {
-                state.blockquote_buffer.push_str(&text);
             } else if state.in_heading {
                 state.heading_buffer.push_str(&text);
+                let value = text.to_string();
+                let span = raw_span(range);
                 let element = if state.in_code_inline {
-                    InlineElement::Code {
-                        value: text.to_string(),
-                    }
+                    InlineElement::Code { value, span }
                 } else if state.in_strong {
-                    InlineElement::Strong {
-                        value: text.to_string(),
-                    }
+                    InlineElement::Strong { value, span }
                 } else if state.in_emphasis {
-                    InlineElement::Emphasis {
-                        value: text.to_string(),
-                    }
+                    InlineElement::Emphasis { value, span }
+                } else if state.in_highlight {
+                    InlineElement::Highlight { value, span }
                 } else {
-                    InlineElement::Text {
-                        value: text.to_string(),
-                    }
+                    InlineElement::Text { value, span }
                 };
                 state.heading_inline.push(element);
             } else if state.in_link || state.in_image {
@@ -671,32 +1016,123 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
                         state.task_list_marker = None;
                     }
                 }
-                state.add_inline_text(&text);
+                state.add_inline_text(&text, range);
             }
         }
-        Event::SoftBreak => {
-            if state.in_paragraph {
-                state.paragraph_buffer.push(' ');
-                state.inline_buffer.push(InlineElement::Text {
-                    value: " ".to_string(),
-                });
-            }
+        Event::SoftBreak if state.in_paragraph => {
+            state.paragraph_buffer.push(' ');
+            state.inline_buffer.push(InlineElement::Text {
+                value: " ".to_string(),
+                span: raw_span(range),
+            });
         }
-        Event::HardBreak => {
-            if state.in_paragraph {
-                state.paragraph_buffer.push('\n');
-                state.inline_buffer.push(InlineElement::Text {
-                    value: "\n".to_string(),
-                });
-            }
+        Event::HardBreak if state.in_paragraph => {
+            state.paragraph_buffer.push('\n');
+            state.inline_buffer.push(InlineElement::Text {
+                value: "\n".to_string(),
+                span: raw_span(range),
+            });
         }
         Event::Rule => {
             state.flush_paragraph(blocks);
-            blocks.push(ContentBlock::HorizontalRule);
+            blocks.push(ContentBlock::HorizontalRule {
+                span: raw_span(range),
+            });
+        }
+        Event::InlineMath(text) => {
+            let source = format!("${}$", text);
+            state.add_inline_element(
+                InlineElement::Math {
+                    value: text.to_string(),
+                    display: false,
+                    span: None,
+                },
+                &source,
+                range,
+            );
+        }
+        Event::DisplayMath(text) => {
+            let source = format!("$${}$$", text);
+            state.add_inline_element(
+                InlineElement::Math {
+                    value: text.trim().to_string(),
+                    display: true,
+                    span: None,
+                },
+                &source,
+                range,
+            );
+        }
+        Event::FootnoteReference(label) => {
+            let source = format!("[^{}]", label);
+            state.add_inline_element(
+                InlineElement::FootnoteReference {
+                    label: label.to_string(),
+                    span: None,
+                },
+                &source,
+                range,
+            );
+        }
+        Event::Start(Tag::FootnoteDefinition(label)) => {
+            state.flush_paragraph(blocks);
+            state.footnote = Some((label.to_string(), blocks.len(), range));
+        }
+        Event::End(TagEnd::FootnoteDefinition) => {
+            state.flush_paragraph(blocks);
+            if let Some((label, start, range)) = state.footnote.take() {
+                let nested = blocks.drain(start.min(blocks.len())..).collect();
+                blocks.push(ContentBlock::FootnoteDefinition {
+                    label,
+                    blocks: nested,
+                    span: raw_span(range),
+                });
+            }
+        }
+        Event::InlineHtml(html) => match html.as_ref() {
+            "<mark>" => {
+                state.in_highlight = true;
+                let start = state.inline_target().len();
+                state.highlight_start = Some((start, range.start));
+            }
+            "</mark>" => {
+                state.in_highlight = false;
+                if let Some((start, offset)) = state.highlight_start.take() {
+                    state.widen_span(start, offset..range.end);
+                }
+            }
+            _ => {
+                if let Some(value) =
+                    comment_placeholder(&html).and_then(|idx| state.comments.get(idx).cloned())
+                {
+                    state.add_inline_element(
+                        InlineElement::Comment { value, span: None },
+                        "",
+                        range,
+                    );
+                }
+            }
+        },
+        Event::Html(html) => {
+            if let Some(content) =
+                comment_placeholder(&html).and_then(|idx| state.comments.get(idx).cloned())
+            {
+                state.flush_paragraph(blocks);
+                let block = ContentBlock::Comment {
+                    content,
+                    span: raw_span(range),
+                };
+                if state.item_depth >= 1 {
+                    state.item_blocks.push(block);
+                } else {
+                    blocks.push(block);
+                }
+            }
         }
         Event::Start(Tag::Heading { level, .. }) => {
             state.flush_paragraph(blocks);
             state.in_heading = true;
+            state.heading_range = range;
             state.heading_level = Some(level as usize);
             state.heading_buffer.clear();
             state.heading_inline.clear();
@@ -712,6 +1148,7 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
                     content: state.heading_buffer.clone(),
                     inline: state.heading_inline.clone(),
                     anchor,
+                    span: raw_span(state.heading_range.clone()),
                 });
             }
             state.in_heading = false;
@@ -723,6 +1160,62 @@ fn process_event(event: Event, state: &mut BlockParserState, blocks: &mut Vec<Co
     }
 }
 
+// ============================================================================
+// Source restoration
+// ============================================================================
+
+/// Links produced by [`preprocess_wikilinks`]: `[alias](wikilink:target)`
+static WIKILINK_LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(!?)\[([^\]]*)\]\(wikilink:([^)]*)\)").unwrap());
+
+/// Turn `[alias](wikilink:target)` back into `[[target|alias]]` (or `[[target]]`).
+pub(crate) fn restore_wikilinks(text: &str) -> String {
+    WIKILINK_LINK_RE
+        .replace_all(text, |caps: &regex::Captures| {
+            let bang = &caps[1];
+            let alias = &caps[2];
+            let target = &caps[3];
+            if alias == target {
+                format!("{}[[{}]]", bang, target)
+            } else {
+                format!("{}[[{}|{}]]", bang, target, alias)
+            }
+        })
+        .to_string()
+}
+
+/// Remove one level of `>` markers from the source of a blockquote,
+/// recording the removed markers
+fn strip_quote_markers(quote: &str) -> (String, OffsetMap) {
+    let mut inner = String::with_capacity(quote.len());
+    let mut map = OffsetMap::default();
+    let mut offset = 0;
+    for (idx, line) in quote.trim_end_matches(['\n', '\r']).split('\n').enumerate() {
+        if idx > 0 {
+            inner.push('\n');
+        }
+        let content = line.strip_suffix('\r').unwrap_or(line);
+        let trimmed = content.trim_start();
+        let rest = match trimmed.strip_prefix('>') {
+            Some(rest) => rest.strip_prefix([' ', '\t']).unwrap_or(rest),
+            None => trimmed,
+        };
+        let marker = content.len() - rest.len();
+        if marker > 0 {
+            map.push(inner.len()..inner.len(), offset..offset + marker);
+        }
+        inner.push_str(rest);
+        if content.len() < line.len() {
+            map.push(
+                inner.len()..inner.len(),
+                offset + content.len()..offset + line.len(),
+            );
+        }
+        offset += line.len() + 1;
+    }
+    (inner, map)
+}
+
 // ============================================================================
 // Slug generation
 // ============================================================================
@@ -810,29 +1303,76 @@ pub fn parse_blocks(markdown: &str) -> Vec<ContentBlock> {
 
 /// Parse markdown content into structured blocks, starting from a specific line.
 ///
-/// Use this when you need accurate line numbers for nested content.
+/// Use this when you need accurate line numbers for nested content: the
+/// lines of every block's and inline element's `span` (and of code blocks'
+/// `start_line`/`end_line`) are counted from `start_line + 1`. Byte offsets
+/// are relative to `markdown`.
 pub fn parse_blocks_from_line(markdown: &str, start_line: usize) -> Vec<ContentBlock> {
+    let mut blocks = parse_blocks_raw(markdown);
+    locate_spans(&mut blocks, markdown, start_line);
+    blocks
+}
+
+/// Parse markdown into blocks whose spans are byte ranges only
+fn parse_blocks_raw(markdown: &str) -> Vec<ContentBlock> {
+    // Extract details blocks
+    let (without_details, details_blocks, details_map) = extract_details_blocks(markdown);
+
+    // Pre-process comments and highlights (before wikilinks shift offsets)
+    let (preprocessed, comments, comments_map) =
+        preprocess_comments_and_highlights(&without_details);
+
     // Pre-process wikilinks
-    let preprocessed = preprocess_wikilinks(markdown);
+    let (preprocessed, wikilinks_map) = preprocess_wikilinks(&preprocessed);
 
     // Pre-process links with spaces
-    let preprocessed = preprocess_links_with_spaces(&preprocessed);
+    let (processed_markdown, spaces_map) = preprocess_links_with_spaces(&preprocessed);
 
-    // Extract details blocks
-    let (processed_markdown, details_blocks) = extract_details_blocks(&preprocessed);
+    let maps = [details_map, comments_map, wikilinks_map, spaces_map];
+    let original = |range: Range<usize>| maps.iter().rev().fold(range, |range, map| map.map(range));
 
     // Enable GFM extensions
     let mut options = Options::empty();
     options.insert(Options::ENABLE_TABLES);
     options.insert(Options::ENABLE_STRIKETHROUGH);
     options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_MATH);
 
     let parser = Parser::new_ext(&processed_markdown, options);
     let mut blocks = Vec::new();
-    let mut state = BlockParserState::new(start_line);
-
-    for event in parser {
-        process_event(event, &mut state, &mut blocks);
+    let mut state = BlockParserState::new(0, comments);
+
+    // Blockquotes keep their inner markdown and are parsed again as a whole,
+    // so events inside them are skipped here
+    let mut quote_depth = 0;
+    for (event, range) in parser.into_offset_iter() {
+        let range = original(range);
+        match event {
+            Event::Start(Tag::BlockQuote(_)) => {
+                if quote_depth == 0 {
+                    process_event(event, range, &mut state, &mut blocks);
+                }
+                quote_depth += 1;
+            }
+            Event::End(TagEnd::BlockQuote(_)) => {
+                quote_depth -= 1;
+                if quote_depth == 0 {
+                    let (inner, quote_map) = strip_quote_markers(&markdown[range.clone()]);
+                    let mut nested = parse_blocks_raw(&inner);
+                    remap_spans(&mut nested, |inner| {
+                        let inner = quote_map.map(inner);
+                        range.start + inner.start..range.start + inner.end
+                    });
+                    state.blockquote_buffer = inner;
+                    state.blockquote_blocks = nested;
+                    state.quote_range = range.clone();
+                    process_event(event, range, &mut state, &mut blocks);
+                }
+            }
+            _ if quote_depth > 0 => {}
+            _ => process_event(event, range, &mut state, &mut blocks),
+        }
     }
 
     state.finalize(&mut blocks);
@@ -878,6 +1418,10 @@ pub fn parse_blocks_from_line(markdown: &str, start_line: usize) -> Vec<ContentB
 /// | `*italic*` | `italic` |
 /// | `` `code` `` | `code` |
 /// | `~~strike~~` | `strike` |
+/// | `==highlight==` | `highlight` |
+/// | `$x^2$` | `x^2` |
+/// | `[^1]` | (removed) |
+/// | `%%comment%%` | (removed) |
 /// | `# Heading` | `Heading` |
 /// | `> quote` | (quote content) |
 /// | Code fences | (content preserved) |
@@ -967,7 +1511,7 @@ mod tests {
         let blocks = parse_blocks(markdown);
 
         assert_eq!(blocks.len(), 1);
-        if let ContentBlock::List { ordered, items } = &blocks[0] {
+        if let ContentBlock::List { ordered, items, .. } = &blocks[0] {
             assert!(!ordered);
             assert_eq!(items.len(), 3);
             assert_eq!(items[0].content, "Item 1");
@@ -984,7 +1528,7 @@ mod tests {
         let blocks = parse_blocks(markdown);
 
         assert_eq!(blocks.len(), 1);
-        if let ContentBlock::List { ordered, items } = &blocks[0] {
+        if let ContentBlock::List { ordered, items, .. } = &blocks[0] {
             assert!(ordered);
             assert_eq!(items.len(), 3);
         } else {
@@ -1046,7 +1590,7 @@ mod tests {
         let blocks = parse_blocks(markdown);
 
         assert_eq!(blocks.len(), 3);
-        assert!(matches!(blocks[1], ContentBlock::HorizontalRule));
+        assert!(matches!(blocks[1], ContentBlock::HorizontalRule { .. }));
     }
 
     #[test]
@@ -1196,6 +1740,50 @@ Inner content here.
         }
     }
 
+    #[test]
+    fn test_source_spans() {
+        let markdown = "# Title\n\nSee [[Note|n]] and ==mark==.\n\n- one\n- **two**\n\n> quoted [[Q]]\n\n```\ncode\n```\n";
+        let blocks = parse_blocks_from_line(markdown, 10);
+        let text = |span: Option<&SourcePosition>| &markdown[span.unwrap().range()];
+
+        let ContentBlock::Heading { inline, .. } = &blocks[0] else {
+            panic!("Expected Heading block");
+        };
+        assert_eq!(text(blocks[0].span()), "# Title");
+        assert_eq!(text(inline[0].span()), "Title");
+        let span = blocks[0].span().unwrap();
+        assert_eq!((span.line, span.column, span.length), (11, 1, 7));
+
+        let ContentBlock::Paragraph { inline, .. } = &blocks[1] else {
+            panic!("Expected Paragraph block");
+        };
+        assert_eq!(text(inline[1].span()), "[[Note|n]]");
+        assert_eq!(text(inline[3].span()), "==mark==");
+        assert_eq!(inline[3].span().unwrap().column, 20);
+
+        let ContentBlock::List { items, .. } = &blocks[2] else {
+            panic!("Expected List block");
+        };
+        assert_eq!(text(items[1].span.as_ref()), "- **two**");
+        assert_eq!(text(items[1].inline[0].span()), "**two**");
+
+        let ContentBlock::Blockquote { blocks: nested, .. } = &blocks[3] else {
+            panic!("Expected Blockquote block");
+        };
+        assert_eq!(text(nested[0].span()), "quoted [[Q]]");
+
+        let ContentBlock::Code {
+            start_line,
+            end_line,
+            ..
+        } = &blocks[4]
+        else {
+            panic!("Expected Code block");
+        };
+        assert_eq!((*start_line, *end_line), (20, 22));
+        assert_eq!(text(blocks[4].span()), "```\ncode\n```");
+    }
+
     #[test]
     fn test_slugify() {
         assert_eq!(slugify("Hello World"), "hello-world");
@@ -1246,7 +1834,7 @@ Inner content here.
             "First block should be H2"
         );
 
-        if let ContentBlock::List { ordered, items } = &blocks[1] {
+        if let ContentBlock::List { ordered, items, .. } = &blocks[1] {
             assert!(ordered, "Should be an ordered list");
             assert_eq!(items.len(), 2, "Should have 2 list items");
 
@@ -1584,4 +2172,106 @@ See [[WikiNote]] for more info."#;
             panic!("Expected Paragraph block");
         }
     }
+
+    #[test]
+    fn test_highlight_inline() {
+        let blocks = parse_blocks("Some ==highlighted [[Note]]== text and `==code==`");
+        let ContentBlock::Paragraph { inline, .. } = &blocks[0] else {
+            panic!("Expected Paragraph block");
+        };
+        assert!(
+            matches!(&inline[1], InlineElement::Highlight { value, .. } if value == "highlighted ")
+        );
+        assert!(matches!(&inline[2], InlineElement::Link { url, .. } if url == "wikilink:Note"));
+        assert!(
+            inline
+                .iter()
+                .any(|e| matches!(e, InlineElement::Code { value, .. } if value == "==code=="))
+        );
+        assert_eq!(
+            to_plain_text("Some ==highlighted== text"),
+            "Some highlighted text"
+        );
+    }
+
+    #[test]
+    fn test_comments_in_block_model() {
+        let markdown = "Visible %%inline [[Hidden]]%% text\n\n%%\nBlock comment\n%%\n\nAfter";
+        let blocks = parse_blocks(markdown);
+        assert_eq!(blocks.len(), 3);
+
+        let ContentBlock::Paragraph { inline, .. } = &blocks[0] else {
+            panic!("Expected Paragraph block");
+        };
+        assert!(inline.iter().any(
+            |e| matches!(e, InlineElement::Comment { value, .. } if value == "inline [[Hidden]]")
+        ));
+        assert!(
+            !inline
+                .iter()
+                .any(|e| matches!(e, InlineElement::Link { .. }))
+        );
+        assert!(
+            matches!(&blocks[1], ContentBlock::Comment { content, .. } if content == "Block comment")
+        );
+        assert_eq!(to_plain_text(markdown), "Visible  text\n\nAfter");
+
+        // A comment at the start of a line keeps the rest of the line
+        let blocks = parse_blocks("%%note%% still visible");
+        assert_eq!(to_plain_text("%%note%% still visible"), " still visible");
+        assert!(matches!(&blocks[0], ContentBlock::Paragraph { .. }));
+    }
+
+    #[test]
+    fn test_math_blocks_and_inline() {
+        let markdown = "Euler: $e^{i\\pi} + 1 = 0$ here.\n\n$$\n\\int_0^1 x\\,dx\n$$";
+        let blocks = parse_blocks(markdown);
+        assert_eq!(blocks.len(), 2);
+
+        let ContentBlock::Paragraph { inline, .. } = &blocks[0] else {
+            panic!("Expected Paragraph block");
+        };
+        assert!(matches!(
+            &inline[1],
+            InlineElement::Math { value, display: false, .. } if value == "e^{i\\pi} + 1 = 0"
+        ));
+        assert!(
+            matches!(&blocks[1], ContentBlock::Math { content, .. } if content == "\\int_0^1 x\\,dx")
+        );
+    }
+
+    #[test]
+    fn test_footnotes_in_block_model() {
+        let markdown = "Claim[^1].\n\n[^1]: The **source**.\n\nAfter.";
+        let blocks = parse_blocks(markdown);
+        assert_eq!(blocks.len(), 3);
+
+        let ContentBlock::Paragraph {
+            inline, content, ..
+        } = &blocks[0]
+        else {
+            panic!("Expected Paragraph block");
+        };
+        assert_eq!(content, "Claim[^1].");
+        assert!(
+            inline.iter().any(
+                |e| matches!(e, InlineElement::FootnoteReference { label, .. } if label == "1")
+            )
+        );
+
+        let ContentBlock::FootnoteDefinition {
+            label,
+            blocks: nested,
+            ..
+        } = &blocks[1]
+        else {
+            panic!("Expected FootnoteDefinition block");
+        };
+        assert_eq!(label, "1");
+        assert_eq!(nested.len(), 1);
+        assert_eq!(blocks[1].to_plain_text(), "The source.");
+        assert!(
+            matches!(&blocks[2], ContentBlock::Paragraph { content, .. } if content == "After.")
+        );
+    }
 }
@@ -0,0 +1,450 @@
+//! JSON Canvas (`.canvas`) documents.
+//!
+//! Models the [JSON Canvas 1.0](https://jsoncanvas.org/spec/1.0/) format used
+//! by Obsidian: text, file, link and group nodes plus edges between them.
+//! Keys the model does not know are kept in `extra` and written back, so a
+//! canvas survives a parse/serialize round trip. [`Canvas::to_json`] writes
+//! tab-indented JSON like Obsidian does.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::path::Path;
+use turbovault_core::{Error, LineIndex, Link, LinkType, Result, SourcePosition, Tag};
+
+use crate::ParseOptions;
+use crate::blocks::to_plain_text;
+use crate::engine::ParseEngine;
+
+/// A JSON Canvas document
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Canvas {
+    #[serde(default)]
+    pub nodes: Vec<CanvasNode>,
+    #[serde(default)]
+    pub edges: Vec<CanvasEdge>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Node type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CanvasNodeType {
+    /// Markdown text card
+    Text,
+    /// A vault file (note, image, PDF, ...), optionally at a `#heading` subpath
+    File,
+    /// An external URL
+    Link,
+    /// A labelled area grouping the nodes inside it
+    Group,
+}
+
+/// A canvas node; which optional fields apply depends on `node_type`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CanvasNode {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub node_type: CanvasNodeType,
+    /// Markdown of a text node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Vault-relative path of a file node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Heading or block of a file node, starting with `#`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subpath: Option<String>,
+    /// URL of a link node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Label of a group node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Background image of a group node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    /// `cover`, `ratio` or `repeat`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_style: Option<String>,
+    #[serde(deserialize_with = "integer")]
+    pub x: i64,
+    #[serde(deserialize_with = "integer")]
+    pub y: i64,
+    #[serde(deserialize_with = "integer")]
+    pub width: i64,
+    #[serde(deserialize_with = "integer")]
+    pub height: i64,
+    /// Preset `"1"`-`"6"` or a hex color like `#FF0000`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Side of a node an edge attaches to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CanvasSide {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+/// Shape of an edge endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CanvasEnd {
+    None,
+    Arrow,
+}
+
+/// A connection between two nodes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CanvasEdge {
+    pub id: String,
+    pub from_node: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_side: Option<CanvasSide>,
+    /// Defaults to `none`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_end: Option<CanvasEnd>,
+    pub to_node: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_side: Option<CanvasSide>,
+    /// Defaults to `arrow`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_end: Option<CanvasEnd>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Coordinates are integers in the spec, but some tools write floats
+fn integer<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<i64, D::Error> {
+    let value = f64::deserialize(deserializer)?;
+    Ok(value.round() as i64)
+}
+
+impl Canvas {
+    /// Parse a canvas; empty content is an empty canvas
+    pub fn parse(content: &str) -> Result<Self> {
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        serde_json::from_str(content)
+            .map_err(|e| Error::parse_error(format!("Invalid JSON Canvas: {}", e)))
+    }
+
+    /// Tab-indented JSON, as Obsidian writes it
+    pub fn to_json(&self) -> String {
+        let mut out = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+        let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+        // Serializing plain data into a Vec cannot fail
+        self.serialize(&mut serializer)
+            .expect("canvas serializes to JSON");
+        String::from_utf8(out).expect("serde_json writes UTF-8")
+    }
+
+    /// Node with the given id
+    pub fn node(&self, id: &str) -> Option<&CanvasNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// Problems with the canvas under the JSON Canvas spec (empty when valid)
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        let mut ids = HashSet::new();
+
+        for node in &self.nodes {
+            if node.id.is_empty() {
+                issues.push("Node without an id".to_string());
+            } else if !ids.insert(node.id.as_str()) {
+                issues.push(format!("Duplicate id '{}'", node.id));
+            }
+            if node.width <= 0 || node.height <= 0 {
+                issues.push(format!(
+                    "Node '{}' has non-positive size {}x{}",
+                    node.id, node.width, node.height
+                ));
+            }
+            let missing = match node.node_type {
+                CanvasNodeType::Text => node.text.is_none().then_some("text"),
+                CanvasNodeType::File => node
+                    .file
+                    .as_deref()
+                    .is_none_or(str::is_empty)
+                    .then_some("file"),
+                CanvasNodeType::Link => node
+                    .url
+                    .as_deref()
+                    .is_none_or(str::is_empty)
+                    .then_some("url"),
+                CanvasNodeType::Group => None,
+            };
+            if let Some(field) = missing {
+                issues.push(format!("Node '{}' is missing '{}'", node.id, field));
+            }
+            if let Some(subpath) = &node.subpath
+                && !subpath.starts_with('#')
+            {
+                issues.push(format!(
+                    "Node '{}' subpath '{}' must start with '#'",
+                    node.id, subpath
+                ));
+            }
+            if let Some(style) = &node.background_style
+                && !matches!(style.as_str(), "cover" | "ratio" | "repeat")
+            {
+                issues.push(format!(
+                    "Node '{}' has invalid backgroundStyle '{}'",
+                    node.id, style
+                ));
+            }
+            if let Some(color) = &node.color
+                && !is_valid_color(color)
+            {
+                issues.push(format!("Node '{}' has invalid color '{}'", node.id, color));
+            }
+        }
+
+        let node_ids: HashSet<&str> = self.nodes.iter().map(|n| n.id.as_str()).collect();
+        for edge in &self.edges {
+            if edge.id.is_empty() {
+                issues.push("Edge without an id".to_string());
+            } else if !ids.insert(edge.id.as_str()) {
+                issues.push(format!("Duplicate id '{}'", edge.id));
+            }
+            for end in [&edge.from_node, &edge.to_node] {
+                if !node_ids.contains(end.as_str()) {
+                    issues.push(format!(
+                        "Edge '{}' refers to unknown node '{}'",
+                        edge.id, end
+                    ));
+                }
+            }
+            if let Some(color) = &edge.color
+                && !is_valid_color(color)
+            {
+                issues.push(format!("Edge '{}' has invalid color '{}'", edge.id, color));
+            }
+        }
+
+        issues
+    }
+
+    /// Links contributed by the canvas: each file node (as an embed) and the
+    /// links in text nodes, positioned at the node in `content`
+    pub fn links(&self, source_file: &Path, content: &str) -> Vec<Link> {
+        let index = LineIndex::new(content);
+        let mut links = Vec::new();
+
+        for node in &self.nodes {
+            let position = node_position(content, &index, &node.id);
+            match node.node_type {
+                CanvasNodeType::File => {
+                    if let Some(file) = &node.file {
+                        let target = match &node.subpath {
+                            Some(subpath) => format!("{}{}", file, subpath),
+                            None => file.clone(),
+                        };
+                        links.push(Link::new(
+                            LinkType::Embed,
+                            source_file.to_path_buf(),
+                            target,
+                            position,
+                        ));
+                    }
+                }
+                CanvasNodeType::Link => {
+                    if let Some(url) = &node.url {
+                        links.push(Link::new(
+                            LinkType::ExternalLink,
+                            source_file.to_path_buf(),
+                            url.clone(),
+                            position,
+                        ));
+                    }
+                }
+                CanvasNodeType::Text => {
+                    if let Some(text) = &node.text {
+                        let result = ParseEngine::with_source_file(text, source_file)
+                            .parse(&ParseOptions::links_only());
+                        links.extend(result.all_links().cloned().map(|mut link| {
+                            link.position = position;
+                            link
+                        }));
+                    }
+                }
+                CanvasNodeType::Group => {}
+            }
+        }
+
+        links
+    }
+
+    /// Inline tags in text nodes, positioned at the node in `content`
+    pub fn tags(&self, content: &str) -> Vec<Tag> {
+        let index = LineIndex::new(content);
+        let options = ParseOptions {
+            parse_tags: true,
+            ..ParseOptions::none()
+        };
+        self.nodes
+            .iter()
+            .filter_map(|node| Some((node, node.text.as_deref()?)))
+            .flat_map(|(node, text)| {
+                let position = node_position(content, &index, &node.id);
+                ParseEngine::new(text)
+                    .parse(&options)
+                    .tags
+                    .into_iter()
+                    .map(move |mut tag| {
+                        tag.position = position;
+                        tag
+                    })
+            })
+            .collect()
+    }
+
+    /// Searchable text: text nodes as plain text, group and edge labels
+    pub fn plain_text(&self) -> String {
+        let nodes = self.nodes.iter().filter_map(|node| match node.node_type {
+            CanvasNodeType::Text => node.text.as_deref().map(to_plain_text),
+            CanvasNodeType::Group => node.label.clone(),
+            _ => None,
+        });
+        let edges = self.edges.iter().filter_map(|edge| edge.label.clone());
+        nodes
+            .chain(edges)
+            .filter(|text| !text.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Preset colors `"1"`-`"6"` or `#RGB` / `#RRGGBB`
+fn is_valid_color(color: &str) -> bool {
+    if matches!(color, "1" | "2" | "3" | "4" | "5" | "6") {
+        return true;
+    }
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Position of a node's `"id"` entry in the canvas source
+fn node_position(content: &str, index: &LineIndex, id: &str) -> SourcePosition {
+    let needle = format!("\"{}\"", id);
+    let offset = content
+        .match_indices(&needle)
+        .map(|(offset, _)| offset)
+        .find(|&offset| content[..offset].trim_end().ends_with(':'))
+        .unwrap_or(0);
+    let (line, column) = index.line_col(offset);
+    SourcePosition::new(line, column, offset, needle.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = r##"{
+	"nodes":[
+		{"id":"a1","type":"text","text":"Plan with [[Roadmap]] and #project/alpha","x":0,"y":0,"width":250,"height":60},
+		{"id":"b2","type":"file","file":"notes/Spec.md","subpath":"#Scope","x":300,"y":0,"width":400,"height":400,"color":"4"},
+		{"id":"c3","type":"link","url":"https://example.com","x":0,"y":200,"width":250,"height":60},
+		{"id":"g1","type":"group","label":"Phase one","x":-20,"y":-20,"width":800,"height":500,"customKey":true}
+	],
+	"edges":[
+		{"id":"e1","fromNode":"a1","fromSide":"right","toNode":"b2","toSide":"left","label":"details"}
+	]
+}"##;
+
+    #[test]
+    fn test_parse_canvas() {
+        let canvas = Canvas::parse(BOARD).unwrap();
+        assert_eq!(canvas.nodes.len(), 4);
+        assert_eq!(canvas.nodes[1].node_type, CanvasNodeType::File);
+        assert_eq!(canvas.nodes[1].subpath.as_deref(), Some("#Scope"));
+        assert_eq!(canvas.nodes[3].extra["customKey"], Value::Bool(true));
+        assert_eq!(canvas.edges[0].from_side, Some(CanvasSide::Right));
+        assert!(canvas.validate().is_empty());
+
+        assert_eq!(Canvas::parse("").unwrap(), Canvas::default());
+        assert!(Canvas::parse("# not json").is_err());
+    }
+
+    #[test]
+    fn test_round_trip_keeps_unknown_keys() {
+        let canvas = Canvas::parse(BOARD).unwrap();
+        let json = canvas.to_json();
+        assert!(json.starts_with("{\n\t\"nodes\": [\n\t\t{\n\t\t\t\"id\": \"a1\""));
+        assert!(json.contains("\"customKey\": true"));
+        assert_eq!(Canvas::parse(&json).unwrap(), canvas);
+    }
+
+    #[test]
+    fn test_float_coordinates_are_rounded() {
+        let canvas = Canvas::parse(
+            r#"{"nodes":[{"id":"a","type":"text","text":"","x":10.4,"y":-3.6,"width":100,"height":50}]}"#,
+        )
+        .unwrap();
+        assert_eq!((canvas.nodes[0].x, canvas.nodes[0].y), (10, -4));
+    }
+
+    #[test]
+    fn test_validate() {
+        let canvas = Canvas::parse(
+            r#"{"nodes":[
+                {"id":"a","type":"file","x":0,"y":0,"width":0,"height":50,"color":"7"},
+                {"id":"a","type":"group","x":0,"y":0,"width":10,"height":10,"backgroundStyle":"tile"}
+            ],"edges":[{"id":"e","fromNode":"a","toNode":"missing"}]}"#,
+        )
+        .unwrap();
+        let issues = canvas.validate();
+        assert!(issues.iter().any(|i| i.contains("non-positive size")));
+        assert!(issues.iter().any(|i| i.contains("missing 'file'")));
+        assert!(issues.iter().any(|i| i.contains("invalid color '7'")));
+        assert!(issues.iter().any(|i| i.contains("Duplicate id 'a'")));
+        assert!(issues.iter().any(|i| i.contains("backgroundStyle 'tile'")));
+        assert!(issues.iter().any(|i| i.contains("unknown node 'missing'")));
+    }
+
+    #[test]
+    fn test_links_tags_and_text() {
+        let canvas = Canvas::parse(BOARD).unwrap();
+        let links = canvas.links(Path::new("board.canvas"), BOARD);
+        let targets: Vec<(&LinkType, &str)> = links
+            .iter()
+            .map(|l| (&l.type_, l.target.as_str()))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                (&LinkType::WikiLink, "Roadmap"),
+                (&LinkType::Embed, "notes/Spec.md#Scope"),
+                (&LinkType::ExternalLink, "https://example.com"),
+            ]
+        );
+        assert_eq!(links[0].position.line, 3);
+        assert_eq!(links[1].position.line, 4);
+
+        let tags = canvas.tags(BOARD);
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name, "project/alpha");
+
+        let text = canvas.plain_text();
+        assert!(text.contains("Plan with Roadmap"));
+        assert!(text.contains("Phase one"));
+        assert!(text.contains("details"));
+    }
+}
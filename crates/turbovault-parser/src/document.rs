@@ -0,0 +1,458 @@
+//! Editable markdown documents that render back losslessly.
+//!
+//! A [`MarkdownDocument`] splits a note into its frontmatter and top-level
+//! source segments (a paragraph, a list, a table, a callout, ...), each
+//! parsed into `ContentBlock`s. Edit [`MarkdownDocument::blocks`] freely;
+//! [`MarkdownDocument::render`] writes every block that still matches a
+//! source segment exactly as it was written, with its surrounding blank
+//! lines, and only renders new or changed blocks canonically with
+//! [`render_block`]. An unmodified document renders byte-for-byte.
+
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use std::ops::Range;
+use turbovault_core::{ContentBlock, LineIndex};
+
+use crate::ParseOptions;
+use crate::blocks::{parse_blocks_from_line, remap_spans};
+use crate::engine::ParseEngine;
+use crate::render::{render_block, render_block_over};
+
+/// A top-level piece of the source and the blocks it parses into
+#[derive(Debug, Clone)]
+struct Segment {
+    span: Range<usize>,
+    blocks: Vec<ContentBlock>,
+}
+
+/// A markdown document whose blocks can be edited and rendered back.
+///
+/// # Example
+///
+/// ```
+/// use turbovault_parser::{ContentBlock, MarkdownDocument};
+///
+/// let source = "---\ntitle: Plan\n---\n# Plan\n\n* one\n* two\n\nSome   *spacing*  kept\n";
+/// let mut doc = MarkdownDocument::parse(source);
+/// assert_eq!(doc.render(), source);
+///
+/// // Swap the list items; the other blocks keep their original formatting
+/// if let ContentBlock::List { items, .. } = &mut doc.blocks[1] {
+///     items.swap(0, 1);
+/// }
+/// assert_eq!(
+///     doc.render(),
+///     "---\ntitle: Plan\n---\n# Plan\n\n- two\n- one\n\nSome   *spacing*  kept\n"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct MarkdownDocument {
+    source: String,
+    body_start: usize,
+    segments: Vec<Segment>,
+    /// Top-level blocks of the body, in order, with spans into the whole source
+    pub blocks: Vec<ContentBlock>,
+}
+
+impl MarkdownDocument {
+    /// Parse a note (frontmatter included) into an editable document.
+    pub fn parse(source: &str) -> Self {
+        let options = ParseOptions {
+            parse_frontmatter: true,
+            parse_comments: true,
+            ..ParseOptions::none()
+        };
+        let parsed = ParseEngine::new(source).parse(&options);
+        // The line break closing the frontmatter belongs to it
+        let mut body_start = parsed.frontmatter_end_offset;
+        if body_start > 0 {
+            let rest = &source[body_start..];
+            if rest.starts_with("\r\n") {
+                body_start += 2;
+            } else if rest.starts_with('\n') {
+                body_start += 1;
+            }
+        }
+
+        // Comments and <details> blocks are parsed as one unit even when they
+        // span several CommonMark blocks
+        let mut atomic: Vec<Range<usize>> = parsed
+            .comments
+            .iter()
+            .map(|c| c.position.offset..c.position.offset + c.position.length)
+            .collect();
+        atomic.extend(details_ranges(source, body_start));
+
+        let index = LineIndex::new(source);
+        let outline = outline(source, body_start);
+        let segments = merge_ranges(outline.ranges, &atomic)
+            .into_iter()
+            .filter_map(|span| {
+                let (line, _) = index.line_col(span.start);
+                let mut blocks = parse_segment(&source[span.clone()], line - 1, &outline.context);
+                remap_spans(&mut blocks, |range| {
+                    span.start + range.start..span.start + range.end
+                });
+                // Raw HTML and other blocks the model drops stay in the gaps
+                (!blocks.is_empty()).then_some(Segment { span, blocks })
+            })
+            .collect::<Vec<_>>();
+        let blocks = segments.iter().flat_map(|s| s.blocks.clone()).collect();
+
+        Self {
+            source: source.to_string(),
+            body_start,
+            segments,
+            blocks,
+        }
+    }
+
+    /// The source the document was parsed from
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Raw frontmatter, delimiters included (empty when there is none)
+    pub fn frontmatter(&self) -> &str {
+        &self.source[..self.body_start]
+    }
+
+    /// Whether the blocks differ from the parsed source
+    pub fn is_modified(&self) -> bool {
+        !self
+            .segments
+            .iter()
+            .flat_map(|s| s.blocks.iter())
+            .eq(self.blocks.iter())
+    }
+
+    /// Render the document back to Markdown.
+    ///
+    /// Blocks matching a source segment are copied from the source; a segment
+    /// that follows its original predecessor also keeps the exact gap before
+    /// it. Everything else is rendered canonically, separated by blank lines.
+    pub fn render(&self) -> String {
+        let mut out = String::with_capacity(self.source.len());
+        out.push_str(self.frontmatter());
+        let body_start = out.len();
+
+        let mut used = vec![false; self.segments.len()];
+        let mut previous: Option<usize> = None;
+        let mut i = 0;
+
+        while i < self.blocks.len() {
+            match self.find_segment(&self.blocks[i..], previous, &used) {
+                Some(k) => {
+                    let segment = &self.segments[k];
+                    let owned = &self.source[self.segment_start(k)..segment.span.end];
+                    if previous.map_or(k == 0 && out.len() == body_start, |p| p + 1 == k) {
+                        out.push_str(owned);
+                    } else {
+                        separate(&mut out, body_start);
+                        out.push_str(skip_blank_lines(owned));
+                    }
+                    used[k] = true;
+                    previous = Some(k);
+                    i += segment.blocks.len();
+                }
+                None => {
+                    let block = &self.blocks[i];
+                    separate(&mut out, body_start);
+                    out.push_str(&match self.original(block) {
+                        Some(original) => render_block_over(block, original, &self.source),
+                        None => render_block(block),
+                    });
+                    previous = None;
+                    i += 1;
+                }
+            }
+        }
+
+        let trailing = &self.source[self.segments.last().map_or(self.body_start, |s| s.span.end)..];
+        let last_in_place = match previous {
+            Some(p) => p + 1 == self.segments.len(),
+            None => self.segments.is_empty() && out.len() == body_start,
+        };
+        if last_in_place {
+            out.push_str(trailing);
+        } else if !trailing.trim().is_empty() {
+            separate(&mut out, body_start);
+            out.push_str(skip_blank_lines(trailing));
+        } else if self.source.ends_with('\n') {
+            if out.len() > body_start && !out.ends_with('\n') {
+                out.push('\n');
+            }
+        } else {
+            out.truncate(out.trim_end_matches('\n').len().max(body_start));
+        }
+        out
+    }
+
+    /// Start of the source owned by segment `k`: right after the previous segment
+    fn segment_start(&self, k: usize) -> usize {
+        k.checked_sub(1)
+            .map_or(self.body_start, |p| self.segments[p].span.end)
+    }
+
+    /// The parsed block an edited block came from, found by its span
+    fn original(&self, block: &ContentBlock) -> Option<&ContentBlock> {
+        let span = block.span()?;
+        self.segments
+            .iter()
+            .flat_map(|s| s.blocks.iter())
+            .find(|b| {
+                b.span() == Some(span)
+                    && std::mem::discriminant(*b) == std::mem::discriminant(block)
+            })
+    }
+
+    /// An unused segment whose blocks start `blocks`, preferring the one
+    /// after `previous` so unchanged runs keep their original gaps
+    fn find_segment(
+        &self,
+        blocks: &[ContentBlock],
+        previous: Option<usize>,
+        used: &[bool],
+    ) -> Option<usize> {
+        let matches = |k: usize| !used[k] && blocks.starts_with(&self.segments[k].blocks);
+        let next = previous.map_or(0, |p| p + 1);
+        if next < self.segments.len() && matches(next) {
+            return Some(next);
+        }
+        (0..self.segments.len()).find(|&k| matches(k))
+    }
+}
+
+/// Top-level structure of a note body
+struct Outline {
+    /// Byte ranges of top-level blocks, from the start of their first line
+    ranges: Vec<Range<usize>>,
+    /// Definitions other blocks may refer to
+    context: SegmentContext,
+}
+
+/// Link reference definitions and footnote labels of the whole note, so a
+/// segment parsed on its own still resolves `[text][ref]` and `[^note]`
+#[derive(Default)]
+struct SegmentContext {
+    link_definitions: Vec<String>,
+    footnote_labels: Vec<String>,
+}
+
+/// Parse one segment, resolving references against the rest of the note
+fn parse_segment(text: &str, line: usize, context: &SegmentContext) -> Vec<ContentBlock> {
+    let plain = parse_blocks_from_line(text, line);
+    if !text.contains('[')
+        || (context.link_definitions.is_empty() && context.footnote_labels.is_empty())
+    {
+        return plain;
+    }
+
+    // Reference definitions produce no blocks; footnote stubs are dropped again
+    let mut with_context = format!("{}\n\n", text.trim_end());
+    for definition in &context.link_definitions {
+        with_context.push_str(definition);
+        with_context.push('\n');
+    }
+    for label in &context.footnote_labels {
+        with_context.push_str(&format!("\n[^{}]: .\n", label));
+    }
+    let mut blocks = parse_blocks_from_line(&with_context, line);
+    for _ in &context.footnote_labels {
+        if matches!(blocks.last(), Some(ContentBlock::FootnoteDefinition { .. })) {
+            blocks.pop();
+        }
+    }
+
+    // An unterminated code block would swallow the definitions
+    let swallowed = blocks
+        .iter()
+        .any(|b| matches!(b, ContentBlock::Code { .. }));
+    if blocks.len() == plain.len() && !swallowed {
+        blocks
+    } else {
+        plain
+    }
+}
+
+/// Top-level CommonMark blocks of the body and the definitions they can use
+fn outline(source: &str, body_start: usize) -> Outline {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_MATH);
+
+    let body = &source[body_start..];
+    let parser = Parser::new_ext(body, options);
+    let mut context = SegmentContext {
+        link_definitions: parser
+            .reference_definitions()
+            .iter()
+            .map(|(_, definition)| body[definition.span.clone()].trim().to_string())
+            .collect(),
+        footnote_labels: Vec::new(),
+    };
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (event, range) in parser.into_offset_iter() {
+        let range = line_start(source, body_start + range.start)..body_start + range.end;
+        match event {
+            Event::Start(tag) => {
+                if depth == 0 {
+                    start = range.start;
+                }
+                if let Tag::FootnoteDefinition(label) = tag {
+                    context.footnote_labels.push(label.to_string());
+                }
+                depth += 1;
+            }
+            Event::End(_) => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    ranges.push(start..range.end);
+                }
+            }
+            _ if depth == 0 => ranges.push(range),
+            _ => {}
+        }
+    }
+    Outline { ranges, context }
+}
+
+/// Move an offset back to the start of its line if only indentation precedes it
+fn line_start(source: &str, offset: usize) -> usize {
+    let start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+    if source[start..offset].trim().is_empty() {
+        start
+    } else {
+        offset
+    }
+}
+
+/// Merge block ranges overlapping the same atomic range (or each other)
+fn merge_ranges(ranges: Vec<Range<usize>>, atomic: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for mut range in ranges {
+        for a in atomic {
+            if a.start < range.end && range.start < a.end {
+                range.start = range.start.min(a.start);
+                range.end = range.end.max(a.end);
+            }
+        }
+        match merged.last_mut() {
+            Some(last) if range.start < last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Byte ranges of `<details>...</details>` blocks in the body
+fn details_ranges(source: &str, body_start: usize) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut pos = body_start;
+    while let Some(found) = source[pos..].find("<details") {
+        let start = pos + found;
+        match source[start..].find("</details>") {
+            Some(end) => {
+                let end = start + end + "</details>".len();
+                ranges.push(start..end);
+                pos = end;
+            }
+            None => break,
+        }
+    }
+    ranges
+}
+
+/// Make sure the output ends with a blank line before another block
+fn separate(out: &mut String, body_start: usize) {
+    if out.len() == body_start {
+        return;
+    }
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    if !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+/// Drop leading blank lines
+fn skip_blank_lines(text: &str) -> &str {
+    let mut rest = text;
+    while let Some(newline) = rest.find('\n') {
+        if rest[..newline].trim().is_empty() {
+            rest = &rest[newline + 1..];
+        } else {
+            break;
+        }
+    }
+    rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use turbovault_core::InlineElement;
+
+    #[test]
+    fn test_document_round_trip_unmodified() {
+        let source = "---\ntags: [a]\n---\n\n#  Title\n\nPara   one\nwrapped\n\n\n* item\n* item 2\n\n<div>raw</div>\n\n%%\ncomment\n\nstill comment\n%%\n\nlast line";
+        let doc = MarkdownDocument::parse(source);
+        assert!(!doc.is_modified());
+        assert_eq!(doc.render(), source);
+        assert_eq!(doc.frontmatter(), "---\ntags: [a]\n---\n");
+    }
+
+    #[test]
+    fn test_document_edit_renders_only_changed_block() {
+        let source = "# Title\n\nFirst   paragraph.\n\nSecond paragraph.\n";
+        let mut doc = MarkdownDocument::parse(source);
+        doc.blocks[2] = ContentBlock::Paragraph {
+            content: "Changed".to_string(),
+            inline: vec![InlineElement::Strong {
+                value: "Changed".to_string(),
+                span: None,
+            }],
+            span: None,
+        };
+        assert!(doc.is_modified());
+        assert_eq!(
+            doc.render(),
+            "# Title\n\nFirst   paragraph.\n\n**Changed**\n"
+        );
+    }
+
+    #[test]
+    fn test_document_reorder_and_delete() {
+        let source = "A\n\nB\n\nC";
+        let mut doc = MarkdownDocument::parse(source);
+        doc.blocks.swap(0, 2);
+        assert_eq!(doc.render(), "C\n\nB\n\nA");
+
+        let mut doc = MarkdownDocument::parse(source);
+        doc.blocks.remove(1);
+        assert_eq!(doc.render(), "A\n\nC");
+    }
+
+    #[test]
+    fn test_document_insert_block() {
+        let mut doc = MarkdownDocument::parse("# Title\n\nBody\n");
+        doc.blocks
+            .insert(1, ContentBlock::HorizontalRule { span: None });
+        assert_eq!(doc.render(), "# Title\n\n---\n\nBody\n");
+    }
+
+    #[test]
+    fn test_document_details_block_is_one_segment() {
+        let source = "<details>\n<summary>More</summary>\n\nHidden text\n\n</details>\n";
+        let doc = MarkdownDocument::parse(source);
+        assert_eq!(doc.blocks.len(), 1);
+        assert!(matches!(doc.blocks[0], ContentBlock::Details { .. }));
+        assert_eq!(doc.render(), source);
+    }
+}
@@ -3,7 +3,7 @@
 //! This module provides a consolidated parsing engine that:
 //! - Uses pulldown-cmark for CommonMark foundation (headings, links, tasks, code blocks)
 //! - Tracks code block/inline code ranges to exclude from OFM regex parsing
-//! - Uses regex only for Obsidian-specific syntax (wikilinks, embeds, tags, callouts)
+//! - Uses regex only for Obsidian-specific syntax (wikilinks, embeds, tags, callouts, block IDs)
 //! - Builds LineIndex once and reuses for all position calculations
 //!
 //! All public parsing APIs delegate to this engine internally.
@@ -14,8 +14,8 @@ use std::ops::Range;
 use std::path::Path;
 use std::sync::LazyLock;
 use turbovault_core::{
-    Callout, CalloutType, Frontmatter, Heading, LineIndex, Link, LinkType, SourcePosition,
-    Tag as OFMTag, TaskItem,
+    Block, Callout, CalloutType, Comment, FootnoteDefinition, FootnoteReference, Frontmatter,
+    Heading, Highlight, LineIndex, Link, LinkType, Math, SourcePosition, Tag as OFMTag, TaskItem,
 };
 
 use crate::ParseOptions;
@@ -33,16 +33,27 @@ static WIKILINK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[\[([^\]]+)\]\
 /// Embed: ![[target]] or ![[target|display]]
 static EMBED: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"!\[\[([^\]]+)\]\]").unwrap());
 
+/// Characters allowed in a tag name, following Obsidian: Unicode letters,
+/// numbers and combining marks, emoji (with skin-tone modifiers and
+/// zero-width joiners), `_`, `-` and `/`. Whitespace and punctuation end a tag.
+pub(crate) const TAG_CHARS: &str =
+    r"\p{L}\p{M}\p{N}\p{Extended_Pictographic}\p{Emoji_Modifier}\u{200D}_\-/";
+
 /// Tag: #tag or #parent/child (but not inside words or URLs)
 static TAG: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"(?:^|[\s\[(])#([a-zA-Z][a-zA-Z0-9_\-/]*)").unwrap());
+    LazyLock::new(|| Regex::new(&format!(r"(?:^|[\s\[(])#([{}]+)", TAG_CHARS)).unwrap());
 
-/// Callout start: > [!TYPE] with optional fold marker and title
+/// Callout start: > [!TYPE] (or > > [!TYPE] when nested) with optional fold marker and title
 static CALLOUT: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^\s*>\s*\[!(\w+)\]([+-]?)\s*(.*?)$").unwrap());
+    LazyLock::new(|| Regex::new(r"^\s*((?:>\s*)+)\[!([\w-]+)\]([+-]?)\s*(.*?)$").unwrap());
+
+/// Block ID: `^block-id` at the end of a line (or alone on its own line)
+static BLOCK_ID: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|[ \t])\^([A-Za-z0-9-]+)[ \t]*$").unwrap());
 
-/// Callout continuation: > content
-static CALLOUT_CONT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*>\s*(.*)$").unwrap());
+/// Highlight: ==text== (no whitespace just inside the markers)
+static HIGHLIGHT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"==([^\s=](?:[^\n]*?[^\s=])??)==").unwrap());
 
 // ============================================================================
 // Fast pre-filters (skip regex if pattern not present)
@@ -58,11 +69,32 @@ fn has_tag(content: &str) -> bool {
     content.contains('#')
 }
 
+/// A tag must contain at least one non-numeric character (`#1984` is not a tag)
+#[inline]
+pub(crate) fn is_tag_name(name: &str) -> bool {
+    !name.chars().all(char::is_numeric)
+}
+
 #[inline]
 fn has_callout(content: &str) -> bool {
     content.contains("[!")
 }
 
+#[inline]
+fn has_block_id(content: &str) -> bool {
+    content.contains('^')
+}
+
+#[inline]
+fn has_comment(content: &str) -> bool {
+    content.contains("%%")
+}
+
+#[inline]
+fn has_highlight(content: &str) -> bool {
+    content.contains("==")
+}
+
 // ============================================================================
 // Excluded ranges (code blocks, inline code, etc.)
 // ============================================================================
@@ -71,14 +103,14 @@ fn has_callout(content: &str) -> bool {
 /// These are regions where markdown syntax should not be interpreted
 /// (code blocks, inline code, HTML blocks, etc.)
 #[derive(Debug, Default, Clone)]
-struct ExcludedRanges {
+pub(crate) struct ExcludedRanges {
     ranges: Vec<Range<usize>>,
 }
 
 impl ExcludedRanges {
     /// Check if a byte offset falls within any excluded range.
     #[inline]
-    fn contains(&self, offset: usize) -> bool {
+    pub(crate) fn contains(&self, offset: usize) -> bool {
         // Optimized: Binary search for O(log N) lookup
         if self.ranges.is_empty() {
             return false;
@@ -103,12 +135,12 @@ impl ExcludedRanges {
     }
 
     /// Add a range to exclude.
-    fn add(&mut self, range: Range<usize>) {
+    pub(crate) fn add(&mut self, range: Range<usize>) {
         self.ranges.push(range);
     }
 
     /// Sort and merge overlapping ranges for efficient lookup.
-    fn optimize(&mut self) {
+    pub(crate) fn optimize(&mut self) {
         if self.ranges.is_empty() {
             return;
         }
@@ -131,6 +163,34 @@ impl ExcludedRanges {
     }
 }
 
+/// Ranges of `%%comments%%` outside excluded ranges, markers included.
+/// An unclosed `%%` comments out the rest of the content, as in Obsidian.
+pub(crate) fn find_comments(
+    body: &str,
+    body_offset: usize,
+    excluded: &ExcludedRanges,
+) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut open: Option<usize> = None;
+    for (local, _) in body.match_indices("%%") {
+        let offset = body_offset + local;
+        if excluded.contains(offset) {
+            continue;
+        }
+        match open {
+            None => open = Some(offset),
+            Some(start) => {
+                ranges.push(start..offset + 2);
+                open = None;
+            }
+        }
+    }
+    if let Some(start) = open {
+        ranges.push(start..body_offset + body.len());
+    }
+    ranges
+}
+
 // ============================================================================
 // Parse result structure
 // ============================================================================
@@ -147,6 +207,15 @@ pub struct ParseResult {
     pub tags: Vec<OFMTag>,
     pub tasks: Vec<TaskItem>,
     pub callouts: Vec<Callout>,
+    pub blocks: Vec<Block>,
+    pub comments: Vec<Comment>,
+    pub highlights: Vec<Highlight>,
+    pub footnote_references: Vec<FootnoteReference>,
+    pub footnote_definitions: Vec<FootnoteDefinition>,
+    pub math: Vec<Math>,
+    /// Ranges the OFM passes skipped (code, HTML, math, comments), without
+    /// the frontmatter
+    pub(crate) excluded: Vec<Range<usize>>,
 }
 
 impl ParseResult {
@@ -213,6 +282,11 @@ impl<'a> ParseEngine<'a> {
             self.content
         };
 
+        // Comments hide everything inside them, so they go before other OFM passes
+        let mut excluded = excluded;
+        self.parse_comments(body, body_start, &mut excluded, options, &mut result);
+        result.excluded = excluded.ranges.clone();
+
         if options.parse_wikilinks {
             self.parse_wikilinks(body, body_start, &excluded, &mut result);
             self.parse_embeds(body, body_start, &excluded, &mut result);
@@ -227,9 +301,32 @@ impl<'a> ParseEngine<'a> {
             self.parse_callouts(body, body_start, &excluded, options, &mut result);
         }
 
+        if options.parse_block_ids {
+            self.parse_block_ids(body, body_start, &excluded, &mut result);
+        }
+
+        if options.parse_highlights {
+            self.parse_highlights(body, body_start, &excluded, &mut result);
+        }
+
         result
     }
 
+    /// Byte ranges the OFM passes skip: frontmatter, code, inline code, HTML,
+    /// math and `%%comments%%`.
+    pub fn excluded_ranges(&self) -> Vec<Range<usize>> {
+        let mut result = ParseResult::default();
+        let options = ParseOptions::none();
+        let (mut excluded, body_start) = self.pulldown_pass(&options, &mut result);
+        let body = &self.content[body_start..];
+        self.parse_comments(body, body_start, &mut excluded, &options, &mut result);
+        if body_start > 0 {
+            excluded.add(0..body_start);
+            excluded.optimize();
+        }
+        excluded.ranges
+    }
+
     /// Phase 1: pulldown-cmark pass for CommonMark elements and excluded ranges.
     fn pulldown_pass(
         &self,
@@ -246,6 +343,8 @@ impl<'a> ParseEngine<'a> {
         opts.insert(Options::ENABLE_HEADING_ATTRIBUTES);
         opts.insert(Options::ENABLE_STRIKETHROUGH);
         opts.insert(Options::ENABLE_TABLES);
+        opts.insert(Options::ENABLE_FOOTNOTES);
+        opts.insert(Options::ENABLE_MATH);
 
         let parser = Parser::new_ext(self.content, opts);
 
@@ -287,6 +386,58 @@ impl<'a> ParseEngine<'a> {
                     excluded.add(range.clone());
                 }
 
+                // === Math ($inline$, $$display$$) ===
+                Event::InlineMath(ref text) | Event::DisplayMath(ref text) => {
+                    excluded.add(range.clone());
+                    let source = &self.content[range.clone()];
+                    if current_heading.is_some() {
+                        heading_text.push_str(source);
+                    } else if current_link.is_some() {
+                        link_text.push_str(source);
+                    } else if in_task_item {
+                        task_content.push_str(source);
+                    }
+                    if options.parse_math {
+                        result.math.push(Math {
+                            expression: text.trim().to_string(),
+                            position: SourcePosition::from_offset_indexed(
+                                &self.index,
+                                range.start,
+                                range.len(),
+                            ),
+                            is_display: matches!(event, Event::DisplayMath(_)),
+                        });
+                    }
+                }
+
+                // === Footnotes ===
+                Event::FootnoteReference(label) if options.parse_footnotes => {
+                    result.footnote_references.push(FootnoteReference {
+                        label: label.to_string(),
+                        position: SourcePosition::from_offset_indexed(
+                            &self.index,
+                            range.start,
+                            range.len(),
+                        ),
+                    });
+                }
+                Event::Start(Tag::FootnoteDefinition(label)) if options.parse_footnotes => {
+                    let source = &self.content[range.clone()];
+                    let content = source
+                        .split_once("]:")
+                        .map(|(_, rest)| rest.trim())
+                        .unwrap_or_default();
+                    result.footnote_definitions.push(FootnoteDefinition {
+                        label: label.to_string(),
+                        content: content.to_string(),
+                        position: SourcePosition::from_offset_indexed(
+                            &self.index,
+                            range.start,
+                            source.trim_end().len(),
+                        ),
+                    });
+                }
+
                 // === Metadata/Frontmatter ===
                 Event::Start(Tag::MetadataBlock(_)) => {
                     in_metadata = true;
@@ -313,12 +464,10 @@ impl<'a> ParseEngine<'a> {
                 }
 
                 // === Headings ===
-                Event::Start(Tag::Heading { level, id, .. }) => {
-                    if options.parse_headings {
-                        current_heading = Some((level, id.map(|s| s.to_string())));
-                        heading_text.clear();
-                        heading_start = range.start;
-                    }
+                Event::Start(Tag::Heading { level, id, .. }) if options.parse_headings => {
+                    current_heading = Some((level, id.map(|s| s.to_string())));
+                    heading_text.clear();
+                    heading_start = range.start;
                 }
                 Event::End(TagEnd::Heading(_)) => {
                     if let Some((level, id)) = current_heading.take() {
@@ -351,13 +500,11 @@ impl<'a> ParseEngine<'a> {
                 }
 
                 // === Tasks ===
-                Event::TaskListMarker(checked) => {
-                    if options.parse_tasks {
-                        in_task_item = true;
-                        task_checked = checked;
-                        task_content.clear();
-                        task_start = range.start;
-                    }
+                Event::TaskListMarker(checked) if options.parse_tasks => {
+                    in_task_item = true;
+                    task_checked = checked;
+                    task_content.clear();
+                    task_start = range.start;
                 }
                 Event::End(TagEnd::Item) if in_task_item => {
                     in_task_item = false;
@@ -382,12 +529,10 @@ impl<'a> ParseEngine<'a> {
                 // === Markdown Links ===
                 Event::Start(Tag::Link {
                     dest_url, title, ..
-                }) => {
-                    if options.parse_markdown_links && !in_code_block {
-                        current_link = Some((dest_url.to_string(), title.to_string()));
-                        link_text.clear();
-                        link_start = range.start;
-                    }
+                }) if options.parse_markdown_links && !in_code_block => {
+                    current_link = Some((dest_url.to_string(), title.to_string()));
+                    link_text.clear();
+                    link_start = range.start;
                 }
                 Event::End(TagEnd::Link) => {
                     if let Some((url, _title)) = current_link.take() {
@@ -424,6 +569,97 @@ impl<'a> ParseEngine<'a> {
         (excluded, body_start)
     }
 
+    /// Parse `%%comments%%`, adding them to the excluded ranges.
+    ///
+    /// Obsidian hides comments in reading view and ignores links, tags and
+    /// other syntax inside them, so CommonMark elements the pulldown pass
+    /// found within a comment are dropped too.
+    fn parse_comments(
+        &self,
+        body: &str,
+        body_offset: usize,
+        excluded: &mut ExcludedRanges,
+        options: &ParseOptions,
+        result: &mut ParseResult,
+    ) {
+        if !has_comment(body) {
+            return;
+        }
+
+        let comments = find_comments(body, body_offset, excluded);
+        if comments.is_empty() {
+            return;
+        }
+
+        let mut hidden = ExcludedRanges::default();
+        for range in &comments {
+            hidden.add(range.clone());
+            excluded.add(range.clone());
+        }
+        hidden.optimize();
+        excluded.optimize();
+
+        let visible = |position: &SourcePosition| !hidden.contains(position.offset);
+        result.headings.retain(|h| visible(&h.position));
+        result.markdown_links.retain(|l| visible(&l.position));
+        result.tasks.retain(|t| visible(&t.position));
+        result.math.retain(|m| visible(&m.position));
+        result.footnote_references.retain(|f| visible(&f.position));
+        result.footnote_definitions.retain(|f| visible(&f.position));
+
+        if !options.parse_comments {
+            return;
+        }
+        for range in comments {
+            let source = &self.content[range.clone()];
+            // An unclosed comment runs to the end of the note
+            let inner = if source.len() >= 4 && source.ends_with("%%") {
+                &source[2..source.len() - 2]
+            } else {
+                &source[2..]
+            };
+            result.comments.push(Comment {
+                content: inner.trim().to_string(),
+                position: SourcePosition::from_offset_indexed(
+                    &self.index,
+                    range.start,
+                    range.len(),
+                ),
+                is_block: source.contains('\n'),
+            });
+        }
+    }
+
+    /// Parse `==highlights==`, respecting excluded ranges.
+    fn parse_highlights(
+        &self,
+        body: &str,
+        body_offset: usize,
+        excluded: &ExcludedRanges,
+        result: &mut ParseResult,
+    ) {
+        if !has_highlight(body) {
+            return;
+        }
+
+        for caps in HIGHLIGHT.captures_iter(body) {
+            let full_match = caps.get(0).unwrap();
+            let global_start = body_offset + full_match.start();
+            if excluded.contains(global_start) {
+                continue;
+            }
+
+            result.highlights.push(Highlight {
+                text: caps[1].to_string(),
+                position: SourcePosition::from_offset_indexed(
+                    &self.index,
+                    global_start,
+                    full_match.len(),
+                ),
+            });
+        }
+    }
+
     /// Parse wikilinks, respecting excluded ranges.
     fn parse_wikilinks(
         &self,
@@ -546,6 +782,9 @@ impl<'a> ParseEngine<'a> {
             }
 
             let name = tag_name.as_str();
+            if !is_tag_name(name) {
+                continue;
+            }
 
             result.tags.push(OFMTag {
                 name: name.to_string(),
@@ -559,7 +798,59 @@ impl<'a> ParseEngine<'a> {
         }
     }
 
+    /// Parse block IDs (`^id` at line end), respecting excluded ranges.
+    fn parse_block_ids(
+        &self,
+        body: &str,
+        body_offset: usize,
+        excluded: &ExcludedRanges,
+        result: &mut ParseResult,
+    ) {
+        if !has_block_id(body) {
+            return;
+        }
+
+        let mut line_start = 0;
+        for raw_line in body.split_inclusive('\n') {
+            let line = raw_line.trim_end_matches(['\n', '\r']);
+            let global_line_start = body_offset + line_start;
+            line_start += raw_line.len();
+
+            let Some(caps) = BLOCK_ID.captures(line) else {
+                continue;
+            };
+            let id = caps.get(1).unwrap();
+            let marker_start = global_line_start + id.start() - 1; // -1 for the ^ prefix
+            if excluded.contains(marker_start) {
+                continue;
+            }
+
+            let text = line[..id.start() - 1].trim();
+            let type_ = if text.is_empty() {
+                "standalone"
+            } else if is_list_item(text) {
+                "list_item"
+            } else {
+                "paragraph"
+            };
+
+            result.blocks.push(Block {
+                content: text.to_string(),
+                block_id: Some(id.as_str().to_string()),
+                position: SourcePosition::from_offset_indexed(
+                    &self.index,
+                    marker_start,
+                    id.len() + 1, // +1 for ^
+                ),
+                type_: type_.to_string(),
+            });
+        }
+    }
+
     /// Parse callouts (line-based, with excluded range awareness).
+    ///
+    /// Nested callouts (`> > [!tip]`) are reported on their own and, with full
+    /// parsing, also stay part of the enclosing callout's content.
     fn parse_callouts(
         &self,
         body: &str,
@@ -573,56 +864,49 @@ impl<'a> ParseEngine<'a> {
         }
 
         let lines: Vec<&str> = body.lines().collect();
+        let mut line_starts = Vec::with_capacity(lines.len());
         let mut offset = 0;
-        let mut i = 0;
-
-        while i < lines.len() {
-            let line = lines[i];
-            let line_start = offset;
-            let global_line_start = body_offset + line_start;
+        for line in &lines {
+            line_starts.push(body_offset + offset);
             offset += line.len() + 1; // +1 for newline
+        }
 
+        for (i, line) in lines.iter().enumerate() {
             // Skip if this line is in an excluded range
-            if excluded.contains(global_line_start) {
-                i += 1;
+            if excluded.contains(line_starts[i]) {
                 continue;
             }
 
             if let Some(caps) = CALLOUT.captures(line) {
-                let callout = if options.full_callouts {
-                    self.parse_callout_full(
-                        &lines,
-                        &mut i,
-                        global_line_start,
-                        &caps,
+                let mut callout = self.parse_callout_header(line, line_starts[i], &caps);
+                if options.full_callouts {
+                    callout.content = callout_content(
+                        &lines[i + 1..],
+                        &line_starts[i + 1..],
+                        callout.depth,
                         excluded,
-                        body_offset,
-                        &mut offset,
-                    )
-                } else {
-                    i += 1;
-                    self.parse_callout_simple(line, global_line_start, &caps)
-                };
+                    );
+                }
                 result.callouts.push(callout);
-            } else {
-                i += 1;
             }
         }
     }
 
-    /// Parse simple callout (header only).
-    fn parse_callout_simple(
+    /// Build a callout from its header line (content is filled in separately).
+    fn parse_callout_header(
         &self,
         line: &str,
         global_offset: usize,
         caps: &regex::Captures,
     ) -> Callout {
-        let type_str = caps.get(1).unwrap().as_str();
-        let fold_marker = caps.get(2).unwrap().as_str();
-        let title_text = caps.get(3).unwrap().as_str();
+        let depth = caps.get(1).unwrap().as_str().matches('>').count();
+        let identifier = caps.get(2).unwrap().as_str();
+        let fold_marker = caps.get(3).unwrap().as_str();
+        let title_text = caps.get(4).unwrap().as_str();
 
         Callout {
-            type_: parse_callout_type(type_str),
+            type_: CalloutType::from_identifier(identifier),
+            identifier: identifier.to_string(),
             title: if title_text.is_empty() {
                 None
             } else {
@@ -631,75 +915,8 @@ impl<'a> ParseEngine<'a> {
             content: String::new(),
             position: SourcePosition::from_offset_indexed(&self.index, global_offset, line.len()),
             is_foldable: !fold_marker.is_empty(),
-        }
-    }
-
-    /// Parse callout with full multi-line content.
-    #[allow(clippy::too_many_arguments)]
-    fn parse_callout_full(
-        &self,
-        lines: &[&str],
-        i: &mut usize,
-        global_line_start: usize,
-        caps: &regex::Captures,
-        excluded: &ExcludedRanges,
-        body_offset: usize,
-        offset: &mut usize,
-    ) -> Callout {
-        let start_line_idx = *i;
-        let first_line = lines[start_line_idx];
-        let type_str = caps.get(1).unwrap().as_str();
-        let fold_marker = caps.get(2).unwrap().as_str();
-        let title_text = caps.get(3).unwrap().as_str();
-
-        // Collect continuation lines
-        let mut callout_content = String::new();
-        *i += 1;
-
-        while *i < lines.len() {
-            let line = lines[*i];
-            let line_global_start = body_offset + *offset;
-
-            // Skip excluded ranges
-            if excluded.contains(line_global_start) {
-                *offset += line.len() + 1;
-                *i += 1;
-                continue;
-            }
-
-            // Check if this is a new callout (stop)
-            if CALLOUT.is_match(line) {
-                break;
-            }
-
-            // Check if continuation line
-            if let Some(cont_caps) = CALLOUT_CONT.captures(line) {
-                let content_part = cont_caps.get(1).unwrap().as_str();
-                if !callout_content.is_empty() {
-                    callout_content.push('\n');
-                }
-                callout_content.push_str(content_part);
-                *offset += line.len() + 1;
-                *i += 1;
-            } else {
-                break;
-            }
-        }
-
-        Callout {
-            type_: parse_callout_type(type_str),
-            title: if title_text.is_empty() {
-                None
-            } else {
-                Some(title_text.to_string())
-            },
-            content: callout_content,
-            position: SourcePosition::from_offset_indexed(
-                &self.index,
-                global_line_start,
-                first_line.len(),
-            ),
-            is_foldable: !fold_marker.is_empty(),
+            is_collapsed: fold_marker == "-",
+            depth,
         }
     }
 }
@@ -719,24 +936,59 @@ fn parse_link_target(raw: &str) -> (String, Option<String>) {
     }
 }
 
-/// Parse callout type string into enum.
-fn parse_callout_type(type_str: &str) -> CalloutType {
-    match type_str.to_lowercase().as_str() {
-        "note" => CalloutType::Note,
-        "tip" => CalloutType::Tip,
-        "info" => CalloutType::Info,
-        "todo" => CalloutType::Todo,
-        "important" => CalloutType::Important,
-        "success" => CalloutType::Success,
-        "question" => CalloutType::Question,
-        "warning" => CalloutType::Warning,
-        "failure" | "fail" | "missing" => CalloutType::Failure,
-        "danger" | "error" => CalloutType::Danger,
-        "bug" => CalloutType::Bug,
-        "example" => CalloutType::Example,
-        "quote" | "cite" => CalloutType::Quote,
-        _ => CalloutType::Note,
+/// Check whether a line starts with a bullet or ordered list marker.
+fn is_list_item(line: &str) -> bool {
+    let line = line.trim_start();
+    if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
+        return true;
     }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    digits > 0 && (line[digits..].starts_with(". ") || line[digits..].starts_with(") "))
+}
+
+/// Collect the content lines of a callout at `depth`, with its quote markers removed.
+///
+/// Stops at the first line quoted less deeply or at the next callout of the
+/// same or a shallower level.
+fn callout_content(
+    lines: &[&str],
+    line_starts: &[usize],
+    depth: usize,
+    excluded: &ExcludedRanges,
+) -> String {
+    let mut content = String::new();
+
+    for (line, &line_start) in lines.iter().zip(line_starts) {
+        // Skip excluded ranges
+        if excluded.contains(line_start) {
+            continue;
+        }
+
+        if let Some(caps) = CALLOUT.captures(line)
+            && caps.get(1).unwrap().as_str().matches('>').count() <= depth
+        {
+            break;
+        }
+
+        let Some(content_part) = strip_quote_markers(line, depth) else {
+            break;
+        };
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        content.push_str(content_part);
+    }
+
+    content
+}
+
+/// Strip `depth` blockquote markers (`>` and surrounding whitespace) from a line.
+fn strip_quote_markers(line: &str, depth: usize) -> Option<&str> {
+    let mut rest = line;
+    for _ in 0..depth {
+        rest = rest.trim_start().strip_prefix('>')?.trim_start();
+    }
+    Some(rest)
 }
 
 // ============================================================================
@@ -796,6 +1048,173 @@ mod tests {
         assert!(result.tags[1].is_nested);
     }
 
+    #[test]
+    fn test_tag_conformance() {
+        // (input, expected tag names) following Obsidian's tag grammar
+        let cases: &[(&str, &[&str])] = &[
+            ("#tag", &["tag"]),
+            ("#parent/child", &["parent/child"]),
+            ("#snake_case and #kebab-case", &["snake_case", "kebab-case"]),
+            ("#_private", &["_private"]),
+            ("#café", &["café"]),
+            ("#cafe\u{301}", &["cafe\u{301}"]),
+            ("#Überblick", &["Überblick"]),
+            ("#über-straße", &["über-straße"]),
+            ("#日本語", &["日本語"]),
+            ("#日本語、テスト", &["日本語"]),
+            ("#مرحبا", &["مرحبا"]),
+            ("#🚀launch", &["🚀launch"]),
+            ("#project/🚀", &["project/🚀"]),
+            ("#👍🏽", &["👍🏽"]),
+            ("#👨\u{200D}💻dev", &["👨\u{200D}💻dev"]),
+            ("#y1984", &["y1984"]),
+            ("#1984s", &["1984s"]),
+            ("#2024/q1", &["2024/q1"]),
+            ("#x²", &["x²"]),
+            // Numbers only are not tags
+            ("#1984", &[]),
+            ("Issue #42", &[]),
+            ("#१२३", &[]),
+            // Whitespace and punctuation end a tag
+            ("#tag.", &["tag"]),
+            ("#tag, #other!", &["tag", "other"]),
+            ("#tag's", &["tag"]),
+            ("#tag:value", &["tag"]),
+            ("#tag\u{3000}next", &["tag"]),
+            ("(#paren) [#bracket]", &["paren", "bracket"]),
+            ("line\n#next", &["next"]),
+            // Not tags
+            ("a#b", &[]),
+            ("C# code", &[]),
+            ("https://example.com/#fragment", &[]),
+            ("# Heading", &[]),
+            ("##", &[]),
+            ("#", &[]),
+        ];
+
+        for (input, expected) in cases {
+            let result = ParseEngine::new(input).parse(&ParseOptions::all());
+            let names: Vec<&str> = result.tags.iter().map(|t| t.name.as_str()).collect();
+            assert_eq!(&names, expected, "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_unicode_tag_position() {
+        let content = "Über #café und #日本語";
+        let result = ParseEngine::new(content).parse(&ParseOptions::all());
+        assert_eq!(result.tags.len(), 2);
+        let tag = &result.tags[1];
+        assert_eq!(
+            &content[tag.position.offset..tag.position.offset + tag.position.length],
+            "#日本語"
+        );
+        assert!(!tag.is_nested);
+    }
+
+    #[test]
+    fn test_comments_hide_links_and_tags() {
+        let content = "Visible [[A]] #a %%hidden [[B]] #b [md](c.md)%% after\n\n\
+%%\n# Not a heading\n- [ ] not a task ^blk\n%%\n\n`%%code%%` and [[C]]";
+        let result = ParseEngine::new(content).parse(&ParseOptions::all());
+
+        let targets: Vec<&str> = result.wikilinks.iter().map(|l| l.target.as_str()).collect();
+        assert_eq!(targets, vec!["A", "C"]);
+        let tags: Vec<&str> = result.tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(tags, vec!["a"]);
+        assert!(result.markdown_links.is_empty());
+        assert!(result.headings.is_empty());
+        assert!(result.tasks.is_empty());
+        assert!(result.blocks.is_empty());
+
+        assert_eq!(result.comments.len(), 2);
+        let inline = &result.comments[0];
+        assert_eq!(inline.content, "hidden [[B]] #b [md](c.md)");
+        assert!(!inline.is_block);
+        assert_eq!(inline.position.line, 1);
+        assert_eq!(
+            &content[inline.position.offset..inline.position.offset + inline.position.length],
+            "%%hidden [[B]] #b [md](c.md)%%"
+        );
+        let block = &result.comments[1];
+        assert!(block.is_block);
+        assert_eq!(block.content, "# Not a heading\n- [ ] not a task ^blk");
+        assert_eq!(block.position.line, 3);
+    }
+
+    #[test]
+    fn test_unclosed_comment_runs_to_end() {
+        let content = "[[A]] %% draft\n[[B]] #later";
+        let result = ParseEngine::new(content).parse(&ParseOptions::all());
+        assert_eq!(result.wikilinks.len(), 1);
+        assert!(result.tags.is_empty());
+        assert_eq!(result.comments.len(), 1);
+        assert_eq!(result.comments[0].content, "draft\n[[B]] #later");
+    }
+
+    #[test]
+    fn test_highlights() {
+        let content = "Some ==important [[Note]]== text, a == b, ==x== and `==code==`";
+        let result = ParseEngine::new(content).parse(&ParseOptions::all());
+
+        let texts: Vec<&str> = result.highlights.iter().map(|h| h.text.as_str()).collect();
+        assert_eq!(texts, vec!["important [[Note]]", "x"]);
+        // Links inside highlights are still links
+        assert_eq!(result.wikilinks.len(), 1);
+        let first = &result.highlights[0].position;
+        assert_eq!(first.column, 6);
+        assert_eq!(first.length, "==important [[Note]]==".len());
+    }
+
+    #[test]
+    fn test_footnotes() {
+        let content =
+            "Claim[^1] and another[^note].\n\n[^1]: First source.\n[^note]: See [[Source]].\n";
+        let result = ParseEngine::new(content).parse(&ParseOptions::all());
+
+        let refs: Vec<&str> = result
+            .footnote_references
+            .iter()
+            .map(|f| f.label.as_str())
+            .collect();
+        assert_eq!(refs, vec!["1", "note"]);
+        assert_eq!(result.footnote_references[0].position.column, 6);
+
+        assert_eq!(result.footnote_definitions.len(), 2);
+        let def = &result.footnote_definitions[1];
+        assert_eq!(def.label, "note");
+        assert_eq!(def.content, "See [[Source]].");
+        assert_eq!(def.position.line, 4);
+        // Links in footnote definitions are real links
+        assert_eq!(result.wikilinks.len(), 1);
+    }
+
+    #[test]
+    fn test_math_excludes_links_and_tags() {
+        let content =
+            "Inline $a^2 #b [[c]]$ and #tag\n\n$$\n\\sum_{i} x_i\n$$\n\nCosts $5 and $10.";
+        let result = ParseEngine::new(content).parse(&ParseOptions::all());
+
+        assert_eq!(result.math.len(), 2);
+        assert_eq!(result.math[0].expression, "a^2 #b [[c]]");
+        assert!(!result.math[0].is_display);
+        assert_eq!(result.math[0].position.column, 8);
+        assert_eq!(result.math[1].expression, "\\sum_{i} x_i");
+        assert!(result.math[1].is_display);
+        assert_eq!(result.math[1].position.line, 3);
+
+        let tags: Vec<&str> = result.tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(tags, vec!["tag"]);
+        assert!(result.wikilinks.is_empty());
+    }
+
+    #[test]
+    fn test_excluded_ranges_cover_comments_and_math() {
+        let content = "a %%b%% $c$ d";
+        let ranges = ParseEngine::new(content).excluded_ranges();
+        assert_eq!(ranges, vec![2..7, 8..11]);
+    }
+
     #[test]
     fn test_engine_headings_via_pulldown() {
         let content = "# Heading 1\n\n## Heading 2\n\n### Heading 3";
@@ -858,6 +1277,41 @@ mod tests {
         assert_eq!(result.callouts[0].content, "Line 1\nLine 2");
     }
 
+    #[test]
+    fn test_engine_callout_aliases_and_custom() {
+        let content = "> [!TLDR]- Short\n\n> [!hint]\n\n> [!decision] Use Rust";
+        let engine = ParseEngine::new(content);
+        let result = engine.parse(&ParseOptions::all());
+
+        assert_eq!(result.callouts.len(), 3);
+        assert_eq!(result.callouts[0].type_, CalloutType::Abstract);
+        assert_eq!(result.callouts[0].identifier, "TLDR");
+        assert!(result.callouts[0].is_collapsed);
+        assert_eq!(result.callouts[1].type_, CalloutType::Tip);
+        assert_eq!(
+            result.callouts[2].type_,
+            CalloutType::Custom("decision".to_string())
+        );
+    }
+
+    #[test]
+    fn test_engine_nested_callouts() {
+        let content = "> [!note] Outer\n> Text\n> > [!todo] Inner\n> > Do it\n> After\n\nPlain";
+        let engine = ParseEngine::new(content);
+        let result = engine.parse(&ParseOptions::all().with_full_callouts());
+
+        assert_eq!(result.callouts.len(), 2);
+        assert_eq!(result.callouts[0].depth, 1);
+        assert_eq!(
+            result.callouts[0].content,
+            "Text\n> [!todo] Inner\n> Do it\nAfter"
+        );
+        assert_eq!(result.callouts[1].type_, CalloutType::Todo);
+        assert_eq!(result.callouts[1].depth, 2);
+        assert_eq!(result.callouts[1].position.line, 3);
+        assert_eq!(result.callouts[1].content, "Do it");
+    }
+
     // =========================================================================
     // CODE BLOCK EXCLUSION TESTS - The main reason for pulldown-cmark
     // =========================================================================
@@ -983,6 +1437,33 @@ Back to normal [[Valid]]
         assert_eq!(result.wikilinks[0].position.offset, 7);
     }
 
+    #[test]
+    fn test_engine_block_ids() {
+        let content = "Para text ^para-1\n- item ^Item2\n\n> quote\n\n^standalone\n\n```\ncode ^not-a-block\n```\nx^y\n";
+        let engine = ParseEngine::new(content);
+        let result = engine.parse(&ParseOptions::all());
+
+        let ids: Vec<_> = result
+            .blocks
+            .iter()
+            .map(|b| (b.block_id.as_deref().unwrap(), b.type_.as_str()))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("para-1", "paragraph"),
+                ("Item2", "list_item"),
+                ("standalone", "standalone"),
+            ]
+        );
+        assert_eq!(result.blocks[0].content, "Para text");
+        assert_eq!(result.blocks[1].position.line, 2);
+        assert_eq!(
+            result.blocks[2].position.offset,
+            content.find("^standalone").unwrap()
+        );
+    }
+
     #[test]
     fn test_selective_parsing() {
         let content = "# Heading\n[[Link]] #tag";
@@ -0,0 +1,223 @@
+//! Excalidraw drawings (`.excalidraw.md`).
+//!
+//! The Obsidian Excalidraw plugin stores a drawing as markdown: frontmatter
+//! with `excalidraw-plugin`, a "switch to Excalidraw view" banner, then
+//! `## Text Elements` (each element's text and `^id`), `## Element Links`,
+//! `## Embedded Files` (`<file id>: [[file]]`) and a `## Drawing` section
+//! holding the scene as (compressed) JSON. Older files use `#` headings and
+//! `# Drawing` with plain JSON. Links live in the sections the plugin writes
+//! for Obsidian to read; the banner and the scene are not note content.
+
+use std::ops::Range;
+use std::path::Path;
+use turbovault_core::Frontmatter;
+
+use crate::blocks::to_plain_text;
+
+/// Frontmatter key the plugin marks its files with
+const PLUGIN_KEY: &str = "excalidraw-plugin";
+
+/// Sections of a drawing, as byte ranges of their bodies
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Sections {
+    text_elements: Option<Range<usize>>,
+    embedded_files: Option<Range<usize>>,
+    /// From the `Drawing` heading to the closing `%%` (or the end)
+    drawing: Option<Range<usize>>,
+    /// The "switch to Excalidraw view" line
+    banner: Option<Range<usize>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Data,
+    TextElements,
+    ElementLinks,
+    EmbeddedFiles,
+    Drawing,
+}
+
+/// Section a line opens, if it is one of the plugin's headings
+fn section_heading(line: &str) -> Option<Section> {
+    let title = line
+        .strip_prefix("## ")
+        .or_else(|| line.strip_prefix("# "))?
+        .trim();
+    match title.to_ascii_lowercase().as_str() {
+        "excalidraw data" => Some(Section::Data),
+        "text elements" => Some(Section::TextElements),
+        "element links" => Some(Section::ElementLinks),
+        "embedded files" => Some(Section::EmbeddedFiles),
+        "drawing" => Some(Section::Drawing),
+        _ => None,
+    }
+}
+
+impl Sections {
+    fn locate(content: &str) -> Self {
+        let mut sections = Self::default();
+        let mut current: Option<(Section, usize)> = None;
+        let mut offset = 0;
+
+        let close =
+            |sections: &mut Self, current: Option<(Section, usize)>, end: usize| match current {
+                Some((Section::TextElements, start)) => sections.text_elements = Some(start..end),
+                Some((Section::EmbeddedFiles, start)) => sections.embedded_files = Some(start..end),
+                _ => {}
+            };
+
+        for line in content.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            let text = line.trim_end_matches(['\r', '\n']);
+
+            if let Some((Section::Drawing, drawing_start)) = current {
+                if text.trim() == "%%" {
+                    sections.drawing = Some(drawing_start..start);
+                    current = None;
+                }
+                continue;
+            }
+
+            if sections.banner.is_none() && text.starts_with("==⚠") {
+                sections.banner = Some(start..start + text.len());
+                continue;
+            }
+
+            if let Some(section) = section_heading(text) {
+                close(&mut sections, current, start);
+                current = Some(match section {
+                    Section::Drawing => (section, start),
+                    _ => (section, offset),
+                });
+            } else if text.trim() == "%%" {
+                // The comment hiding the drawing opens right after the last section
+                close(&mut sections, current, start);
+                current = None;
+            }
+        }
+
+        match current {
+            Some((Section::Drawing, start)) => sections.drawing = Some(start..content.len()),
+            other => close(&mut sections, other, content.len()),
+        }
+        sections
+    }
+}
+
+/// Whether a note is an Excalidraw drawing: named `*.excalidraw.md` or
+/// marked with `excalidraw-plugin` in its frontmatter
+pub fn is_excalidraw(path: &Path, frontmatter: Option<&Frontmatter>) -> bool {
+    let named = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.to_ascii_lowercase().ends_with(".excalidraw.md"));
+    named || frontmatter.is_some_and(|fm| fm.data.contains_key(PLUGIN_KEY))
+}
+
+/// The drawing with its banner and scene blanked out
+///
+/// Blanked bytes become spaces and line breaks are kept, so offsets and
+/// line numbers of everything else still match `content`.
+pub fn mask_scene(content: &str) -> String {
+    let sections = Sections::locate(content);
+    let mut masked = content.to_string();
+    for range in [sections.banner, sections.drawing].into_iter().flatten() {
+        let blank: String = content[range.clone()]
+            .chars()
+            .flat_map(|c| {
+                let width = if matches!(c, '\n' | '\r') {
+                    0
+                } else {
+                    c.len_utf8()
+                };
+                std::iter::repeat_n(' ', width).chain((width == 0).then_some(c))
+            })
+            .collect();
+        masked.replace_range(range, &blank);
+    }
+    masked
+}
+
+/// Byte range of the `Embedded Files` section, whose links are embeds
+pub fn embedded_files_range(content: &str) -> Option<Range<usize>> {
+    Sections::locate(content).embedded_files
+}
+
+/// Searchable text of a drawing: its text elements without their `^id`s
+pub fn plain_text(content: &str) -> String {
+    let Some(range) = Sections::locate(content).text_elements else {
+        return String::new();
+    };
+    let text: Vec<&str> = content[range]
+        .lines()
+        .map(|line| strip_element_id(line.trim_end()))
+        .collect();
+    to_plain_text(&text.join("\n"))
+}
+
+/// Remove the trailing ` ^elementId` the plugin appends to each text element
+fn strip_element_id(line: &str) -> &str {
+    match line.rsplit_once(" ^") {
+        Some((text, id))
+            if !id.is_empty()
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+        {
+            text
+        }
+        _ => line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DRAWING: &str = "---\n\nexcalidraw-plugin: parsed\ntags: [excalidraw]\n\n---\n==⚠  Switch to EXCALIDRAW VIEW in the MORE OPTIONS menu of this document. ⚠== #banner\n\n\n# Excalidraw Data\n\n## Text Elements\nProject kickoff ^k1A2b3C4\n\nSee [[Roadmap]] ^x9Y8z7W6\n\n## Element Links\na1B2c3D4: [[Spec]]\n\n## Embedded Files\n0f1e2d3c: [[diagram.png]]\n\n%%\n## Drawing\n```json\n{\"type\":\"excalidraw\",\"elements\":[{\"strokeColor\":\"#1e1e1e\",\"link\":\"[[Hidden]]\"}]}\n```\n%%";
+
+    #[test]
+    fn test_is_excalidraw() {
+        assert!(is_excalidraw(Path::new("art/Board.excalidraw.md"), None));
+        assert!(!is_excalidraw(Path::new("art/Board.md"), None));
+
+        let mut frontmatter = Frontmatter {
+            data: Default::default(),
+            position: turbovault_core::SourcePosition::start(),
+        };
+        frontmatter
+            .data
+            .insert(PLUGIN_KEY.to_string(), "parsed".into());
+        assert!(is_excalidraw(Path::new("art/Board.md"), Some(&frontmatter)));
+    }
+
+    #[test]
+    fn test_mask_scene_keeps_offsets() {
+        let masked = mask_scene(DRAWING);
+        assert_eq!(masked.len(), DRAWING.len());
+        assert_eq!(masked.lines().count(), DRAWING.lines().count());
+        assert!(!masked.contains("Hidden"));
+        assert!(!masked.contains("#1e1e1e"));
+        assert!(!masked.contains("#banner"));
+        assert!(masked.contains("See [[Roadmap]] ^x9Y8z7W6"));
+        assert!(masked.contains("a1B2c3D4: [[Spec]]"));
+        assert!(masked.ends_with("%%"));
+    }
+
+    #[test]
+    fn test_sections() {
+        let range = embedded_files_range(DRAWING).unwrap();
+        assert_eq!(DRAWING[range].trim(), "0f1e2d3c: [[diagram.png]]");
+        assert_eq!(plain_text(DRAWING), "Project kickoff\nSee Roadmap");
+    }
+
+    #[test]
+    fn test_legacy_layout_without_comment() {
+        let legacy = "# Text Elements\nHello ^abcdefgh\n\n# Drawing\n```json\n{\"elements\":[\"#tag\"]}\n```\n";
+        assert_eq!(plain_text(legacy), "Hello");
+        let masked = mask_scene(legacy);
+        assert!(!masked.contains("#tag"));
+        assert!(masked.starts_with("# Text Elements\nHello ^abcdefgh\n"));
+    }
+}
@@ -0,0 +1,675 @@
+//! Incremental reparsing of notes after an edit.
+//!
+//! Reparsing a multi-megabyte note on every save is wasteful when the edit
+//! touches one paragraph. [`IncrementalParse`] keeps a note's parse results
+//! together with the boundaries between its top-level blocks: line starts
+//! after a blank line that no list, quote, table, code block, comment, math
+//! block or parsed element continues across. An edit reparses only the text
+//! between the boundaries around it and shifts the positions of everything
+//! after it.
+//!
+//! Edits whose effect can reach past the region (a code block or `%%comment%%`
+//! left open, footnotes, HTML blocks, an unclosed `[[`) and edits to the
+//! frontmatter fall back to a full parse, as does every edit of a note with
+//! link reference definitions or of an Excalidraw drawing. Either way the results equal what
+//! [`Parser::parse_file`](crate::Parser::parse_file) returns for the new
+//! content.
+//!
+//! ```
+//! use std::path::Path;
+//! use turbovault_parser::{IncrementalParse, TextEdit};
+//!
+//! let old = "# Plan\n\nSee [[Roadmap]].\n\nShip #v2 soon.\n";
+//! let new = "# Plan\n\nSee [[Roadmap]] and [[Risks]].\n\nShip #v2 soon.\n";
+//!
+//! let mut parse = IncrementalParse::new(Path::new("plan.md"), old);
+//! let incremental = parse.apply_edit(&TextEdit::between(old, new)).unwrap();
+//! assert!(incremental);
+//!
+//! let file = parse.vault_file();
+//! assert_eq!(file.links.len(), 2);
+//! assert_eq!(file.tags[0].position.offset, new.find("#v2").unwrap());
+//! ```
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use turbovault_core::{Error, LineIndex, Result, SourcePosition, VaultFile};
+
+use crate::ParseOptions;
+use crate::engine::{ExcludedRanges, ParseEngine, ParseResult};
+use crate::excalidraw;
+use crate::parsers::{file_metadata, mark_parsed, parse_note, transfer_result};
+
+/// Replacement of a byte range of a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// Byte range of the old content being replaced
+    pub range: Range<usize>,
+    /// Text put in its place
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn new(range: Range<usize>, text: impl Into<String>) -> Self {
+        Self {
+            range,
+            text: text.into(),
+        }
+    }
+
+    /// The single edit turning `old` into `new`: whatever lies between their
+    /// common prefix and common suffix
+    pub fn between(old: &str, new: &str) -> Self {
+        let (old_bytes, new_bytes) = (old.as_bytes(), new.as_bytes());
+        let mut prefix = old_bytes
+            .iter()
+            .zip(new_bytes)
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !old.is_char_boundary(prefix) || !new.is_char_boundary(prefix) {
+            prefix -= 1;
+        }
+
+        let max_suffix = old.len().min(new.len()) - prefix;
+        let mut suffix = old_bytes
+            .iter()
+            .rev()
+            .zip(new_bytes.iter().rev())
+            .take(max_suffix)
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !old.is_char_boundary(old.len() - suffix) || !new.is_char_boundary(new.len() - suffix)
+        {
+            suffix -= 1;
+        }
+
+        Self::new(prefix..old.len() - suffix, &new[prefix..new.len() - suffix])
+    }
+}
+
+/// A parsed note that can be updated edit by edit
+#[derive(Debug, Clone)]
+pub struct IncrementalParse {
+    path: PathBuf,
+    content: String,
+    index: LineIndex,
+    result: ParseResult,
+    /// Line starts where a reparsed region may begin or end, ascending
+    boundaries: Vec<usize>,
+    /// Every edit reparses the whole note
+    full_only: bool,
+}
+
+/// Where an edit's region lies, before and after the edit
+struct Region {
+    start: usize,
+    old_end: usize,
+    new_end: usize,
+    line_delta: isize,
+}
+
+impl IncrementalParse {
+    /// Parse a Markdown note in full
+    pub fn new(path: &Path, content: &str) -> Self {
+        let mut parse = Self {
+            path: path.to_path_buf(),
+            content: content.to_string(),
+            index: LineIndex::new(""),
+            result: ParseResult::default(),
+            boundaries: Vec::new(),
+            full_only: true,
+        };
+        parse.parse_all();
+        parse
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The note's full content, frontmatter included
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn line_index(&self) -> &LineIndex {
+        &self.index
+    }
+
+    /// Apply an edit and update the parse results.
+    ///
+    /// Returns `true` when only the blocks around the edit were reparsed and
+    /// `false` when the note was parsed again in full.
+    pub fn apply_edit(&mut self, edit: &TextEdit) -> Result<bool> {
+        let range = edit.range.clone();
+        if range.start > range.end
+            || range.end > self.content.len()
+            || !self.content.is_char_boundary(range.start)
+            || !self.content.is_char_boundary(range.end)
+        {
+            return Err(Error::validation_error(format!(
+                "Edit range {}..{} is not a valid range of {} (length {})",
+                range.start,
+                range.end,
+                self.path.display(),
+                self.content.len()
+            )));
+        }
+
+        let removed = self.content[range.clone()].to_string();
+        self.content.replace_range(range.clone(), &edit.text);
+        self.index.apply_edit(range.clone(), &edit.text);
+
+        let Some(region) = self.region(&range, &edit.text, &removed) else {
+            self.parse_all();
+            return Ok(false);
+        };
+        let new_text = &self.content[region.start..region.new_end];
+        let edit_end = range.start + edit.text.len();
+        let old_text = format!(
+            "{}{}{}",
+            &self.content[region.start..range.start],
+            removed,
+            &self.content[edit_end..region.new_end]
+        );
+        if crosses_blocks(&old_text) || crosses_blocks(new_text) || link_open_after(new_text) {
+            self.parse_all();
+            return Ok(false);
+        }
+
+        let mut parsed =
+            ParseEngine::with_source_file(new_text, &self.path).parse(&ParseOptions::all());
+        // Code or a comment left open would run on past the region in the note
+        let left_open = region.new_end < self.content.len()
+            && parsed.excluded.iter().any(|r| r.end >= new_text.len());
+        if left_open || parsed.frontmatter.is_some() || parsed.frontmatter_end_offset > 0 {
+            self.parse_all();
+            return Ok(false);
+        }
+
+        // Region results are relative to its first line
+        let (first_line, _) = self.index.line_col(region.start);
+        for_each_position(&mut parsed, |position| {
+            position.offset += region.start;
+            position.line += first_line - 1;
+        });
+        for excluded in &mut parsed.excluded {
+            *excluded = excluded.start + region.start..excluded.end + region.start;
+        }
+        let spans = element_spans(&mut parsed);
+        let cuts = find_boundaries(new_text, region.start, &spans);
+
+        let (old, new, r) = (&mut self.result, parsed, &region);
+        splice(&mut old.headings, new.headings, r, |e| &mut e.position);
+        splice(&mut old.wikilinks, new.wikilinks, r, |e| &mut e.position);
+        splice(&mut old.embeds, new.embeds, r, |e| &mut e.position);
+        splice(&mut old.markdown_links, new.markdown_links, r, |e| {
+            &mut e.position
+        });
+        splice(&mut old.tags, new.tags, r, |e| &mut e.position);
+        splice(&mut old.tasks, new.tasks, r, |e| &mut e.position);
+        splice(&mut old.callouts, new.callouts, r, |e| &mut e.position);
+        splice(&mut old.blocks, new.blocks, r, |e| &mut e.position);
+        splice(&mut old.comments, new.comments, r, |e| &mut e.position);
+        splice(&mut old.highlights, new.highlights, r, |e| &mut e.position);
+        splice(
+            &mut old.footnote_references,
+            new.footnote_references,
+            r,
+            |e| &mut e.position,
+        );
+        splice(
+            &mut old.footnote_definitions,
+            new.footnote_definitions,
+            r,
+            |e| &mut e.position,
+        );
+        splice(&mut old.math, new.math, r, |e| &mut e.position);
+
+        let kept = self.boundaries.partition_point(|&b| b <= region.start);
+        let after = self.boundaries.partition_point(|&b| b < region.old_end);
+        let shifted: Vec<usize> = self.boundaries[after..]
+            .iter()
+            .map(|&b| b - region.old_end + region.new_end)
+            .collect();
+        self.boundaries.truncate(kept);
+        self.boundaries.extend(cuts);
+        self.boundaries.extend(shifted);
+
+        Ok(true)
+    }
+
+    /// The note as [`Parser::parse_file`](crate::Parser::parse_file) would
+    /// return it
+    pub fn vault_file(&self) -> VaultFile {
+        let mut vault_file = VaultFile::new(
+            self.path.clone(),
+            self.content.clone(),
+            file_metadata(&self.path, &self.content),
+        );
+        transfer_result(&mut vault_file, self.result.clone());
+        mark_parsed(&mut vault_file);
+        vault_file
+    }
+
+    fn parse_all(&mut self) {
+        self.result = parse_note(&self.path, &self.content);
+        self.index = LineIndex::new(&self.content);
+
+        let body_start = self.result.frontmatter_end_offset;
+        let body = &self.content[body_start..];
+        self.full_only = self.path.extension().and_then(|e| e.to_str()) != Some("md")
+            || excalidraw::is_excalidraw(&self.path, self.result.frontmatter.as_ref())
+            // An unclosed `---` may become frontmatter after any edit
+            || (body_start == 0 && body.starts_with("---"))
+            || has_link_definitions(body);
+
+        self.boundaries = if self.full_only {
+            Vec::new()
+        } else {
+            let spans = element_spans(&mut self.result);
+            let mut boundaries = Vec::new();
+            if starts_block(body) {
+                boundaries.push(body_start);
+            }
+            boundaries.extend(find_boundaries(body, body_start, &spans));
+            boundaries
+        };
+    }
+
+    /// Region to reparse for an edit already applied to `content`, or `None`
+    /// when the whole note must be parsed again
+    fn region(&self, range: &Range<usize>, text: &str, removed: &str) -> Option<Region> {
+        if self.full_only {
+            return None;
+        }
+        let edit_end = range.start + text.len();
+
+        let before = self.boundaries.partition_point(|&b| b <= range.start);
+        let start = self.boundaries[..before]
+            .iter()
+            .rev()
+            .copied()
+            .find(|&b| self.is_cut(b))?;
+
+        let after = self.boundaries.partition_point(|&b| b < range.end);
+        let (old_end, new_end) = self.boundaries[after..]
+            .iter()
+            .map(|&b| (b, b - range.end + edit_end))
+            .find(|&(_, b)| self.is_cut(b))
+            .unwrap_or((
+                self.content.len() - edit_end + range.end,
+                self.content.len(),
+            ));
+
+        Some(Region {
+            start,
+            old_end,
+            new_end,
+            line_delta: text.matches('\n').count() as isize
+                - removed.matches('\n').count() as isize,
+        })
+    }
+
+    /// Whether a region can start at `offset` of the current content, as far
+    /// as the text around it goes
+    fn is_cut(&self, offset: usize) -> bool {
+        let body_start = self.result.frontmatter_end_offset;
+        if offset < body_start || offset >= self.content.len() {
+            return false;
+        }
+        if offset == body_start {
+            return starts_block(&self.content[offset..]);
+        }
+        if self.content.as_bytes()[offset - 1] != b'\n' {
+            return false;
+        }
+        let previous = &self.content[..offset - 1];
+        let previous = &previous[previous.rfind('\n').map_or(0, |i| i + 1)..];
+        previous.trim().is_empty() && starts_block(&self.content[offset..])
+    }
+}
+
+/// Calls `f` on the position of every element of `result`
+fn for_each_position(result: &mut ParseResult, mut f: impl FnMut(&mut SourcePosition)) {
+    result.headings.iter_mut().for_each(|e| f(&mut e.position));
+    result.wikilinks.iter_mut().for_each(|e| f(&mut e.position));
+    result.embeds.iter_mut().for_each(|e| f(&mut e.position));
+    result
+        .markdown_links
+        .iter_mut()
+        .for_each(|e| f(&mut e.position));
+    result.tags.iter_mut().for_each(|e| f(&mut e.position));
+    result.tasks.iter_mut().for_each(|e| f(&mut e.position));
+    result.callouts.iter_mut().for_each(|e| f(&mut e.position));
+    result.blocks.iter_mut().for_each(|e| f(&mut e.position));
+    result.comments.iter_mut().for_each(|e| f(&mut e.position));
+    result
+        .highlights
+        .iter_mut()
+        .for_each(|e| f(&mut e.position));
+    result
+        .footnote_references
+        .iter_mut()
+        .for_each(|e| f(&mut e.position));
+    result
+        .footnote_definitions
+        .iter_mut()
+        .for_each(|e| f(&mut e.position));
+    result.math.iter_mut().for_each(|e| f(&mut e.position));
+}
+
+/// Offsets no region may start at: strictly inside an excluded range or a
+/// parsed element. Takes the excluded ranges out of `result`.
+fn element_spans(result: &mut ParseResult) -> ExcludedRanges {
+    let mut spans = ExcludedRanges::default();
+    for range in std::mem::take(&mut result.excluded) {
+        spans.add(range.start + 1..range.end);
+    }
+    for_each_position(result, |position| {
+        spans.add(position.offset + 1..position.offset + position.length);
+    });
+    spans.optimize();
+    spans
+}
+
+/// Line starts of `text` (at `base` in the note) where a region may start,
+/// the first line excepted
+fn find_boundaries(text: &str, base: usize, spans: &ExcludedRanges) -> Vec<usize> {
+    let mut boundaries = Vec::new();
+    let mut previous_blank = false;
+    let mut link_open = false;
+    let mut local = 0;
+    for line in text.split_inclusive('\n') {
+        let offset = base + local;
+        if local > 0
+            && previous_blank
+            && !link_open
+            && starts_block(line)
+            && !spans.contains(offset)
+        {
+            boundaries.push(offset);
+        }
+        previous_blank = line.trim().is_empty();
+        link_open = link_state(line, link_open);
+        local += line.len();
+    }
+    boundaries
+}
+
+/// Whether a line can open a block that nothing before it continues into:
+/// not indented, and not a list item, quote, table row, HTML, math, comment,
+/// code fence or bracketed line
+fn starts_block(line: &str) -> bool {
+    line.chars()
+        .next()
+        .is_some_and(|c| !c.is_whitespace() && !c.is_ascii_digit() && !"-*+>|[]<$%`~:".contains(c))
+}
+
+/// Whether a `[[` is still waiting for its `]` after `line`. The wikilink
+/// pattern runs across lines, so a region can't end while one is open.
+fn link_state(line: &str, mut open: bool) -> bool {
+    let bytes = line.as_bytes();
+    for (i, &byte) in bytes.iter().enumerate() {
+        if byte == b']' {
+            open = false;
+        } else if byte == b'[' && bytes.get(i + 1) == Some(&b'[') {
+            open = true;
+        }
+    }
+    open
+}
+
+fn link_open_after(text: &str) -> bool {
+    link_state(text, false)
+}
+
+/// Whether `text` holds syntax that depends on, or can reach into, the rest
+/// of the note: footnotes and HTML blocks
+fn crosses_blocks(text: &str) -> bool {
+    text.contains("[^")
+        || text.contains("<!--")
+        || text.lines().any(|line| line.trim_start().starts_with('<'))
+        || has_link_definitions(text)
+}
+
+/// Whether `text` may define link references (`[label]: url`), which
+/// resolve `[text][label]` links anywhere in the note. Footnote definitions
+/// (`[^label]:`) and `[[wikilink]]:` don't count.
+fn has_link_definitions(text: &str) -> bool {
+    text.match_indices("]:").any(|(i, _)| {
+        let before = &text[..i];
+        !before.ends_with(']')
+            && before
+                .rfind('[')
+                .is_some_and(|open| !before[open + 1..].starts_with('^'))
+    })
+}
+
+/// Replace the elements of `items` in the region with `parsed` and shift the
+/// ones after it
+fn splice<T>(
+    items: &mut Vec<T>,
+    parsed: Vec<T>,
+    region: &Region,
+    position: impl Fn(&mut T) -> &mut SourcePosition,
+) {
+    items.retain_mut(|item| {
+        let offset = position(item).offset;
+        offset < region.start || offset >= region.old_end
+    });
+    let at = items
+        .iter_mut()
+        .position(|item| position(item).offset >= region.start)
+        .unwrap_or(items.len());
+    for item in &mut items[at..] {
+        let position = position(item);
+        position.offset = position.offset - region.old_end + region.new_end;
+        position.line = position.line.wrapping_add_signed(region.line_delta);
+    }
+    items.splice(at..at, parsed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::Rng;
+    use serde_json::{Value, json};
+
+    /// Every element of a parse, positions included
+    fn snapshot(result: &ParseResult) -> Value {
+        json!({
+            "frontmatter": result.frontmatter,
+            "frontmatter_end_offset": result.frontmatter_end_offset,
+            "headings": result.headings,
+            "wikilinks": result.wikilinks,
+            "embeds": result.embeds,
+            "markdown_links": result.markdown_links,
+            "tags": result.tags,
+            "tasks": result.tasks,
+            "callouts": result.callouts,
+            "blocks": result.blocks,
+            "comments": result.comments,
+            "highlights": result.highlights,
+            "footnote_references": result.footnote_references,
+            "footnote_definitions": result.footnote_definitions,
+            "math": result.math,
+        })
+    }
+
+    fn assert_matches_full_parse(parse: &IncrementalParse, context: &str) {
+        let full = parse_note(&parse.path, &parse.content);
+        assert_eq!(snapshot(&parse.result), snapshot(&full), "{}", context);
+
+        let index = LineIndex::new(&parse.content);
+        assert_eq!(parse.index.line_count(), index.line_count(), "{}", context);
+        for line in 1..=index.line_count() {
+            assert_eq!(parse.index.line_start(line), index.line_start(line));
+        }
+    }
+
+    /// Prose blocks first: [`document`] picks from them half of the time
+    const PROSE: usize = 4;
+
+    const BLOCKS: &[&str] = &[
+        "# Heading {n}\n",
+        "## Section {n} #tag{n}\n",
+        "Paragraph {n} links [[Note {n}]] and [[Other#Part|alias]] with #topic/{n}.\nSecond line ^block{n}\n",
+        "See [site](https://example.com/{n}) and ![[image{n}.png]] ==marked {n}==.\n",
+        "- [ ] task {n} #todo\n- [x] done {n}\n  - [ ] nested {n}\n",
+        "1. first {n}\n2. second [[Ordered {n}]]\n",
+        "> [!note] Callout {n}\n> body with [[Inside {n}]]\n> > [!tip] nested\n> > deep #deep\n",
+        "```rust\nlet x = \"[[NotALink]] #notag\";\n```\n",
+        "%% hidden [[Hidden {n}]] %%\n",
+        "$$\nx^{n} + [[NoLink]]\n$$\n",
+        "Text with a footnote[^{n}].\n\n[^{n}]: The note {n} #foot\n",
+        "<div>\n[[InHtml]] #html\n</div>\n",
+        "| a | b |\n| - | - |\n| [[Cell {n}]] | #cell |\n",
+        "Inline `[[code]]` and $x_{n}$ math with #tag{n}\n",
+        "Über {n} straße #café [[Ünïcode {n}]]\n",
+    ];
+
+    const TYPING: &[&str] = &[
+        "",
+        "word ",
+        "\n",
+        "\n\n",
+        "[[New]] ",
+        "#fresh ",
+        "==hi== ",
+        "é",
+        "\nPlain paragraph [[Added]] #added\n\n",
+    ];
+
+    /// Half-typed syntax, which can change how later blocks parse
+    const SYNTAX: &[&str] = &[
+        "[[", "]]", "- [ ] ", "> ", "# ", "```\n", "%%", "$$", "    ", "[^9]", "<span>",
+    ];
+
+    fn document(rng: &mut Rng, blocks: usize) -> String {
+        let mut content = String::from("---\ntitle: Test\ntags: [a, b]\n---\n");
+        for n in 0..blocks {
+            let blocks = if rng.below(2) == 0 {
+                &BLOCKS[..PROSE]
+            } else {
+                BLOCKS
+            };
+            content.push_str(&rng.pick(blocks).replace("{n}", &n.to_string()));
+            content.push('\n');
+        }
+        content
+    }
+
+    /// An edit of the body (frontmatter edits always reparse in full)
+    fn random_edit(rng: &mut Rng, content: &str, body_start: usize) -> TextEdit {
+        let mut start = body_start + rng.below(content.len() - body_start + 1);
+        while !content.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = (start + rng.below(40)).min(content.len());
+        if rng.below(3) == 0 {
+            end = start;
+        }
+        while !content.is_char_boundary(end) {
+            end -= 1;
+        }
+        let inserts = if rng.below(8) == 0 { SYNTAX } else { TYPING };
+        TextEdit::new(start..end, rng.pick(inserts))
+    }
+
+    #[test]
+    fn test_randomized_edits_match_full_parse() {
+        let path = Path::new("note.md");
+        let mut incremental_edits = 0;
+        for seed in 1..=12u64 {
+            let mut rng = Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+            let content = document(&mut rng, 40);
+            let mut parse = IncrementalParse::new(path, &content);
+            assert_matches_full_parse(&parse, "initial parse");
+
+            for step in 0..150 {
+                let body_start = parse.result.frontmatter_end_offset;
+                let edit = random_edit(&mut rng, parse.content(), body_start);
+                let before = parse.content().to_string();
+                if parse.apply_edit(&edit).unwrap() {
+                    incremental_edits += 1;
+                }
+                let context = format!("seed {} step {}: {:?} on\n{}", seed, step, edit, before);
+                assert_matches_full_parse(&parse, &context);
+            }
+        }
+        // Stray `%%` and fences make many of these edits reparse in full, but
+        // a good share must still go through the incremental path
+        assert!(incremental_edits > 12 * 150 / 3, "{}", incremental_edits);
+    }
+
+    #[test]
+    fn test_paragraph_edit_reparses_locally() {
+        let content = "# Title\n\nFirst [[A]] #one\n\nSecond [[B]]\n\n## Next\n\nThird #three\n";
+        let mut parse = IncrementalParse::new(Path::new("note.md"), content);
+
+        let at = content.find("Second").unwrap();
+        let edit = TextEdit::new(at..at, "Very\nlong ");
+        assert!(parse.apply_edit(&edit).unwrap());
+        assert_matches_full_parse(&parse, "insert");
+
+        let file = parse.vault_file();
+        let third = file.tags.iter().find(|t| t.name == "three").unwrap();
+        assert_eq!(third.position.line, 10);
+        assert_eq!(file.headings[1].position.line, 8);
+    }
+
+    #[test]
+    fn test_cross_block_edits_fall_back() {
+        let content = "Intro\n\nBody [[A]]\n\nMore #tag\n";
+        let body = content.find("Body").unwrap();
+        let line_end = content.find("]]\n").unwrap() + 2;
+
+        for (at, text) in [
+            (body, "```\n"),
+            (body, "%% "),
+            (line_end, " [["),
+            (body, "[^1] "),
+            (body, "[x]: https://example.com\n"),
+        ] {
+            let mut parse = IncrementalParse::new(Path::new("note.md"), content);
+            assert!(!parse.apply_edit(&TextEdit::new(at..at, text)).unwrap());
+            assert_matches_full_parse(&parse, text);
+        }
+
+        // Frontmatter edits and non-Markdown files always reparse in full
+        let note = "---\ntitle: A\n---\nBody\n\nMore\n";
+        let mut parse = IncrementalParse::new(Path::new("note.md"), note);
+        assert!(!parse.apply_edit(&TextEdit::new(4..9, "name")).unwrap());
+        assert_matches_full_parse(&parse, "frontmatter");
+
+        let mut parse = IncrementalParse::new(Path::new("note.txt"), content);
+        assert!(!parse.apply_edit(&TextEdit::new(body..body, "x")).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_edit_range() {
+        let mut parse = IncrementalParse::new(Path::new("note.md"), "café");
+        assert!(parse.apply_edit(&TextEdit::new(4..4, "x")).is_err());
+        assert!(parse.apply_edit(&TextEdit::new(2..9, "x")).is_err());
+        assert_eq!(parse.content(), "café");
+    }
+
+    #[test]
+    fn test_text_edit_between() {
+        assert_eq!(
+            TextEdit::between("one two three", "one 2 three"),
+            TextEdit::new(4..7, "2")
+        );
+        assert_eq!(TextEdit::between("same", "same"), TextEdit::new(4..4, ""));
+        assert_eq!(TextEdit::between("aaa", "aaaa"), TextEdit::new(3..3, "a"));
+        // Multi-byte characters sharing a leading byte stay whole
+        assert_eq!(TextEdit::between("café", "cafè"), TextEdit::new(3..5, "è"));
+
+        let (old, new) = ("x ü y ü z", "x ü y ö z");
+        let edit = TextEdit::between(old, new);
+        let mut edited = old.to_string();
+        edited.replace_range(edit.range, &edit.text);
+        assert_eq!(edited, new);
+    }
+}
@@ -16,10 +16,11 @@
 //!
 //! ### Phase 1: pulldown-cmark pass
 //! - Extracts CommonMark elements: headings, markdown links, tasks, frontmatter
-//! - Builds excluded ranges (code blocks, inline code, HTML) for Phase 2
+//! - Builds excluded ranges (code blocks, inline code, HTML, math) for Phase 2
 //!
 //! ### Phase 2: Regex pass (OFM extensions)
 //! - Parses Obsidian-specific syntax: wikilinks `[[]]`, embeds `![[]]`, tags `#tag`, callouts
+//! - Finds `%%comments%%` first and adds them to the excluded ranges
 //! - **Skips excluded ranges** to avoid matching inside code blocks
 //!
 //! ### Performance optimizations
@@ -108,6 +109,32 @@
 //! - **Tasks**: Markdown checkboxes with completion status
 //! - **Tags**: Inline tags like `#important`
 //! - **Callouts**: Obsidian callout syntax `> [!TYPE]` with multi-line content
+//! - **Block IDs**: `^block-id` markers targeted by `[[Note#^block-id]]`
+//! - **Comments**: `%%hidden%%`, inline or across lines; links and tags inside
+//!   them are ignored
+//! - **Highlights**: `==text==`
+//! - **Footnotes**: `[^label]` references and `[^label]: text` definitions
+//! - **Math**: `$inline$` and `$$display$$`; links and tags inside are ignored
+//!
+//! ### Excalidraw
+//! Drawings (`*.excalidraw.md` or `excalidraw-plugin` frontmatter) are parsed
+//! without their scene payload: links come from text elements, element links
+//! and embedded files (as embeds). See [`excalidraw`].
+//!
+//! ### Canvas
+//! `.canvas` files are parsed as [JSON Canvas](https://jsoncanvas.org/) with
+//! [`Canvas`]: file nodes become embeds of their file, and links and tags in
+//! text nodes are extracted like in notes.
+//!
+//! ### Bases
+//! `.base` files (Obsidian Bases) are YAML view definitions; [`Base`] models
+//! their filters, formulas, properties and views.
+//!
+//! ### Incremental reparsing
+//! [`IncrementalParse`] keeps a note's parse results between edits and, for
+//! an edit that stays within a few top-level blocks, reparses only those
+//! blocks and shifts the positions of the elements after them. See
+//! [`incremental`].
 //!
 //! ## Performance
 //!
@@ -119,11 +146,21 @@
 //! - Excluded range tracking to avoid parsing inside code blocks
 
 // Core modules
+pub mod bases;
 mod blocks;
+pub mod canvas;
+mod document;
 mod engine;
+pub mod excalidraw;
+pub mod incremental;
 pub mod parsers;
+mod render;
 mod standalone;
 
+#[cfg(test)]
+#[path = "../tests/support/rng.rs"]
+mod test_rng;
+
 // Main exports
 pub use parsers::Parser;
 pub use standalone::{ParseOptions, ParsedContent};
@@ -135,6 +172,19 @@ pub use parsers::frontmatter_parser::extract_frontmatter;
 // Block-level parsing (for treemd integration)
 pub use blocks::{parse_blocks, parse_blocks_from_line, slugify, to_plain_text};
 
+// Block model back to Markdown (canonical, or lossless for untouched blocks)
+pub use document::MarkdownDocument;
+pub use render::{render_block, render_blocks, render_inline};
+
+// JSON Canvas documents
+pub use canvas::{Canvas, CanvasEdge, CanvasEnd, CanvasNode, CanvasNodeType, CanvasSide};
+
+// Obsidian Bases view definitions
+pub use bases::{Base, BaseFilter, BaseSort, BaseView, SortDirection};
+
+// Reparsing only the blocks an edit touches
+pub use incremental::{IncrementalParse, TextEdit};
+
 // Re-export core types for consumers (no need to depend on turbovault-core separately)
 pub use turbovault_core::{
     ContentBlock, InlineElement, LineIndex, LinkType, ListItem, SourcePosition, TableAlignment,
@@ -300,27 +350,64 @@ pub fn parse_callouts_full(content: &str) -> Vec<turbovault_core::Callout> {
     engine.parse(&opts).callouts
 }
 
+/// Parse block IDs (`^block-id` at the end of a line) from content.
+///
+/// # Example
+/// ```
+/// use turbovault_parser::parse_block_ids;
+///
+/// let blocks = parse_block_ids("A paragraph ^intro\n- item ^first");
+/// assert_eq!(blocks.len(), 2);
+/// assert_eq!(blocks[0].block_id.as_deref(), Some("intro"));
+/// ```
+pub fn parse_block_ids(content: &str) -> Vec<turbovault_core::Block> {
+    let engine = engine::ParseEngine::new(content);
+    let opts = ParseOptions {
+        parse_block_ids: true,
+        ..ParseOptions::none()
+    };
+    engine.parse(&opts).blocks
+}
+
+/// Byte ranges where Obsidian syntax is not interpreted: frontmatter, code
+/// blocks, inline code, HTML, math and `%%comments%%`. Sorted and merged.
+///
+/// # Example
+/// ```
+/// use turbovault_parser::excluded_ranges;
+///
+/// let content = "---\ntitle: x\n---\nSee `code` here %%hidden%%";
+/// let ranges = excluded_ranges(content);
+/// assert_eq!(ranges.len(), 3);
+/// assert_eq!(&content[ranges[1].clone()], "`code`");
+/// assert_eq!(&content[ranges[2].clone()], "%%hidden%%");
+/// ```
+pub fn excluded_ranges(content: &str) -> Vec<std::ops::Range<usize>> {
+    engine::ParseEngine::new(content).excluded_ranges()
+}
+
 /// Convenient prelude for common imports.
 ///
 /// Includes core types, the main parser, standalone parsing API, and all parser functions.
 pub mod prelude {
     // Core types from turbovault-core
     pub use turbovault_core::{
-        Callout, CalloutType, ContentBlock, Frontmatter, Heading, InlineElement, LineIndex, Link,
-        LinkType, ListItem, SourcePosition, TableAlignment, Tag, TaskItem,
+        Callout, CalloutType, Comment, ContentBlock, FootnoteDefinition, FootnoteReference,
+        Frontmatter, Heading, Highlight, InlineElement, LineIndex, Link, LinkType, ListItem, Math,
+        SourcePosition, TableAlignment, Tag, TaskItem,
     };
 
     // Main parser
     pub use crate::Parser;
 
     // Standalone parsing API
-    pub use crate::{ParseOptions, ParsedContent};
+    pub use crate::{MarkdownDocument, ParseOptions, ParsedContent};
 
     // Individual parsers
     #[allow(deprecated)]
     pub use crate::{
-        extract_frontmatter, parse_blocks, parse_blocks_from_line, parse_callouts,
+        extract_frontmatter, parse_block_ids, parse_blocks, parse_blocks_from_line, parse_callouts,
         parse_callouts_full, parse_embeds, parse_headings, parse_markdown_links, parse_tags,
-        parse_tasks, parse_wikilinks, slugify, to_plain_text,
+        parse_tasks, parse_wikilinks, render_blocks, slugify, to_plain_text,
     };
 }
@@ -1,10 +1,12 @@
 //! OFM parser implementation using unified ParseEngine.
 
 use std::path::{Path, PathBuf};
-use turbovault_core::{FileMetadata, Frontmatter, Result, SourcePosition, VaultFile};
+use turbovault_core::{FileMetadata, Frontmatter, LinkType, Result, SourcePosition, VaultFile};
 
 use crate::ParseOptions;
-use crate::engine::ParseEngine;
+use crate::canvas::Canvas;
+use crate::engine::{ParseEngine, ParseResult};
+use crate::excalidraw;
 
 // Individual parser modules are still available for backwards compatibility
 // and granular use cases, but the main Parser uses the unified engine.
@@ -41,74 +43,58 @@ impl Parser {
 
     /// Parse a file from path and content.
     pub fn parse_file(&self, path: &Path, content: &str) -> Result<VaultFile> {
-        let metadata = self.extract_metadata(path, content)?;
-        let mut vault_file = VaultFile::new(path.to_path_buf(), content.to_string(), metadata);
-
-        // Parse content if markdown
-        if path.extension().is_some_and(|ext| ext == "md") {
-            self.parse_content(&mut vault_file)?;
-            vault_file.is_parsed = true;
-            vault_file.last_parsed = Some(
-                std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_secs_f64(),
-            );
+        let mut vault_file = VaultFile::new(
+            path.to_path_buf(),
+            content.to_string(),
+            file_metadata(path, content),
+        );
+
+        // Parse content if markdown or JSON Canvas
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+        let parsed = match extension.as_deref() {
+            Some("md") => {
+                self.parse_content(&mut vault_file)?;
+                true
+            }
+            Some("canvas") => self.parse_canvas(&mut vault_file),
+            _ => false,
+        };
+        if parsed {
+            mark_parsed(&mut vault_file);
         }
 
         Ok(vault_file)
     }
 
-    fn extract_metadata(&self, path: &Path, content: &str) -> Result<FileMetadata> {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let size = content.len() as u64;
-        let mut hasher = DefaultHasher::new();
-        content.hash(&mut hasher);
-        let checksum = format!("{:x}", hasher.finish());
-
-        Ok(FileMetadata {
-            path: path.to_path_buf(),
-            size,
-            created_at: 0.0,
-            modified_at: 0.0,
-            checksum,
-            is_attachment: !matches!(
-                path.extension().map(|e| e.to_str()),
-                Some(Some("md" | "txt"))
-            ),
-        })
-    }
-
     /// Parse all content elements from file using unified engine.
     fn parse_content(&self, vault_file: &mut VaultFile) -> Result<()> {
-        let content = &vault_file.content;
-
-        // Use ParseEngine with source file for vault-aware parsing
-        let engine = ParseEngine::with_source_file(content, &vault_file.path);
-        let result = engine.parse(&ParseOptions::all());
-
-        // Transfer results to VaultFile
-        vault_file.frontmatter = result.frontmatter;
+        let result = parse_note(&vault_file.path, &vault_file.content);
+        transfer_result(vault_file, result);
+        Ok(())
+    }
 
-        // Strip frontmatter using pulldown-cmark's byte offset (avoids redundant regex parse)
-        if result.frontmatter_end_offset > 0 {
-            vault_file.content = content[result.frontmatter_end_offset..].to_string();
+    /// Take links and tags from a canvas's nodes; an invalid canvas is kept
+    /// unparsed with its `parse_error` set.
+    fn parse_canvas(&self, vault_file: &mut VaultFile) -> bool {
+        match Canvas::parse(&vault_file.content) {
+            Ok(canvas) => {
+                vault_file.links = canvas.links(&vault_file.path, &vault_file.content);
+                vault_file.tags = canvas.tags(&vault_file.content);
+                true
+            }
+            Err(e) => {
+                log::warn!(
+                    "Failed to parse canvas {}: {}",
+                    vault_file.path.display(),
+                    e
+                );
+                vault_file.parse_error = Some(e.to_string());
+                false
+            }
         }
-
-        // Links (wikilinks, embeds, markdown links)
-        vault_file.links.extend(result.wikilinks);
-        vault_file.links.extend(result.embeds);
-        vault_file.links.extend(result.markdown_links);
-
-        // Other elements
-        vault_file.tags.extend(result.tags);
-        vault_file.tasks.extend(result.tasks);
-        vault_file.callouts.extend(result.callouts);
-        vault_file.headings.extend(result.headings);
-
-        Ok(())
     }
 
     /// Parse frontmatter from YAML string.
@@ -128,6 +114,90 @@ impl Parser {
     }
 }
 
+/// Run the engine over a note, handling Excalidraw drawings.
+pub(crate) fn parse_note(path: &Path, content: &str) -> ParseResult {
+    // Use ParseEngine with source file for vault-aware parsing
+    let engine = ParseEngine::with_source_file(content, path);
+    let mut result = engine.parse(&ParseOptions::all());
+
+    // Excalidraw drawings: skip the scene payload, keep the links the
+    // plugin writes for Obsidian, and treat embedded files as embeds
+    if excalidraw::is_excalidraw(path, result.frontmatter.as_ref()) {
+        let masked = excalidraw::mask_scene(content);
+        let frontmatter = result.frontmatter.take();
+        result = ParseEngine::with_source_file(&masked, path).parse(&ParseOptions::all());
+        result.frontmatter = frontmatter;
+        // "Excalidraw Data", "Text Elements", ... structure the file, not the note
+        result.headings.clear();
+
+        if let Some(embedded) = excalidraw::embedded_files_range(content) {
+            for link in &mut result.wikilinks {
+                if embedded.contains(&link.position.offset) {
+                    link.type_ = LinkType::Embed;
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Size, checksum and attachment flag of a file's content.
+pub(crate) fn file_metadata(path: &Path, content: &str) -> FileMetadata {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let size = content.len() as u64;
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    let checksum = format!("{:x}", hasher.finish());
+
+    FileMetadata {
+        path: path.to_path_buf(),
+        size,
+        created_at: 0.0,
+        modified_at: 0.0,
+        checksum,
+        is_attachment: !matches!(
+            path.extension().map(|e| e.to_str()),
+            Some(Some("md" | "txt"))
+        ),
+    }
+}
+
+/// Move a note's parse results into its `VaultFile`, whose `content` is
+/// still the full file.
+pub(crate) fn transfer_result(vault_file: &mut VaultFile, result: ParseResult) {
+    vault_file.frontmatter = result.frontmatter;
+
+    // Strip frontmatter using pulldown-cmark's byte offset (avoids redundant regex parse)
+    if result.frontmatter_end_offset > 0 {
+        vault_file.content = vault_file.content[result.frontmatter_end_offset..].to_string();
+    }
+
+    // Links (wikilinks, embeds, markdown links)
+    vault_file.links.extend(result.wikilinks);
+    vault_file.links.extend(result.embeds);
+    vault_file.links.extend(result.markdown_links);
+
+    // Other elements
+    vault_file.tags.extend(result.tags);
+    vault_file.tasks.extend(result.tasks);
+    vault_file.callouts.extend(result.callouts);
+    vault_file.headings.extend(result.headings);
+    vault_file.blocks.extend(result.blocks);
+}
+
+/// Flag a file as parsed now.
+pub(crate) fn mark_parsed(vault_file: &mut VaultFile) {
+    vault_file.is_parsed = true;
+    vault_file.last_parsed = Some(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64(),
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,6 +235,20 @@ title: Test
         assert_eq!(result.callouts.len(), 1);
     }
 
+    #[test]
+    fn test_has_unicode_tag() {
+        let parser = Parser::new(PathBuf::from("/vault"));
+        let content = "---\ntags: [Überblick]\n---\nNotes on #café and #日本語/文法";
+        let result = parser
+            .parse_file(&PathBuf::from("test.md"), content)
+            .unwrap();
+
+        assert!(result.has_tag("Überblick"));
+        assert!(result.has_tag("café"));
+        assert!(result.has_tag("日本語/文法"));
+        assert!(!result.has_tag("cafe"));
+    }
+
     #[test]
     fn test_parse_file_non_markdown() {
         let parser = Parser::new(PathBuf::from("/vault"));
@@ -176,4 +260,48 @@ title: Test
         // .txt files are not parsed for OFM elements
         assert!(!result.is_parsed);
     }
+
+    #[test]
+    fn test_parse_file_excalidraw() {
+        let parser = Parser::new(PathBuf::from("/vault"));
+        let content = "---\nexcalidraw-plugin: parsed\ntags: [excalidraw]\n---\n# Excalidraw Data\n\n## Text Elements\nSee [[Roadmap]] ^x9Y8z7W6\n\n## Embedded Files\n0f1e2d3c: [[Spec]]\n\n%%\n## Drawing\n```json\n{\"strokeColor\":\"#1e1e1e\",\"link\":\"[[Hidden]]\"}\n```\n%%";
+        let result = parser
+            .parse_file(&PathBuf::from("Board.md"), content)
+            .unwrap();
+
+        let links: Vec<(&LinkType, &str)> = result
+            .links
+            .iter()
+            .map(|l| (&l.type_, l.target.as_str()))
+            .collect();
+        assert_eq!(
+            links,
+            vec![(&LinkType::WikiLink, "Roadmap"), (&LinkType::Embed, "Spec")]
+        );
+        assert!(result.tags.is_empty());
+        assert!(result.headings.is_empty());
+        assert!(result.has_tag("excalidraw"));
+    }
+
+    #[test]
+    fn test_parse_file_canvas() {
+        let parser = Parser::new(PathBuf::from("/vault"));
+        let content = r#"{"nodes":[
+            {"id":"a","type":"text","text":"See [[Plan]] #todo","x":0,"y":0,"width":100,"height":50},
+            {"id":"b","type":"file","file":"Spec.md","x":200,"y":0,"width":100,"height":50}
+        ],"edges":[]}"#;
+        let result = parser
+            .parse_file(&PathBuf::from("board.canvas"), content)
+            .unwrap();
+        assert!(result.is_parsed);
+        assert_eq!(result.links.len(), 2);
+        assert!(result.has_tag("todo"));
+
+        let result = parser
+            .parse_file(&PathBuf::from("broken.canvas"), "[[Plan]]")
+            .unwrap();
+        assert!(!result.is_parsed);
+        assert!(result.links.is_empty());
+        assert!(result.parse_error.is_some());
+    }
 }
@@ -1,7 +1,7 @@
 //! Callout parser: > `[!NOTE]`, > `[!TIP]`, etc.
 //!
 //! Supports Obsidian callout syntax with:
-//! - 13 standard types: note, tip, info, todo, important, success, question, warning, failure, danger, bug, example, quote
+//! - Obsidian's built-in types and aliases (see [`CalloutType::from_identifier`])
 //! - Custom callout types (any unrecognized type is preserved as Custom)
 //! - Foldable callouts with `+` or `-` markers
 //! - Multi-line content continuation
@@ -15,7 +15,7 @@ use turbovault_core::{Callout, CalloutType, LineIndex, SourcePosition};
 
 /// Matches > [!TYPE] callout start
 static CALLOUT_PATTERN: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^\s*>\s*\[!(\w+)\]([+-]?)\s*(.*?)$").unwrap());
+    LazyLock::new(|| Regex::new(r"^\s*>\s*\[!([\w-]+)\]([+-]?)\s*(.*?)$").unwrap());
 
 /// Matches continuation lines (start with >)
 static CONTINUATION_PATTERN: LazyLock<Regex> =
@@ -27,26 +27,6 @@ fn has_callout(content: &str) -> bool {
     content.contains("[!")
 }
 
-/// Parse callout type string into CalloutType enum.
-fn parse_callout_type(type_str: &str) -> CalloutType {
-    match type_str.to_lowercase().as_str() {
-        "note" => CalloutType::Note,
-        "tip" => CalloutType::Tip,
-        "info" => CalloutType::Info,
-        "todo" => CalloutType::Todo,
-        "important" => CalloutType::Important,
-        "success" => CalloutType::Success,
-        "question" => CalloutType::Question,
-        "warning" => CalloutType::Warning,
-        "failure" | "fail" | "missing" => CalloutType::Failure,
-        "danger" | "error" => CalloutType::Danger,
-        "bug" => CalloutType::Bug,
-        "example" => CalloutType::Example,
-        "quote" | "cite" => CalloutType::Quote,
-        _ => CalloutType::Note, // Default to Note for unknown types
-    }
-}
-
 /// Parse all callouts from content (simple, single-line parsing).
 ///
 /// **Deprecated**: Use `turbovault_parser::parse_callouts()` instead.
@@ -69,7 +49,7 @@ pub fn parse_callouts(content: &str) -> Vec<Callout> {
 
             CALLOUT_PATTERN.captures(line).map(|caps| {
                 let type_str = caps.get(1).unwrap().as_str();
-                let type_ = parse_callout_type(type_str);
+                let type_ = CalloutType::from_identifier(type_str);
 
                 let fold_marker = caps.get(2).unwrap().as_str();
                 let is_foldable = !fold_marker.is_empty();
@@ -83,10 +63,13 @@ pub fn parse_callouts(content: &str) -> Vec<Callout> {
 
                 Callout {
                     type_,
+                    identifier: type_str.to_string(),
                     title,
                     content: String::new(),
                     position: SourcePosition::new(idx + 1, 1, line_start, line.len()),
                     is_foldable,
+                    is_collapsed: fold_marker == "-",
+                    depth: 1,
                 }
             })
         })
@@ -133,7 +116,7 @@ pub fn parse_callouts_full(content: &str) -> Vec<Callout> {
         if let Some(caps) = CALLOUT_PATTERN.captures(lines[i]) {
             let start_line = i;
             let type_str = caps.get(1).unwrap().as_str();
-            let type_ = parse_callout_type(type_str);
+            let type_ = CalloutType::from_identifier(type_str);
 
             let fold_marker = caps.get(2).unwrap().as_str();
             let is_foldable = !fold_marker.is_empty();
@@ -171,10 +154,13 @@ pub fn parse_callouts_full(content: &str) -> Vec<Callout> {
             let offset = line_offsets.get(start_line).copied().unwrap_or(0);
             callouts.push(Callout {
                 type_,
+                identifier: type_str.to_string(),
                 title,
                 content: callout_content,
                 position: SourcePosition::new(start_line + 1, 1, offset, lines[start_line].len()),
                 is_foldable,
+                is_collapsed: fold_marker == "-",
+                depth: 1,
             });
         } else {
             i += 1;
@@ -227,7 +213,7 @@ mod tests {
             ("TIP", CalloutType::Tip),
             ("INFO", CalloutType::Info),
             ("TODO", CalloutType::Todo),
-            ("IMPORTANT", CalloutType::Important),
+            ("IMPORTANT", CalloutType::Tip),
             ("SUCCESS", CalloutType::Success),
             ("QUESTION", CalloutType::Question),
             ("WARNING", CalloutType::Warning),
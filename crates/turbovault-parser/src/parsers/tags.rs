@@ -3,12 +3,14 @@
 //! **Deprecated**: Use `turbovault_parser::parse_tags()` or `ParsedContent::parse()` instead.
 //! These functions are kept for backwards compatibility but will be removed in a future version.
 
+use crate::engine::{TAG_CHARS, is_tag_name};
 use regex::Regex;
 use std::sync::LazyLock;
 use turbovault_core::{LineIndex, SourcePosition, Tag};
 
 /// Matches #tag or #parent/child tags
-static TAG_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"#([a-zA-Z0-9_\-/]+)").unwrap());
+static TAG_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!("#([{}]+)", TAG_CHARS)).unwrap());
 
 /// Parse all tags from content.
 ///
@@ -17,6 +19,7 @@ static TAG_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"#([a-zA-Z0-9
 pub fn parse_tags(content: &str) -> Vec<Tag> {
     TAG_PATTERN
         .captures_iter(content)
+        .filter(|caps| is_tag_name(&caps[1]))
         .map(|caps| {
             let full_match = caps.get(0).unwrap();
             let name = caps.get(1).unwrap().as_str();
@@ -42,6 +45,7 @@ pub fn parse_tags(content: &str) -> Vec<Tag> {
 pub fn parse_tags_indexed(content: &str, index: &LineIndex) -> Vec<Tag> {
     TAG_PATTERN
         .captures_iter(content)
+        .filter(|caps| is_tag_name(&caps[1]))
         .map(|caps| {
             let full_match = caps.get(0).unwrap();
             let name = caps.get(1).unwrap().as_str();
@@ -90,6 +94,15 @@ mod tests {
         assert_eq!(tags.len(), 3);
     }
 
+    #[test]
+    fn test_numeric_only_is_not_tag() {
+        let content = "Issue #1984 and #2024q1";
+        let index = LineIndex::new(content);
+        let names: Vec<_> = parse_tags(content).into_iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["2024q1"]);
+        assert_eq!(parse_tags_indexed(content, &index).len(), 1);
+    }
+
     #[test]
     fn test_tag_position_tracking() {
         let content = "First line\nSecond #tag here";
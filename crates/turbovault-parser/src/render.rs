@@ -0,0 +1,662 @@
+//! Canonical Markdown rendering of the block model.
+//!
+//! [`render_blocks`] turns `ContentBlock` trees back into Obsidian Flavored
+//! Markdown: wikilinks and embeds come back as `[[...]]`, highlights as
+//! `==...==`, comments as `%%...%%`, and text is escaped where it would
+//! otherwise be read as markup. Formatting is canonical (`-` bullets, `**`
+//! strong, fenced code, `---` rules), so use
+//! [`MarkdownDocument`](crate::MarkdownDocument) to keep untouched blocks
+//! exactly as written.
+
+use turbovault_core::{ContentBlock, InlineElement, ListItem, TableAlignment};
+
+use crate::blocks::{parse_blocks, restore_wikilinks};
+
+/// Prefix of link URLs standing for wikilinks in the block model
+const WIKILINK_PREFIX: &str = "wikilink:";
+
+/// Render blocks as Markdown, separated by blank lines.
+///
+/// # Example
+///
+/// ```
+/// use turbovault_parser::{parse_blocks, render_blocks};
+///
+/// let blocks = parse_blocks("# Title\n\nSee [[Note|the note]] and ==this==.");
+/// assert_eq!(
+///     render_blocks(&blocks),
+///     "# Title\n\nSee [[Note|the note]] and ==this==.\n"
+/// );
+/// ```
+pub fn render_blocks(blocks: &[ContentBlock]) -> String {
+    blocks
+        .iter()
+        .map(render_block)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render one block as Markdown, ending with a newline.
+pub fn render_block(block: &ContentBlock) -> String {
+    match block {
+        ContentBlock::Heading {
+            level,
+            content,
+            inline,
+            ..
+        } => {
+            let text = if inline.is_empty() {
+                escape_text(content)
+            } else {
+                render_inline(inline)
+            };
+            format!("{} {}\n", "#".repeat((*level).clamp(1, 6)), text.trim())
+        }
+        ContentBlock::Paragraph {
+            content, inline, ..
+        } => {
+            let text = if inline.is_empty() {
+                restore_wikilinks(content)
+            } else {
+                render_inline(inline)
+            };
+            format!("{}\n", escape_block_start(text.trim()))
+        }
+        ContentBlock::Code {
+            language, content, ..
+        } => {
+            let fence = "`".repeat(longest_run(content, '`').max(2) + 1);
+            format!(
+                "{}{}\n{}\n{}\n",
+                fence,
+                language.as_deref().unwrap_or_default(),
+                content,
+                fence
+            )
+        }
+        ContentBlock::List { ordered, items, .. } => render_list(*ordered, items, |_, _| None),
+        ContentBlock::Blockquote {
+            content, blocks, ..
+        } => {
+            let inner = if blocks.is_empty() || parses_to(content, blocks) {
+                content.trim_end().to_string()
+            } else {
+                render_blocks(blocks).trim_end().to_string()
+            };
+            prefix_lines(&inner, "> ", ">")
+        }
+        ContentBlock::Table {
+            headers,
+            alignments,
+            rows,
+            ..
+        } => render_table(headers, alignments, rows, |_, _, _| None),
+        ContentBlock::Image {
+            alt, src, title, ..
+        } => {
+            format!("{}\n", render_image(alt, src, title.as_deref()))
+        }
+        ContentBlock::HorizontalRule { .. } => "---\n".to_string(),
+        ContentBlock::Details {
+            summary,
+            content,
+            blocks,
+            ..
+        } => {
+            let inner = if blocks.is_empty() || parses_to(content, blocks) {
+                content.trim().to_string()
+            } else {
+                render_blocks(blocks).trim_end().to_string()
+            };
+            format!(
+                "<details>\n<summary>{}</summary>\n\n{}\n</details>\n",
+                summary, inner
+            )
+        }
+        ContentBlock::Math { content, .. } => format!("$$\n{}\n$$\n", content.trim()),
+        ContentBlock::FootnoteDefinition { label, blocks, .. } => {
+            let body = render_blocks(blocks);
+            let mut lines = body.trim_end().lines();
+            let mut out = format!("[^{}]: {}\n", label, lines.next().unwrap_or_default());
+            for line in lines {
+                if line.is_empty() {
+                    out.push('\n');
+                } else {
+                    out.push_str(&format!("    {}\n", line));
+                }
+            }
+            out
+        }
+        ContentBlock::Comment { content, .. } => {
+            if content.contains('\n') {
+                format!("%%\n{}\n%%\n", content.trim())
+            } else {
+                format!("%%{}%%\n", content.trim())
+            }
+        }
+    }
+}
+
+/// Render a changed block, keeping the source of the parts it shares with
+/// `original`, the block parsed from `source` at the same span.
+///
+/// List items unchanged apart from their checkbox are copied from the
+/// source, nested items and all, and table cells with the same text keep
+/// their markup. Everything else is rendered as by [`render_block`].
+pub(crate) fn render_block_over(
+    block: &ContentBlock,
+    original: &ContentBlock,
+    source: &str,
+) -> String {
+    match (block, original) {
+        (
+            ContentBlock::List { ordered, items, .. },
+            ContentBlock::List {
+                items: originals, ..
+            },
+        ) => render_list(*ordered, items, |marker, item| {
+            source_list_item(marker, item, originals, source)
+        }),
+        (
+            ContentBlock::Table {
+                headers,
+                alignments,
+                rows,
+                ..
+            },
+            ContentBlock::Table {
+                headers: original_headers,
+                rows: original_rows,
+                span: Some(span),
+                ..
+            },
+        ) => {
+            let plain: Vec<&Vec<String>> = std::iter::once(original_headers)
+                .chain(original_rows)
+                .collect();
+            let written = source
+                .get(span.range())
+                .map(source_cells)
+                .unwrap_or_default();
+            let written_at = |row: usize, column: usize, text: &str| {
+                let cell = plain.get(row)?.get(column)?;
+                (cell == text).then(|| written.get(row)?.get(column).copied())?
+            };
+            render_table(headers, alignments, rows, |row, column, text| {
+                written_at(row, column, text)
+                    .or_else(|| {
+                        (0..plain.len())
+                            .find_map(|r| (0..plain[r].len()).find_map(|c| written_at(r, c, text)))
+                    })
+                    .map(str::to_string)
+            })
+        }
+        _ => render_block(block),
+    }
+}
+
+/// Render inline elements as Markdown.
+///
+/// # Example
+///
+/// ```
+/// use turbovault_parser::{InlineElement, render_inline};
+///
+/// let inline = vec![
+///     InlineElement::Text { value: "Use ".to_string(), span: None },
+///     InlineElement::Code { value: "cargo".to_string(), span: None },
+///     InlineElement::Text { value: " *now*".to_string(), span: None },
+/// ];
+/// assert_eq!(render_inline(&inline), "Use `cargo` \\*now\\*");
+/// ```
+pub fn render_inline(inline: &[InlineElement]) -> String {
+    inline.iter().map(render_inline_element).collect()
+}
+
+fn render_inline_element(element: &InlineElement) -> String {
+    match element {
+        // A text node that is only a line break comes from a hard break
+        InlineElement::Text { value, .. } if value == "\n" => "\\\n".to_string(),
+        InlineElement::Text { value, .. } => escape_text(value),
+        InlineElement::Strong { value, .. } => format!("**{}**", escape_text(value)),
+        InlineElement::Emphasis { value, .. } => format!("*{}*", escape_text(value)),
+        InlineElement::Strikethrough { value, .. } => format!("~~{}~~", escape_text(value)),
+        InlineElement::Highlight { value, .. } => format!("=={}==", escape_text(value)),
+        InlineElement::Code { value, .. } => {
+            let ticks = "`".repeat(longest_run(value, '`') + 1);
+            if value.starts_with('`') || value.ends_with('`') {
+                format!("{} {} {}", ticks, value, ticks)
+            } else {
+                format!("{}{}{}", ticks, value, ticks)
+            }
+        }
+        InlineElement::Link {
+            text, url, title, ..
+        } => match url.strip_prefix(WIKILINK_PREFIX) {
+            Some(target) if text == target || text.is_empty() => format!("[[{}]]", target),
+            Some(target) => format!("[[{}|{}]]", target, text),
+            None => format!(
+                "[{}]({}{})",
+                escape_text(text),
+                link_destination(url),
+                link_title(title.as_deref())
+            ),
+        },
+        InlineElement::Image {
+            alt, src, title, ..
+        } => render_image(alt, src, title.as_deref()),
+        InlineElement::Math { value, display, .. } => {
+            if *display {
+                format!("$${}$$", value)
+            } else {
+                format!("${}$", value)
+            }
+        }
+        InlineElement::FootnoteReference { label, .. } => format!("[^{}]", label),
+        InlineElement::Comment { value, .. } => format!("%%{}%%", value),
+    }
+}
+
+/// Whether `content` parses to `blocks`, wherever the blocks were parsed from
+fn parses_to(content: &str, blocks: &[ContentBlock]) -> bool {
+    let mut parsed = parse_blocks(content);
+    let mut blocks = blocks.to_vec();
+    parsed.iter_mut().for_each(ContentBlock::clear_spans);
+    blocks.iter_mut().for_each(ContentBlock::clear_spans);
+    parsed == blocks
+}
+
+fn render_image(alt: &str, src: &str, title: Option<&str>) -> String {
+    match src.strip_prefix(WIKILINK_PREFIX) {
+        Some(target) if alt == target || alt.is_empty() => format!("![[{}]]", target),
+        Some(target) => format!("![[{}|{}]]", target, alt),
+        None => format!(
+            "![{}]({}{})",
+            escape_text(alt),
+            link_destination(src),
+            link_title(title)
+        ),
+    }
+}
+
+fn link_destination(url: &str) -> String {
+    if url.contains(' ') || url.contains('(') || url.contains(')') {
+        format!("<{}>", url)
+    } else {
+        url.to_string()
+    }
+}
+
+fn link_title(title: Option<&str>) -> String {
+    match title {
+        Some(title) if !title.is_empty() => format!(" \"{}\"", title.replace('"', "\\\"")),
+        _ => String::new(),
+    }
+}
+
+/// Render list items with canonical markers; `written` may supply an item
+/// as written instead
+fn render_list(
+    ordered: bool,
+    items: &[ListItem],
+    written: impl Fn(&str, &ListItem) -> Option<String>,
+) -> String {
+    items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let marker = if ordered {
+                format!("{}. ", idx + 1)
+            } else {
+                "- ".to_string()
+            };
+            written(&marker, item).unwrap_or_else(|| render_list_item(&marker, item))
+        })
+        .collect()
+}
+
+/// An item of `originals` as written in `source`, under a new marker, when
+/// at most its checkbox changed
+fn source_list_item(
+    marker: &str,
+    item: &ListItem,
+    originals: &[ListItem],
+    source: &str,
+) -> Option<String> {
+    let span = item.span?;
+    let original = originals.iter().find(|o| o.span == Some(span))?;
+    if original.content != item.content
+        || original.inline != item.inline
+        || original.blocks != item.blocks
+        || original.checked.is_some() != item.checked.is_some()
+    {
+        return None;
+    }
+
+    let mut lines = source.get(span.range())?.trim_end().lines();
+    let first = lines.next()?;
+    let rest = strip_list_marker(first.trim_start())?;
+    // Continuation lines are indented to the text after the original marker
+    let width = span.column - 1 + first.len() - rest.len();
+    let rest = match (item.checked, original.checked) {
+        (Some(checked), Some(was)) if checked != was && rest.len() >= 3 => {
+            format!("[{}]{}", if checked { 'x' } else { ' ' }, &rest[3..])
+        }
+        _ => rest.to_string(),
+    };
+
+    let indent = " ".repeat(marker.len());
+    let mut out = format!("{}{}\n", marker, rest);
+    for line in lines {
+        if line.trim().is_empty() {
+            out.push('\n');
+        } else {
+            let spaces = line.len() - line.trim_start_matches(' ').len();
+            out.push_str(&format!("{}{}\n", indent, &line[spaces.min(width)..]));
+        }
+    }
+    Some(out)
+}
+
+/// Text after a list marker (`-`, `*`, `+`, `1.` or `1)`) and its spacing
+fn strip_list_marker(line: &str) -> Option<&str> {
+    let rest = match line.strip_prefix(['-', '*', '+']) {
+        Some(rest) => rest,
+        None => {
+            let digits = line.chars().take_while(char::is_ascii_digit).count();
+            line.get(digits..)
+                .filter(|_| digits > 0)?
+                .strip_prefix(['.', ')'])?
+        }
+    };
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    Some(rest.trim_start_matches([' ', '\t']))
+}
+
+/// Render a list item with its marker; nested items recorded as indented
+/// lines of `content` come back as nested bullets
+fn render_list_item(marker: &str, item: &ListItem) -> String {
+    let task = match item.checked {
+        Some(true) => "[x] ",
+        Some(false) => "[ ] ",
+        None => "",
+    };
+    let indent = " ".repeat(marker.len());
+
+    let mut lines = item.content.lines();
+    let first = if item.content.contains('\n') || item.inline.is_empty() {
+        restore_wikilinks(lines.next().unwrap_or_default())
+    } else {
+        lines.next();
+        render_inline(&item.inline)
+    };
+    let mut out = format!("{}{}{}\n", marker, task, first.trim());
+
+    for line in lines {
+        let trimmed = line.trim_start();
+        let depth = (line.len() - trimmed.len()) / 2;
+        if depth == 0 {
+            out.push_str(&format!("{}{}\n", indent, restore_wikilinks(trimmed)));
+        } else {
+            out.push_str(&format!(
+                "{}{}- {}\n",
+                indent,
+                "  ".repeat(depth - 1),
+                restore_wikilinks(trimmed)
+            ));
+        }
+    }
+
+    for block in &item.blocks {
+        out.push('\n');
+        out.push_str(&prefix_lines(render_block(block).trim_end(), &indent, ""));
+    }
+    out
+}
+
+/// Render a table; `written` may supply a cell's markup as written, given
+/// its row (0 for the header), column and text
+fn render_table(
+    headers: &[String],
+    alignments: &[TableAlignment],
+    rows: &[Vec<String>],
+    written: impl Fn(usize, usize, &str) -> Option<String>,
+) -> String {
+    let columns = headers
+        .len()
+        .max(rows.iter().map(Vec::len).max().unwrap_or(0))
+        .max(1);
+    let row = |index: usize, cells: &[String]| {
+        let cells: Vec<String> = (0..columns)
+            .map(|i| {
+                cells
+                    .get(i)
+                    .map(|c| {
+                        written(index, i, c)
+                            .unwrap_or_else(|| restore_wikilinks(c).replace('|', "\\|"))
+                    })
+                    .unwrap_or_default()
+            })
+            .collect();
+        format!("| {} |\n", cells.join(" | "))
+    };
+
+    let mut out = row(0, headers);
+    let delimiters: Vec<&str> = (0..columns)
+        .map(|i| match alignments.get(i) {
+            Some(TableAlignment::Left) => ":---",
+            Some(TableAlignment::Center) => ":---:",
+            Some(TableAlignment::Right) => "---:",
+            _ => "---",
+        })
+        .collect();
+    out.push_str(&format!("| {} |\n", delimiters.join(" | ")));
+    for (index, cells) in rows.iter().enumerate() {
+        out.push_str(&row(index + 1, cells));
+    }
+    out
+}
+
+/// Cells of a table as written, header row first and the delimiter row
+/// skipped, with `|` still escaped
+fn source_cells(table: &str) -> Vec<Vec<&str>> {
+    table
+        .lines()
+        .enumerate()
+        .filter(|(i, _)| *i != 1)
+        .map(|(_, line)| {
+            let line = line.trim();
+            let line = line.strip_prefix('|').unwrap_or(line);
+            let mut cells = Vec::new();
+            let mut start = 0;
+            let mut escaped = false;
+            for (i, c) in line.char_indices() {
+                if c == '|' && !escaped {
+                    cells.push(line[start..i].trim());
+                    start = i + 1;
+                }
+                escaped = c == '\\' && !escaped;
+            }
+            if !line[start..].trim().is_empty() {
+                cells.push(line[start..].trim());
+            }
+            cells
+        })
+        .collect()
+}
+
+/// Prefix every line; empty lines get `empty_prefix`
+fn prefix_lines(text: &str, prefix: &str, empty_prefix: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                format!("{}\n", empty_prefix)
+            } else {
+                format!("{}{}\n", prefix, line)
+            }
+        })
+        .collect()
+}
+
+/// Length of the longest run of `ch`
+fn longest_run(text: &str, ch: char) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in text.chars() {
+        if c == ch {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+/// Escape characters that would otherwise start inline markup.
+///
+/// `#` is left alone so tags survive; `_` is only escaped at word edges,
+/// where it could open or close emphasis.
+fn escape_text(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1).copied();
+        let escape = match c {
+            '\\' | '*' | '`' | '[' | ']' | '<' | '$' | '~' => true,
+            '_' => {
+                !(prev.is_some_and(char::is_alphanumeric)
+                    && next.is_some_and(char::is_alphanumeric))
+            }
+            '=' | '%' => next == Some(c),
+            _ => false,
+        };
+        if escape {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Escape a paragraph start that would be read as a heading, quote, list or rule
+fn escape_block_start(text: &str) -> String {
+    let heading = text.starts_with('#')
+        && text
+            .trim_start_matches('#')
+            .chars()
+            .next()
+            .is_none_or(char::is_whitespace);
+    let bullet = ["- ", "+ ", "> "].iter().any(|m| text.starts_with(m)) || text == "-";
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    let ordered =
+        digits > 0 && (text[digits..].starts_with(". ") || text[digits..].starts_with(") "));
+
+    if heading || bullet {
+        format!("\\{}", text)
+    } else if ordered {
+        format!("{}\\{}", &text[..digits], &text[digits..])
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rendering then parsing gives back the same blocks
+    fn assert_stable(markdown: &str) {
+        let blocks = parse_blocks(markdown);
+        let rendered = render_blocks(&blocks);
+        assert!(parses_to(&rendered, &blocks), "rendered:\n{}", rendered);
+    }
+
+    #[test]
+    fn test_render_heading_and_paragraph() {
+        let blocks = parse_blocks("## Some *title*\n\nA **bold** `code` [link](https://x.io).");
+        assert_eq!(
+            render_blocks(&blocks),
+            "## Some *title*\n\nA **bold** `code` [link](https://x.io).\n"
+        );
+    }
+
+    #[test]
+    fn test_render_wikilinks_and_embeds() {
+        let blocks = parse_blocks("See [[Note]], [[Other|alias]] and ![[image.png]]");
+        assert_eq!(
+            render_blocks(&blocks),
+            "See [[Note]], [[Other|alias]] and ![[image.png]]\n"
+        );
+    }
+
+    #[test]
+    fn test_render_code_fence_longer_than_content() {
+        let block = ContentBlock::Code {
+            language: Some("md".to_string()),
+            content: "```\ninner\n```".to_string(),
+            start_line: 0,
+            end_line: 0,
+            span: None,
+        };
+        assert_eq!(render_block(&block), "````md\n```\ninner\n```\n````\n");
+    }
+
+    #[test]
+    fn test_render_task_list() {
+        let blocks = parse_blocks("- [ ] open\n- [x] done\n\n1. one\n2. two");
+        assert_eq!(
+            render_blocks(&blocks),
+            "- [ ] open\n- [x] done\n\n1. one\n2. two\n"
+        );
+    }
+
+    #[test]
+    fn test_render_table() {
+        let blocks = parse_blocks("| A | B |\n|:-|-:|\n| 1 | [[L]] |");
+        assert_eq!(
+            render_blocks(&blocks),
+            "| A | B |\n| :--- | ---: |\n| 1 | [[L]] |\n"
+        );
+    }
+
+    #[test]
+    fn test_render_callout() {
+        let blocks = parse_blocks("> [!note] Title\n> Body with ==mark==");
+        assert_eq!(
+            render_blocks(&blocks),
+            "> [!note] Title\n> Body with ==mark==\n"
+        );
+    }
+
+    #[test]
+    fn test_render_escapes_markup_in_text() {
+        let inline = vec![InlineElement::Text {
+            value: "2 * 3 = $6 [x] snake_case _edge".to_string(),
+            span: None,
+        }];
+        assert_eq!(
+            render_inline(&inline),
+            "2 \\* 3 = \\$6 \\[x\\] snake_case \\_edge"
+        );
+        assert_eq!(
+            render_block(&ContentBlock::Paragraph {
+                content: "# not a heading".to_string(),
+                inline: vec![],
+                span: None,
+            }),
+            "\\# not a heading\n"
+        );
+    }
+
+    #[test]
+    fn test_render_is_stable() {
+        assert_stable("# H\n\nText with *em*, ~~gone~~, $x^2$ and a note[^1].\n\n[^1]: The note.");
+        assert_stable("- a\n- b\n\n---\n\n$$\nE = mc^2\n$$\n\n%%\nhidden\n%%");
+        assert_stable("> quote with [[Link]]\n>\n> second paragraph");
+        assert_stable("```rust\nfn main() {}\n```\n\n![alt](img.png)");
+    }
+}
@@ -28,7 +28,10 @@
 //! assert_eq!(parsed.tags.len(), 1);
 //! ```
 
-use turbovault_core::{Callout, Frontmatter, Heading, Link, Tag, TaskItem};
+use turbovault_core::{
+    Block, Callout, Comment, FootnoteDefinition, FootnoteReference, Frontmatter, Heading,
+    Highlight, Link, Math, Tag, TaskItem,
+};
 
 use crate::engine::ParseEngine;
 
@@ -54,6 +57,16 @@ pub struct ParseOptions {
     pub parse_tags: bool,
     /// Use full callout parsing (extracts multi-line content)
     pub full_callouts: bool,
+    /// Parse block IDs (`^block-id`)
+    pub parse_block_ids: bool,
+    /// Parse Obsidian comments (`%%text%%`); links and tags inside them are always skipped
+    pub parse_comments: bool,
+    /// Parse highlights (`==text==`)
+    pub parse_highlights: bool,
+    /// Parse footnote references and definitions (`[^label]`)
+    pub parse_footnotes: bool,
+    /// Parse math (`$inline$`, `$$display$$`)
+    pub parse_math: bool,
 }
 
 impl Default for ParseOptions {
@@ -74,6 +87,11 @@ impl ParseOptions {
             parse_callouts: true,
             parse_tags: true,
             full_callouts: false,
+            parse_block_ids: true,
+            parse_comments: true,
+            parse_highlights: true,
+            parse_footnotes: true,
+            parse_math: true,
         }
     }
 
@@ -88,6 +106,11 @@ impl ParseOptions {
             parse_callouts: false,
             parse_tags: false,
             full_callouts: false,
+            parse_block_ids: false,
+            parse_comments: false,
+            parse_highlights: false,
+            parse_footnotes: false,
+            parse_math: false,
         }
     }
 
@@ -102,6 +125,11 @@ impl ParseOptions {
             parse_callouts: true,
             parse_tags: false,
             full_callouts: true, // treemd needs full callout content
+            parse_block_ids: false,
+            parse_comments: false,
+            parse_highlights: false,
+            parse_footnotes: false,
+            parse_math: false,
         }
     }
 
@@ -116,6 +144,11 @@ impl ParseOptions {
             parse_callouts: false,
             parse_tags: false,
             full_callouts: false,
+            parse_block_ids: false,
+            parse_comments: false,
+            parse_highlights: false,
+            parse_footnotes: false,
+            parse_math: false,
         }
     }
 
@@ -154,6 +187,18 @@ pub struct ParsedContent {
     pub tasks: Vec<TaskItem>,
     /// Callout blocks: > [!NOTE]
     pub callouts: Vec<Callout>,
+    /// Blocks carrying an ID: `text ^block-id`
+    pub blocks: Vec<Block>,
+    /// Obsidian comments: %%hidden%%
+    pub comments: Vec<Comment>,
+    /// Highlights: ==text==
+    pub highlights: Vec<Highlight>,
+    /// Footnote references: [^label]
+    pub footnote_references: Vec<FootnoteReference>,
+    /// Footnote definitions: [^label]: text
+    pub footnote_definitions: Vec<FootnoteDefinition>,
+    /// Math: $inline$, $$display$$
+    pub math: Vec<Math>,
 }
 
 impl ParsedContent {
@@ -200,6 +245,12 @@ impl ParsedContent {
             tags: result.tags,
             tasks: result.tasks,
             callouts: result.callouts,
+            blocks: result.blocks,
+            comments: result.comments,
+            highlights: result.highlights,
+            footnote_references: result.footnote_references,
+            footnote_definitions: result.footnote_definitions,
+            math: result.math,
         }
     }
 
@@ -231,6 +282,12 @@ impl ParsedContent {
             && self.tags.is_empty()
             && self.tasks.is_empty()
             && self.callouts.is_empty()
+            && self.blocks.is_empty()
+            && self.comments.is_empty()
+            && self.highlights.is_empty()
+            && self.footnote_references.is_empty()
+            && self.footnote_definitions.is_empty()
+            && self.math.is_empty()
     }
 }
 
@@ -272,6 +329,38 @@ This has [[WikiLink]] and [markdown](url).
         assert_eq!(parsed.embeds.len(), 1);
     }
 
+    #[test]
+    fn test_comments_highlights_footnotes_math() {
+        let content = r#"Key ==idea==[^1] with $x^2$ and [[Visible]].
+
+%%
+Draft: [[Hidden]] #todo
+%%
+
+[^1]: Source note.
+"#;
+        let parsed = ParsedContent::parse(content);
+
+        assert_eq!(parsed.highlights.len(), 1);
+        assert_eq!(parsed.highlights[0].text, "idea");
+        assert_eq!(parsed.footnote_references.len(), 1);
+        assert_eq!(parsed.footnote_definitions[0].content, "Source note.");
+        assert_eq!(parsed.math[0].expression, "x^2");
+        assert_eq!(parsed.comments.len(), 1);
+        assert!(parsed.comments[0].is_block);
+        assert_eq!(parsed.comments[0].position.line, 3);
+
+        // Comment contents are not links or tags
+        assert_eq!(parsed.wikilinks.len(), 1);
+        assert_eq!(parsed.wikilinks[0].target, "Visible");
+        assert!(parsed.tags.is_empty());
+
+        let links = ParsedContent::parse_with_options(content, ParseOptions::links_only());
+        assert_eq!(links.wikilinks.len(), 1);
+        assert!(links.comments.is_empty());
+        assert!(links.highlights.is_empty());
+    }
+
     #[test]
     fn test_all_links() {
         let content = "[[wiki]] and [md](url) and ![[embed]]";
@@ -0,0 +1,237 @@
+//! Round-trip properties of `MarkdownDocument` and `render_blocks` over the
+//! sample vault in `tests/fixtures/sample_vault`.
+//!
+//! Random edits are generated from a fixed seed, so failures are reproducible.
+
+use std::path::{Path, PathBuf};
+use turbovault_parser::{
+    ContentBlock, InlineElement, MarkdownDocument, SourcePosition, parse_blocks, render_blocks,
+};
+
+#[path = "support/rng.rs"]
+mod rng;
+
+use rng::Rng;
+
+const EDITS_PER_NOTE: usize = 200;
+
+fn sample_notes() -> Vec<(PathBuf, String)> {
+    fn walk(dir: &Path, notes: &mut Vec<(PathBuf, String)>) {
+        let mut entries: Vec<_> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        entries.sort();
+        for path in entries {
+            if path.is_dir() {
+                walk(&path, notes);
+            } else if path.extension().is_some_and(|e| e == "md") {
+                let content = std::fs::read_to_string(&path).unwrap();
+                notes.push((path, content));
+            }
+        }
+    }
+
+    let mut notes = Vec::new();
+    walk(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample_vault"),
+        &mut notes,
+    );
+    assert!(notes.len() >= 5, "sample vault is missing");
+    notes
+}
+
+fn new_paragraph(rng: &mut Rng) -> ContentBlock {
+    let text = format!("Inserted paragraph {}", rng.below(1000));
+    ContentBlock::Paragraph {
+        content: text.clone(),
+        inline: vec![InlineElement::Text {
+            value: text,
+            span: None,
+        }],
+        span: None,
+    }
+}
+
+/// Apply one random structural edit
+fn random_edit(blocks: &mut Vec<ContentBlock>, rng: &mut Rng) {
+    let len = blocks.len();
+    match rng.below(5) {
+        0 if len > 0 => {
+            blocks.remove(rng.below(len));
+        }
+        1 if len > 1 => {
+            let (a, b) = (rng.below(len), rng.below(len));
+            blocks.swap(a, b);
+        }
+        2 if len > 0 => {
+            let block = blocks.remove(rng.below(len));
+            blocks.insert(rng.below(len), block);
+        }
+        3 if len > 0 => {
+            let idx = rng.below(len);
+            blocks[idx] = new_paragraph(rng);
+        }
+        _ => {
+            let paragraph = new_paragraph(rng);
+            blocks.insert(rng.below(len + 1), paragraph);
+        }
+    }
+}
+
+/// Source text of one block, as the document would copy it
+fn block_source(source: &str, block: &ContentBlock) -> String {
+    let mut doc = MarkdownDocument::parse(source);
+    doc.blocks = vec![block.clone()];
+    doc.render()
+        .trim_start_matches(doc.frontmatter())
+        .trim()
+        .to_string()
+}
+
+#[test]
+fn unmodified_documents_render_byte_for_byte() {
+    for (path, source) in sample_notes() {
+        let doc = MarkdownDocument::parse(&source);
+        assert!(!doc.blocks.is_empty(), "{}: no blocks", path.display());
+        assert!(!doc.is_modified());
+        assert_eq!(doc.render(), source, "{}", path.display());
+    }
+}
+
+#[test]
+fn canonical_rendering_is_a_fixed_point() {
+    for (path, source) in sample_notes() {
+        for block in MarkdownDocument::parse(&source).blocks {
+            // Inline text may split differently, so compare the rendered text
+            let once = render_blocks(&parse_blocks(&render_blocks(std::slice::from_ref(&block))));
+            let twice = render_blocks(&parse_blocks(&once));
+            assert_eq!(twice, once, "{}: {:?}", path.display(), block);
+        }
+    }
+}
+
+#[test]
+fn random_edits_keep_untouched_blocks_verbatim() {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+
+    for (path, source) in sample_notes() {
+        let original = MarkdownDocument::parse(&source);
+        let originals: Vec<(ContentBlock, String)> = original
+            .blocks
+            .iter()
+            .map(|b| (b.clone(), block_source(&source, b)))
+            .collect();
+
+        for _ in 0..EDITS_PER_NOTE {
+            let mut doc = original.clone();
+            for _ in 0..=rng.below(3) {
+                random_edit(&mut doc.blocks, &mut rng);
+            }
+            let rendered = doc.render();
+
+            assert!(
+                rendered.starts_with(original.frontmatter()),
+                "{}: frontmatter lost",
+                path.display()
+            );
+            for (block, text) in &originals {
+                if doc.blocks.contains(block) {
+                    assert!(
+                        rendered.contains(text.as_str()),
+                        "{}: untouched block changed\n--- expected ---\n{}\n--- rendered ---\n{}",
+                        path.display(),
+                        text,
+                        rendered
+                    );
+                }
+            }
+
+            // Whatever was rendered is itself a document that round-trips
+            assert_eq!(MarkdownDocument::parse(&rendered).render(), rendered);
+        }
+    }
+}
+
+#[test]
+fn spans_point_into_the_source() {
+    for (path, source) in sample_notes() {
+        let mut doc = MarkdownDocument::parse(&source);
+        for block in &mut doc.blocks {
+            assert!(block.span().is_some(), "{}: {:?}", path.display(), block);
+            block.for_each_span_mut(&mut |span| {
+                let span = span.as_ref().expect("parsed elements have spans");
+                let text = source.get(span.range()).expect("span within the source");
+                assert!(!text.is_empty() || span.length == 0);
+                let start = SourcePosition::from_offset(&source, span.offset, span.length);
+                assert_eq!(*span, start);
+            });
+        }
+    }
+}
+
+#[test]
+fn editing_a_table_cell_rewrites_only_the_table() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/sample_vault/projects/Project Alpha.md");
+    let source = std::fs::read_to_string(path).unwrap();
+    let mut doc = MarkdownDocument::parse(&source);
+
+    let table = doc
+        .blocks
+        .iter_mut()
+        .find(|b| matches!(b, ContentBlock::Table { .. }))
+        .unwrap();
+    if let ContentBlock::Table { rows, .. } = table {
+        rows[1][2] = "2024-05-01".to_string();
+    }
+
+    let rendered = doc.render();
+    assert!(rendered.contains(
+        "| Milestone | Owner | Due |\n| :--- | :---: | ---: |\n| Design | Alice | 2024-02-01 |\n| Build | Bob | 2024-05-01 |\n| Launch | [[Carol]] | 2024-06-01 |\n"
+    ));
+    let (before, _) = source.split_once("| Milestone").unwrap();
+    let (_, after) = source.split_once("## Decisions").unwrap();
+    assert!(rendered.starts_with(before));
+    assert!(rendered.ends_with(after));
+}
+
+#[test]
+fn editing_a_list_keeps_formatted_nested_items() {
+    let source = "# Tasks\n\n* [x] done\n  * child **bold** and [[Link|x]]\n* see `code` here\n  1. nested *em*\n\nAfter\n";
+    let mut doc = MarkdownDocument::parse(source);
+    let ContentBlock::List { items, .. } = &mut doc.blocks[1] else {
+        panic!("expected a list: {:?}", doc.blocks[1]);
+    };
+    items[0].checked = Some(false);
+    items.swap(0, 1);
+    items.push(items[1].clone());
+    items[2].span = None;
+    items[2].checked = None;
+    items[2].content = "new".to_string();
+    items[2].inline.clear();
+    items[2].blocks.clear();
+
+    assert_eq!(
+        doc.render(),
+        "# Tasks\n\n- see `code` here\n  1. nested *em*\n- [ ] done\n  * child **bold** and [[Link|x]]\n- new\n\nAfter\n"
+    );
+}
+
+#[test]
+fn editing_a_table_keeps_formatted_cells() {
+    let source = "| **Bold** | Plain |\n| --- | --- |\n| ==hi== ~~old~~ | [[Note\\|alias]] |\n| `a\\|b` | x |\n";
+    let mut doc = MarkdownDocument::parse(source);
+    let ContentBlock::Table { rows, .. } = &mut doc.blocks[0] else {
+        panic!("expected a table: {:?}", doc.blocks[0]);
+    };
+    rows[1][1] = "y".to_string();
+    rows.swap(0, 1);
+
+    let rendered = doc.render();
+    assert_eq!(
+        rendered,
+        "| **Bold** | Plain |\n| --- | --- |\n| `a\\|b` | y |\n| ==hi== ~~old~~ | [[Note\\|alias]] |\n"
+    );
+    assert_eq!(MarkdownDocument::parse(&rendered).render(), rendered);
+}
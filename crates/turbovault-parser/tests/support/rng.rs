@@ -0,0 +1,25 @@
+//! Deterministic random source shared by the unit tests in `src/` and the
+//! integration tests in `tests/`, so generated cases reproduce from a seed.
+//!
+//! Each test target uses a subset of the helpers.
+#![allow(dead_code)]
+
+/// xorshift64*, enough to drive reproducible random edits
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    pub fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+}
@@ -0,0 +1,736 @@
+//! Obsidian Bases: list `.base` views and evaluate them against the vault
+//!
+//! Bases filter expressions are compiled into [`QueryFilter`]s and matched
+//! against each note's frontmatter plus the `file.*` properties Bases
+//! exposes (`file.name`, `file.folder`, `file.tags`, `file.links`, ...).
+//! Supported: `file.hasTag`, `file.inFolder`, `file.hasLink`,
+//! `file.hasProperty`, `==` / `!=` / `>` / `>=` / `<` / `<=` against literals,
+//! `today()`, `now()` and `date("...")`, the `contains`, `containsAny`,
+//! `containsAll`, `startsWith`, `endsWith` and `isEmpty` methods, and
+//! `&&`, `||`, `!` with parentheses. Formulas and arithmetic are not
+//! evaluated; views filtering on them are refused rather than answered
+//! wrongly, and formula columns come back empty.
+
+use crate::metadata_tools::{QueryFilter, Token, comparison_filter, lookup, scalar_text, tokenize};
+use chrono::{DateTime, Local};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use turbovault_core::prelude::*;
+use turbovault_parser::{Base, BaseFilter, BaseView, SortDirection};
+use turbovault_vault::VaultManager;
+
+/// Column shown when a view has no `order`
+const DEFAULT_COLUMN: &str = "file.name";
+
+/// A `.base` file and its views
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseSummary {
+    pub path: String,
+    pub views: Vec<BaseViewSummary>,
+    /// Why the file could not be parsed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A view of a base
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseViewSummary {
+    pub name: String,
+    pub view_type: String,
+    pub columns: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Filters and columns this crate cannot evaluate
+    pub unsupported: Vec<String>,
+}
+
+/// A column of an evaluated view
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseColumn {
+    pub property: String,
+    /// Display name from the base's `properties`, or the property itself
+    pub name: String,
+}
+
+/// A note matched by a view, with its column values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseRow {
+    pub path: String,
+    /// Column property to value (`null` when the note lacks it)
+    pub values: Map<String, Value>,
+}
+
+/// Result of evaluating a view
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseQueryResult {
+    pub path: String,
+    pub view: String,
+    pub view_type: String,
+    pub columns: Vec<BaseColumn>,
+    pub rows: Vec<BaseRow>,
+    /// Matching notes before the view's `limit`
+    pub total: usize,
+    /// Columns left empty because they are formulas
+    pub unsupported_columns: Vec<String>,
+}
+
+/// Bases tools context
+pub struct BaseTools {
+    pub manager: Arc<VaultManager>,
+}
+
+impl BaseTools {
+    /// Create new Bases tools
+    pub fn new(manager: Arc<VaultManager>) -> Self {
+        Self { manager }
+    }
+
+    /// List every `.base` file with its views
+    pub async fn list_bases(&self) -> Result<Vec<BaseSummary>> {
+        let mut summaries = Vec::new();
+        // Bases are found on their own: they are not notes, so they stay out
+        // of the link graph and the vault's note counts
+        for file_path in self.manager.scan_files_with_extension("base").await? {
+            let path = self.display_path(&file_path);
+            let content = self.manager.read_file(&file_path).await?;
+            let summary = match Base::parse(&content) {
+                Ok(base) => BaseSummary {
+                    path,
+                    views: base.views.iter().map(|v| summarize(&base, v)).collect(),
+                    error: None,
+                },
+                Err(e) => BaseSummary {
+                    path,
+                    views: Vec::new(),
+                    error: Some(e.to_string()),
+                },
+            };
+            summaries.push(summary);
+        }
+        summaries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(summaries)
+    }
+
+    /// Evaluate a view (the first one when `view` is `None`) against the
+    /// vault's notes
+    pub async fn query_base(&self, path: &str, view: Option<&str>) -> Result<BaseQueryResult> {
+        if !is_base_file(path) {
+            return Err(Error::validation_error(format!(
+                "Not a base file (expected .base): {}",
+                path
+            )));
+        }
+        let content = self.manager.read_file(&PathBuf::from(path)).await?;
+        let base = Base::parse(&content)?;
+        let selected = base.view(view).ok_or_else(|| {
+            let names: Vec<&str> = base.views.iter().map(|v| v.name.as_str()).collect();
+            Error::not_found(match view {
+                Some(name) => format!(
+                    "No view '{}' in {} (views: {})",
+                    name,
+                    path,
+                    names.join(", ")
+                ),
+                None => format!("{} has no views", path),
+            })
+        })?;
+
+        let filters = [&base.filters, &selected.filters]
+            .into_iter()
+            .flatten()
+            .map(compile_filter)
+            .collect::<Result<Vec<_>>>()?;
+        let filter = QueryFilter::And(filters);
+
+        let properties = columns(selected);
+        let mut matched = Vec::new();
+        for file_path in self.manager.scan_vault().await? {
+            if !file_path.to_string_lossy().to_lowercase().ends_with(".md") {
+                continue;
+            }
+            let Ok(note) = self.note_properties(&file_path).await else {
+                continue;
+            };
+            if filter.matches(&note) {
+                matched.push((self.display_path(&file_path), note));
+            }
+        }
+
+        matched.sort_by(|(a_path, a), (b_path, b)| {
+            selected
+                .sort
+                .iter()
+                .map(|sort| {
+                    let key = property_key(&sort.property).unwrap_or_default();
+                    let ordering = compare_values(lookup(a, key), lookup(b, key));
+                    match sort.direction {
+                        SortDirection::Asc => ordering,
+                        SortDirection::Desc => ordering.reverse(),
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a_path.cmp(b_path))
+        });
+
+        let total = matched.len();
+        let rows = matched
+            .into_iter()
+            .take(selected.limit.unwrap_or(usize::MAX))
+            .map(|(path, note)| BaseRow {
+                path,
+                values: properties
+                    .iter()
+                    .map(|property| {
+                        let value = property_key(property)
+                            .ok()
+                            .and_then(|key| lookup(&note, key))
+                            .cloned()
+                            .unwrap_or(Value::Null);
+                        (property.clone(), value)
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Ok(BaseQueryResult {
+            path: path.to_string(),
+            view: selected.name.clone(),
+            view_type: selected.view_type.clone(),
+            columns: properties
+                .iter()
+                .map(|property| BaseColumn {
+                    property: property.clone(),
+                    name: base.display_name(property).unwrap_or(property).to_string(),
+                })
+                .collect(),
+            rows,
+            total,
+            unsupported_columns: properties
+                .iter()
+                .filter(|p| property_key(p).is_err())
+                .cloned()
+                .collect(),
+        })
+    }
+
+    /// Frontmatter of a note plus the `file.*` properties Bases exposes
+    async fn note_properties(&self, file_path: &Path) -> Result<HashMap<String, Value>> {
+        let vault_file = self.manager.parse_file(file_path).await?;
+        let mut properties = vault_file
+            .frontmatter
+            .as_ref()
+            .map(|fm| fm.data.clone())
+            .unwrap_or_default();
+
+        let relative = self.display_path(file_path);
+        let (folder, name) = relative.rsplit_once('/').unwrap_or(("", &relative));
+        let (basename, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+
+        let mut tags = BTreeSet::new();
+        let tag_names = vault_file
+            .frontmatter
+            .as_ref()
+            .map(|fm| fm.tags())
+            .unwrap_or_default()
+            .into_iter()
+            .chain(vault_file.tags.iter().map(|tag| tag.name.clone()));
+        for tag in tag_names {
+            // `#a/b` also counts as `#a`, as in Obsidian
+            let tag = normalize_tag(&tag);
+            let mut prefix = String::new();
+            for part in tag.split('/') {
+                if !prefix.is_empty() {
+                    prefix.push('/');
+                }
+                prefix.push_str(part);
+                tags.insert(prefix.clone());
+            }
+        }
+        let links: BTreeSet<String> = vault_file
+            .links
+            .iter()
+            .filter(|link| !matches!(link.type_, LinkType::ExternalLink | LinkType::Anchor))
+            .map(|link| link_key(&link.target))
+            .filter(|key| !key.is_empty())
+            .collect();
+
+        let file_properties = [
+            ("file.name", Value::from(name)),
+            ("file.basename", Value::from(basename)),
+            ("file.ext", Value::from(ext)),
+            ("file.folder", Value::from(folder)),
+            ("file.path", Value::from(relative.as_str())),
+            ("file.size", Value::from(vault_file.metadata.size)),
+            ("file.tags", tags.into_iter().collect()),
+            ("file.links", links.into_iter().collect()),
+        ];
+        for (key, value) in file_properties {
+            properties.insert(key.to_string(), value);
+        }
+
+        if let Ok(metadata) = tokio::fs::metadata(file_path).await {
+            let times = [
+                ("file.mtime", metadata.modified()),
+                ("file.ctime", metadata.created()),
+            ];
+            for (key, time) in times {
+                if let Ok(time) = time {
+                    let time: DateTime<Local> = time.into();
+                    properties.insert(
+                        key.to_string(),
+                        Value::from(time.format("%Y-%m-%dT%H:%M:%S").to_string()),
+                    );
+                }
+            }
+        }
+
+        Ok(properties)
+    }
+
+    fn display_path(&self, path: &Path) -> String {
+        path.strip_prefix(self.manager.vault_path())
+            .unwrap_or(path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+fn is_base_file(path: &str) -> bool {
+    path.to_lowercase().ends_with(".base")
+}
+
+/// Columns of a view, in order
+fn columns(view: &BaseView) -> Vec<String> {
+    if view.order.is_empty() {
+        vec![DEFAULT_COLUMN.to_string()]
+    } else {
+        view.order.clone()
+    }
+}
+
+fn summarize(base: &Base, view: &BaseView) -> BaseViewSummary {
+    let columns = columns(view);
+    let unsupported = [&base.filters, &view.filters]
+        .into_iter()
+        .flatten()
+        .flat_map(BaseFilter::expressions)
+        .filter(|expression| compile_expression(expression).is_err())
+        .map(str::to_string)
+        .chain(columns.iter().filter(|p| property_key(p).is_err()).cloned())
+        .collect();
+    BaseViewSummary {
+        name: view.name.clone(),
+        view_type: view.view_type.clone(),
+        columns,
+        limit: view.limit,
+        unsupported,
+    }
+}
+
+/// Key of a property in the metadata a note is matched against:
+/// `note.status` and `status` are the frontmatter key `status`, `file.*`
+/// stays as is; formulas and `this` are not supported
+fn property_key(property: &str) -> Result<&str> {
+    let property = property.trim();
+    if property.starts_with("formula.") || property == "this" || property.starts_with("this.") {
+        return Err(Error::validation_error(format!(
+            "'{}' cannot be evaluated (formulas and `this` are not supported)",
+            property
+        )));
+    }
+    Ok(property.strip_prefix("note.").unwrap_or(property))
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// Note a link points at, as compared by `file.hasLink`: no heading or
+/// block, no folder, no `.md`, lowercase
+fn link_key(target: &str) -> String {
+    let file = target.split('#').next().unwrap_or_default().trim();
+    let name = file.rsplit('/').next().unwrap_or(file);
+    let name = if name.to_lowercase().ends_with(".md") {
+        &name[..name.len() - 3]
+    } else {
+        name
+    };
+    name.to_lowercase()
+}
+
+/// Order of two property values: numbers numerically, text
+/// case-insensitively, missing values last
+fn compare_values(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    let a = a.filter(|v| !v.is_null());
+    let b = b.filter(|v| !v.is_null());
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(Value::Number(x)), Some(Value::Number(y))) => x
+            .as_f64()
+            .partial_cmp(&y.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Some(x), Some(y)) => {
+            let text = |v: &Value| scalar_text(v).unwrap_or_else(|| v.to_string());
+            text(x).to_lowercase().cmp(&text(y).to_lowercase())
+        }
+    }
+}
+
+/// Compile a (nested) base filter; `not` matches when none of its filters do
+fn compile_filter(filter: &BaseFilter) -> Result<QueryFilter> {
+    let compile_all = |filters: &[BaseFilter]| {
+        filters
+            .iter()
+            .map(compile_filter)
+            .collect::<Result<Vec<_>>>()
+    };
+    Ok(match filter {
+        BaseFilter::Expression(expression) => compile_expression(expression)?,
+        BaseFilter::And { and } => QueryFilter::And(compile_all(and)?),
+        BaseFilter::Or { or } => QueryFilter::Or(compile_all(or)?),
+        BaseFilter::Not { not } => QueryFilter::Not(Box::new(QueryFilter::Or(compile_all(not)?))),
+    })
+}
+
+fn compile_expression(expression: &str) -> Result<QueryFilter> {
+    let mut parser = ExpressionParser {
+        tokens: tokenize(expression)?,
+        pos: 0,
+        expression,
+    };
+    if parser.peek().is_none() {
+        return Err(parser.error("empty expression"));
+    }
+    let filter = parser.parse_or()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected trailing input"));
+    }
+    Ok(filter)
+}
+
+/// Recursive-descent parser over the tokens of a Bases expression
+struct ExpressionParser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    expression: &'a str,
+}
+
+impl ExpressionParser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn error(&self, reason: &str) -> Error {
+        Error::validation_error(format!(
+            "Unsupported Bases expression: {} ({})",
+            self.expression, reason
+        ))
+    }
+
+    fn expect(&mut self, expected: Token, what: &str) -> Result<()> {
+        if self.next() == Some(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {}", what)))
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<QueryFilter> {
+        let mut terms = vec![self.parse_and()?];
+        while self.peek() == Some(&Token::Op("||")) {
+            self.pos += 1;
+            terms.push(self.parse_and()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            QueryFilter::Or(terms)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<QueryFilter> {
+        let mut terms = vec![self.parse_unary()?];
+        while self.peek() == Some(&Token::Op("&&")) {
+            self.pos += 1;
+            terms.push(self.parse_unary()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            QueryFilter::And(terms)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<QueryFilter> {
+        match self.peek() {
+            Some(Token::Op("!")) => {
+                self.pos += 1;
+                Ok(QueryFilter::Not(Box::new(self.parse_unary()?)))
+            }
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let inner = self.parse_or()?;
+                self.expect(Token::RParen, "')'")?;
+                Ok(inner)
+            }
+            _ => self.parse_term(),
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<QueryFilter> {
+        let Some(Token::Word(word)) = self.next() else {
+            return Err(self.error("expected a property or function"));
+        };
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let arguments = self.parse_arguments()?;
+            return self.call(&word, arguments);
+        }
+
+        let key = self.key(&word)?;
+        match self.peek() {
+            Some(Token::Op(op @ ("==" | "=" | "!=" | ">" | ">=" | "<" | "<="))) => {
+                let op = *op;
+                self.pos += 1;
+                let value = self.parse_value()?;
+                match op {
+                    "==" | "=" => Ok(QueryFilter::Equals(key, value)),
+                    "!=" => Ok(QueryFilter::Not(Box::new(QueryFilter::Equals(key, value)))),
+                    _ => comparison_filter(key, op, &value)
+                        .ok_or_else(|| self.error("comparisons need a number or a date")),
+                }
+            }
+            // A bare property is a boolean check
+            _ => Ok(QueryFilter::Equals(key, Value::Bool(true))),
+        }
+    }
+
+    /// Compile `file.function(...)` or `property.method(...)`
+    fn call(&self, word: &str, arguments: Vec<Value>) -> Result<QueryFilter> {
+        let (object, method) = word
+            .rsplit_once('.')
+            .ok_or_else(|| self.error(&format!("unknown function '{}'", word)))?;
+        let texts: Vec<String> = arguments
+            .iter()
+            .map(|a| scalar_text(a).unwrap_or_default())
+            .collect();
+        let single = || match texts.as_slice() {
+            [text] => Ok(text.clone()),
+            _ => Err(self.error(&format!("{} takes one argument", method))),
+        };
+        let any = |filters: Vec<QueryFilter>| match filters.len() {
+            1 => filters
+                .into_iter()
+                .next()
+                .unwrap_or(QueryFilter::Or(vec![])),
+            _ => QueryFilter::Or(filters),
+        };
+
+        if object == "file" {
+            return match method {
+                "hasTag" => Ok(any(texts
+                    .iter()
+                    .map(|tag| QueryFilter::Contains("file.tags".to_string(), normalize_tag(tag)))
+                    .collect())),
+                "inFolder" => {
+                    let folder = single()?.trim_matches('/').to_string();
+                    let pattern = if folder.is_empty() {
+                        "^".to_string()
+                    } else {
+                        format!("(?i)^{}(/|$)", regex::escape(&folder))
+                    };
+                    self.regex("file.folder", &pattern)
+                }
+                "hasLink" => Ok(QueryFilter::Contains(
+                    "file.links".to_string(),
+                    link_key(&single()?),
+                )),
+                "hasProperty" => Ok(QueryFilter::Exists(self.key(&single()?)?)),
+                _ => Err(self.error(&format!("unknown function 'file.{}'", method))),
+            };
+        }
+
+        let key = self.key(object)?;
+        match method {
+            "contains" => Ok(QueryFilter::Contains(key, single()?)),
+            "containsAny" => Ok(any(texts
+                .into_iter()
+                .map(|t| QueryFilter::Contains(key.clone(), t))
+                .collect())),
+            "containsAll" => Ok(QueryFilter::And(
+                texts
+                    .into_iter()
+                    .map(|t| QueryFilter::Contains(key.clone(), t))
+                    .collect(),
+            )),
+            "startsWith" => self.regex(&key, &format!("^{}", regex::escape(&single()?))),
+            "endsWith" => self.regex(&key, &format!("{}$", regex::escape(&single()?))),
+            "isEmpty" if texts.is_empty() => Ok(QueryFilter::Or(vec![
+                QueryFilter::Not(Box::new(QueryFilter::Exists(key.clone()))),
+                QueryFilter::Equals(key.clone(), Value::Null),
+                QueryFilter::Equals(key.clone(), Value::from("")),
+                QueryFilter::Equals(key, Value::Array(Vec::new())),
+            ])),
+            _ => Err(self.error(&format!("unknown method '{}'", method))),
+        }
+    }
+
+    fn key(&self, property: &str) -> Result<String> {
+        property_key(property)
+            .map(str::to_string)
+            .map_err(|_| self.error(&format!("'{}' cannot be evaluated", property)))
+    }
+
+    fn regex(&self, key: &str, pattern: &str) -> Result<QueryFilter> {
+        Regex::new(pattern)
+            .map(|regex| QueryFilter::Matches(key.to_string(), regex))
+            .map_err(|e| self.error(&format!("invalid pattern: {}", e)))
+    }
+
+    /// Arguments up to and including the closing `)`
+    fn parse_arguments(&mut self) -> Result<Vec<Value>> {
+        let mut arguments = Vec::new();
+        if self.peek() == Some(&Token::RParen) {
+            self.pos += 1;
+            return Ok(arguments);
+        }
+        loop {
+            arguments.push(self.parse_value()?);
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::RParen) => return Ok(arguments),
+                _ => return Err(self.error("expected ',' or ')'")),
+            }
+        }
+    }
+
+    /// A literal, `today()`, `now()`, or `date("...")` / `link("...")`
+    fn parse_value(&mut self) -> Result<Value> {
+        match self.next() {
+            Some(Token::Str(s)) => Ok(Value::String(s)),
+            Some(Token::Word(word)) if self.peek() == Some(&Token::LParen) => {
+                self.pos += 1;
+                let arguments = self.parse_arguments()?;
+                match (word.as_str(), arguments.as_slice()) {
+                    ("today" | "now", []) => Ok(Value::String(word)),
+                    ("date" | "link", [argument]) => Ok(argument.clone()),
+                    _ => Err(self.error(&format!("unsupported function '{}'", word))),
+                }
+            }
+            Some(Token::Word(word)) => match word.as_str() {
+                "true" => Ok(Value::Bool(true)),
+                "false" => Ok(Value::Bool(false)),
+                "null" => Ok(Value::Null),
+                _ => word
+                    .parse::<i64>()
+                    .map(Value::from)
+                    .or_else(|_| word.parse::<f64>().map(Value::from))
+                    .map_err(|_| self.error(&format!("'{}' is not a literal value", word))),
+            },
+            _ => Err(self.error("expected a value")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn note(pairs: Value) -> HashMap<String, Value> {
+        pairs
+            .as_object()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_compile_expressions() {
+        let book = note(json!({
+            "status": "reading",
+            "pages": 320,
+            "finished": "2024-03-01",
+            "author": "Ursula K. Le Guin",
+            "file.tags": ["book", "book/fiction"],
+            "file.folder": "Library/Fiction",
+            "file.links": ["earthsea"],
+        }));
+
+        for expression in [
+            r#"file.hasTag("Book")"#,
+            r##"file.hasTag("#book/fiction", "film")"##,
+            r#"file.inFolder("Library")"#,
+            r#"file.hasLink(link("Earthsea"))"#,
+            r#"file.hasProperty("pages")"#,
+            r#"note.status == "reading" && pages > 300"#,
+            r#"status != "done""#,
+            r#"finished < date("2024-06-01") || !(pages >= 100)"#,
+            r#"author.startsWith("Ursula") && author.contains("Guin")"#,
+            "summary.isEmpty()",
+            "finished < today()",
+        ] {
+            let filter = compile_expression(expression).unwrap();
+            assert!(filter.matches(&book), "{} should match", expression);
+        }
+
+        for expression in [
+            r#"file.hasTag("fiction")"#,
+            r#"file.inFolder("Lib")"#,
+            r#"status == "done""#,
+            "pages < 100",
+            "author.isEmpty()",
+        ] {
+            let filter = compile_expression(expression).unwrap();
+            assert!(!filter.matches(&book), "{} should not match", expression);
+        }
+    }
+
+    #[test]
+    fn test_unsupported_expressions() {
+        for expression in [
+            "formula.left > 3",
+            "file.mtime > now() - \"1 week\"",
+            "status == other",
+            "pages.round() > 3",
+            "",
+        ] {
+            assert!(compile_expression(expression).is_err(), "{}", expression);
+        }
+    }
+
+    #[test]
+    fn test_not_matches_none() {
+        let filter = compile_filter(&BaseFilter::Not {
+            not: vec![
+                BaseFilter::Expression(r#"status == "done""#.to_string()),
+                BaseFilter::Expression(r#"status == "dropped""#.to_string()),
+            ],
+        })
+        .unwrap();
+        assert!(filter.matches(&note(json!({"status": "reading"}))));
+        assert!(!filter.matches(&note(json!({"status": "dropped"}))));
+    }
+
+    #[test]
+    fn test_link_key() {
+        assert_eq!(link_key("notes/Earthsea.md#Plot"), "earthsea");
+        assert_eq!(link_key("Earthsea#^block"), "earthsea");
+    }
+}
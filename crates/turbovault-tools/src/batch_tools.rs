@@ -1,5 +1,6 @@
 //! Batch operation tools for coordinated multi-file operations
 
+use std::path::Path;
 use std::sync::Arc;
 use turbovault_batch::{BatchExecutor, BatchOperation, BatchResult};
 use turbovault_core::prelude::*;
@@ -30,12 +31,103 @@ impl BatchTools {
         let executor = BatchExecutor::new(self.manager.clone(), temp_dir);
         executor.execute(operations).await
     }
+
+    /// Rewrite notes as one batch, all or nothing.
+    ///
+    /// If a write fails, the notes already written get their original
+    /// content back and the failure is returned as an error; notes that could
+    /// not be restored are listed in it.
+    pub(crate) async fn rewrite_notes(&self, notes: Vec<NoteRewrite>) -> Result<BatchResult> {
+        let operations = notes
+            .iter()
+            .map(|note| BatchOperation::WriteNote {
+                path: note.path.clone(),
+                content: note.content.clone(),
+            })
+            .collect();
+        let batch = self.batch_execute(operations).await?;
+        if batch.success {
+            return Ok(batch);
+        }
+
+        let written = batch.failed_at.unwrap_or(0);
+        let mut unrestored = Vec::new();
+        for note in notes[..written].iter().rev() {
+            if let Err(e) = self
+                .manager
+                .write_file(Path::new(&note.path), &note.original)
+                .await
+            {
+                log::warn!("Failed to restore {}: {}", note.path, e);
+                unrestored.push(note.path.clone());
+            }
+        }
+
+        let cause = batch.errors.join("; ");
+        Err(if unrestored.is_empty() {
+            Error::other(format!(
+                "Batch failed, {} written note(s) restored: {}",
+                written, cause
+            ))
+        } else {
+            Error::other(format!(
+                "Batch failed and left these notes partially applied: {} ({})",
+                unrestored.join(", "),
+                cause
+            ))
+        })
+    }
+}
+
+/// A note to rewrite in [`BatchTools::rewrite_notes`]
+pub(crate) struct NoteRewrite {
+    /// Path relative to the vault root
+    pub path: String,
+    /// Content to restore if the batch fails
+    pub original: String,
+    pub content: String,
 }
 
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn test_batch_tools_creation() {
-        // Tests in integration tests file
+    use super::*;
+    use turbovault_core::{ConfigProfile, VaultConfig};
+
+    #[tokio::test]
+    async fn test_rewrite_notes_restores_on_failure() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("a.md"), "old a").unwrap();
+        std::fs::write(temp_dir.path().join("b.md"), "old b").unwrap();
+
+        let mut config = ConfigProfile::Development.create_config();
+        config.vaults.push(
+            VaultConfig::builder("test", temp_dir.path())
+                .build()
+                .unwrap(),
+        );
+        let manager = Arc::new(VaultManager::new(config).unwrap());
+        let tools = BatchTools::new(manager);
+
+        let rewrite = |path: &str, original: &str| NoteRewrite {
+            path: path.to_string(),
+            original: original.to_string(),
+            content: "new".to_string(),
+        };
+        // `a.md` is a file, so nothing can be written below it
+        let error = tools
+            .rewrite_notes(vec![rewrite("b.md", "old b"), rewrite("a.md/c.md", "")])
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("1 written note(s) restored"));
+        let b = std::fs::read_to_string(temp_dir.path().join("b.md")).unwrap();
+        assert_eq!(b, "old b");
+
+        let batch = tools
+            .rewrite_notes(vec![rewrite("a.md", "old a"), rewrite("b.md", "old b")])
+            .await
+            .unwrap();
+        assert!(batch.success);
+        let a = std::fs::read_to_string(temp_dir.path().join("a.md")).unwrap();
+        assert_eq!(a, "new");
     }
 }
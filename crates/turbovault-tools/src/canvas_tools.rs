@@ -0,0 +1,366 @@
+//! JSON Canvas editing: read, create and extend `.canvas` files
+//!
+//! Canvases are read and written through [`Canvas`], so keys this crate does
+//! not model survive edits. Writes that would introduce a JSON Canvas spec
+//! violation are refused; file nodes must point at a file in the vault and are stored
+//! with its vault-relative path, the way Obsidian writes them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use turbovault_core::prelude::*;
+use turbovault_parser::{Canvas, CanvasEdge, CanvasNode, CanvasNodeType, CanvasSide};
+use turbovault_vault::VaultManager;
+
+/// Gap left between an appended node and the nodes already on the canvas
+const NODE_GAP: i64 = 50;
+
+/// A canvas with any spec violations it has
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanvasInfo {
+    pub path: String,
+    #[serde(flatten)]
+    pub canvas: Canvas,
+    /// Spec violations; Obsidian may drop or misplace these nodes and edges
+    pub issues: Vec<String>,
+}
+
+/// Node to add with [`CanvasTools::add_node`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewCanvasNode {
+    #[serde(rename = "type")]
+    pub node_type: CanvasNodeType,
+    /// Text of a text node, file (path or note name) of a file node, URL of
+    /// a link node or label of a group node
+    pub content: String,
+    /// `#Heading` or `#^block` of a file node
+    #[serde(default)]
+    pub subpath: Option<String>,
+    /// Placed right of the existing nodes when absent
+    #[serde(default)]
+    pub x: Option<i64>,
+    #[serde(default)]
+    pub y: Option<i64>,
+    #[serde(default)]
+    pub width: Option<i64>,
+    #[serde(default)]
+    pub height: Option<i64>,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Edge to add with [`CanvasTools::connect_nodes`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewCanvasEdge {
+    /// Node id, or the file of a file node
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub from_side: Option<CanvasSide>,
+    #[serde(default)]
+    pub to_side: Option<CanvasSide>,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// Canvas tools context
+pub struct CanvasTools {
+    pub manager: Arc<VaultManager>,
+}
+
+impl CanvasTools {
+    /// Create new canvas tools
+    pub fn new(manager: Arc<VaultManager>) -> Self {
+        Self { manager }
+    }
+
+    /// Read a canvas with its spec violations
+    pub async fn read_canvas(&self, path: &str) -> Result<CanvasInfo> {
+        let canvas = self.load(path).await?;
+        let issues = canvas.validate();
+        Ok(CanvasInfo {
+            path: path.to_string(),
+            canvas,
+            issues,
+        })
+    }
+
+    /// Create a canvas; fails if the file exists or the canvas is invalid
+    pub async fn create_canvas(&self, path: &str, mut canvas: Canvas) -> Result<Canvas> {
+        check_extension(path)?;
+        if self.manager.read_file(&PathBuf::from(path)).await.is_ok() {
+            return Err(Error::validation_error(format!(
+                "Canvas already exists: {}",
+                path
+            )));
+        }
+        for node in &mut canvas.nodes {
+            if node.node_type == CanvasNodeType::File
+                && let Some(file) = &node.file
+            {
+                node.file = Some(self.resolve_file(file).await?);
+            }
+        }
+        self.save(path, &canvas, &[]).await?;
+        Ok(canvas)
+    }
+
+    /// Add a node, returning it with its generated id and position
+    pub async fn add_node(&self, path: &str, new: NewCanvasNode) -> Result<CanvasNode> {
+        let mut canvas = self.load(path).await?;
+        let existing = canvas.validate();
+
+        let (width, height) = match new.node_type {
+            CanvasNodeType::Text => (250, 60),
+            CanvasNodeType::Link => (400, 300),
+            CanvasNodeType::File | CanvasNodeType::Group => (400, 400),
+        };
+        // Right of everything already on the canvas, top-aligned with it
+        let right = canvas.nodes.iter().map(|n| n.x + n.width).max();
+        let top = canvas.nodes.iter().map(|n| n.y).min().unwrap_or(0);
+
+        let mut node = CanvasNode {
+            id: new_id(&canvas, &new.content),
+            node_type: new.node_type,
+            text: None,
+            file: None,
+            subpath: new.subpath,
+            url: None,
+            label: None,
+            background: None,
+            background_style: None,
+            x: new.x.unwrap_or(right.map_or(0, |r| r + NODE_GAP)),
+            y: new.y.unwrap_or(top),
+            width: new.width.unwrap_or(width),
+            height: new.height.unwrap_or(height),
+            color: new.color,
+            extra: Default::default(),
+        };
+        match new.node_type {
+            CanvasNodeType::Text => node.text = Some(new.content),
+            CanvasNodeType::File => node.file = Some(self.resolve_file(&new.content).await?),
+            CanvasNodeType::Link => node.url = Some(new.content),
+            CanvasNodeType::Group => {
+                node.label = Some(new.content).filter(|label| !label.is_empty())
+            }
+        }
+
+        canvas.nodes.push(node.clone());
+        self.save(path, &canvas, &existing).await?;
+        Ok(node)
+    }
+
+    /// Connect two nodes, returning the new edge
+    pub async fn connect_nodes(&self, path: &str, new: NewCanvasEdge) -> Result<CanvasEdge> {
+        let mut canvas = self.load(path).await?;
+        let existing = canvas.validate();
+        let from = find_node(&canvas, &new.from, path)?;
+        let to = find_node(&canvas, &new.to, path)?;
+
+        let edge = CanvasEdge {
+            id: new_id(&canvas, &format!("{}->{}", from, to)),
+            from_node: from,
+            from_side: new.from_side,
+            from_end: None,
+            to_node: to,
+            to_side: new.to_side,
+            to_end: None,
+            color: new.color,
+            label: new.label,
+            extra: Default::default(),
+        };
+
+        canvas.edges.push(edge.clone());
+        self.save(path, &canvas, &existing).await?;
+        Ok(edge)
+    }
+
+    async fn load(&self, path: &str) -> Result<Canvas> {
+        check_extension(path)?;
+        let content = self.manager.read_file(&PathBuf::from(path)).await?;
+        Canvas::parse(&content)
+    }
+
+    /// Write a canvas unless the edit introduced spec violations beyond
+    /// those it already had
+    async fn save(&self, path: &str, canvas: &Canvas, existing: &[String]) -> Result<()> {
+        let issues: Vec<String> = canvas
+            .validate()
+            .into_iter()
+            .filter(|issue| !existing.contains(issue))
+            .collect();
+        if !issues.is_empty() {
+            return Err(Error::validation_error(format!(
+                "Invalid canvas: {}",
+                issues.join("; ")
+            )));
+        }
+        self.manager
+            .write_file(&PathBuf::from(path), &canvas.to_json())
+            .await
+    }
+
+    /// Vault-relative path of a file given by path (with or without `.md`)
+    /// or by note name
+    async fn resolve_file(&self, file: &str) -> Result<String> {
+        let relative = Path::new(file.trim_start_matches('/'));
+        if relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            let vault = self.manager.vault_path();
+            if vault.join(relative).is_file() {
+                return Ok(to_vault_path(relative));
+            }
+            let with_ext = relative.with_extension("md");
+            if relative.extension().is_none() && vault.join(&with_ext).is_file() {
+                return Ok(to_vault_path(&with_ext));
+            }
+        }
+
+        let graph = self.manager.link_graph();
+        let graph = graph.read().await;
+        let resolved = graph
+            .resolve_target(file)
+            .or_else(|| graph.resolve_target(&format!("{}.md", file)));
+        match resolved {
+            Some(path) => Ok(to_vault_path(
+                path.strip_prefix(self.manager.vault_path())
+                    .unwrap_or(&path),
+            )),
+            None => Err(Error::not_found(format!(
+                "No file '{}' in the vault for a file node",
+                file
+            ))),
+        }
+    }
+}
+
+fn check_extension(path: &str) -> Result<()> {
+    if path.to_lowercase().ends_with(".canvas") {
+        Ok(())
+    } else {
+        Err(Error::validation_error(format!(
+            "Not a canvas file (expected .canvas): {}",
+            path
+        )))
+    }
+}
+
+/// Path with `/` separators, as stored in canvas file nodes
+fn to_vault_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Id of the node given by id, or by the file of a single file node
+fn find_node(canvas: &Canvas, reference: &str, path: &str) -> Result<String> {
+    if canvas.node(reference).is_some() {
+        return Ok(reference.to_string());
+    }
+
+    let wanted = reference.trim_start_matches('/');
+    let matches: Vec<&CanvasNode> = canvas
+        .nodes
+        .iter()
+        .filter(|n| {
+            n.file.as_deref().is_some_and(|file| {
+                file == wanted
+                    || file.strip_suffix(".md") == Some(wanted)
+                    || file.rsplit('/').next().is_some_and(|name| {
+                        name == wanted || name.strip_suffix(".md") == Some(wanted)
+                    })
+            })
+        })
+        .collect();
+    match matches.as_slice() {
+        [node] => Ok(node.id.clone()),
+        [] => Err(Error::not_found(format!(
+            "No node '{}' in {}",
+            reference, path
+        ))),
+        _ => Err(Error::validation_error(format!(
+            "'{}' matches {} file nodes in {}; use a node id",
+            reference,
+            matches.len(),
+            path
+        ))),
+    }
+}
+
+/// A fresh 16-hex-digit id, the format Obsidian uses
+fn new_id(canvas: &Canvas, seed: &str) -> String {
+    let taken: HashSet<&str> = canvas
+        .nodes
+        .iter()
+        .map(|n| n.id.as_str())
+        .chain(canvas.edges.iter().map(|e| e.id.as_str()))
+        .collect();
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+
+    (0u32..)
+        .map(|attempt| {
+            let mut hasher = DefaultHasher::new();
+            (seed, nanos, taken.len(), attempt).hash(&mut hasher);
+            format!("{:016x}", hasher.finish())
+        })
+        .find(|id| !taken.contains(id.as_str()))
+        .expect("an unused id exists")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_id_is_unique_hex() {
+        let mut canvas = Canvas::default();
+        let id = new_id(&canvas, "seed");
+        assert_eq!(id.len(), 16);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+
+        canvas.edges.push(CanvasEdge {
+            id: id.clone(),
+            from_node: "a".to_string(),
+            from_side: None,
+            from_end: None,
+            to_node: "b".to_string(),
+            to_side: None,
+            to_end: None,
+            color: None,
+            label: None,
+            extra: Default::default(),
+        });
+        assert_ne!(new_id(&canvas, "seed"), id);
+    }
+
+    #[test]
+    fn test_find_node_by_id_or_file() {
+        let canvas = Canvas::parse(
+            r#"{"nodes":[
+                {"id":"a","type":"file","file":"notes/Spec.md","x":0,"y":0,"width":10,"height":10},
+                {"id":"b","type":"file","file":"Spec.md","x":0,"y":0,"width":10,"height":10},
+                {"id":"c","type":"file","file":"Plan.md","x":0,"y":0,"width":10,"height":10}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(find_node(&canvas, "c", "x.canvas").unwrap(), "c");
+        assert_eq!(find_node(&canvas, "Plan", "x.canvas").unwrap(), "c");
+        assert_eq!(
+            find_node(&canvas, "notes/Spec.md", "x.canvas").unwrap(),
+            "a"
+        );
+        assert!(find_node(&canvas, "Spec", "x.canvas").is_err());
+        assert!(find_node(&canvas, "Missing", "x.canvas").is_err());
+    }
+}
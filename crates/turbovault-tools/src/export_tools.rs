@@ -1,14 +1,36 @@
 //! Export tools for vault analysis data
 
+use crate::graph_tools::GraphTools;
+use crate::search_engine::searchable_text;
+use crate::tag_tools::{TagTools, note_tags};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use turbovault_core::prelude::*;
 use turbovault_export::{
-    AnalysisReportExporter, BrokenLinkRecord, BrokenLinksExporter, HealthReportExporter,
+    AnalysisReportExporter, BrokenLinkRecord, BrokenLinksExporter, ExportFormat,
+    GraphExportOptions, GraphExporter, HealthReportExporter, NoteAttributes, SubgraphSelection,
     VaultStatsExporter, VaultStatsRecord, create_health_report,
 };
+use turbovault_graph::EdgeKind;
 use turbovault_parser::to_plain_text;
 use turbovault_vault::VaultManager;
 
+/// Rendered link graph export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphExportInfo {
+    pub format: String,
+    pub content: String,
+    /// Notes in the exported subgraph
+    pub node_count: usize,
+    pub edge_count: usize,
+    /// Notes in the whole vault
+    pub total_nodes: usize,
+    /// Notes left out of Mermaid output by the size cap
+    pub omitted_nodes: usize,
+}
+
 /// Export tools for vault analysis and reporting
 pub struct ExportTools {
     pub manager: Arc<VaultManager>,
@@ -22,6 +44,11 @@ impl ExportTools {
 
     /// Export health report
     pub async fn export_health_report(&self, format: &str) -> Result<String> {
+        let broken_links = GraphTools::new(self.manager.clone())
+            .get_broken_links()
+            .await?
+            .len();
+
         let graph = self.manager.link_graph();
         let graph_read = graph.read().await;
 
@@ -30,7 +57,6 @@ impl ExportTools {
         // Calculate metrics
         let total_notes = stats.total_files;
         let total_links = stats.total_links;
-        let broken_links = 0; // Would need HealthAnalyzer for actual count
         let orphaned_notes = stats.orphaned_files;
 
         // Health score heuristic
@@ -62,8 +88,17 @@ impl ExportTools {
 
     /// Export broken links
     pub async fn export_broken_links(&self, format: &str) -> Result<String> {
-        // In real implementation, would get actual broken links from HealthAnalyzer
-        let links: Vec<BrokenLinkRecord> = vec![];
+        let links: Vec<BrokenLinkRecord> = GraphTools::new(self.manager.clone())
+            .get_broken_links()
+            .await?
+            .into_iter()
+            .map(|link| BrokenLinkRecord {
+                source_file: link.source_file,
+                target: link.target,
+                line: link.line,
+                suggestions: link.suggestions,
+            })
+            .collect();
 
         match format {
             "json" => BrokenLinksExporter::to_json(&links),
@@ -91,7 +126,7 @@ impl ExportTools {
                 continue;
             }
             if let Ok(vault_file) = self.manager.parse_file(file_path).await {
-                let plain_text = to_plain_text(&vault_file.content);
+                let plain_text = searchable_text(&vault_file);
                 total_words += plain_text.split_whitespace().count();
                 total_readable_chars += plain_text.chars().count();
                 note_count += 1;
@@ -173,4 +208,78 @@ impl ExportTools {
             )),
         }
     }
+
+    /// Export the link graph, or part of it, in a graph format
+    ///
+    /// An ego `center` may be vault-relative. `mermaid_max_nodes` defaults to
+    /// [`turbovault_export::DEFAULT_MERMAID_MAX_NODES`].
+    pub async fn export_graph(
+        &self,
+        format: &str,
+        selection: SubgraphSelection,
+        kinds: Vec<EdgeKind>,
+        mermaid_max_nodes: Option<usize>,
+    ) -> Result<GraphExportInfo> {
+        let export_format = ExportFormat::from_name(format).ok_or_else(|| {
+            Error::config_error(format!(
+                "Invalid graph export format '{}'. Use graphml, gexf, dot, mermaid, json_graph, json or csv",
+                format
+            ))
+        })?;
+
+        let vault_path = self.manager.vault_path().clone();
+        let selection = match selection {
+            SubgraphSelection::Ego { center, hops } => SubgraphSelection::Ego {
+                center: vault_path.join(center),
+                hops,
+            },
+            other => other,
+        };
+
+        let mut attributes: HashMap<PathBuf, NoteAttributes> = HashMap::new();
+        for (path, content) in TagTools::new(self.manager.clone()).notes().await? {
+            let mut tags = note_tags(&content);
+            tags.sort();
+            tags.dedup();
+            attributes.insert(
+                vault_path.join(path),
+                NoteAttributes {
+                    tags,
+                    word_count: to_plain_text(&content).split_whitespace().count(),
+                },
+            );
+        }
+
+        let defaults = GraphExportOptions::default();
+        let options = GraphExportOptions {
+            selection,
+            kinds,
+            mermaid_max_nodes: mermaid_max_nodes.unwrap_or(defaults.mermaid_max_nodes),
+        };
+
+        let graph = self.manager.link_graph();
+        let graph_read = graph.read().await;
+        let snapshot = GraphExporter::new(&graph_read, &vault_path)
+            .with_attributes(&attributes)
+            .snapshot(&options)?;
+        let content = snapshot.render(export_format, options.mermaid_max_nodes)?;
+
+        let omitted_nodes = if export_format == ExportFormat::Mermaid {
+            snapshot
+                .nodes
+                .len()
+                .saturating_sub(options.mermaid_max_nodes)
+        } else {
+            0
+        };
+
+        Ok(GraphExportInfo {
+            format: export_format.as_str().to_string(),
+            content,
+            node_count: snapshot.nodes.len(),
+            edge_count: snapshot.edges.len(),
+            total_nodes: snapshot.total_nodes,
+            omitted_nodes,
+        })
+    }
 }
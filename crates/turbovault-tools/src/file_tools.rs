@@ -29,6 +29,18 @@ impl FileTools {
         self.manager.write_file(&file_path, content).await
     }
 
+    /// Note-schema issues for content at a path (empty when no schema applies)
+    ///
+    /// In `reject` mode failing writes never reach disk; this reports what a
+    /// `warn`-mode write let through.
+    pub async fn schema_issues(&self, path: &str, content: &str) -> Result<Vec<String>> {
+        let report = self
+            .manager
+            .validate_note_schema(&PathBuf::from(path), content)
+            .await?;
+        Ok(report.issues.into_iter().map(|i| i.message).collect())
+    }
+
     /// Edit file using SEARCH/REPLACE blocks (LLM-optimized)
     ///
     /// Uses aider-inspired git merge conflict syntax that reduces LLM laziness by 3X.
@@ -67,6 +79,7 @@ impl FileTools {
         tokio::fs::remove_file(&file_path)
             .await
             .map_err(Error::io)?;
+        self.manager.forget_file(&file_path).await?;
 
         Ok(())
     }
@@ -93,6 +106,7 @@ impl FileTools {
         tokio::fs::rename(&from_path, &to_path)
             .await
             .map_err(Error::io)?;
+        self.manager.forget_file(&from_path).await?;
 
         Ok(())
     }
@@ -1,9 +1,15 @@
 //! Graph operations and link analysis tools
 
+use crate::tag_tools::{TagTools, note_tags};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use turbovault_core::prelude::*;
-use turbovault_graph::HealthAnalyzer;
+use turbovault_graph::{
+    BrokenLinkKind, CommunityConfig, CommunityDetector, CommunityReport, EdgeKind, FolderCount,
+    HealthAnalyzer, NeighborRelation, PathDirection, TagCount,
+};
 use turbovault_vault::VaultManager;
 
 /// Graph tools context
@@ -18,6 +24,7 @@ pub struct BrokenLinkInfo {
     pub target: String,
     pub line: usize,
     pub suggestions: Vec<String>,
+    pub kind: BrokenLinkKind,
 }
 
 /// Simplified health report for JSON serialization
@@ -26,6 +33,7 @@ pub struct HealthInfo {
     pub total_notes: usize,
     pub total_links: usize,
     pub broken_links_count: usize,
+    pub broken_fragments_count: usize,
     pub orphaned_notes_count: usize,
     pub dead_end_notes_count: usize,
     pub hub_notes_count: usize,
@@ -33,37 +41,138 @@ pub struct HealthInfo {
     pub is_healthy: bool,
 }
 
+/// Community of densely linked notes, with vault-relative paths
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommunityInfo {
+    pub id: usize,
+    pub size: usize,
+    pub notes: Vec<String>,
+    /// Members with the highest PageRank, most central first
+    pub central_notes: Vec<String>,
+    pub dominant_tags: Vec<TagCount>,
+    pub folders: Vec<FolderCount>,
+    pub internal_links: usize,
+    pub external_links: usize,
+}
+
+/// Community detection result for JSON serialization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommunitiesInfo {
+    pub communities: Vec<CommunityInfo>,
+    /// Notes not in any community of at least `min_size` notes
+    pub unclustered: Vec<String>,
+    pub modularity: f64,
+    pub note_count: usize,
+}
+
+/// A note filed outside its community's dominant folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderMismatchInfo {
+    pub path: String,
+    pub folder: String,
+    pub community: usize,
+    pub expected_folder: String,
+    /// Share of the community in `expected_folder`
+    pub share: f64,
+    /// Most central notes of the community, for context
+    pub community_central_notes: Vec<String>,
+}
+
+/// One hop of a path between notes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathHopInfo {
+    pub from: String,
+    pub to: String,
+    /// `false` when the hop follows a link backwards (`to` links to `from`)
+    pub forward: bool,
+    pub kind: EdgeKind,
+    /// Link target as written
+    pub target: String,
+    /// Display text, or the target when the link has none
+    pub link_text: String,
+    pub line: usize,
+}
+
+/// A shortest path between two notes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotePathInfo {
+    pub length: usize,
+    pub notes: Vec<String>,
+    pub hops: Vec<PathHopInfo>,
+}
+
+/// A note in an ego network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EgoNodeInfo {
+    pub path: String,
+    pub distance: usize,
+}
+
+/// A link inside an ego network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EgoEdgeInfo {
+    pub source: String,
+    pub target: String,
+    pub kind: EdgeKind,
+}
+
+/// Notes within k hops of a note
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EgoNetworkInfo {
+    pub center: String,
+    /// Neighbors by distance, then path
+    pub nodes: Vec<EgoNodeInfo>,
+    pub edges: Vec<EgoEdgeInfo>,
+}
+
+/// A note linked with both notes of a pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommonNeighborInfo {
+    pub path: String,
+    pub first: NeighborRelation,
+    pub second: NeighborRelation,
+}
+
 impl GraphTools {
     /// Create new graph tools
     pub fn new(manager: Arc<VaultManager>) -> Self {
         Self { manager }
     }
 
-    /// Get detailed broken links information
+    /// Get detailed broken links information.
+    ///
+    /// Missing notes come first, followed by links whose `#Heading` or
+    /// `#^block` fragment no longer exists (see [`BrokenLinkInfo::kind`]).
     pub async fn get_broken_links(&self) -> Result<Vec<BrokenLinkInfo>> {
+        let validation = self.manager.validate_links().await?;
         let graph_lock = self.manager.link_graph();
         let graph = graph_lock.read().await;
-        let analyzer = HealthAnalyzer::new(&graph);
+        let analyzer =
+            HealthAnalyzer::with_files(&graph, &validation.links).with_anchors(&validation.anchors);
 
-        let report = analyzer.analyze()?;
+        let report = analyzer.quick_check()?;
 
         Ok(report
             .broken_links
             .into_iter()
+            .chain(report.broken_fragments)
             .map(|bl| BrokenLinkInfo {
                 source_file: bl.source_file.to_string_lossy().to_string(),
                 target: bl.target,
                 line: bl.line,
                 suggestions: bl.suggestions,
+                kind: bl.kind,
             })
             .collect())
     }
 
     /// Run quick health check
     pub async fn quick_health_check(&self) -> Result<HealthInfo> {
+        let validation = self.manager.validate_links().await?;
         let graph_lock = self.manager.link_graph();
         let graph = graph_lock.read().await;
-        let analyzer = HealthAnalyzer::new(&graph);
+        let analyzer =
+            HealthAnalyzer::with_files(&graph, &validation.links).with_anchors(&validation.anchors);
 
         let report = analyzer.quick_check()?;
 
@@ -71,6 +180,7 @@ impl GraphTools {
             total_notes: report.total_notes,
             total_links: report.total_links,
             broken_links_count: report.broken_links.len(),
+            broken_fragments_count: report.broken_fragments.len(),
             orphaned_notes_count: report.orphaned_notes.len(),
             dead_end_notes_count: 0,
             hub_notes_count: 0,
@@ -81,9 +191,11 @@ impl GraphTools {
 
     /// Run comprehensive health analysis
     pub async fn full_health_analysis(&self) -> Result<HealthInfo> {
+        let validation = self.manager.validate_links().await?;
         let graph_lock = self.manager.link_graph();
         let graph = graph_lock.read().await;
-        let analyzer = HealthAnalyzer::new(&graph);
+        let analyzer =
+            HealthAnalyzer::with_files(&graph, &validation.links).with_anchors(&validation.anchors);
 
         let report = analyzer.analyze()?;
 
@@ -91,6 +203,7 @@ impl GraphTools {
             total_notes: report.total_notes,
             total_links: report.total_links,
             broken_links_count: report.broken_links.len(),
+            broken_fragments_count: report.broken_fragments.len(),
             orphaned_notes_count: report.orphaned_notes.len(),
             dead_end_notes_count: report.dead_end_notes.len(),
             hub_notes_count: report.hub_notes.len(),
@@ -183,4 +296,195 @@ impl GraphTools {
             })
             .collect())
     }
+
+    /// Detect topic communities with the Louvain method.
+    ///
+    /// `resolution` above 1.0 favours smaller communities; communities with
+    /// fewer than `min_size` notes are listed as unclustered.
+    pub async fn get_communities(
+        &self,
+        resolution: Option<f64>,
+        min_size: Option<usize>,
+    ) -> Result<CommunitiesInfo> {
+        let report = self.detect_communities(resolution, min_size).await?;
+        let relative = |path: &PathBuf| self.relative_path(path);
+
+        Ok(CommunitiesInfo {
+            communities: report
+                .communities
+                .iter()
+                .map(|c| CommunityInfo {
+                    id: c.id,
+                    size: c.size,
+                    notes: c.notes.iter().map(relative).collect(),
+                    central_notes: c.central_notes.iter().map(relative).collect(),
+                    dominant_tags: c.dominant_tags.clone(),
+                    folders: c.folders.clone(),
+                    internal_links: c.internal_links,
+                    external_links: c.external_links,
+                })
+                .collect(),
+            unclustered: report.unclustered.iter().map(relative).collect(),
+            modularity: report.modularity,
+            note_count: report.note_count,
+        })
+    }
+
+    /// Find notes whose folder disagrees with their community.
+    ///
+    /// Only communities where at least `min_share` (0.0-1.0) of the notes
+    /// share one folder are considered.
+    pub async fn find_folder_mismatches(
+        &self,
+        min_share: f64,
+        resolution: Option<f64>,
+    ) -> Result<Vec<FolderMismatchInfo>> {
+        let report = self.detect_communities(resolution, None).await?;
+
+        Ok(report
+            .folder_mismatches(min_share)
+            .into_iter()
+            .map(|m| FolderMismatchInfo {
+                path: self.relative_path(&m.path),
+                folder: m.folder,
+                community: m.community,
+                expected_folder: m.expected_folder,
+                share: m.share,
+                community_central_notes: report.communities[m.community]
+                    .central_notes
+                    .iter()
+                    .map(|p| self.relative_path(p))
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// Shortest paths between two notes, with the link followed at each hop
+    pub async fn find_shortest_paths(
+        &self,
+        from: &str,
+        to: &str,
+        direction: PathDirection,
+        kinds: &[EdgeKind],
+        max_paths: usize,
+    ) -> Result<Vec<NotePathInfo>> {
+        let paths = self
+            .manager
+            .get_shortest_paths(Path::new(from), Path::new(to), direction, kinds, max_paths)
+            .await?;
+
+        Ok(paths
+            .into_iter()
+            .map(|path| NotePathInfo {
+                length: path.len(),
+                notes: path.notes.iter().map(|p| self.relative_path(p)).collect(),
+                hops: path
+                    .hops
+                    .into_iter()
+                    .map(|hop| PathHopInfo {
+                        from: self.relative_path(&hop.from),
+                        to: self.relative_path(&hop.to),
+                        forward: hop.forward,
+                        kind: hop.kind,
+                        target: hop.target,
+                        link_text: hop.link_text,
+                        line: hop.line,
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// Notes within `max_hops` of a note, with distances and the links among them
+    pub async fn get_ego_network(
+        &self,
+        path: &str,
+        max_hops: usize,
+        direction: PathDirection,
+        kinds: &[EdgeKind],
+    ) -> Result<EgoNetworkInfo> {
+        let ego = self
+            .manager
+            .get_ego_network(Path::new(path), max_hops, direction, kinds)
+            .await?;
+
+        Ok(EgoNetworkInfo {
+            center: self.relative_path(&ego.center),
+            nodes: ego
+                .nodes
+                .iter()
+                .map(|node| EgoNodeInfo {
+                    path: self.relative_path(&node.path),
+                    distance: node.distance,
+                })
+                .collect(),
+            edges: ego
+                .edges
+                .iter()
+                .map(|edge| EgoEdgeInfo {
+                    source: self.relative_path(&edge.source),
+                    target: self.relative_path(&edge.target),
+                    kind: edge.kind,
+                })
+                .collect(),
+        })
+    }
+
+    /// Notes linked with both `first` and `second`
+    pub async fn find_common_neighbors(
+        &self,
+        first: &str,
+        second: &str,
+        kinds: &[EdgeKind],
+    ) -> Result<Vec<CommonNeighborInfo>> {
+        let common = self
+            .manager
+            .get_common_neighbors(Path::new(first), Path::new(second), kinds)
+            .await?;
+
+        Ok(common
+            .into_iter()
+            .map(|neighbor| CommonNeighborInfo {
+                path: self.relative_path(&neighbor.path),
+                first: neighbor.first,
+                second: neighbor.second,
+            })
+            .collect())
+    }
+
+    async fn detect_communities(
+        &self,
+        resolution: Option<f64>,
+        min_size: Option<usize>,
+    ) -> Result<CommunityReport> {
+        let vault_path = self.manager.vault_path().clone();
+        let tags: HashMap<PathBuf, Vec<String>> = TagTools::new(self.manager.clone())
+            .notes()
+            .await?
+            .into_iter()
+            .map(|(path, content)| (vault_path.join(path), note_tags(&content)))
+            .collect();
+
+        let defaults = CommunityConfig::default();
+        let config = CommunityConfig {
+            resolution: resolution.unwrap_or(defaults.resolution),
+            min_size: min_size.unwrap_or(defaults.min_size),
+            ..defaults
+        };
+
+        let graph_lock = self.manager.link_graph();
+        let graph = graph_lock.read().await;
+        Ok(CommunityDetector::new(&graph)
+            .with_tags(&tags)
+            .with_vault_root(&vault_path)
+            .with_config(config)
+            .detect())
+    }
+
+    fn relative_path(&self, path: &Path) -> String {
+        path.strip_prefix(self.manager.vault_path())
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    }
 }
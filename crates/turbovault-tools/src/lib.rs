@@ -29,6 +29,8 @@
 //! - Detect orphaned notes
 //! - Analyze vault health
 //! - Find broken links
+//! - Detect topic communities and notes filed outside their community
+//! - Shortest paths, ego networks and common neighbors between notes
 //!
 //! ### Search Tools
 //!
@@ -61,6 +63,48 @@
 //! - Parse tags
 //! - Extract headers
 //! - Get file properties
+//! - Bulk-update properties of notes matching a query
+//! - List callouts by type across the vault
+//!
+//! ### Property Schema Tools
+//!
+//! [`property_schema::PropertySchemaTools`] - Frontmatter consistency:
+//! - Infer per-key types, value distributions and casing variants
+//! - Flag properties that drift across notes
+//! - Suggest an `.obsidian/types.json` schema
+//!
+//! ### Tag Tools
+//!
+//! [`tag_tools::TagTools`] - Tag management:
+//! - List the tag hierarchy with counts
+//! - Rename, merge and delete tags in frontmatter and note bodies
+//! - Preview changes before an atomic rewrite
+//!
+//! ### Mention Tools
+//!
+//! [`mention_tools::MentionTools`] - Unlinked mentions:
+//! - Find plain-text mentions of note titles and aliases
+//! - Convert selected mentions into wikilinks as one batch
+//!
+//! ### Canvas Tools
+//!
+//! [`canvas_tools::CanvasTools`] - JSON Canvas files:
+//! - Read a canvas with its spec violations
+//! - Create canvases and add text, file, link and group nodes
+//! - Connect nodes by id or by the file they show
+//!
+//! ### Base Tools
+//!
+//! [`base_tools::BaseTools`] - Obsidian Bases (`.base`) views:
+//! - List bases with their views and what cannot be evaluated
+//! - Evaluate a view's filters, columns, sort and limit against note metadata
+//!
+//! ### Table Tools
+//!
+//! [`table_tools::TableTools`] - Markdown tables:
+//! - List the tables in a note by index and heading
+//! - Read a table as JSON records keyed by header
+//! - Update, insert and delete rows or columns by header name
 //!
 //! ### Validation Tools
 //!
@@ -84,6 +128,8 @@
 //! - Find note connections
 //! - Build relationship maps
 //! - Analyze link patterns
+//! - Suggest links from content, tag and link similarity
+//!   ([`similarity::TfIdfIndex`])
 //!
 //! ### Template Tools
 //!
@@ -167,32 +213,66 @@
 //! See [`turbovault_core::error`] for error types.
 
 pub mod analysis_tools;
+pub mod base_tools;
 pub mod batch_tools;
+pub mod canvas_tools;
 pub mod export_tools;
 pub mod file_tools;
 pub mod graph_tools;
+pub mod mention_tools;
 pub mod metadata_tools;
 pub mod output_formatter;
+pub mod property_schema;
 pub mod relationship_tools;
 pub mod response_utils;
 pub mod search_engine;
 pub mod search_tools;
+pub mod similarity;
+pub mod table_tools;
+pub mod tag_tools;
 pub mod templates;
 pub mod validation_tools;
 pub mod vault_lifecycle;
 
 pub use analysis_tools::{AnalysisTools, VaultStats};
+pub use base_tools::{
+    BaseColumn, BaseQueryResult, BaseRow, BaseSummary, BaseTools, BaseViewSummary,
+};
 pub use batch_tools::BatchTools;
-pub use export_tools::ExportTools;
+pub use canvas_tools::{CanvasInfo, CanvasTools, NewCanvasEdge, NewCanvasNode};
+pub use export_tools::{ExportTools, GraphExportInfo};
 pub use file_tools::FileTools;
-pub use graph_tools::{BrokenLinkInfo, GraphTools, HealthInfo};
-pub use metadata_tools::MetadataTools;
+pub use graph_tools::{
+    BrokenLinkInfo, CommonNeighborInfo, CommunitiesInfo, CommunityInfo, EgoEdgeInfo,
+    EgoNetworkInfo, EgoNodeInfo, FolderMismatchInfo, GraphTools, HealthInfo, NotePathInfo,
+    PathHopInfo,
+};
+pub use mention_tools::{
+    MentionLinkReport, MentionTools, NoteMentions, UnlinkedMention, UnlinkedMentionsReport,
+};
+pub use metadata_tools::{
+    BulkPropertyUpdate, CalloutEntry, CalloutListing, MetadataTools, PropertyMutation, QueryFilter,
+};
 pub use output_formatter::{OutputFormat, ResponseFormatter};
+pub use property_schema::{
+    ObsidianPropertyType, PropertyProfile, PropertySchemaReport, PropertySchemaTools,
+};
 pub use relationship_tools::RelationshipTools;
 pub use search_engine::{SearchEngine, SearchQuery, SearchResultInfo};
-pub use search_tools::SearchTools;
+pub use search_tools::{LinkEdgeInfo, SearchTools};
+pub use similarity::TfIdfIndex;
+pub use table_tools::{
+    RowSelector, TableData, TableOperation, TableSummary, TableTools, TableUpdateReport,
+};
+pub use tag_tools::{
+    RelatedTagsReport, TagBridgesReport, TagChangeReport, TagHierarchy, TagNode, TagTools,
+};
 pub use templates::{TemplateDefinition, TemplateEngine, TemplateFieldType};
 pub use turbovault_batch::{BatchOperation, BatchResult};
 pub use turbovault_core::prelude::*;
+pub use turbovault_export::SubgraphSelection;
+pub use turbovault_graph::{
+    BrokenLinkKind, CentralityMetric, EdgeKind, NeighborRelation, PathDirection,
+};
 pub use validation_tools::{ValidationReportInfo, ValidationTools};
 pub use vault_lifecycle::VaultLifecycleTools;
@@ -0,0 +1,648 @@
+//! Unlinked mentions: plain-text occurrences of note titles and aliases
+//!
+//! A mention is a case-insensitive, whole-word occurrence of another note's
+//! file name or one of its frontmatter `aliases`. Frontmatter, code blocks,
+//! inline code, HTML, tags and existing links are never scanned. Mentions can
+//! be turned into `[[Note|original text]]` wikilinks; all changed notes are
+//! written as one batch, restored if any write fails, with a dry-run preview.
+
+use crate::batch_tools::{BatchTools, NoteRewrite};
+use crate::table_tools::table_ranges;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use std::sync::Arc;
+use turbovault_batch::BatchResult;
+use turbovault_core::prelude::*;
+use turbovault_core::{LineIndex, SourcePosition};
+use turbovault_parser::{ParsedContent, excluded_ranges};
+use turbovault_vault::VaultManager;
+
+/// Names shorter than this (in characters) are not matched by default
+pub const DEFAULT_MIN_NAME_LENGTH: usize = 3;
+
+/// Characters of surrounding text kept on each side of a mention
+const CONTEXT_CHARS: usize = 60;
+
+/// One plain-text mention of a note
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnlinkedMention {
+    /// `source:line:column`, used to select mentions for linking
+    pub id: String,
+    /// Mentioning note, relative to vault root
+    pub source: String,
+    pub line: usize,
+    /// 1-based byte column
+    pub column: usize,
+    /// Text as written in the source note
+    pub text: String,
+    /// Surrounding text on the same line
+    pub context: String,
+}
+
+/// Unlinked mentions of one note
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteMentions {
+    /// Mentioned note, relative to vault root
+    pub target: String,
+    /// Title and aliases that were searched for
+    pub names: Vec<String>,
+    pub mentions: Vec<UnlinkedMention>,
+}
+
+/// Unlinked mentions across the vault
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlinkedMentionsReport {
+    pub total_mentions: usize,
+    /// Notes with at least one mention, most mentioned first
+    pub notes: Vec<NoteMentions>,
+}
+
+/// One mention turned into a wikilink
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MentionLinkChange {
+    pub id: String,
+    pub line: usize,
+    pub text: String,
+    pub replacement: String,
+}
+
+/// Links planned in one note
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteMentionChanges {
+    pub path: String,
+    pub changes: Vec<MentionLinkChange>,
+}
+
+/// Result of linking mentions (or its preview)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MentionLinkReport {
+    pub target: String,
+    pub dry_run: bool,
+    pub notes_changed: usize,
+    pub mentions_linked: usize,
+    pub notes: Vec<NoteMentionChanges>,
+    /// Selected ids that no longer match a mention
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub not_found: Vec<String>,
+    /// Batch execution result (absent for dry runs or when nothing changes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch: Option<BatchResult>,
+}
+
+/// A note that can be mentioned
+struct Target {
+    path: String,
+    /// Wikilink target: the file name, or the path when names collide
+    link: String,
+    names: Vec<String>,
+}
+
+/// A mention located in a note's content
+struct Found {
+    target: usize,
+    range: Range<usize>,
+    mention: UnlinkedMention,
+}
+
+/// Unlinked mention tools context
+pub struct MentionTools {
+    pub manager: Arc<VaultManager>,
+}
+
+impl MentionTools {
+    /// Create new mention tools
+    pub fn new(manager: Arc<VaultManager>) -> Self {
+        Self { manager }
+    }
+
+    /// Find unlinked mentions of every note, or only of `target`
+    pub async fn find_unlinked_mentions(
+        &self,
+        target: Option<&str>,
+        min_name_length: usize,
+    ) -> Result<UnlinkedMentionsReport> {
+        let notes = self.notes().await?;
+        let targets = targets(&notes, min_name_length);
+        let only = target.map(|t| resolve_target(&targets, t)).transpose()?;
+
+        let mut by_target: BTreeMap<usize, Vec<UnlinkedMention>> = BTreeMap::new();
+        if let Some(matcher) = Matcher::new(&targets, only)? {
+            for (path, content) in &notes {
+                for found in matcher.scan(path, content, &targets) {
+                    by_target
+                        .entry(found.target)
+                        .or_default()
+                        .push(found.mention);
+                }
+            }
+        }
+
+        let mut report_notes: Vec<NoteMentions> = by_target
+            .into_iter()
+            .map(|(index, mentions)| NoteMentions {
+                target: targets[index].path.clone(),
+                names: targets[index].names.clone(),
+                mentions,
+            })
+            .collect();
+        report_notes.sort_by(|a, b| {
+            b.mentions
+                .len()
+                .cmp(&a.mentions.len())
+                .then_with(|| a.target.cmp(&b.target))
+        });
+
+        Ok(UnlinkedMentionsReport {
+            total_mentions: report_notes.iter().map(|n| n.mentions.len()).sum(),
+            notes: report_notes,
+        })
+    }
+
+    /// Turn mentions of `target` into `[[Note|original text]]` wikilinks
+    ///
+    /// `ids` selects mentions from [`Self::find_unlinked_mentions`]; empty
+    /// links every mention. Mentions are located again in the current
+    /// content, so ids of notes edited since are reported in `not_found`.
+    /// If a write fails, the notes already written are restored and an error
+    /// is returned.
+    pub async fn link_mentions(
+        &self,
+        target: &str,
+        ids: &[String],
+        min_name_length: usize,
+        dry_run: bool,
+    ) -> Result<MentionLinkReport> {
+        let notes = self.notes().await?;
+        let targets = targets(&notes, min_name_length);
+        let index = resolve_target(&targets, target)?;
+        let link = &targets[index].link;
+
+        let mut report_notes = Vec::new();
+        let mut rewrites = Vec::new();
+        let mut matched_ids = Vec::new();
+
+        if let Some(matcher) = Matcher::new(&targets, Some(index))? {
+            for (path, content) in &notes {
+                let found: Vec<Found> = matcher
+                    .scan(path, content, &targets)
+                    .into_iter()
+                    .filter(|f| ids.is_empty() || ids.contains(&f.mention.id))
+                    .collect();
+                if found.is_empty() {
+                    continue;
+                }
+
+                let tables = table_ranges(content);
+                let mut updated = content.clone();
+                let mut changes = Vec::new();
+                for f in found.iter().rev() {
+                    // An unescaped alias pipe would split the table cell
+                    let in_table = tables.iter().any(|t| t.contains(&f.range.start));
+                    let separator = if in_table { "\\|" } else { "|" };
+                    let replacement = if f.mention.text == *link {
+                        format!("[[{}]]", link)
+                    } else {
+                        format!("[[{}{}{}]]", link, separator, f.mention.text)
+                    };
+                    updated.replace_range(f.range.clone(), &replacement);
+                    changes.push(MentionLinkChange {
+                        id: f.mention.id.clone(),
+                        line: f.mention.line,
+                        text: f.mention.text.clone(),
+                        replacement,
+                    });
+                }
+                changes.reverse();
+                matched_ids.extend(changes.iter().map(|c| c.id.clone()));
+
+                rewrites.push(NoteRewrite {
+                    path: path.clone(),
+                    original: content.clone(),
+                    content: updated,
+                });
+                report_notes.push(NoteMentionChanges {
+                    path: path.clone(),
+                    changes,
+                });
+            }
+        }
+
+        let not_found = ids
+            .iter()
+            .filter(|id| !matched_ids.contains(id))
+            .cloned()
+            .collect();
+
+        let batch = if dry_run || rewrites.is_empty() {
+            None
+        } else {
+            Some(
+                BatchTools::new(self.manager.clone())
+                    .rewrite_notes(rewrites)
+                    .await?,
+            )
+        };
+
+        Ok(MentionLinkReport {
+            target: targets[index].path.clone(),
+            dry_run,
+            notes_changed: report_notes.len(),
+            mentions_linked: report_notes.iter().map(|n| n.changes.len()).sum(),
+            notes: report_notes,
+            not_found,
+            batch,
+        })
+    }
+
+    /// Raw content of every markdown note, keyed by vault-relative path
+    async fn notes(&self) -> Result<Vec<(String, String)>> {
+        let mut files = self.manager.scan_vault().await?;
+        files.sort();
+
+        let mut notes = Vec::new();
+        for file_path in files {
+            if !file_path.to_string_lossy().to_lowercase().ends_with(".md") {
+                continue;
+            }
+            let content = self.manager.read_file(&file_path).await?;
+            let path = file_path
+                .strip_prefix(self.manager.vault_path())
+                .unwrap_or(&file_path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            notes.push((path, content));
+        }
+        Ok(notes)
+    }
+}
+
+/// Every note with its title and aliases of at least `min_name_length` characters
+fn targets(notes: &[(String, String)], min_name_length: usize) -> Vec<Target> {
+    let stem = |path: &str| -> String {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        name.strip_suffix(".md").unwrap_or(name).to_string()
+    };
+    let mut stem_counts: HashMap<String, usize> = HashMap::new();
+    for (path, _) in notes {
+        *stem_counts.entry(stem(path).to_lowercase()).or_insert(0) += 1;
+    }
+
+    notes
+        .iter()
+        .map(|(path, content)| {
+            let title = stem(path);
+            let link = if stem_counts[&title.to_lowercase()] > 1 {
+                path.strip_suffix(".md").unwrap_or(path).to_string()
+            } else {
+                title.clone()
+            };
+
+            let aliases = ParsedContent::parse(content)
+                .frontmatter
+                .map(|fm| fm.aliases())
+                .unwrap_or_default();
+            let mut names: Vec<String> = Vec::new();
+            for name in std::iter::once(title).chain(aliases) {
+                let name = name.trim().to_string();
+                if name.chars().count() >= min_name_length.max(1)
+                    && !names.iter().any(|n| n.eq_ignore_ascii_case(&name))
+                {
+                    names.push(name);
+                }
+            }
+
+            Target {
+                path: path.clone(),
+                link,
+                names,
+            }
+        })
+        .collect()
+}
+
+/// Find a note by path (with or without `.md`) or by unique title
+fn resolve_target(targets: &[Target], wanted: &str) -> Result<usize> {
+    let wanted = wanted.trim().trim_start_matches("./").replace('\\', "/");
+    let with_ext = if wanted.to_lowercase().ends_with(".md") {
+        wanted.clone()
+    } else {
+        format!("{}.md", wanted)
+    };
+    if let Some(index) = targets.iter().position(|t| t.path == with_ext) {
+        return Ok(index);
+    }
+
+    let by_title: Vec<usize> = targets
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| {
+            t.path
+                .rsplit('/')
+                .next()
+                .is_some_and(|name| name.eq_ignore_ascii_case(&with_ext))
+        })
+        .map(|(i, _)| i)
+        .collect();
+    match by_title.as_slice() {
+        [index] => Ok(*index),
+        [] => Err(Error::not_found(format!("Note not found: {}", wanted))),
+        _ => Err(Error::config_error(format!(
+            "'{}' matches several notes; use a vault-relative path",
+            wanted
+        ))),
+    }
+}
+
+/// One case-insensitive regex over every searched name, longest first
+struct Matcher {
+    regex: Regex,
+    /// Lowercased name to the notes it refers to
+    owners: HashMap<String, Vec<usize>>,
+}
+
+impl Matcher {
+    fn new(targets: &[Target], only: Option<usize>) -> Result<Option<Self>> {
+        let mut owners: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, target) in targets.iter().enumerate() {
+            if only.is_some_and(|o| o != index) {
+                continue;
+            }
+            for name in &target.names {
+                owners.entry(name.to_lowercase()).or_default().push(index);
+            }
+        }
+        if owners.is_empty() {
+            return Ok(None);
+        }
+
+        let mut names: Vec<&String> = owners.keys().collect();
+        names.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        // Word-character edges carry `\b` so a longer name failing the boundary
+        // check does not hide a shorter one at the same offset ("Foo" in
+        // "Foo Barn" next to a "Foo Bar" note)
+        let pattern = names
+            .iter()
+            .map(|name| {
+                let start = if name.starts_with(is_word_char) {
+                    r"\b"
+                } else {
+                    ""
+                };
+                let end = if name.ends_with(is_word_char) {
+                    r"\b"
+                } else {
+                    ""
+                };
+                format!("{}{}{}", start, regex::escape(name), end)
+            })
+            .collect::<Vec<_>>()
+            .join("|");
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(true)
+            .size_limit(1 << 26)
+            .build()
+            .map_err(|e| Error::other(format!("Failed to build mention matcher: {}", e)))?;
+        Ok(Some(Self { regex, owners }))
+    }
+
+    /// Mentions in one note, in content order
+    fn scan(&self, path: &str, content: &str, targets: &[Target]) -> Vec<Found> {
+        let skipped = skipped_ranges(content);
+        let index = LineIndex::new(content);
+        let mut found = Vec::new();
+
+        for m in self.regex.find_iter(content) {
+            let range = m.range();
+            if !is_word_boundary(content, range.start, range.end)
+                || skipped
+                    .iter()
+                    .any(|r| r.start < range.end && range.start < r.end)
+                || in_url(content, range.start)
+            {
+                continue;
+            }
+            let Some(owners) = self.owners.get(&m.as_str().to_lowercase()) else {
+                continue;
+            };
+
+            let position = SourcePosition::from_offset_indexed(&index, range.start, range.len());
+            for &target in owners {
+                if targets[target].path == path {
+                    continue;
+                }
+                found.push(Found {
+                    target,
+                    range: range.clone(),
+                    mention: UnlinkedMention {
+                        id: format!("{}:{}:{}", path, position.line, position.column),
+                        source: path.to_string(),
+                        line: position.line,
+                        column: position.column,
+                        text: m.as_str().to_string(),
+                        context: context(content, range.clone()),
+                    },
+                });
+            }
+        }
+        found
+    }
+}
+
+/// Frontmatter, code, HTML, links and tags
+fn skipped_ranges(content: &str) -> Vec<Range<usize>> {
+    let parsed = ParsedContent::parse(content);
+    let mut ranges = excluded_ranges(content);
+    ranges.extend(
+        parsed
+            .wikilinks
+            .iter()
+            .chain(&parsed.embeds)
+            .chain(&parsed.markdown_links)
+            .map(|l| l.position.offset..l.position.offset + l.position.length),
+    );
+    ranges.extend(
+        parsed
+            .tags
+            .iter()
+            .map(|t| t.position.offset..t.position.offset + t.position.length),
+    );
+    ranges
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Whether `start..end` is not part of a longer word
+fn is_word_boundary(content: &str, start: usize, end: usize) -> bool {
+    let before = content[..start].chars().next_back();
+    let after = content[end..].chars().next();
+    !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+}
+
+/// Whether the whitespace-delimited token around `offset` is a URL
+fn in_url(content: &str, offset: usize) -> bool {
+    let start = content[..offset]
+        .rfind(char::is_whitespace)
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let end = content[offset..]
+        .find(char::is_whitespace)
+        .map(|i| offset + i)
+        .unwrap_or(content.len());
+    content[start..end].contains("://")
+}
+
+/// Up to [`CONTEXT_CHARS`] characters on each side, within the line
+fn context(content: &str, range: Range<usize>) -> String {
+    let line_start = content[..range.start]
+        .rfind('\n')
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let line_end = content[range.end..]
+        .find('\n')
+        .map(|i| range.end + i)
+        .unwrap_or(content.len());
+
+    let before: String = {
+        let chars: Vec<char> = content[line_start..range.start].chars().collect();
+        let skip = chars.len().saturating_sub(CONTEXT_CHARS);
+        let text: String = chars[skip..].iter().collect();
+        if skip > 0 {
+            format!("…{}", text)
+        } else {
+            text
+        }
+    };
+    let after: String = {
+        let text = &content[range.end..line_end];
+        let kept: String = text.chars().take(CONTEXT_CHARS).collect();
+        if kept.len() < text.len() {
+            format!("{}…", kept)
+        } else {
+            kept
+        }
+    };
+    format!("{}{}{}", before, &content[range], after)
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notes(entries: &[(&str, &str)]) -> Vec<(String, String)> {
+        entries
+            .iter()
+            .map(|(p, c)| (p.to_string(), c.to_string()))
+            .collect()
+    }
+
+    fn mentions(entries: &[(&str, &str)], target: usize) -> Vec<(String, String)> {
+        let notes = notes(entries);
+        let targets = targets(&notes, DEFAULT_MIN_NAME_LENGTH);
+        let matcher = Matcher::new(&targets, Some(target)).unwrap().unwrap();
+        notes
+            .iter()
+            .flat_map(|(path, content)| matcher.scan(path, content, &targets))
+            .map(|f| (f.mention.source, f.mention.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_titles_and_aliases_on_word_boundaries() {
+        let vault = [
+            (
+                "Rust.md",
+                "---\naliases: [rustlang, \"Rust language\"]\n---\n# Rust\n",
+            ),
+            (
+                "a.md",
+                "I like rust. The RUST LANGUAGE is great; rustlang too.\nNot rusty or trust.",
+            ),
+            ("b.md", "Rust_lang isn't it, but (Rust) is."),
+        ];
+        let found = mentions(&vault, 0);
+        assert_eq!(
+            found,
+            vec![
+                ("a.md".to_string(), "rust".to_string()),
+                ("a.md".to_string(), "RUST LANGUAGE".to_string()),
+                ("a.md".to_string(), "rustlang".to_string()),
+                ("b.md".to_string(), "Rust".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shorter_name_after_longer_fails_boundary() {
+        let vault = [
+            ("Foo.md", "# Foo\n"),
+            ("Foo Bar.md", "# Foo Bar\n"),
+            ("a.md", "Foo Barn, then Foo Bar."),
+        ];
+        let notes = notes(&vault);
+        let targets = targets(&notes, DEFAULT_MIN_NAME_LENGTH);
+        let matcher = Matcher::new(&targets, None).unwrap().unwrap();
+        let found: Vec<(String, String)> = matcher
+            .scan("a.md", &notes[2].1, &targets)
+            .into_iter()
+            .map(|f| (targets[f.target].path.clone(), f.mention.text))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Foo.md".to_string(), "Foo".to_string()),
+                ("Foo Bar.md".to_string(), "Foo Bar".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_skips_code_links_tags_frontmatter_and_urls() {
+        let vault = [
+            ("Project.md", "# Project\n"),
+            (
+                "a.md",
+                "---\ntitle: Project\n---\n`Project` and\n```\nProject\n```\n[[Project]] [Project](Project.md) #project https://x.io/project\nfinal project",
+            ),
+        ];
+        let found = mentions(&vault, 0);
+        assert_eq!(found, vec![("a.md".to_string(), "project".to_string())]);
+    }
+
+    #[test]
+    fn test_short_names_self_mentions_and_collisions() {
+        let vault = [
+            ("AI.md", "# AI\nAI is mentioned here"),
+            ("x/Plan.md", "Plan"),
+            ("y/Plan.md", "See the plan"),
+        ];
+        let notes = notes(&vault);
+        let targets = targets(&notes, DEFAULT_MIN_NAME_LENGTH);
+        assert!(targets[0].names.is_empty());
+        assert_eq!(targets[1].link, "x/Plan");
+
+        // y/Plan mentions x/Plan (and not itself)
+        let found = mentions(&vault, 1);
+        assert_eq!(found, vec![("y/Plan.md".to_string(), "plan".to_string())]);
+
+        assert_eq!(resolve_target(&targets, "x/Plan").unwrap(), 1);
+        assert!(resolve_target(&targets, "plan").is_err());
+        assert!(resolve_target(&targets, "missing").is_err());
+    }
+
+    #[test]
+    fn test_context_window() {
+        let content = format!("{}Target{}\nnext", "a".repeat(100), " b".repeat(50));
+        let start = content.find("Target").unwrap();
+        let snippet = context(&content, start..start + 6);
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.ends_with('…'));
+        assert!(snippet.contains("Target"));
+        assert!(!snippet.contains("next"));
+    }
+}
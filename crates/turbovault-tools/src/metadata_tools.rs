@@ -1,107 +1,780 @@
-//! Metadata query tools for finding and extracting file metadata
+//! Metadata query tools for finding, extracting and bulk-updating file metadata
 
-use serde_json::{Value, json};
-use std::collections::HashMap;
+use crate::batch_tools::{BatchTools, NoteRewrite};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::sync::Arc;
+use turbovault_batch::BatchResult;
 use turbovault_core::prelude::*;
-use turbovault_vault::VaultManager;
+use turbovault_vault::{FrontmatterDocument, VaultManager};
+
+/// Comparison operator used by date filters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Less => ordering == Ordering::Less,
+            Comparison::LessOrEqual => ordering != Ordering::Greater,
+            Comparison::Greater => ordering == Ordering::Greater,
+            Comparison::GreaterOrEqual => ordering != Ordering::Less,
+        }
+    }
+}
 
 /// Metadata query filter
+///
+/// Keys may use dot notation (`project.owner`) to reach nested YAML values;
+/// a literal key containing dots takes precedence over the nested path.
 #[derive(Debug, Clone)]
 pub enum QueryFilter {
     Equals(String, Value),
     GreaterThan(String, f64),
     LessThan(String, f64),
+    GreaterOrEqual(String, f64),
+    LessOrEqual(String, f64),
+    /// Substring match for strings, element match for lists (e.g. `tags`, `aliases`)
     Contains(String, String),
+    /// Value (or any list element) equals one of the candidates
+    In(String, Vec<Value>),
+    /// Value (or any list element) matches the regular expression
+    Matches(String, Regex),
+    /// Key is present in the frontmatter
+    Exists(String),
+    /// Value parsed as a date compares against the given instant
+    DateCompare(String, Comparison, NaiveDateTime),
     And(Vec<QueryFilter>),
     Or(Vec<QueryFilter>),
+    Not(Box<QueryFilter>),
 }
 
 impl QueryFilter {
+    /// Parse a query pattern (see [`parse_query`] for the syntax)
+    pub fn parse(pattern: &str) -> Result<Self> {
+        parse_query(pattern)
+    }
+
     /// Check if metadata matches this filter
-    fn matches(&self, metadata: &HashMap<String, Value>) -> bool {
+    pub fn matches(&self, metadata: &HashMap<String, Value>) -> bool {
         match self {
-            QueryFilter::Equals(key, expected) => metadata.get(key) == Some(expected),
+            QueryFilter::Equals(key, expected) => {
+                lookup(metadata, key).is_some_and(|v| values_equal(v, expected))
+            }
             QueryFilter::GreaterThan(key, threshold) => {
-                if let Some(Value::Number(num)) = metadata.get(key)
-                    && let Some(n) = num.as_f64()
-                {
-                    return n > *threshold;
-                }
-                false
+                lookup_number(metadata, key).is_some_and(|n| n > *threshold)
             }
             QueryFilter::LessThan(key, threshold) => {
-                if let Some(Value::Number(num)) = metadata.get(key)
-                    && let Some(n) = num.as_f64()
-                {
-                    return n < *threshold;
-                }
-                false
+                lookup_number(metadata, key).is_some_and(|n| n < *threshold)
+            }
+            QueryFilter::GreaterOrEqual(key, threshold) => {
+                lookup_number(metadata, key).is_some_and(|n| n >= *threshold)
+            }
+            QueryFilter::LessOrEqual(key, threshold) => {
+                lookup_number(metadata, key).is_some_and(|n| n <= *threshold)
             }
-            QueryFilter::Contains(key, substring) => metadata
-                .get(key)
-                .and_then(|v| v.as_str())
-                .map(|s| s.contains(substring))
-                .unwrap_or(false),
+            QueryFilter::Contains(key, needle) => match lookup(metadata, key) {
+                Some(Value::Array(items)) => {
+                    let needle = needle.trim_start_matches('#');
+                    items.iter().any(|item| {
+                        scalar_text(item).is_some_and(|t| t.trim_start_matches('#') == needle)
+                    })
+                }
+                Some(value) => scalar_text(value).is_some_and(|t| t.contains(needle.as_str())),
+                None => false,
+            },
+            QueryFilter::In(key, candidates) => lookup(metadata, key).is_some_and(|v| {
+                any_element(v, |item| candidates.iter().any(|c| values_equal(item, c)))
+            }),
+            QueryFilter::Matches(key, regex) => lookup(metadata, key).is_some_and(|v| {
+                any_element(v, |item| {
+                    scalar_text(item).is_some_and(|t| regex.is_match(&t))
+                })
+            }),
+            QueryFilter::Exists(key) => lookup(metadata, key).is_some(),
+            QueryFilter::DateCompare(key, op, instant) => lookup(metadata, key)
+                .and_then(scalar_text)
+                .and_then(|t| parse_date(&t))
+                .is_some_and(|date| op.holds(date.cmp(instant))),
             QueryFilter::And(filters) => filters.iter().all(|f| f.matches(metadata)),
             QueryFilter::Or(filters) => filters.iter().any(|f| f.matches(metadata)),
+            QueryFilter::Not(filter) => !filter.matches(metadata),
+        }
+    }
+}
+
+/// Resolve a key against frontmatter, falling back to dot-separated nested lookup
+pub(crate) fn lookup<'a>(metadata: &'a HashMap<String, Value>, key: &str) -> Option<&'a Value> {
+    if let Some(value) = metadata.get(key) {
+        return Some(value);
+    }
+
+    let mut parts = key.split('.');
+    let mut current = metadata.get(parts.next()?)?;
+    for part in parts {
+        current = match current {
+            Value::Object(map) => map.get(part)?,
+            Value::Array(items) => items.get(part.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+fn lookup_number(metadata: &HashMap<String, Value>, key: &str) -> Option<f64> {
+    match lookup(metadata, key)? {
+        Value::Number(num) => num.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    }
+}
+
+/// Text form of a scalar value (strings unquoted); `None` for objects, arrays and null
+pub(crate) fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Apply a predicate to a value, or to each element when the value is a list
+fn any_element(value: &Value, predicate: impl Fn(&Value) -> bool) -> bool {
+    match value {
+        Value::Array(items) => items.iter().any(predicate),
+        other => predicate(other),
+    }
+}
+
+/// Equality that tolerates YAML scalars typed differently from the query literal
+/// (e.g. `priority: "3"` in frontmatter against `priority: 3` in the query)
+fn values_equal(actual: &Value, expected: &Value) -> bool {
+    if actual == expected {
+        return true;
+    }
+    match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        (Value::String(_), Value::Number(_) | Value::Bool(_))
+        | (Value::Number(_) | Value::Bool(_), Value::String(_)) => {
+            scalar_text(actual) == scalar_text(expected)
+        }
+        _ => false,
+    }
+}
+
+/// Parse a frontmatter date (`2024-01-31`, `2024-01-31T09:30`, RFC 3339, ...).
+/// Date-only values compare as midnight.
+fn parse_date(text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(text) {
+        return Some(dt.naive_utc());
+    }
+    for format in [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(text, format) {
+            return Some(dt);
         }
     }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+}
+
+/// Filter for `key <op> value` with `op` one of `>`, `>=`, `<`, `<=`.
+/// Numbers compare numerically; anything else must be a date, `today` or `now`.
+pub(crate) fn comparison_filter(key: String, op: &str, value: &Value) -> Option<QueryFilter> {
+    if let Value::Number(num) = value
+        && let Some(n) = num.as_f64()
+    {
+        return Some(match op {
+            ">" => QueryFilter::GreaterThan(key, n),
+            ">=" => QueryFilter::GreaterOrEqual(key, n),
+            "<" => QueryFilter::LessThan(key, n),
+            _ => QueryFilter::LessOrEqual(key, n),
+        });
+    }
+
+    let text = scalar_text(value)?;
+    let instant = match text.to_ascii_lowercase().as_str() {
+        "today" => Local::now().date_naive().and_hms_opt(0, 0, 0),
+        "now" => Some(Local::now().naive_local()),
+        _ => parse_date(&text),
+    }?;
+
+    let comparison = match op {
+        ">" => Comparison::Greater,
+        ">=" => Comparison::GreaterOrEqual,
+        "<" => Comparison::Less,
+        _ => Comparison::LessOrEqual,
+    };
+    Some(QueryFilter::DateCompare(key, comparison, instant))
 }
 
-/// Parse simple query patterns
-/// Examples:
-/// - 'status: "draft"' → Equals("status", String("draft"))
-/// - 'priority > 3' → GreaterThan("priority", 3.0)
-/// - 'priority < 5' → LessThan("priority", 5.0)
-/// - 'tags: contains("important")' → Contains("tags", "important")
-fn parse_query(pattern: &str) -> Result<QueryFilter> {
-    let pattern = pattern.trim();
-
-    // Try: key: "value" (equals string)
-    if let Some(colon_pos) = pattern.find(':') {
-        let key = pattern[..colon_pos].trim();
-        let rest = pattern[colon_pos + 1..].trim();
-
-        // Check for string literal
-        if rest.starts_with('"') && rest.ends_with('"') {
-            let value = rest[1..rest.len() - 1].to_string();
-            return Ok(QueryFilter::Equals(key.to_string(), Value::String(value)));
-        }
-
-        // Check for contains()
-        if rest.starts_with("contains(") && rest.ends_with(")") {
-            let inner = &rest[9..rest.len() - 1];
-            if inner.starts_with('"') && inner.ends_with('"') {
-                let substring = inner[1..inner.len() - 1].to_string();
-                return Ok(QueryFilter::Contains(key.to_string(), substring));
+/// Lexical token of the query language
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    /// Operator symbol (`:`, `=`, `==`, `!=`, `>`, `>=`, `<`, `<=`, `=~`, `!`, `&&`, `||`)
+    Op(&'static str),
+    /// Quoted string literal
+    Str(String),
+    /// Bare word: key, keyword, number, date or unquoted value
+    Word(String),
+}
+
+const OPERATORS: [&str; 12] = [
+    ">=", "<=", "!=", "==", "=~", "&&", "||", ":", "=", ">", "<", "!",
+];
+
+pub(crate) fn tokenize(pattern: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = pattern;
+
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let single = match c {
+            '(' => Some(Token::LParen),
+            ')' => Some(Token::RParen),
+            '[' => Some(Token::LBracket),
+            ']' => Some(Token::RBracket),
+            ',' => Some(Token::Comma),
+            _ => None,
+        };
+        if let Some(token) = single {
+            tokens.push(token);
+            rest = &rest[1..];
+            continue;
+        }
+
+        if c == '"' || c == '\'' {
+            let mut literal = String::new();
+            let mut chars = rest[1..].char_indices();
+            let mut closed_at = None;
+            while let Some((i, ch)) = chars.next() {
+                match ch {
+                    '\\' => {
+                        if let Some((_, escaped)) = chars.next() {
+                            literal.push(escaped);
+                        }
+                    }
+                    ch if ch == c => {
+                        closed_at = Some(i);
+                        break;
+                    }
+                    ch => literal.push(ch),
+                }
             }
+            let end = closed_at.ok_or_else(|| {
+                Error::config_error(format!("Unterminated string in query: {}", pattern))
+            })?;
+            tokens.push(Token::Str(literal));
+            rest = &rest[end + 2..];
+            continue;
         }
+
+        if let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(**op)) {
+            tokens.push(Token::Op(op));
+            rest = &rest[op.len()..];
+            continue;
+        }
+
+        let end = rest
+            .find(|ch: char| ch.is_whitespace() || "()[],\"':=<>!&|".contains(ch))
+            .unwrap_or(rest.len());
+        if end == 0 {
+            return Err(Error::config_error(format!(
+                "Unexpected character '{}' in query: {}",
+                c, pattern
+            )));
+        }
+        tokens.push(Token::Word(rest[..end].to_string()));
+        rest = &rest[end..];
     }
 
-    // Try: key > number
-    if let Some(gt_pos) = pattern.find(" > ") {
-        let key = pattern[..gt_pos].trim();
-        let rest = pattern[gt_pos + 3..].trim();
-        if let Ok(num) = rest.parse::<f64>() {
-            return Ok(QueryFilter::GreaterThan(key.to_string(), num));
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over query tokens
+struct QueryParser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    pattern: &'a str,
+}
+
+impl<'a> QueryParser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn error(&self, reason: &str) -> Error {
+        Error::config_error(format!(
+            "Unable to parse query pattern: {} ({})",
+            self.pattern, reason
+        ))
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    fn expect(&mut self, expected: Token, what: &str) -> Result<()> {
+        if self.next() == Some(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {}", what)))
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<QueryFilter> {
+        let mut terms = vec![self.parse_and()?];
+        while self.peek_keyword("or") || self.peek() == Some(&Token::Op("||")) {
+            self.pos += 1;
+            terms.push(self.parse_and()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            QueryFilter::Or(terms)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<QueryFilter> {
+        let mut terms = vec![self.parse_unary()?];
+        while self.peek_keyword("and") || self.peek() == Some(&Token::Op("&&")) {
+            self.pos += 1;
+            terms.push(self.parse_unary()?);
+        }
+        Ok(if terms.len() == 1 {
+            terms.remove(0)
+        } else {
+            QueryFilter::And(terms)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<QueryFilter> {
+        if self.peek_keyword("not") || self.peek() == Some(&Token::Op("!")) {
+            self.pos += 1;
+            return Ok(QueryFilter::Not(Box::new(self.parse_unary()?)));
+        }
+
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let inner = self.parse_or()?;
+            self.expect(Token::RParen, "')'")?;
+            return Ok(inner);
+        }
+
+        // exists(key)
+        if self.peek_keyword("exists") && self.tokens.get(self.pos + 1) == Some(&Token::LParen) {
+            self.pos += 2;
+            let key = self.parse_key()?;
+            self.expect(Token::RParen, "')'")?;
+            return Ok(QueryFilter::Exists(key));
         }
+
+        self.parse_predicate()
     }
 
-    // Try: key < number
-    if let Some(lt_pos) = pattern.find(" < ") {
-        let key = pattern[..lt_pos].trim();
-        let rest = pattern[lt_pos + 3..].trim();
-        if let Ok(num) = rest.parse::<f64>() {
-            return Ok(QueryFilter::LessThan(key.to_string(), num));
+    fn parse_key(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Word(w)) | Some(Token::Str(w)) => Ok(w),
+            _ => Err(self.error("expected property name")),
         }
     }
 
-    Err(Error::config_error(format!(
-        "Unable to parse query pattern: {}",
-        pattern
-    )))
+    fn parse_predicate(&mut self) -> Result<QueryFilter> {
+        let key = self.parse_key()?;
+
+        match self.next() {
+            Some(Token::Op(":")) => {
+                if self.peek_keyword("contains") || self.peek_keyword("matches") {
+                    let function = self.parse_key()?.to_ascii_lowercase();
+                    self.expect(Token::LParen, "'('")?;
+                    let argument = self.parse_value()?;
+                    self.expect(Token::RParen, "')'")?;
+                    let text = scalar_text(&argument).unwrap_or_default();
+                    return if function == "contains" {
+                        Ok(QueryFilter::Contains(key, text))
+                    } else {
+                        self.regex_filter(key, &text)
+                    };
+                }
+                Ok(QueryFilter::Equals(key, self.parse_value()?))
+            }
+            Some(Token::Op("=")) | Some(Token::Op("==")) => {
+                Ok(QueryFilter::Equals(key, self.parse_value()?))
+            }
+            Some(Token::Op("!=")) => Ok(QueryFilter::Not(Box::new(QueryFilter::Equals(
+                key,
+                self.parse_value()?,
+            )))),
+            Some(Token::Op("=~")) => {
+                let value = self.parse_value()?;
+                self.regex_filter(key, &scalar_text(&value).unwrap_or_default())
+            }
+            Some(Token::Op(op @ (">" | ">=" | "<" | "<="))) => self.parse_comparison(key, op),
+            Some(Token::Word(word)) => {
+                let negated = word.eq_ignore_ascii_case("not");
+                let operator = if negated {
+                    self.parse_key()?.to_ascii_lowercase()
+                } else {
+                    word.to_ascii_lowercase()
+                };
+                let filter = match operator.as_str() {
+                    "contains" => {
+                        let value = self.parse_value()?;
+                        QueryFilter::Contains(key, scalar_text(&value).unwrap_or_default())
+                    }
+                    "matches" => {
+                        let value = self.parse_value()?;
+                        self.regex_filter(key, &scalar_text(&value).unwrap_or_default())?
+                    }
+                    "in" => QueryFilter::In(key, self.parse_list()?),
+                    "exists" => QueryFilter::Exists(key),
+                    _ => return Err(self.error(&format!("unknown operator '{}'", word))),
+                };
+                Ok(if negated {
+                    QueryFilter::Not(Box::new(filter))
+                } else {
+                    filter
+                })
+            }
+            _ => Err(self.error(&format!("expected operator after '{}'", key))),
+        }
+    }
+
+    fn parse_comparison(&mut self, key: String, op: &str) -> Result<QueryFilter> {
+        let value = self.parse_value()?;
+        comparison_filter(key, op, &value).ok_or_else(|| {
+            self.error(&format!(
+                "'{}' is neither a number nor a date",
+                scalar_text(&value).unwrap_or_default()
+            ))
+        })
+    }
+
+    fn regex_filter(&self, key: String, pattern: &str) -> Result<QueryFilter> {
+        Regex::new(pattern)
+            .map(|regex| QueryFilter::Matches(key, regex))
+            .map_err(|e| self.error(&format!("invalid regex: {}", e)))
+    }
+
+    fn parse_list(&mut self) -> Result<Vec<Value>> {
+        let close = match self.next() {
+            Some(Token::LParen) => Token::RParen,
+            Some(Token::LBracket) => Token::RBracket,
+            _ => return Err(self.error("expected '(' or '[' after 'in'")),
+        };
+
+        let mut values = Vec::new();
+        loop {
+            if self.peek() == Some(&close) {
+                self.pos += 1;
+                return Ok(values);
+            }
+            values.push(self.parse_value()?);
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(token) if token == close => return Ok(values),
+                _ => return Err(self.error("expected ',' or end of list")),
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value> {
+        match self.next() {
+            Some(Token::Str(s)) => Ok(Value::String(s)),
+            Some(Token::Word(w)) => Ok(match w.as_str() {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                "null" => Value::Null,
+                _ => {
+                    if let Ok(i) = w.parse::<i64>() {
+                        Value::from(i)
+                    } else if let Ok(f) = w.parse::<f64>() {
+                        Value::from(f)
+                    } else {
+                        Value::String(w)
+                    }
+                }
+            }),
+            _ => Err(self.error("expected value")),
+        }
+    }
+}
+
+/// Parse a metadata query pattern.
+///
+/// Predicates:
+/// - `status: "draft"`, `status = draft`, `status != done` - equality
+/// - `priority > 3`, `priority <= 5` - numeric comparison
+/// - `due < 2024-06-01`, `created >= "2024-01-01T09:00"`, `due < today` - date comparison
+/// - `tags contains "important"`, `tags: contains("important")` - list membership or substring
+/// - `status in ("draft", "review")` - value (or any list element) is one of the candidates
+/// - `title matches "^Meeting"`, `title =~ "\d{4}"` - regular expression
+/// - `reviewer exists`, `exists(reviewer)` - key presence
+///
+/// Keys may be nested (`project.owner = "alice"`). Predicates combine with
+/// `AND`/`&&`, `OR`/`||` and `NOT`/`!` (also `tags not contains "x"`), grouped
+/// with parentheses; `AND` binds tighter than `OR`.
+pub fn parse_query(pattern: &str) -> Result<QueryFilter> {
+    let tokens = tokenize(pattern)?;
+    let mut parser = QueryParser {
+        tokens,
+        pos: 0,
+        pattern,
+    };
+
+    if parser.peek().is_none() {
+        return Err(parser.error("empty query"));
+    }
+
+    let filter = parser.parse_or()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected trailing input"));
+    }
+    Ok(filter)
+}
+
+/// Frontmatter mutation applied by [`MetadataTools::bulk_update_properties`]
+///
+/// Keys accept dot notation for nested properties (`project.owner`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PropertyMutation {
+    /// Set a property, creating intermediate objects for nested keys
+    Set { key: String, value: Value },
+    /// Remove a property
+    Unset { key: String },
+    /// Append a value to a list property (no duplicates; scalars become lists)
+    AddToList { key: String, value: Value },
+    /// Remove a value from a list property (a matching scalar is unset)
+    RemoveFromList { key: String, value: Value },
+    /// Rename a property in place; a dotted `to` moves the value instead
+    RenameKey { from: String, to: String },
+}
+
+impl PropertyMutation {
+    /// Keys whose values may change when this mutation is applied
+    fn touched_keys(&self) -> Vec<&str> {
+        match self {
+            PropertyMutation::Set { key, .. }
+            | PropertyMutation::Unset { key }
+            | PropertyMutation::AddToList { key, .. }
+            | PropertyMutation::RemoveFromList { key, .. } => vec![key],
+            PropertyMutation::RenameKey { from, to } => vec![from, to],
+        }
+    }
+
+    /// Apply this mutation to frontmatter properties
+    fn apply(&self, properties: &mut Map<String, Value>) -> Result<()> {
+        match self {
+            PropertyMutation::Set { key, value } => {
+                let (parent, leaf) = property_parent(properties, key, true)?;
+                parent.insert(leaf, value.clone());
+            }
+            PropertyMutation::Unset { key } => {
+                if let Ok((parent, leaf)) = property_parent(properties, key, false) {
+                    parent.shift_remove(&leaf);
+                }
+            }
+            PropertyMutation::AddToList { key, value } => {
+                let (parent, leaf) = property_parent(properties, key, true)?;
+                let updated = match parent.shift_remove(&leaf) {
+                    None | Some(Value::Null) => vec![value.clone()],
+                    Some(Value::Array(mut items)) => {
+                        if !items.iter().any(|item| values_equal(item, value)) {
+                            items.push(value.clone());
+                        }
+                        items
+                    }
+                    Some(scalar) if values_equal(&scalar, value) => vec![scalar],
+                    Some(scalar) => vec![scalar, value.clone()],
+                };
+                parent.insert(leaf, Value::Array(updated));
+            }
+            PropertyMutation::RemoveFromList { key, value } => {
+                if let Ok((parent, leaf)) = property_parent(properties, key, false) {
+                    match parent.get_mut(&leaf) {
+                        Some(Value::Array(items)) => {
+                            items.retain(|item| !values_equal(item, value))
+                        }
+                        Some(scalar) if values_equal(scalar, value) => {
+                            parent.shift_remove(&leaf);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            PropertyMutation::RenameKey { from, to } => {
+                if from == to || lookup_property(properties, from).is_none() {
+                    return Ok(());
+                }
+                if lookup_property(properties, to).is_some() {
+                    return Err(Error::config_error(format!(
+                        "Cannot rename '{}' to '{}': target property already exists",
+                        from, to
+                    )));
+                }
+
+                if to.contains('.') {
+                    let (parent, leaf) = property_parent(properties, from, false)?;
+                    let value = parent.shift_remove(&leaf).unwrap_or(Value::Null);
+                    let (parent, leaf) = property_parent(properties, to, true)?;
+                    parent.insert(leaf, value);
+                } else {
+                    let (parent, leaf) = property_parent(properties, from, false)?;
+                    let entries = std::mem::take(parent);
+                    *parent = entries
+                        .into_iter()
+                        .map(|(k, v)| if k == leaf { (to.clone(), v) } else { (k, v) })
+                        .collect();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Resolve a property key to its containing map and leaf name.
+///
+/// A literal key (even one containing dots) wins over the nested path. With
+/// `create`, missing intermediate objects are inserted.
+fn property_parent<'a>(
+    properties: &'a mut Map<String, Value>,
+    key: &str,
+    create: bool,
+) -> Result<(&'a mut Map<String, Value>, String)> {
+    if properties.contains_key(key) || !key.contains('.') {
+        return Ok((properties, key.to_string()));
+    }
+
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let leaf = parts.pop().unwrap_or_default().to_string();
+    let mut current = properties;
+    for part in parts {
+        if create && !current.contains_key(part) {
+            current.insert(part.to_string(), Value::Object(Map::new()));
+        }
+        current = match current.get_mut(part) {
+            Some(Value::Object(map)) => map,
+            Some(_) => {
+                return Err(Error::config_error(format!(
+                    "Cannot address '{}': '{}' is not an object",
+                    key, part
+                )));
+            }
+            None => return Err(Error::not_found(format!("Property not found: {}", key))),
+        };
+    }
+    Ok((current, leaf))
+}
+
+/// Read a property by key with the same resolution rules as [`property_parent`]
+fn lookup_property<'a>(properties: &'a Map<String, Value>, key: &str) -> Option<&'a Value> {
+    if let Some(value) = properties.get(key) {
+        return Some(value);
+    }
+    let mut parts = key.split('.');
+    let mut current = properties.get(parts.next()?)?;
+    for part in parts {
+        current = current.as_object()?.get(part)?;
+    }
+    Some(current)
+}
+
+/// Before/after value of a single property
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropertyChange {
+    pub key: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// Property changes planned for one note
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotePropertyChanges {
+    /// Note path relative to vault root
+    pub path: String,
+    pub changes: Vec<PropertyChange>,
+}
+
+/// Result of a bulk property update (or its dry-run preview)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkPropertyUpdate {
+    pub query: String,
+    pub dry_run: bool,
+    /// Notes matching the query
+    pub matched: usize,
+    /// Notes whose frontmatter changes
+    pub updated: usize,
+    pub notes: Vec<NotePropertyChanges>,
+    /// Batch execution result (absent for dry runs or when nothing changes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch: Option<BatchResult>,
+}
+
+/// A callout found in a note
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalloutEntry {
+    /// Note path relative to vault root
+    pub path: String,
+    /// Canonical type (`abstract` for `[!tldr]`, the name itself for custom types)
+    pub callout_type: String,
+    /// Identifier as written in the note
+    pub identifier: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// 1-based line of the callout header
+    pub line: usize,
+    /// 1 for top-level callouts, 2+ when nested in another callout
+    pub depth: usize,
+    pub is_foldable: bool,
+    pub is_collapsed: bool,
+    pub content: String,
+    /// Unchecked `- [ ]` tasks in the callout body
+    pub open_tasks: usize,
+}
+
+/// Callouts across the vault
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalloutListing {
+    /// Canonical type filter, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callout_type: Option<String>,
+    /// Callouts per canonical type in the scanned notes, before the type filter
+    pub by_type: BTreeMap<String, usize>,
+    pub total: usize,
+    pub callouts: Vec<CalloutEntry>,
 }
 
 /// Metadata tools for querying and extracting file metadata
@@ -124,7 +797,7 @@ impl MetadataTools {
         let mut matches = Vec::new();
 
         for file_path in files {
-            if !file_path.ends_with(".md") {
+            if !file_path.to_string_lossy().to_lowercase().ends_with(".md") {
                 continue;
             }
 
@@ -159,6 +832,189 @@ impl MetadataTools {
         }))
     }
 
+    /// Apply frontmatter mutations to every note matching a metadata query.
+    ///
+    /// All rewrites run as one batch: if any write fails, the notes already
+    /// written are restored and an error is returned. Only the changed
+    /// properties are rewritten, so comments and quoting elsewhere in the
+    /// frontmatter are kept. With `dry_run` nothing is written and the report
+    /// lists the before/after values that would change.
+    pub async fn bulk_update_properties(
+        &self,
+        pattern: &str,
+        mutations: &[PropertyMutation],
+        dry_run: bool,
+    ) -> Result<BulkPropertyUpdate> {
+        let filter = parse_query(pattern)?;
+        if mutations.is_empty() {
+            return Err(Error::config_error(
+                "At least one property mutation is required".to_string(),
+            ));
+        }
+
+        let mut files = self.manager.scan_vault().await?;
+        files.sort();
+
+        let mut matched = 0;
+        let mut notes = Vec::new();
+        let mut rewrites = Vec::new();
+
+        for file_path in files {
+            if !file_path.to_string_lossy().to_lowercase().ends_with(".md") {
+                continue;
+            }
+
+            let content = self.manager.read_file(&file_path).await?;
+            let Ok(mut document) = FrontmatterDocument::parse(&content) else {
+                // Skip notes whose frontmatter can't be parsed
+                continue;
+            };
+
+            let metadata: HashMap<String, Value> =
+                document.properties().clone().into_iter().collect();
+            if !filter.matches(&metadata) {
+                continue;
+            }
+            matched += 1;
+
+            let display_path = file_path
+                .strip_prefix(self.manager.vault_path())
+                .unwrap_or(&file_path)
+                .to_string_lossy()
+                .to_string();
+
+            let original = document.properties().clone();
+            for mutation in mutations {
+                mutation
+                    .apply(document.properties_mut())
+                    .map_err(|e| Error::config_error(format!("{}: {}", display_path, e)))?;
+            }
+
+            let mut changes: Vec<PropertyChange> = Vec::new();
+            for key in mutations.iter().flat_map(|m| m.touched_keys()) {
+                if changes.iter().any(|c| c.key == key) {
+                    continue;
+                }
+                let before = lookup_property(&original, key).cloned();
+                let after = lookup_property(document.properties(), key).cloned();
+                if before != after {
+                    changes.push(PropertyChange {
+                        key: key.to_string(),
+                        before,
+                        after,
+                    });
+                }
+            }
+
+            if changes.is_empty() {
+                continue;
+            }
+
+            rewrites.push(NoteRewrite {
+                path: display_path.clone(),
+                content: document.render_onto(&content)?,
+                original: content,
+            });
+            notes.push(NotePropertyChanges {
+                path: display_path,
+                changes,
+            });
+        }
+
+        let batch = if dry_run || rewrites.is_empty() {
+            None
+        } else {
+            Some(
+                BatchTools::new(self.manager.clone())
+                    .rewrite_notes(rewrites)
+                    .await?,
+            )
+        };
+
+        Ok(BulkPropertyUpdate {
+            query: pattern.to_string(),
+            dry_run,
+            matched,
+            updated: notes.len(),
+            notes,
+            batch,
+        })
+    }
+
+    /// List callouts in every note, optionally of one type and under a folder.
+    ///
+    /// The type filter accepts aliases (`tldr` finds `[!summary]` callouts) and
+    /// custom types; nested callouts are listed alongside their parents.
+    pub async fn list_callouts(
+        &self,
+        callout_type: Option<&str>,
+        path_prefix: Option<&str>,
+    ) -> Result<CalloutListing> {
+        let wanted = callout_type.map(CalloutType::from_identifier);
+        let prefix = path_prefix.map(|p| p.trim_start_matches("./").trim_start_matches('/'));
+
+        let mut files = self.manager.scan_vault().await?;
+        files.sort();
+
+        let mut by_type: BTreeMap<String, usize> = BTreeMap::new();
+        let mut callouts = Vec::new();
+
+        for file_path in files {
+            if !file_path.to_string_lossy().to_lowercase().ends_with(".md") {
+                continue;
+            }
+
+            let relative = file_path
+                .strip_prefix(self.manager.vault_path())
+                .unwrap_or(&file_path);
+            // Whole path segments only: `projects` is not `projects-archive/`
+            if let Some(prefix) = prefix
+                && !relative.starts_with(prefix)
+            {
+                continue;
+            }
+            let display_path = relative.to_string_lossy().to_string();
+
+            let content = self.manager.read_file(&file_path).await?;
+            for callout in turbovault_parser::parse_callouts_full(&content) {
+                *by_type
+                    .entry(callout.type_.as_str().to_string())
+                    .or_insert(0) += 1;
+                if wanted.as_ref().is_some_and(|w| *w != callout.type_) {
+                    continue;
+                }
+
+                let open_tasks = callout
+                    .content
+                    .lines()
+                    .filter(|l| {
+                        let l = l.trim_start();
+                        l.starts_with("- [ ]") || l.starts_with("* [ ]") || l.starts_with("+ [ ]")
+                    })
+                    .count();
+                callouts.push(CalloutEntry {
+                    path: display_path.clone(),
+                    callout_type: callout.type_.to_string(),
+                    identifier: callout.identifier,
+                    title: callout.title,
+                    line: callout.position.line,
+                    depth: callout.depth,
+                    is_foldable: callout.is_foldable,
+                    is_collapsed: callout.is_collapsed,
+                    content: callout.content,
+                    open_tasks,
+                });
+            }
+        }
+
+        Ok(CalloutListing {
+            callout_type: wanted.map(|w| w.to_string()),
+            by_type,
+            total: callouts.len(),
+            callouts,
+        })
+    }
+
     /// Get metadata value from a file by key (supports dot notation for nested keys)
     pub async fn get_metadata_value(&self, file: &str, key: &str) -> Result<Value> {
         // Resolve file path
@@ -301,4 +1157,248 @@ mod tests {
         ]);
         assert!(!filter_no_match.matches(&metadata));
     }
+
+    fn sample_metadata() -> HashMap<String, Value> {
+        let value = json!({
+            "status": "active",
+            "priority": 4,
+            "due": "2024-03-15",
+            "tags": ["#project/alpha", "urgent"],
+            "aliases": ["Alpha Plan"],
+            "project": {"owner": "alice", "members": ["bob", "carol"]}
+        });
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_nested_key_lookup() {
+        let metadata = sample_metadata();
+        assert!(
+            parse_query("project.owner = alice")
+                .unwrap()
+                .matches(&metadata)
+        );
+        assert!(
+            parse_query("project.members contains carol")
+                .unwrap()
+                .matches(&metadata)
+        );
+        assert!(
+            parse_query("project.members.0 = bob")
+                .unwrap()
+                .matches(&metadata)
+        );
+        assert!(
+            !parse_query("project.missing exists")
+                .unwrap()
+                .matches(&metadata)
+        );
+    }
+
+    #[test]
+    fn test_list_membership() {
+        let metadata = sample_metadata();
+        assert!(
+            parse_query("tags contains urgent")
+                .unwrap()
+                .matches(&metadata)
+        );
+        assert!(
+            parse_query(r#"tags: contains("project/alpha")"#)
+                .unwrap()
+                .matches(&metadata)
+        );
+        // Element match, not substring, for lists
+        assert!(!parse_query("tags contains urg").unwrap().matches(&metadata));
+        assert!(
+            parse_query(r#"aliases in ("Alpha Plan", "Other")"#)
+                .unwrap()
+                .matches(&metadata)
+        );
+        assert!(
+            parse_query("status in [active, done]")
+                .unwrap()
+                .matches(&metadata)
+        );
+        assert!(
+            !parse_query("status not in (active)")
+                .unwrap()
+                .matches(&metadata)
+        );
+    }
+
+    #[test]
+    fn test_date_comparisons() {
+        let metadata = sample_metadata();
+        assert!(parse_query("due < 2024-04-01").unwrap().matches(&metadata));
+        assert!(
+            parse_query("due >= '2024-03-15'")
+                .unwrap()
+                .matches(&metadata)
+        );
+        assert!(
+            !parse_query(r#"due > "2024-03-15T00:00:00""#)
+                .unwrap()
+                .matches(&metadata)
+        );
+        assert!(parse_query("due < today").unwrap().matches(&metadata));
+        assert!(matches!(
+            parse_query("due < 2024-04-01").unwrap(),
+            QueryFilter::DateCompare(_, Comparison::Less, _)
+        ));
+    }
+
+    #[test]
+    fn test_regex_and_existence() {
+        let metadata = sample_metadata();
+        assert!(
+            parse_query(r#"status matches "^act""#)
+                .unwrap()
+                .matches(&metadata)
+        );
+        assert!(
+            parse_query(r#"aliases =~ "(?i)alpha""#)
+                .unwrap()
+                .matches(&metadata)
+        );
+        assert!(parse_query("exists(due)").unwrap().matches(&metadata));
+        assert!(
+            parse_query("NOT exists(reviewer)")
+                .unwrap()
+                .matches(&metadata)
+        );
+        assert!(
+            parse_query("status: matches(\"ive$\")")
+                .unwrap()
+                .matches(&metadata)
+        );
+    }
+
+    #[test]
+    fn test_boolean_precedence_and_grouping() {
+        let metadata = sample_metadata();
+        // AND binds tighter than OR: false OR (true AND true)
+        assert!(
+            parse_query("status = done OR priority > 3 AND tags contains urgent")
+                .unwrap()
+                .matches(&metadata)
+        );
+        // Grouping changes the result: (false OR true) AND false
+        assert!(
+            !parse_query("(status = done OR priority > 3) AND tags contains missing")
+                .unwrap()
+                .matches(&metadata)
+        );
+        assert!(
+            parse_query("!(status != active) && priority <= 4")
+                .unwrap()
+                .matches(&metadata)
+        );
+    }
+
+    #[test]
+    fn test_loose_scalar_equality() {
+        let metadata = sample_metadata();
+        assert!(parse_query("priority: 4").unwrap().matches(&metadata));
+        assert!(parse_query(r#"priority: "4""#).unwrap().matches(&metadata));
+    }
+
+    #[test]
+    fn test_parse_query_errors() {
+        assert!(parse_query("").is_err());
+        assert!(parse_query("(status = active").is_err());
+        assert!(parse_query("status >").is_err());
+        assert!(parse_query("due < soon").is_err());
+        assert!(parse_query(r#"title matches "([""#).is_err());
+        assert!(parse_query(r#"status = "open"#).is_err());
+        assert!(parse_query("status = active extra").is_err());
+    }
+
+    fn props(value: Value) -> Map<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_mutation_set_and_unset_nested() {
+        let mut properties = props(json!({"status": "draft"}));
+        PropertyMutation::Set {
+            key: "project.owner".to_string(),
+            value: json!("alice"),
+        }
+        .apply(&mut properties)
+        .unwrap();
+        assert_eq!(properties["project"], json!({"owner": "alice"}));
+
+        PropertyMutation::Unset {
+            key: "status".to_string(),
+        }
+        .apply(&mut properties)
+        .unwrap();
+        assert!(!properties.contains_key("status"));
+
+        // Unsetting a missing key is a no-op
+        PropertyMutation::Unset {
+            key: "missing.key".to_string(),
+        }
+        .apply(&mut properties)
+        .unwrap();
+    }
+
+    #[test]
+    fn test_mutation_list_operations() {
+        let mut properties = props(json!({"tags": ["a", "b"], "alias": "solo"}));
+
+        let add = |key: &str, value: Value| PropertyMutation::AddToList {
+            key: key.to_string(),
+            value,
+        };
+        add("tags", json!("c")).apply(&mut properties).unwrap();
+        add("tags", json!("a")).apply(&mut properties).unwrap();
+        add("alias", json!("other")).apply(&mut properties).unwrap();
+        add("new", json!(1)).apply(&mut properties).unwrap();
+        assert_eq!(properties["tags"], json!(["a", "b", "c"]));
+        assert_eq!(properties["alias"], json!(["solo", "other"]));
+        assert_eq!(properties["new"], json!([1]));
+
+        PropertyMutation::RemoveFromList {
+            key: "tags".to_string(),
+            value: json!("b"),
+        }
+        .apply(&mut properties)
+        .unwrap();
+        assert_eq!(properties["tags"], json!(["a", "c"]));
+    }
+
+    #[test]
+    fn test_mutation_rename_key_preserves_position() {
+        let mut properties = props(json!({"title": "x", "Status": "open", "due": "2024-01-01"}));
+        PropertyMutation::RenameKey {
+            from: "Status".to_string(),
+            to: "status".to_string(),
+        }
+        .apply(&mut properties)
+        .unwrap();
+        let keys: Vec<_> = properties.keys().cloned().collect();
+        assert_eq!(keys, ["title", "status", "due"]);
+
+        let conflict = PropertyMutation::RenameKey {
+            from: "title".to_string(),
+            to: "due".to_string(),
+        };
+        assert!(conflict.apply(&mut properties).is_err());
+    }
+
+    #[test]
+    fn test_mutation_deserialization() {
+        let mutation: PropertyMutation =
+            serde_json::from_value(json!({"op": "add_to_list", "key": "tags", "value": "x"}))
+                .unwrap();
+        assert_eq!(
+            mutation,
+            PropertyMutation::AddToList {
+                key: "tags".to_string(),
+                value: json!("x")
+            }
+        );
+    }
 }
@@ -0,0 +1,761 @@
+//! Vault-wide frontmatter property schema inference
+//!
+//! Profiles every top-level frontmatter key across the vault: which value
+//! types it holds, how its values are distributed, which casing variants of
+//! the key are in use and how many notes use it. Keys that drift (a `due`
+//! that is sometimes a date and sometimes free text, `tags` as a string in
+//! some notes and a list in others, `Status` next to `status`) are flagged,
+//! and a suggested schema is emitted in the format of Obsidian's
+//! `.obsidian/types.json`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use turbovault_core::prelude::*;
+use turbovault_vault::{FrontmatterDocument, VaultManager};
+
+/// Number of most frequent values reported per property
+const TOP_VALUES: usize = 10;
+
+/// Maximum number of note paths listed per inconsistency
+const MAX_EXAMPLE_NOTES: usize = 20;
+
+/// Kind of a single observed frontmatter value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueKind {
+    Text,
+    Number,
+    Boolean,
+    /// `YYYY-MM-DD` string (YAML dates are read as strings)
+    Date,
+    /// `YYYY-MM-DDTHH:MM[:SS]` string
+    DateTime,
+    List,
+    Object,
+    /// Key present without a value
+    Empty,
+}
+
+impl ValueKind {
+    /// Classify a frontmatter value
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => ValueKind::Empty,
+            Value::Bool(_) => ValueKind::Boolean,
+            Value::Number(_) => ValueKind::Number,
+            Value::Array(_) => ValueKind::List,
+            Value::Object(_) => ValueKind::Object,
+            Value::String(s) => {
+                let s = s.trim();
+                if s.is_empty() {
+                    ValueKind::Empty
+                } else if is_date(s) {
+                    ValueKind::Date
+                } else if is_datetime(s) {
+                    ValueKind::DateTime
+                } else {
+                    ValueKind::Text
+                }
+            }
+        }
+    }
+
+    /// Date and datetime values are interchangeable for consistency purposes
+    fn family(self) -> Self {
+        match self {
+            ValueKind::DateTime => ValueKind::Date,
+            other => other,
+        }
+    }
+}
+
+fn is_date(s: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok()
+}
+
+fn is_datetime(s: &str) -> bool {
+    chrono::DateTime::parse_from_rfc3339(s).is_ok()
+        || [
+            "%Y-%m-%dT%H:%M:%S",
+            "%Y-%m-%dT%H:%M",
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%d %H:%M",
+        ]
+        .iter()
+        .any(|fmt| chrono::NaiveDateTime::parse_from_str(s, fmt).is_ok())
+}
+
+/// Obsidian property type as stored in `.obsidian/types.json`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ObsidianPropertyType {
+    Text,
+    Multitext,
+    Number,
+    Checkbox,
+    Date,
+    Datetime,
+    Aliases,
+    Tags,
+}
+
+impl ObsidianPropertyType {
+    /// Name used in `types.json`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ObsidianPropertyType::Text => "text",
+            ObsidianPropertyType::Multitext => "multitext",
+            ObsidianPropertyType::Number => "number",
+            ObsidianPropertyType::Checkbox => "checkbox",
+            ObsidianPropertyType::Date => "date",
+            ObsidianPropertyType::Datetime => "datetime",
+            ObsidianPropertyType::Aliases => "aliases",
+            ObsidianPropertyType::Tags => "tags",
+        }
+    }
+
+    /// Parse a `types.json` type name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(ObsidianPropertyType::Text),
+            "multitext" => Some(ObsidianPropertyType::Multitext),
+            "number" => Some(ObsidianPropertyType::Number),
+            "checkbox" => Some(ObsidianPropertyType::Checkbox),
+            "date" => Some(ObsidianPropertyType::Date),
+            "datetime" => Some(ObsidianPropertyType::Datetime),
+            "aliases" => Some(ObsidianPropertyType::Aliases),
+            "tags" => Some(ObsidianPropertyType::Tags),
+            _ => None,
+        }
+    }
+
+    /// Whether a value of the given kind fits this property type as-is
+    fn accepts(self, kind: ValueKind) -> bool {
+        match self {
+            ObsidianPropertyType::Text => kind == ValueKind::Text,
+            ObsidianPropertyType::Multitext
+            | ObsidianPropertyType::Aliases
+            | ObsidianPropertyType::Tags => kind == ValueKind::List,
+            ObsidianPropertyType::Number => kind == ValueKind::Number,
+            ObsidianPropertyType::Checkbox => kind == ValueKind::Boolean,
+            ObsidianPropertyType::Date | ObsidianPropertyType::Datetime => {
+                kind.family() == ValueKind::Date
+            }
+        }
+    }
+}
+
+/// How often a spelling of a key is used
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyVariant {
+    pub name: String,
+    pub count: usize,
+}
+
+/// How often a value kind was observed for a key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KindCount {
+    pub kind: ValueKind,
+    pub count: usize,
+}
+
+/// How often a value was observed (list elements count individually)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueCount {
+    pub value: String,
+    pub count: usize,
+}
+
+/// Category of a property inconsistency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InconsistencyKind {
+    /// Values of incompatible kinds (e.g. text and date)
+    MixedTypes,
+    /// The key is spelled with different casing across notes
+    CasingVariants,
+    /// Values don't fit the type declared in `.obsidian/types.json`
+    DeclaredTypeMismatch,
+}
+
+/// A detected inconsistency with the notes that deviate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropertyInconsistency {
+    pub kind: InconsistencyKind,
+    pub message: String,
+    /// Deviating notes (capped), relative to vault root
+    pub notes: Vec<String>,
+}
+
+/// Inferred profile of one frontmatter property
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropertyProfile {
+    /// Most used spelling of the key
+    pub key: String,
+    /// Notes using the key under any spelling
+    pub note_count: usize,
+    /// Spellings of the key, most used first
+    pub variants: Vec<KeyVariant>,
+    /// Observed value kinds, most common first
+    pub kinds: Vec<KindCount>,
+    pub distinct_values: usize,
+    /// Most frequent values
+    pub top_values: Vec<ValueCount>,
+    /// Type from `.obsidian/types.json`, if declared
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declared_type: Option<ObsidianPropertyType>,
+    /// Suggested Obsidian type (absent for nested objects, which Obsidian can't type)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_type: Option<ObsidianPropertyType>,
+    pub inconsistencies: Vec<PropertyInconsistency>,
+}
+
+impl PropertyProfile {
+    /// Whether any inconsistency was flagged
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+}
+
+/// Vault-wide property schema report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropertySchemaReport {
+    pub total_notes: usize,
+    pub notes_with_frontmatter: usize,
+    /// Notes whose frontmatter could not be parsed
+    pub unparseable_notes: Vec<String>,
+    /// Profiles ordered by usage, most used first
+    pub properties: Vec<PropertyProfile>,
+    pub inconsistent_properties: usize,
+}
+
+impl PropertySchemaReport {
+    /// Build a report from `(note path, frontmatter properties)` pairs.
+    ///
+    /// `declared` holds types already present in `.obsidian/types.json`,
+    /// keyed by property name.
+    pub fn from_notes(
+        notes: &[(String, Map<String, Value>)],
+        declared: &HashMap<String, ObsidianPropertyType>,
+    ) -> Self {
+        // Group keys case-insensitively, keeping every observation
+        let mut groups: BTreeMap<String, Vec<Observation<'_>>> = BTreeMap::new();
+        for (path, properties) in notes {
+            for (key, value) in properties {
+                groups
+                    .entry(key.to_lowercase())
+                    .or_default()
+                    .push(Observation { path, key, value });
+            }
+        }
+
+        let mut properties: Vec<PropertyProfile> = groups
+            .into_values()
+            .map(|observations| profile_property(&observations, declared))
+            .collect();
+        properties.sort_by(|a, b| b.note_count.cmp(&a.note_count).then(a.key.cmp(&b.key)));
+
+        let inconsistent_properties = properties.iter().filter(|p| !p.is_consistent()).count();
+
+        Self {
+            total_notes: notes.len(),
+            notes_with_frontmatter: notes.iter().filter(|(_, p)| !p.is_empty()).count(),
+            unparseable_notes: Vec::new(),
+            properties,
+            inconsistent_properties,
+        }
+    }
+
+    /// Suggested schema in `.obsidian/types.json` format.
+    ///
+    /// Declared types are kept; inferred types are added for the remaining
+    /// properties under their most used spelling.
+    pub fn obsidian_types_json(&self) -> Value {
+        let mut types = Map::new();
+        for profile in &self.properties {
+            if let Some(ty) = profile.declared_type.or(profile.suggested_type) {
+                types.insert(profile.key.clone(), json!(ty.as_str()));
+            }
+        }
+        json!({ "types": types })
+    }
+}
+
+struct Observation<'a> {
+    path: &'a str,
+    key: &'a str,
+    value: &'a Value,
+}
+
+fn profile_property(
+    observations: &[Observation<'_>],
+    declared: &HashMap<String, ObsidianPropertyType>,
+) -> PropertyProfile {
+    // Spellings: most used first, all-lowercase wins ties
+    let mut variant_counts: HashMap<&str, usize> = HashMap::new();
+    for obs in observations {
+        *variant_counts.entry(obs.key).or_default() += 1;
+    }
+    let mut variants: Vec<KeyVariant> = variant_counts
+        .into_iter()
+        .map(|(name, count)| KeyVariant {
+            name: name.to_string(),
+            count,
+        })
+        .collect();
+    variants.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| (b.name == b.name.to_lowercase()).cmp(&(a.name == a.name.to_lowercase())))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    let key = variants[0].name.clone();
+
+    let mut kind_counts: HashMap<ValueKind, usize> = HashMap::new();
+    let mut value_counts: HashMap<String, usize> = HashMap::new();
+    for obs in observations {
+        *kind_counts.entry(ValueKind::of(obs.value)).or_default() += 1;
+        match obs.value {
+            Value::Array(items) => {
+                for item in items {
+                    if let Some(text) = display_value(item) {
+                        *value_counts.entry(text).or_default() += 1;
+                    }
+                }
+            }
+            value => {
+                if let Some(text) = display_value(value) {
+                    *value_counts.entry(text).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    let mut kinds: Vec<KindCount> = kind_counts
+        .into_iter()
+        .map(|(kind, count)| KindCount { kind, count })
+        .collect();
+    kinds.sort_by(|a, b| b.count.cmp(&a.count).then(a.kind.cmp(&b.kind)));
+
+    let distinct_values = value_counts.len();
+    let mut top_values: Vec<ValueCount> = value_counts
+        .into_iter()
+        .map(|(value, count)| ValueCount { value, count })
+        .collect();
+    top_values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    top_values.truncate(TOP_VALUES);
+
+    let declared_type = variants.iter().find_map(|v| declared.get(&v.name)).copied();
+    let suggested_type = suggest_type(&key, &kinds);
+
+    let mut inconsistencies = Vec::new();
+
+    if variants.len() > 1 {
+        let names: Vec<&str> = variants.iter().map(|v| v.name.as_str()).collect();
+        inconsistencies.push(PropertyInconsistency {
+            kind: InconsistencyKind::CasingVariants,
+            message: format!("Key is spelled {}; prefer '{}'", names.join(", "), key),
+            notes: example_notes(observations, |obs| obs.key != key),
+        });
+    }
+
+    let mut families: Vec<ValueKind> = kinds
+        .iter()
+        .map(|k| k.kind.family())
+        .filter(|k| *k != ValueKind::Empty)
+        .collect();
+    families.sort();
+    families.dedup();
+    if families.len() > 1 {
+        let summary: Vec<String> = kinds
+            .iter()
+            .filter(|k| k.kind != ValueKind::Empty)
+            .map(|k| format!("{} {}", k.count, kind_name(k.kind)))
+            .collect();
+        let dominant = kinds
+            .iter()
+            .find(|k| k.kind != ValueKind::Empty)
+            .map(|k| k.kind.family());
+        inconsistencies.push(PropertyInconsistency {
+            kind: InconsistencyKind::MixedTypes,
+            message: format!("Values have mixed types: {}", summary.join(", ")),
+            notes: example_notes(observations, |obs| {
+                let kind = ValueKind::of(obs.value);
+                kind != ValueKind::Empty && Some(kind.family()) != dominant
+            }),
+        });
+    }
+
+    if let Some(declared_type) = declared_type {
+        let offending = example_notes(observations, |obs| {
+            let kind = ValueKind::of(obs.value);
+            kind != ValueKind::Empty && !declared_type.accepts(kind)
+        });
+        if !offending.is_empty() {
+            inconsistencies.push(PropertyInconsistency {
+                kind: InconsistencyKind::DeclaredTypeMismatch,
+                message: format!(
+                    "Declared as '{}' in .obsidian/types.json but some values don't fit",
+                    declared_type.as_str()
+                ),
+                notes: offending,
+            });
+        }
+    }
+
+    PropertyProfile {
+        key,
+        note_count: observations.len(),
+        variants,
+        kinds,
+        distinct_values,
+        top_values,
+        declared_type,
+        suggested_type,
+        inconsistencies,
+    }
+}
+
+/// Infer the Obsidian type from the observed value kinds
+fn suggest_type(key: &str, kinds: &[KindCount]) -> Option<ObsidianPropertyType> {
+    match key.to_lowercase().as_str() {
+        "tags" | "tag" => return Some(ObsidianPropertyType::Tags),
+        "aliases" | "alias" => return Some(ObsidianPropertyType::Aliases),
+        "cssclasses" | "cssclass" => return Some(ObsidianPropertyType::Multitext),
+        _ => {}
+    }
+
+    let count = |kind: ValueKind| {
+        kinds
+            .iter()
+            .filter(|k| k.kind == kind)
+            .map(|k| k.count)
+            .sum::<usize>()
+    };
+
+    let dominant = kinds
+        .iter()
+        .filter(|k| k.kind != ValueKind::Empty)
+        .map(|k| k.kind)
+        .max_by_key(|kind| match kind {
+            // Dates and datetimes vote together
+            ValueKind::Date | ValueKind::DateTime => {
+                count(ValueKind::Date) + count(ValueKind::DateTime)
+            }
+            other => count(*other),
+        });
+
+    Some(match dominant {
+        None | Some(ValueKind::Text) | Some(ValueKind::Empty) => {
+            // A list anywhere means single strings are really one-element lists
+            if count(ValueKind::List) > 0 {
+                ObsidianPropertyType::Multitext
+            } else {
+                ObsidianPropertyType::Text
+            }
+        }
+        Some(ValueKind::List) => ObsidianPropertyType::Multitext,
+        Some(ValueKind::Number) => ObsidianPropertyType::Number,
+        Some(ValueKind::Boolean) => ObsidianPropertyType::Checkbox,
+        Some(ValueKind::Date) | Some(ValueKind::DateTime) => {
+            if count(ValueKind::DateTime) > 0 {
+                ObsidianPropertyType::Datetime
+            } else {
+                ObsidianPropertyType::Date
+            }
+        }
+        Some(ValueKind::Object) => return None,
+    })
+}
+
+fn kind_name(kind: ValueKind) -> &'static str {
+    match kind {
+        ValueKind::Text => "text",
+        ValueKind::Number => "number",
+        ValueKind::Boolean => "boolean",
+        ValueKind::Date => "date",
+        ValueKind::DateTime => "datetime",
+        ValueKind::List => "list",
+        ValueKind::Object => "object",
+        ValueKind::Empty => "empty",
+    }
+}
+
+/// Text form of a scalar value for distributions (objects and empty values are skipped)
+fn display_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn example_notes(
+    observations: &[Observation<'_>],
+    predicate: impl Fn(&Observation<'_>) -> bool,
+) -> Vec<String> {
+    let mut notes: Vec<String> = observations
+        .iter()
+        .filter(|obs| predicate(obs))
+        .map(|obs| obs.path.to_string())
+        .collect();
+    notes.sort();
+    notes.dedup();
+    notes.truncate(MAX_EXAMPLE_NOTES);
+    notes
+}
+
+/// Property schema tools context
+pub struct PropertySchemaTools {
+    pub manager: Arc<VaultManager>,
+}
+
+impl PropertySchemaTools {
+    /// Create new property schema tools
+    pub fn new(manager: Arc<VaultManager>) -> Self {
+        Self { manager }
+    }
+
+    /// Profile frontmatter properties across every note in the vault
+    pub async fn analyze_properties(&self) -> Result<PropertySchemaReport> {
+        let mut files = self.manager.scan_vault().await?;
+        files.sort();
+
+        let mut notes = Vec::new();
+        let mut unparseable_notes = Vec::new();
+        for file_path in files {
+            if !file_path.to_string_lossy().to_lowercase().ends_with(".md") {
+                continue;
+            }
+
+            let display_path = file_path
+                .strip_prefix(self.manager.vault_path())
+                .unwrap_or(&file_path)
+                .to_string_lossy()
+                .to_string();
+
+            let content = self.manager.read_file(&file_path).await?;
+            match FrontmatterDocument::parse(&content) {
+                Ok(document) => notes.push((display_path, document.properties().clone())),
+                Err(_) => unparseable_notes.push(display_path),
+            }
+        }
+
+        let declared = self.declared_types().await?;
+        let mut report = PropertySchemaReport::from_notes(&notes, &declared);
+        report.total_notes += unparseable_notes.len();
+        report.unparseable_notes = unparseable_notes;
+        Ok(report)
+    }
+
+    /// Property types declared in `.obsidian/types.json` (empty if absent)
+    pub async fn declared_types(&self) -> Result<HashMap<String, ObsidianPropertyType>> {
+        let path = self
+            .manager
+            .vault_path()
+            .join(".obsidian")
+            .join("types.json");
+        let content = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(e) => return Err(Error::io(e)),
+        };
+
+        let parsed: Value = serde_json::from_str(&content)
+            .map_err(|e| Error::parse_error(format!("Invalid .obsidian/types.json: {}", e)))?;
+
+        Ok(parsed
+            .get("types")
+            .and_then(Value::as_object)
+            .map(|types| {
+                types
+                    .iter()
+                    .filter_map(|(key, ty)| {
+                        ty.as_str()
+                            .and_then(ObsidianPropertyType::from_name)
+                            .map(|ty| (key.clone(), ty))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(path: &str, value: Value) -> (String, Map<String, Value>) {
+        (path.to_string(), value.as_object().cloned().unwrap())
+    }
+
+    fn profile<'a>(report: &'a PropertySchemaReport, key: &str) -> &'a PropertyProfile {
+        report
+            .properties
+            .iter()
+            .find(|p| p.key.eq_ignore_ascii_case(key))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_value_kind_classification() {
+        assert_eq!(ValueKind::of(&json!("hello")), ValueKind::Text);
+        assert_eq!(ValueKind::of(&json!("2024-01-05")), ValueKind::Date);
+        assert_eq!(
+            ValueKind::of(&json!("2024-01-05T10:30")),
+            ValueKind::DateTime
+        );
+        assert_eq!(
+            ValueKind::of(&json!("2024-01-05T10:30:00Z")),
+            ValueKind::DateTime
+        );
+        assert_eq!(ValueKind::of(&json!(3)), ValueKind::Number);
+        assert_eq!(ValueKind::of(&json!(true)), ValueKind::Boolean);
+        assert_eq!(ValueKind::of(&json!(["a"])), ValueKind::List);
+        assert_eq!(ValueKind::of(&json!({"a": 1})), ValueKind::Object);
+        assert_eq!(ValueKind::of(&Value::Null), ValueKind::Empty);
+        assert_eq!(ValueKind::of(&json!("  ")), ValueKind::Empty);
+    }
+
+    #[test]
+    fn test_consistent_property() {
+        let notes = vec![
+            note("a.md", json!({"priority": 1})),
+            note("b.md", json!({"priority": 2})),
+            note("c.md", json!({"priority": 2})),
+        ];
+        let report = PropertySchemaReport::from_notes(&notes, &HashMap::new());
+        let p = profile(&report, "priority");
+        assert_eq!(p.note_count, 3);
+        assert_eq!(p.suggested_type, Some(ObsidianPropertyType::Number));
+        assert_eq!(p.distinct_values, 2);
+        assert_eq!(
+            p.top_values[0],
+            ValueCount {
+                value: "2".into(),
+                count: 2
+            }
+        );
+        assert!(p.is_consistent());
+        assert_eq!(report.inconsistent_properties, 0);
+    }
+
+    #[test]
+    fn test_mixed_types_flagged() {
+        let notes = vec![
+            note("a.md", json!({"due": "2024-01-05"})),
+            note("b.md", json!({"due": "2024-02-01"})),
+            note("c.md", json!({"due": "next week"})),
+        ];
+        let report = PropertySchemaReport::from_notes(&notes, &HashMap::new());
+        let p = profile(&report, "due");
+        assert_eq!(p.suggested_type, Some(ObsidianPropertyType::Date));
+        let issue = &p.inconsistencies[0];
+        assert_eq!(issue.kind, InconsistencyKind::MixedTypes);
+        assert_eq!(issue.notes, ["c.md"]);
+    }
+
+    #[test]
+    fn test_dates_and_datetimes_are_compatible() {
+        let notes = vec![
+            note("a.md", json!({"created": "2024-01-05"})),
+            note("b.md", json!({"created": "2024-01-05T09:00"})),
+            note("c.md", json!({"created": null})),
+        ];
+        let report = PropertySchemaReport::from_notes(&notes, &HashMap::new());
+        let p = profile(&report, "created");
+        assert!(p.is_consistent());
+        assert_eq!(p.suggested_type, Some(ObsidianPropertyType::Datetime));
+    }
+
+    #[test]
+    fn test_scalar_and_list_tags() {
+        let notes = vec![
+            note("a.md", json!({"tags": ["x", "y"]})),
+            note("b.md", json!({"tags": "x"})),
+        ];
+        let report = PropertySchemaReport::from_notes(&notes, &HashMap::new());
+        let p = profile(&report, "tags");
+        assert_eq!(p.suggested_type, Some(ObsidianPropertyType::Tags));
+        assert_eq!(p.inconsistencies[0].kind, InconsistencyKind::MixedTypes);
+        assert_eq!(
+            p.top_values[0],
+            ValueCount {
+                value: "x".into(),
+                count: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_casing_variants() {
+        let notes = vec![
+            note("a.md", json!({"status": "draft"})),
+            note("b.md", json!({"status": "done"})),
+            note("c.md", json!({"Status": "draft"})),
+        ];
+        let report = PropertySchemaReport::from_notes(&notes, &HashMap::new());
+        assert_eq!(report.properties.len(), 1);
+        let p = &report.properties[0];
+        assert_eq!(p.key, "status");
+        assert_eq!(p.note_count, 3);
+        assert_eq!(p.variants.len(), 2);
+        let issue = &p.inconsistencies[0];
+        assert_eq!(issue.kind, InconsistencyKind::CasingVariants);
+        assert_eq!(issue.notes, ["c.md"]);
+    }
+
+    #[test]
+    fn test_lowercase_spelling_wins_ties() {
+        let notes = vec![
+            note("a.md", json!({"Status": "draft"})),
+            note("b.md", json!({"status": "done"})),
+        ];
+        let report = PropertySchemaReport::from_notes(&notes, &HashMap::new());
+        assert_eq!(report.properties[0].key, "status");
+    }
+
+    #[test]
+    fn test_declared_type_mismatch() {
+        let notes = vec![
+            note("a.md", json!({"done": true})),
+            note("b.md", json!({"done": "yes"})),
+        ];
+        let declared = HashMap::from([("done".to_string(), ObsidianPropertyType::Checkbox)]);
+        let report = PropertySchemaReport::from_notes(&notes, &declared);
+        let p = profile(&report, "done");
+        assert_eq!(p.declared_type, Some(ObsidianPropertyType::Checkbox));
+        assert!(
+            p.inconsistencies
+                .iter()
+                .any(|i| i.kind == InconsistencyKind::DeclaredTypeMismatch && i.notes == ["b.md"])
+        );
+    }
+
+    #[test]
+    fn test_obsidian_types_json() {
+        let notes = vec![
+            note(
+                "a.md",
+                json!({"tags": ["a"], "aliases": ["A"], "rating": 4, "due": "2024-01-01", "meta": {"x": 1}}),
+            ),
+            note("b.md", json!({"published": false, "summary": "text"})),
+        ];
+        let declared = HashMap::from([("summary".to_string(), ObsidianPropertyType::Multitext)]);
+        let report = PropertySchemaReport::from_notes(&notes, &declared);
+        assert_eq!(
+            report.obsidian_types_json(),
+            json!({"types": {
+                "aliases": "aliases",
+                "due": "date",
+                "published": "checkbox",
+                "rating": "number",
+                "summary": "multitext",
+                "tags": "tags"
+            }})
+        );
+    }
+}
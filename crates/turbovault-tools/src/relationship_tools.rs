@@ -1,11 +1,28 @@
 //! Relationship analysis tools for link strength, suggestions, and centrality
 
+use crate::search_engine::searchable_text;
+use crate::similarity::TfIdfIndex;
+use serde::Serialize;
 use serde_json::{Value, json};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use turbovault_core::prelude::*;
+use turbovault_graph::{CentralityConfig, CentralityMetric};
 use turbovault_vault::VaultManager;
 
+/// Weight of content similarity in a link suggestion score
+pub const CONTENT_WEIGHT: f64 = 0.5;
+/// Weight of tag overlap in a link suggestion score
+pub const TAG_WEIGHT: f64 = 0.2;
+/// Weight of shared outgoing links in a link suggestion score
+pub const OUTGOING_LINK_WEIGHT: f64 = 0.15;
+/// Weight of shared backlinks in a link suggestion score
+pub const BACKLINK_WEIGHT: f64 = 0.15;
+
+/// Shared terms, tags or notes listed per reason
+const SHOWN_ITEMS: usize = 5;
+
 /// Result of link strength calculation
 #[derive(Debug, Clone)]
 pub struct LinkStrengthResult {
@@ -22,18 +39,51 @@ pub struct LinkStrengthResult {
 pub struct LinkSuggestion {
     pub target: String,
     pub strength: f64,
+    pub signals: SuggestionSignals,
     pub reasons: Vec<String>,
 }
 
+/// Per-signal similarity behind a link suggestion, each in [0, 1].
+///
+/// A signal is `None` when the source note has nothing to compare on
+/// (no indexed terms, tags, outgoing links or backlinks).
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SuggestionSignals {
+    pub content: Option<f64>,
+    pub tags: Option<f64>,
+    pub outgoing_links: Option<f64>,
+    pub backlinks: Option<f64>,
+}
+
+impl SuggestionSignals {
+    /// Weighted mean of the present signals.
+    ///
+    /// Weights are [`CONTENT_WEIGHT`], [`TAG_WEIGHT`],
+    /// [`OUTGOING_LINK_WEIGHT`] and [`BACKLINK_WEIGHT`]; missing signals are
+    /// left out and the remaining weights rescaled to sum to one.
+    pub fn score(&self) -> f64 {
+        let (sum, total) = [
+            (self.content, CONTENT_WEIGHT),
+            (self.tags, TAG_WEIGHT),
+            (self.outgoing_links, OUTGOING_LINK_WEIGHT),
+            (self.backlinks, BACKLINK_WEIGHT),
+        ]
+        .into_iter()
+        .filter_map(|(value, weight)| value.map(|v| (v * weight, weight)))
+        .fold((0.0, 0.0), |(sum, total), (v, w)| (sum + v, total + w));
+        if total > 0.0 { sum / total } else { 0.0 }
+    }
+}
+
 /// Centrality rank for a file
 #[derive(Debug, Clone)]
 pub struct CentralityRank {
     pub rank: usize,
     pub file: String,
     pub score: f64,
+    pub pagerank: f64,
     pub betweenness: f64,
     pub closeness: f64,
-    pub eigenvector: f64,
     pub interpretation: String,
 }
 
@@ -115,77 +165,177 @@ impl RelationshipTools {
         }))
     }
 
-    /// Suggest files to link from a given file
-    pub async fn suggest_links(&self, file: &str, limit: usize) -> Result<Value> {
-        let graph = self.manager.link_graph();
-        let read = graph.read().await;
-
-        let file_path = std::path::PathBuf::from(file);
-        let all_files = read.all_files();
-
-        // Get existing forward links to exclude
-        let mut existing_links = std::collections::HashSet::new();
-        if let Ok(forward_links) = read.forward_links(&file_path) {
-            for (linked_file, _) in forward_links {
-                existing_links.insert(linked_file.to_string_lossy().to_string());
+    /// Suggest notes to link from a given note.
+    ///
+    /// Every note not already linked from `file` is scored on four signals,
+    /// each in [0, 1]:
+    /// - content: cosine similarity of TF-IDF vectors over the notes' plain
+    ///   text (the same text the search index holds)
+    /// - tags: Jaccard overlap of the notes' tags
+    /// - outgoing links: Jaccard overlap of the notes they link to
+    /// - backlinks: Jaccard overlap of the notes linking to them (co-citation)
+    ///
+    /// The score is the weighted mean of the signals the source note has
+    /// data for (see [`SuggestionSignals::score`]), so a new note without
+    /// links is ranked on content and tags alone. Suggestions below the
+    /// configured `link_similarity_threshold` are dropped; `limit` defaults
+    /// to, and is capped by, `max_link_suggestions`.
+    pub async fn suggest_links(&self, file: &str, limit: Option<usize>) -> Result<Value> {
+        let config = self.manager.config();
+        if !config.link_suggestions_enabled {
+            return Err(Error::config_error(
+                "Link suggestions are disabled (link_suggestions_enabled = false)".to_string(),
+            ));
+        }
+        let limit = limit
+            .unwrap_or(config.max_link_suggestions)
+            .min(config.max_link_suggestions);
+        let threshold = f64::from(config.link_similarity_threshold);
+
+        let vault_path = self.manager.vault_path().clone();
+        let source = vault_path.join(file);
+        let relative = |path: &Path| {
+            path.strip_prefix(&vault_path)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string()
+        };
+
+        let mut files = self.manager.scan_vault().await?;
+        files.retain(|p| p.to_string_lossy().to_lowercase().ends_with(".md"));
+        files.sort();
+
+        let mut documents = Vec::with_capacity(files.len());
+        let mut tags: HashMap<PathBuf, BTreeSet<String>> = HashMap::new();
+        for path in &files {
+            let Ok(vault_file) = self.manager.parse_file(path).await else {
+                continue;
+            };
+            let mut note_tags: BTreeSet<String> = vault_file
+                .tags
+                .iter()
+                .map(|t| t.name.to_lowercase())
+                .collect();
+            if let Some(frontmatter) = &vault_file.frontmatter {
+                note_tags.extend(
+                    frontmatter
+                        .tags()
+                        .iter()
+                        .map(|t| t.trim_start_matches('#').to_lowercase()),
+                );
             }
+            tags.insert(path.clone(), note_tags);
+            documents.push((path.clone(), searchable_text(&vault_file)));
         }
+        if !tags.contains_key(&source) {
+            return Err(Error::not_found(format!("Note not found: {}", file)));
+        }
+        let index = TfIdfIndex::build(&documents);
 
-        // Score each candidate
-        let mut suggestions: Vec<LinkSuggestion> = Vec::new();
-
-        for candidate in all_files {
-            let candidate_str = candidate.to_string_lossy().to_string();
+        let graph = self.manager.link_graph();
+        let read = graph.read().await;
+        let outgoing = |path: &PathBuf| -> BTreeSet<PathBuf> {
+            read.forward_links(path)
+                .map(|links| links.into_iter().map(|(p, _)| p).collect())
+                .unwrap_or_default()
+        };
+        let incoming = |path: &PathBuf| -> BTreeSet<PathBuf> {
+            read.backlinks(path)
+                .map(|links| links.into_iter().map(|(p, _)| p).collect())
+                .unwrap_or_default()
+        };
+
+        let source_tags = &tags[&source];
+        let source_out = outgoing(&source);
+        let source_in = incoming(&source);
+        let has_terms = index.has_terms(&source);
 
-            // Skip self and existing links
-            if candidate_str.contains(file) || existing_links.contains(&candidate_str) {
+        let mut suggestions: Vec<LinkSuggestion> = Vec::new();
+        for candidate in &files {
+            if candidate == &source || source_out.contains(candidate) {
                 continue;
             }
-
-            // Calculate co-reference strength (shared backlinks)
-            let mut shared_refs = Vec::new();
-            if let Ok(source_backlinks) = read.backlinks(&file_path)
-                && let Ok(target_backlinks) = read.backlinks(&candidate)
-            {
-                let source_set: std::collections::HashSet<_> =
-                    source_backlinks.iter().map(|(p, _)| p.clone()).collect();
-                let target_set: std::collections::HashSet<_> =
-                    target_backlinks.iter().map(|(p, _)| p.clone()).collect();
-
-                for intersection_file in source_set.intersection(&target_set) {
-                    if let Some(name) = intersection_file.file_name() {
-                        shared_refs.push(name.to_string_lossy().to_string());
-                    }
-                }
+            let Some(candidate_tags) = tags.get(candidate) else {
+                continue;
+            };
+
+            // Links between the pair say nothing about shared neighbours
+            let pair = [&source, candidate];
+            let (tag_score, shared_tags) = jaccard(source_tags, candidate_tags, &[]);
+            let (out_score, shared_out) = jaccard(&source_out, &outgoing(candidate), &pair);
+            let (in_score, shared_in) = jaccard(&source_in, &incoming(candidate), &pair);
+
+            let signals = SuggestionSignals {
+                content: has_terms.then(|| index.similarity(&source, candidate)),
+                tags: (!source_tags.is_empty()).then_some(tag_score),
+                outgoing_links: (!source_out.is_empty()).then_some(out_score),
+                backlinks: (!source_in.is_empty()).then_some(in_score),
+            };
+            let strength = signals.score();
+            if strength <= 0.0 || strength < threshold {
+                continue;
             }
 
-            let shared_count = shared_refs.len();
-            let strength = ((shared_count as f64) * 0.3).min(1.0);
-
-            if strength > 0.0 || shared_count > 0 {
-                let mut reasons = Vec::new();
-                if shared_count > 0 {
-                    reasons.push(format!("{} shared backlinks", shared_count));
-                }
-                if strength > 0.7 {
-                    reasons.push("Frequently co-referenced".to_string());
-                }
-                if reasons.is_empty() {
-                    reasons.push("Related file".to_string());
-                }
-
-                suggestions.push(LinkSuggestion {
-                    target: candidate_str,
-                    strength,
-                    reasons,
-                });
+            let names = |paths: Vec<&PathBuf>| {
+                paths
+                    .into_iter()
+                    .take(SHOWN_ITEMS)
+                    .map(|p| relative(p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let mut reasons = Vec::new();
+            if let Some(score) = signals.content.filter(|s| *s > 0.0) {
+                reasons.push(format!(
+                    "Similar content ({:.2}): shares {}",
+                    score,
+                    index
+                        .shared_terms(&source, candidate, SHOWN_ITEMS)
+                        .join(", ")
+                ));
+            }
+            if !shared_tags.is_empty() {
+                reasons.push(format!(
+                    "Shared tags ({:.2}): {}",
+                    tag_score,
+                    shared_tags
+                        .iter()
+                        .take(SHOWN_ITEMS)
+                        .map(|t| format!("#{}", t))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
             }
+            if !shared_out.is_empty() {
+                reasons.push(format!(
+                    "Both link to ({:.2}): {}",
+                    out_score,
+                    names(shared_out)
+                ));
+            }
+            if !shared_in.is_empty() {
+                reasons.push(format!(
+                    "Linked from the same notes ({:.2}): {}",
+                    in_score,
+                    names(shared_in)
+                ));
+            }
+
+            suggestions.push(LinkSuggestion {
+                target: relative(candidate),
+                strength,
+                signals,
+                reasons,
+            });
         }
 
-        // Sort by strength descending
-        suggestions.sort_by(|a, b| b.strength.partial_cmp(&a.strength).unwrap());
+        suggestions.sort_by(|a, b| {
+            b.strength
+                .total_cmp(&a.strength)
+                .then_with(|| a.target.cmp(&b.target))
+        });
+        let total_candidates = suggestions.len();
 
-        // Take top N
         let results: Vec<_> = suggestions
             .into_iter()
             .take(limit)
@@ -193,95 +343,139 @@ impl RelationshipTools {
                 json!({
                     "target": s.target,
                     "strength": s.strength,
+                    "signals": s.signals,
                     "reasons": s.reasons
                 })
             })
             .collect();
 
         Ok(json!({
-            "file": file,
+            "file": relative(&source),
+            "threshold": threshold,
+            "limit": limit,
+            "total_candidates": total_candidates,
+            "weights": {
+                "content": CONTENT_WEIGHT,
+                "tags": TAG_WEIGHT,
+                "outgoing_links": OUTGOING_LINK_WEIGHT,
+                "backlinks": BACKLINK_WEIGHT
+            },
             "suggestions": results
         }))
     }
 
-    /// Get centrality ranking for all files
-    pub async fn get_centrality_ranking(&self) -> Result<Value> {
+    /// Rank notes by graph centrality.
+    ///
+    /// Scores come from the link graph's cached PageRank, betweenness and
+    /// harmonic closeness; `metrics` selects which are reported (empty = all)
+    /// and the first one orders the ranking. `top_n` limits the result.
+    pub async fn get_centrality_ranking(
+        &self,
+        metrics: &[CentralityMetric],
+        top_n: Option<usize>,
+    ) -> Result<Value> {
         let graph = self.manager.link_graph();
         let read = graph.read().await;
-
-        let all_files = read.all_files();
-
-        // Simple heuristic-based centrality calculation
-        let mut rankings: Vec<(String, f64, HashMap<&str, f64>)> = Vec::new();
-
-        for file in all_files {
-            let file_str = file.to_string_lossy().to_string();
-
-            // Betweenness: count edges if this file connects two others
-            let forward = read.forward_links(&file).unwrap_or_default().len() as f64;
-            let backward = read.backlinks(&file).unwrap_or_default().len() as f64;
-            let betweenness = ((forward + backward) / 10.0).min(1.0);
-
-            // Closeness: ability to reach others (normalized edge count)
-            let all_count = read.all_files().len() as f64;
-            let closeness = (forward / all_count).min(1.0);
-
-            // Eigenvector: importance based on connection to important files
-            // Simplified: count backlinks (files that link to this one)
-            let eigenvector = (backward / all_count).min(1.0);
-
-            // Combined score (equal weighting)
-            let combined = (betweenness * 0.33 + closeness * 0.33 + eigenvector * 0.34) / 1.0;
-
-            let mut metrics = HashMap::new();
-            metrics.insert("betweenness", betweenness);
-            metrics.insert("closeness", closeness);
-            metrics.insert("eigenvector", eigenvector);
-
-            rankings.push((file_str, combined, metrics));
-        }
-
-        // Sort by combined score descending
-        rankings.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-
-        // Build result
-        let ranked: Vec<_> = rankings
+        let centrality = read.centrality(&CentralityConfig::default());
+        drop(read);
+
+        let metrics: Vec<CentralityMetric> = if metrics.is_empty() {
+            CentralityMetric::ALL.to_vec()
+        } else {
+            metrics.to_vec()
+        };
+        let sort_by = metrics[0];
+
+        let max = |metric: CentralityMetric| {
+            centrality
+                .scores(metric)
+                .values()
+                .copied()
+                .fold(0.0f64, f64::max)
+        };
+        let (max_pagerank, max_betweenness, max_closeness) = (
+            max(CentralityMetric::PageRank),
+            max(CentralityMetric::Betweenness),
+            max(CentralityMetric::Closeness),
+        );
+
+        let ranking = centrality.ranking(sort_by);
+        let total_files = ranking.len();
+        let ranked: Vec<_> = ranking
             .into_iter()
+            .take(top_n.unwrap_or(usize::MAX))
             .enumerate()
-            .map(|(idx, (file, score, metrics))| {
-                let b = metrics.get("betweenness").copied().unwrap_or(0.0);
-                let c = metrics.get("closeness").copied().unwrap_or(0.0);
-                let e = metrics.get("eigenvector").copied().unwrap_or(0.0);
-
-                let interpretation = if b > 0.7 {
-                    "Central hub"
-                } else if e > 0.7 {
-                    "Authority file"
-                } else if c > 0.7 {
-                    "Highly connected"
-                } else {
-                    "Peripheral"
+            .map(|(idx, (path, score))| {
+                let pagerank = centrality.score(CentralityMetric::PageRank, &path);
+                let betweenness = centrality.score(CentralityMetric::Betweenness, &path);
+                let closeness = centrality.score(CentralityMetric::Closeness, &path);
+
+                let interpretation =
+                    if max_betweenness > 0.0 && betweenness >= max_betweenness * 0.5 {
+                        "Bridge between clusters"
+                    } else if max_pagerank > 0.0
+                        && pagerank >= max_pagerank * 0.5
+                        && pagerank * total_files as f64 > 1.0
+                    {
+                        // Above both half the top score and the uniform share
+                        "Authority file"
+                    } else if max_closeness > 0.0 && closeness >= max_closeness * 0.5 {
+                        "Highly reachable"
+                    } else {
+                        "Peripheral"
+                    };
+
+                let rank = CentralityRank {
+                    rank: idx + 1,
+                    file: path.to_string_lossy().to_string(),
+                    score,
+                    pagerank,
+                    betweenness,
+                    closeness,
+                    interpretation: interpretation.to_string(),
                 };
 
-                json!({
-                    "rank": idx + 1,
-                    "file": file,
-                    "score": score,
-                    "betweenness": b,
-                    "closeness": c,
-                    "eigenvector": e,
-                    "interpretation": interpretation
-                })
+                let mut entry = json!({
+                    "rank": rank.rank,
+                    "file": rank.file,
+                    "score": rank.score,
+                });
+                for metric in &metrics {
+                    let value = match metric {
+                        CentralityMetric::PageRank => rank.pagerank,
+                        CentralityMetric::Betweenness => rank.betweenness,
+                        CentralityMetric::Closeness => rank.closeness,
+                    };
+                    entry[metric.as_str()] = json!(value);
+                }
+                entry["interpretation"] = json!(rank.interpretation);
+                entry
             })
             .collect();
 
         Ok(json!({
-            "total_files": ranked.len(),
+            "total_files": total_files,
+            "sort_by": sort_by.as_str(),
+            "metrics": metrics.iter().map(|m| m.as_str()).collect::<Vec<_>>(),
+            "betweenness_sampled": centrality.is_sampled(),
+            "betweenness_sources": centrality.betweenness_sources,
             "rankings": ranked
         }))
     }
 }
 
+/// Jaccard overlap of two sets, ignoring `exclude`, with the shared items
+fn jaccard<'a, T: Ord>(a: &'a BTreeSet<T>, b: &BTreeSet<T>, exclude: &[&T]) -> (f64, Vec<&'a T>) {
+    let kept = |item: &&T| !exclude.contains(item);
+    let shared: Vec<&T> = a.iter().filter(kept).filter(|x| b.contains(*x)).collect();
+    let union = a.iter().filter(kept).count() + b.iter().filter(kept).count() - shared.len();
+    if union == 0 {
+        (0.0, shared)
+    } else {
+        (shared.len() as f64 / union as f64, shared)
+    }
+}
+
 /// Interpret link strength as human-readable text
 fn interpret_strength(strength: f64) -> String {
     match strength {
@@ -305,4 +499,39 @@ mod tests {
         assert!(interpret_strength(0.3).contains("Weak"));
         assert!(interpret_strength(0.0).contains("No"));
     }
+
+    #[test]
+    fn test_suggestion_score_rescales_missing_signals() {
+        let content_only = SuggestionSignals {
+            content: Some(0.4),
+            ..Default::default()
+        };
+        assert!((content_only.score() - 0.4).abs() < 1e-9);
+
+        let all = SuggestionSignals {
+            content: Some(1.0),
+            tags: Some(0.5),
+            outgoing_links: Some(0.0),
+            backlinks: Some(1.0),
+        };
+        let expected = CONTENT_WEIGHT + TAG_WEIGHT * 0.5 + BACKLINK_WEIGHT;
+        assert!((all.score() - expected).abs() < 1e-9);
+
+        assert_eq!(SuggestionSignals::default().score(), 0.0);
+    }
+
+    #[test]
+    fn test_jaccard_excludes_items() {
+        let a: BTreeSet<&str> = ["x", "y", "z"].into_iter().collect();
+        let b: BTreeSet<&str> = ["y", "z", "w"].into_iter().collect();
+        let (score, shared) = jaccard(&a, &b, &[]);
+        assert_eq!(score, 0.5);
+        assert_eq!(shared, vec![&"y", &"z"]);
+
+        let (score, shared) = jaccard(&a, &b, &[&"z"]);
+        assert!((score - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(shared, vec![&"y"]);
+
+        assert_eq!(jaccard(&BTreeSet::<&str>::new(), &b, &[]).0, 0.0);
+    }
 }
@@ -7,6 +7,7 @@
 //! - Fuzzy/approximate queries via regex
 //! - Fast searching even on large vaults
 
+use crate::metadata_tools::QueryFilter;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -16,7 +17,7 @@ use tantivy::schema::*;
 use tantivy::{Index, ReloadPolicy, TantivyDocument, doc};
 use tracing::instrument;
 use turbovault_core::prelude::*;
-use turbovault_parser::to_plain_text;
+use turbovault_parser::{Canvas, excalidraw, to_plain_text};
 use turbovault_vault::VaultManager;
 
 /// Search result metadata for LLM consumption
@@ -32,7 +33,7 @@ pub struct SearchResultInfo {
     pub score: f64,
     /// Matching snippet with context (plain text)
     pub snippet: String,
-    /// Front matter tags
+    /// Front matter and inline tags
     pub tags: Vec<String>,
     /// Files this note links to
     pub outgoing_links: Vec<String>,
@@ -49,7 +50,14 @@ pub struct SearchResultInfo {
 pub struct SearchFilter {
     /// Only match specific tags
     pub tags: Option<Vec<String>>,
-    /// Only match specific frontmatter keys
+    /// Only match notes whose frontmatter satisfies this query
+    pub frontmatter_filter: Option<QueryFilter>,
+    /// Pairs added with [`SearchQuery::with_frontmatter`]; they are also part
+    /// of `frontmatter_filter`, which is what the search applies
+    #[deprecated(
+        since = "1.2.7",
+        note = "Use frontmatter_filter, which includes these pairs"
+    )]
     pub frontmatter_filters: Option<Vec<(String, String)>>,
     /// Only match notes linked by these paths
     pub backlinks_from: Option<Vec<String>>,
@@ -81,11 +89,29 @@ impl SearchQuery {
     }
 
     /// Add frontmatter filter (e.g., "type", "project")
+    ///
+    /// Matches when the value contains `value` (or, for lists, has it as an element).
     pub fn with_frontmatter(mut self, key: String, value: String) -> Self {
+        #[allow(deprecated)]
         self.filter
             .frontmatter_filters
             .get_or_insert_with(Vec::new)
-            .push((key, value));
+            .push((key.clone(), value.clone()));
+        self.with_metadata_filter(QueryFilter::Contains(key, value))
+    }
+
+    /// Add a metadata query filter (same syntax as `query_metadata`)
+    ///
+    /// Multiple filters are combined with AND.
+    pub fn with_metadata_filter(mut self, filter: QueryFilter) -> Self {
+        self.filter.frontmatter_filter = Some(match self.filter.frontmatter_filter.take() {
+            Some(QueryFilter::And(mut filters)) => {
+                filters.push(filter);
+                QueryFilter::And(filters)
+            }
+            Some(existing) => QueryFilter::And(vec![existing, filter]),
+            None => filter,
+        });
         self
     }
 
@@ -145,7 +171,7 @@ impl SearchEngine {
             // Convert PathBuf to string to check extension (case-insensitive)
             let path_str = file_path.to_string_lossy();
             let path_lower = path_str.to_lowercase();
-            if !path_lower.ends_with(".md") {
+            if !path_lower.ends_with(".md") && !path_lower.ends_with(".canvas") {
                 continue;
             }
 
@@ -168,15 +194,21 @@ impl SearchEngine {
                         })
                         .to_string();
 
-                    // Get tags
-                    let tags_str = vault_file
+                    // Get frontmatter and inline tags
+                    let mut tags: Vec<String> = vault_file
                         .frontmatter
                         .as_ref()
-                        .map(|fm| fm.tags().join(" "))
+                        .map(|fm| fm.tags())
                         .unwrap_or_default();
+                    for tag in &vault_file.tags {
+                        if !tags.contains(&tag.name) {
+                            tags.push(tag.name.clone());
+                        }
+                    }
+                    let tags_str = tags.join(" ");
 
                     // Extract plain text for indexing (excludes markdown syntax, URLs, etc.)
-                    let plain_content = to_plain_text(&vault_file.content);
+                    let plain_content = searchable_text(&vault_file);
 
                     // Add document to index with plain text content
                     let _ = index_writer.add_document(doc!(
@@ -244,7 +276,7 @@ impl SearchEngine {
         let vault_file = self.manager.parse_file(&PathBuf::from(path)).await?;
 
         // Extract key terms from plain text content (excludes URLs, markdown syntax)
-        let plain_content = to_plain_text(&vault_file.content);
+        let plain_content = searchable_text(&vault_file);
         let keywords = extract_keywords(&plain_content);
 
         // Search for similar notes using tantivy query
@@ -357,7 +389,9 @@ impl SearchQuery {
 
             // Apply filter filters
             if let Some(tags) = &filter.tags
-                && !file_tags.iter().any(|t| tags.contains(t))
+                && !file_tags
+                    .iter()
+                    .any(|t| tags.iter().any(|f| f.trim_start_matches('#') == t))
             {
                 continue;
             }
@@ -370,30 +404,15 @@ impl SearchQuery {
             }
 
             // Apply frontmatter filters
-            if let Some(fm_filters) = &filter.frontmatter_filters {
+            if let Some(fm_filter) = &filter.frontmatter_filter {
                 let file_path = PathBuf::from(&path);
-                if let Ok(vault_file) = engine.manager.parse_file(&file_path).await {
-                    let mut matches_all = true;
-                    if let Some(fm) = &vault_file.frontmatter {
-                        for (key, value) in fm_filters {
-                            if let Some(fm_value) = fm.data.get(key) {
-                                let fm_str = fm_value.to_string();
-                                if !fm_str.contains(value) {
-                                    matches_all = false;
-                                    break;
-                                }
-                            } else {
-                                matches_all = false;
-                                break;
-                            }
-                        }
-                    } else {
-                        matches_all = false;
-                    }
-                    if !matches_all {
-                        continue;
-                    }
-                } else {
+                let matches = match engine.manager.parse_file(&file_path).await {
+                    Ok(vault_file) => vault_file
+                        .frontmatter
+                        .is_some_and(|fm| fm_filter.matches(&fm.data)),
+                    Err(_) => false,
+                };
+                if !matches {
                     continue;
                 }
             }
@@ -402,7 +421,7 @@ impl SearchQuery {
             let file_path = PathBuf::from(&path);
             if let Ok(vault_file) = engine.manager.parse_file(&file_path).await {
                 // Extract plain text for preview, snippet, and metrics
-                let plain_content = to_plain_text(&vault_file.content);
+                let plain_content = searchable_text(&vault_file);
 
                 // Generate preview from plain text (first line, up to 200 chars)
                 let preview = plain_content
@@ -453,6 +472,24 @@ impl SearchQuery {
     }
 }
 
+/// Plain text of a note for indexing: a canvas's text nodes and labels, a
+/// drawing's text elements, or the note body
+pub(crate) fn searchable_text(vault_file: &VaultFile) -> String {
+    if vault_file
+        .path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("canvas"))
+    {
+        return Canvas::parse(&vault_file.content)
+            .map(|canvas| canvas.plain_text())
+            .unwrap_or_default();
+    }
+    if excalidraw::is_excalidraw(&vault_file.path, vault_file.frontmatter.as_ref()) {
+        return excalidraw::plain_text(&vault_file.content);
+    }
+    to_plain_text(&vault_file.content)
+}
+
 /// Extract keywords from content for recommendations
 fn extract_keywords(content: &str) -> Vec<String> {
     content
@@ -465,7 +502,7 @@ fn extract_keywords(content: &str) -> Vec<String> {
 }
 
 /// Check if word is a common stopword
-fn is_stopword(word: &str) -> bool {
+pub(crate) fn is_stopword(word: &str) -> bool {
     matches!(
         word.to_lowercase().as_str(),
         "the"
@@ -547,6 +584,22 @@ mod tests {
         assert!(keywords.iter().any(|k| k == "quick" || k == "brown"));
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_with_frontmatter_keeps_legacy_pairs() {
+        let (_, filter, _) = SearchQuery::new("q")
+            .with_frontmatter("type".to_string(), "project".to_string())
+            .build();
+        assert_eq!(
+            filter.frontmatter_filters,
+            Some(vec![("type".to_string(), "project".to_string())])
+        );
+        assert!(matches!(
+            filter.frontmatter_filter,
+            Some(QueryFilter::Contains(ref key, _)) if key == "type"
+        ));
+    }
+
     #[test]
     fn test_is_stopword() {
         assert!(is_stopword("the"));
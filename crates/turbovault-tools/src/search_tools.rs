@@ -1,8 +1,10 @@
 //! Search and relationship discovery tools
 
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Arc;
 use turbovault_core::prelude::*;
+use turbovault_graph::{EdgeKind, LinkEdge};
 use turbovault_vault::VaultManager;
 
 /// Search tools context
@@ -10,6 +12,19 @@ pub struct SearchTools {
     pub manager: Arc<VaultManager>,
 }
 
+/// A typed link to or from a note, for JSON serialization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkEdgeInfo {
+    /// The note at the other end of the link
+    pub path: String,
+    pub kind: EdgeKind,
+    /// Frontmatter property holding the link, for `property` edges
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
+    pub target: String,
+    pub line: usize,
+}
+
 impl SearchTools {
     /// Create new search tools
     pub fn new(manager: Arc<VaultManager>) -> Self {
@@ -38,6 +53,50 @@ impl SearchTools {
             .collect())
     }
 
+    /// Find backlinks to a file as typed edges, keeping only `kinds` (empty = all)
+    pub async fn find_backlink_edges(
+        &self,
+        path: &str,
+        kinds: &[EdgeKind],
+    ) -> Result<Vec<LinkEdgeInfo>> {
+        let edges = self
+            .manager
+            .get_backlink_edges(&PathBuf::from(path), kinds)
+            .await?;
+        Ok(edge_infos(edges))
+    }
+
+    /// Find forward links from a file as typed edges, keeping only `kinds` (empty = all)
+    pub async fn find_forward_link_edges(
+        &self,
+        path: &str,
+        kinds: &[EdgeKind],
+    ) -> Result<Vec<LinkEdgeInfo>> {
+        let edges = self
+            .manager
+            .get_forward_link_edges(&PathBuf::from(path), kinds)
+            .await?;
+        Ok(edge_infos(edges))
+    }
+
+    /// Find related notes within N hops, following only `kinds` of edges (empty = all)
+    pub async fn find_related_notes_by_kind(
+        &self,
+        path: &str,
+        max_hops: usize,
+        kinds: &[EdgeKind],
+    ) -> Result<Vec<String>> {
+        let related = self
+            .manager
+            .get_related_notes_by_kind(&PathBuf::from(path), max_hops, kinds)
+            .await?;
+
+        Ok(related
+            .into_iter()
+            .filter_map(|p| p.to_str().map(|s| s.to_string()))
+            .collect())
+    }
+
     /// Find related notes within N hops
     pub async fn find_related_notes(&self, path: &str, max_hops: usize) -> Result<Vec<String>> {
         let file_path = PathBuf::from(path);
@@ -77,3 +136,22 @@ impl SearchTools {
         Ok(results)
     }
 }
+
+/// Flatten grouped edges into sorted per-link records
+fn edge_infos(grouped: Vec<(PathBuf, Vec<LinkEdge>)>) -> Vec<LinkEdgeInfo> {
+    let mut infos: Vec<LinkEdgeInfo> = grouped
+        .into_iter()
+        .flat_map(|(path, edges)| {
+            let path = path.to_string_lossy().to_string();
+            edges.into_iter().map(move |edge| LinkEdgeInfo {
+                path: path.clone(),
+                kind: edge.kind,
+                property: edge.property,
+                target: edge.link.target,
+                line: edge.link.position.line,
+            })
+        })
+        .collect();
+    infos.sort_by(|a, b| (&a.path, a.line, &a.target).cmp(&(&b.path, b.line, &b.target)));
+    infos
+}
@@ -0,0 +1,179 @@
+//! TF-IDF document vectors for content similarity
+//!
+//! Terms are lowercase alphanumeric words of at least three characters,
+//! minus the search engine's stopwords. Weights use sublinear term frequency
+//! (`1 + ln tf`) and smoothed inverse document frequency
+//! (`1 + ln((n + 1) / (df + 1))`); vectors are L2-normalized so the dot
+//! product of two vectors is their cosine similarity.
+
+use crate::search_engine::is_stopword;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// TF-IDF vectors of a set of documents
+#[derive(Debug, Clone, Default)]
+pub struct TfIdfIndex {
+    vectors: HashMap<PathBuf, HashMap<String, f64>>,
+}
+
+impl TfIdfIndex {
+    /// Build vectors for documents given as (path, plain text)
+    pub fn build(documents: &[(PathBuf, String)]) -> Self {
+        let counts: Vec<(&PathBuf, HashMap<String, usize>)> = documents
+            .iter()
+            .map(|(path, text)| {
+                let mut tf: HashMap<String, usize> = HashMap::new();
+                for term in tokenize(text) {
+                    *tf.entry(term).or_insert(0) += 1;
+                }
+                (path, tf)
+            })
+            .collect();
+
+        let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+        for (_, tf) in &counts {
+            for term in tf.keys() {
+                *document_frequency.entry(term.as_str()).or_insert(0) += 1;
+            }
+        }
+
+        let n = documents.len() as f64;
+        let vectors = counts
+            .iter()
+            .map(|(path, tf)| {
+                let mut vector: HashMap<String, f64> = tf
+                    .iter()
+                    .map(|(term, &count)| {
+                        let df = document_frequency[term.as_str()] as f64;
+                        let idf = 1.0 + ((n + 1.0) / (df + 1.0)).ln();
+                        (term.clone(), (1.0 + (count as f64).ln()) * idf)
+                    })
+                    .collect();
+                let norm = vector.values().map(|w| w * w).sum::<f64>().sqrt();
+                if norm > 0.0 {
+                    for weight in vector.values_mut() {
+                        *weight /= norm;
+                    }
+                }
+                ((*path).clone(), vector)
+            })
+            .collect();
+
+        Self { vectors }
+    }
+
+    /// Number of indexed documents
+    pub fn len(&self) -> usize {
+        self.vectors.len()
+    }
+
+    /// Whether no documents are indexed
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty()
+    }
+
+    /// Whether a document has any terms
+    pub fn has_terms(&self, path: &Path) -> bool {
+        self.vectors.get(path).is_some_and(|v| !v.is_empty())
+    }
+
+    /// Cosine similarity of two documents in [0, 1] (0 if either is unknown)
+    pub fn similarity(&self, a: &Path, b: &Path) -> f64 {
+        self.contributions(a, b)
+            .iter()
+            .map(|(_, w)| w)
+            .sum::<f64>()
+            .min(1.0)
+    }
+
+    /// Terms contributing most to the similarity of two documents
+    pub fn shared_terms(&self, a: &Path, b: &Path, limit: usize) -> Vec<String> {
+        let mut contributions = self.contributions(a, b);
+        contributions.sort_by(|x, y| y.1.total_cmp(&x.1).then_with(|| x.0.cmp(y.0)));
+        contributions
+            .into_iter()
+            .take(limit)
+            .map(|(term, _)| term.to_string())
+            .collect()
+    }
+
+    fn contributions(&self, a: &Path, b: &Path) -> Vec<(&str, f64)> {
+        let (Some(va), Some(vb)) = (self.vectors.get(a), self.vectors.get(b)) else {
+            return Vec::new();
+        };
+        let (small, large) = if va.len() <= vb.len() {
+            (va, vb)
+        } else {
+            (vb, va)
+        };
+        small
+            .iter()
+            .filter_map(|(term, w)| large.get(term).map(|v| (term.as_str(), w * v)))
+            .collect()
+    }
+}
+
+/// Lowercase words of at least three characters, without stopwords
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .filter(|word| !is_stopword(word) && !word.chars().all(|c| c.is_ascii_digit()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> TfIdfIndex {
+        TfIdfIndex::build(&[
+            (
+                PathBuf::from("rust.md"),
+                "Rust ownership and borrowing make memory safety explicit.".to_string(),
+            ),
+            (
+                PathBuf::from("borrow.md"),
+                "The borrow checker enforces ownership rules; borrowing is checked.".to_string(),
+            ),
+            (
+                PathBuf::from("bread.md"),
+                "Sourdough bread needs flour, water and time.".to_string(),
+            ),
+            (PathBuf::from("empty.md"), "A an the 2024".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("The Borrow-checker, in 2024: it's ok!"),
+            vec!["borrow", "checker"]
+        );
+    }
+
+    #[test]
+    fn test_similarity_and_shared_terms() {
+        let index = index();
+        let rust = Path::new("rust.md");
+        let borrow = Path::new("borrow.md");
+        let bread = Path::new("bread.md");
+
+        let related = index.similarity(rust, borrow);
+        assert!(related > 0.1);
+        assert_eq!(index.similarity(rust, bread), 0.0);
+        assert!((index.similarity(rust, rust) - 1.0).abs() < 1e-9);
+        assert_eq!(
+            index.similarity(rust, borrow),
+            index.similarity(borrow, rust)
+        );
+
+        let mut shared = index.shared_terms(rust, borrow, 5);
+        shared.sort();
+        assert_eq!(shared, vec!["borrowing", "ownership"]);
+
+        assert!(!index.has_terms(Path::new("empty.md")));
+        assert_eq!(index.similarity(rust, Path::new("missing.md")), 0.0);
+        assert_eq!(index.len(), 4);
+    }
+}
@@ -0,0 +1,642 @@
+//! Structured access to Markdown tables
+//!
+//! Tables are addressed by their 0-based index in a note, optionally counted
+//! among the tables under a given heading. Rows are returned as JSON records
+//! keyed by header. Edits work on the cells as written, so markup in cells
+//! that are not edited survives; only the edited table is rewritten, keeping
+//! its alignment row, escaping `|` in new values and keeping the `>` prefix of
+//! tables inside callouts. The rest of the note is left byte-for-byte intact.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Arc;
+use turbovault_core::prelude::*;
+use turbovault_parser::MarkdownDocument;
+use turbovault_vault::VaultManager;
+
+/// A table in a note
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableSummary {
+    /// Position among the note's tables, in document order
+    pub index: usize,
+    /// Text of the nearest heading above the table
+    pub heading: Option<String>,
+    /// Line of the header row
+    pub line: usize,
+    pub headers: Vec<String>,
+    pub row_count: usize,
+}
+
+/// A table with its rows as records keyed by header
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableData {
+    pub index: usize,
+    pub heading: Option<String>,
+    pub line: usize,
+    /// Record keys, in column order; repeated or empty headers are made
+    /// unique as `Name (2)` or `Column 3`
+    pub headers: Vec<String>,
+    pub alignments: Vec<TableAlignment>,
+    pub records: Vec<Map<String, Value>>,
+}
+
+/// Rows an operation applies to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RowSelector {
+    /// 0-based index of a data row (the header row is not counted)
+    Index(usize),
+    /// Every row whose cell in `column` equals `equals`
+    Match { column: String, equals: String },
+}
+
+/// Change applied by [`TableTools::update_table`]
+///
+/// Columns are named by header. Values are cell Markdown; `|` is escaped and
+/// line breaks become `<br>`. Non-string JSON values are written as JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TableOperation {
+    /// Set cells of the selected rows
+    UpdateRows {
+        rows: RowSelector,
+        set: Map<String, Value>,
+    },
+    /// Insert a row at `position` (appended when absent); missing cells are empty
+    InsertRow {
+        values: Map<String, Value>,
+        #[serde(default)]
+        position: Option<usize>,
+    },
+    /// Delete the selected rows
+    DeleteRows { rows: RowSelector },
+    /// Insert a column at `position` (appended when absent), filled with `value`
+    InsertColumn {
+        name: String,
+        #[serde(default)]
+        position: Option<usize>,
+        #[serde(default)]
+        value: Option<Value>,
+        #[serde(default)]
+        alignment: Option<TableAlignment>,
+    },
+    /// Delete a column
+    DeleteColumn { name: String },
+    /// Rename a column header
+    RenameColumn { from: String, to: String },
+}
+
+/// Result of updating a table (or its preview)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableUpdateReport {
+    pub path: String,
+    pub dry_run: bool,
+    /// Rows updated, inserted or deleted
+    pub rows_affected: usize,
+    pub before: TableData,
+    pub after: TableData,
+    /// Markdown the table was rewritten as
+    pub markdown: String,
+}
+
+/// A table as written in the note
+#[derive(Debug, Clone)]
+struct SourceTable {
+    /// Byte range from the first cell of the header row to the end of the last row
+    range: Range<usize>,
+    /// Text before the table on each line after the first, e.g. `> `
+    /// inside a callout
+    prefix: String,
+    newline: &'static str,
+    heading: Option<String>,
+    line: usize,
+    /// Cells as written, `|` still escaped
+    header: Vec<String>,
+    delimiter: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+/// Table tools context
+pub struct TableTools {
+    pub manager: Arc<VaultManager>,
+}
+
+impl TableTools {
+    /// Create new table tools
+    pub fn new(manager: Arc<VaultManager>) -> Self {
+        Self { manager }
+    }
+
+    /// List the tables of a note
+    pub async fn list_tables(&self, path: &str) -> Result<Vec<TableSummary>> {
+        let content = self.manager.read_file(&PathBuf::from(path)).await?;
+        Ok(find_tables(&content)
+            .iter()
+            .enumerate()
+            .map(|(index, table)| TableSummary {
+                index,
+                heading: table.heading.clone(),
+                line: table.line,
+                headers: table.keys(),
+                row_count: table.rows.len(),
+            })
+            .collect())
+    }
+
+    /// Read one table as records
+    ///
+    /// With `heading`, `index` counts only the tables under that heading.
+    pub async fn read_table(
+        &self,
+        path: &str,
+        index: Option<usize>,
+        heading: Option<&str>,
+    ) -> Result<TableData> {
+        let content = self.manager.read_file(&PathBuf::from(path)).await?;
+        let tables = find_tables(&content);
+        let selected = select_table(&tables, index, heading, path)?;
+        Ok(tables[selected].data(selected))
+    }
+
+    /// Apply operations to one table, in order, and write the note back
+    ///
+    /// Nothing is written when `dry_run` is set or an operation fails.
+    pub async fn update_table(
+        &self,
+        path: &str,
+        index: Option<usize>,
+        heading: Option<&str>,
+        operations: &[TableOperation],
+        dry_run: bool,
+    ) -> Result<TableUpdateReport> {
+        let file_path = PathBuf::from(path);
+        let content = self.manager.read_file(&file_path).await?;
+        let tables = find_tables(&content);
+        let selected = select_table(&tables, index, heading, path)?;
+
+        let before = tables[selected].clone();
+        let mut table = before.clone();
+        let mut rows_affected = 0;
+        for operation in operations {
+            rows_affected += table.apply(operation)?;
+        }
+
+        let markdown = table.render();
+        if !dry_run {
+            let mut updated = content.clone();
+            updated.replace_range(table.range.clone(), &markdown);
+            if updated != content {
+                self.manager.write_file(&file_path, &updated).await?;
+            }
+        }
+
+        Ok(TableUpdateReport {
+            path: path.to_string(),
+            dry_run,
+            rows_affected,
+            before: before.data(selected),
+            after: table.data(selected),
+            markdown,
+        })
+    }
+}
+
+/// Every table in the note, in document order, including tables nested in
+/// callouts, lists and `<details>`
+fn find_tables(content: &str) -> Vec<SourceTable> {
+    fn walk(
+        blocks: &[ContentBlock],
+        content: &str,
+        heading: &mut Option<String>,
+        tables: &mut Vec<SourceTable>,
+    ) {
+        for block in blocks {
+            match block {
+                ContentBlock::Heading { content, .. } => *heading = Some(content.clone()),
+                ContentBlock::Table {
+                    span: Some(span), ..
+                } => {
+                    if let Some(table) = SourceTable::parse(content, span, heading.clone()) {
+                        tables.push(table);
+                    }
+                }
+                ContentBlock::List { items, .. } => {
+                    for item in items {
+                        walk(&item.blocks, content, heading, tables);
+                    }
+                }
+                ContentBlock::Blockquote { blocks, .. }
+                | ContentBlock::Details { blocks, .. }
+                | ContentBlock::FootnoteDefinition { blocks, .. } => {
+                    walk(blocks, content, heading, tables)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let document = MarkdownDocument::parse(content);
+    let mut tables = Vec::new();
+    walk(&document.blocks, content, &mut None, &mut tables);
+    tables
+}
+
+/// Byte ranges of every table in the note
+pub(crate) fn table_ranges(content: &str) -> Vec<Range<usize>> {
+    find_tables(content).into_iter().map(|t| t.range).collect()
+}
+
+/// Index of the table selected by `index` and `heading`
+fn select_table(
+    tables: &[SourceTable],
+    index: Option<usize>,
+    heading: Option<&str>,
+    path: &str,
+) -> Result<usize> {
+    let normalize = |text: &str| text.trim().trim_start_matches('#').trim().to_lowercase();
+    let candidates: Vec<usize> = match heading {
+        Some(wanted) => {
+            let wanted = normalize(wanted);
+            (0..tables.len())
+                .filter(|&i| {
+                    tables[i]
+                        .heading
+                        .as_deref()
+                        .is_some_and(|h| normalize(h) == wanted)
+                })
+                .collect()
+        }
+        None => (0..tables.len()).collect(),
+    };
+
+    let position = index.unwrap_or(0);
+    candidates.get(position).copied().ok_or_else(|| {
+        let scope = match heading {
+            Some(h) => format!("under heading '{}' ", h),
+            None => String::new(),
+        };
+        Error::not_found(format!(
+            "Table {} {}not found in {} ({} tables)",
+            position,
+            scope,
+            path,
+            candidates.len()
+        ))
+    })
+}
+
+impl SourceTable {
+    fn parse(content: &str, span: &SourcePosition, heading: Option<String>) -> Option<Self> {
+        let range = span.range();
+        let text = content.get(range.clone())?;
+        let mut lines = text
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line));
+
+        let header = lines.next()?;
+        // Continuation lines carry the container's markers, e.g. `> ` in a
+        // callout or the indentation of a list item
+        let delimiter_line = lines.next()?;
+        let delimiter = delimiter_line.trim_start_matches(|c: char| c == '>' || c.is_whitespace());
+        let prefix = delimiter_line[..delimiter_line.len() - delimiter.len()].to_string();
+        let mut lines = std::iter::once(header)
+            .chain(std::iter::once(delimiter))
+            .chain(lines.map(|line| {
+                line.strip_prefix(prefix.as_str()).unwrap_or_else(|| {
+                    line.trim_start_matches(|c: char| c == '>' || c.is_whitespace())
+                })
+            }));
+        let header = split_row(lines.next()?);
+        let delimiter = split_row(lines.next()?);
+        let rows = lines.map(split_row).collect();
+
+        Some(Self {
+            range,
+            prefix,
+            newline: if text.contains("\r\n") { "\r\n" } else { "\n" },
+            heading,
+            line: span.line,
+            header,
+            delimiter,
+            rows,
+        })
+    }
+
+    /// Header names made unique, used as record keys and to find columns
+    fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = Vec::new();
+        for (i, cell) in self.header.iter().enumerate() {
+            let name = unescape_cell(cell);
+            let base = if name.is_empty() {
+                format!("Column {}", i + 1)
+            } else {
+                name
+            };
+            let mut key = base.clone();
+            let mut n = 2;
+            while keys.contains(&key) {
+                key = format!("{} ({})", base, n);
+                n += 1;
+            }
+            keys.push(key);
+        }
+        keys
+    }
+
+    fn alignments(&self) -> Vec<TableAlignment> {
+        (0..self.header.len())
+            .map(|i| {
+                let cell = self.delimiter.get(i).map_or("", |c| c.as_str());
+                match (cell.starts_with(':'), cell.ends_with(':') && cell.len() > 1) {
+                    (true, true) => TableAlignment::Center,
+                    (true, false) => TableAlignment::Left,
+                    (false, true) => TableAlignment::Right,
+                    (false, false) => TableAlignment::None,
+                }
+            })
+            .collect()
+    }
+
+    fn data(&self, index: usize) -> TableData {
+        let keys = self.keys();
+        let records = self
+            .rows
+            .iter()
+            .map(|row| {
+                keys.iter()
+                    .enumerate()
+                    .map(|(i, key)| {
+                        let cell = row.get(i).map_or(String::new(), |c| unescape_cell(c));
+                        (key.clone(), Value::String(cell))
+                    })
+                    .collect()
+            })
+            .collect();
+        TableData {
+            index,
+            heading: self.heading.clone(),
+            line: self.line,
+            headers: keys,
+            alignments: self.alignments(),
+            records,
+        }
+    }
+
+    /// Column index of a header name (exact, then case-insensitive)
+    fn column(&self, name: &str) -> Result<usize> {
+        let keys = self.keys();
+        keys.iter()
+            .position(|k| k == name)
+            .or_else(|| keys.iter().position(|k| k.eq_ignore_ascii_case(name)))
+            .ok_or_else(|| {
+                Error::validation_error(format!(
+                    "Unknown column '{}'; columns are: {}",
+                    name,
+                    keys.join(", ")
+                ))
+            })
+    }
+
+    /// Indexes of the selected rows, in order
+    fn select_rows(&self, rows: &RowSelector) -> Result<Vec<usize>> {
+        match rows {
+            RowSelector::Index(index) if *index < self.rows.len() => Ok(vec![*index]),
+            RowSelector::Index(index) => Err(Error::validation_error(format!(
+                "Row {} out of range; the table has {} rows",
+                index,
+                self.rows.len()
+            ))),
+            RowSelector::Match { column, equals } => {
+                let column = self.column(column)?;
+                Ok((0..self.rows.len())
+                    .filter(|&i| {
+                        self.rows[i]
+                            .get(column)
+                            .is_some_and(|c| unescape_cell(c) == equals.trim())
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    /// Cells for `values`, keyed by column index
+    fn cells(&self, values: &Map<String, Value>) -> Result<Vec<(usize, String)>> {
+        values
+            .iter()
+            .map(|(name, value)| Ok((self.column(name)?, escape_cell(value))))
+            .collect()
+    }
+
+    fn pad(&mut self) {
+        let width = self.header.len();
+        for row in &mut self.rows {
+            row.resize(width, String::new());
+        }
+    }
+
+    /// Apply one operation, returning the number of rows it touched
+    fn apply(&mut self, operation: &TableOperation) -> Result<usize> {
+        self.pad();
+        match operation {
+            TableOperation::UpdateRows { rows, set } => {
+                let selected = self.select_rows(rows)?;
+                let cells = self.cells(set)?;
+                for &row in &selected {
+                    for (column, cell) in &cells {
+                        self.rows[row][*column] = cell.clone();
+                    }
+                }
+                Ok(selected.len())
+            }
+            TableOperation::InsertRow { values, position } => {
+                let mut row = vec![String::new(); self.header.len()];
+                for (column, cell) in self.cells(values)? {
+                    row[column] = cell;
+                }
+                let position = position.unwrap_or(self.rows.len()).min(self.rows.len());
+                self.rows.insert(position, row);
+                Ok(1)
+            }
+            TableOperation::DeleteRows { rows } => {
+                let selected = self.select_rows(rows)?;
+                for &row in selected.iter().rev() {
+                    self.rows.remove(row);
+                }
+                Ok(selected.len())
+            }
+            TableOperation::InsertColumn {
+                name,
+                position,
+                value,
+                alignment,
+            } => {
+                if self.column(name).is_ok() {
+                    return Err(Error::validation_error(format!(
+                        "Column '{}' already exists",
+                        name
+                    )));
+                }
+                let position = position.unwrap_or(self.header.len()).min(self.header.len());
+                let fill = value.as_ref().map(escape_cell).unwrap_or_default();
+                self.header
+                    .insert(position, escape_cell(&Value::String(name.clone())));
+                self.delimiter
+                    .resize(self.header.len() - 1, "---".to_string());
+                self.delimiter.insert(
+                    position,
+                    delimiter_cell(alignment.unwrap_or(TableAlignment::None)),
+                );
+                for row in &mut self.rows {
+                    row.insert(position, fill.clone());
+                }
+                Ok(0)
+            }
+            TableOperation::DeleteColumn { name } => {
+                let column = self.column(name)?;
+                if self.header.len() == 1 {
+                    return Err(Error::validation_error(
+                        "Cannot delete the only column of a table",
+                    ));
+                }
+                self.header.remove(column);
+                if column < self.delimiter.len() {
+                    self.delimiter.remove(column);
+                }
+                for row in &mut self.rows {
+                    row.remove(column);
+                }
+                Ok(0)
+            }
+            TableOperation::RenameColumn { from, to } => {
+                let column = self.column(from)?;
+                if self.column(to).is_ok_and(|c| c != column) {
+                    return Err(Error::validation_error(format!(
+                        "Column '{}' already exists",
+                        to
+                    )));
+                }
+                self.header[column] = escape_cell(&Value::String(to.clone()));
+                Ok(0)
+            }
+        }
+    }
+
+    /// The table as Markdown, starting at its first cell
+    fn render(&self) -> String {
+        let width = self.header.len();
+        let row = |cells: &[String]| {
+            let cells: Vec<&str> = (0..width)
+                .map(|i| cells.get(i).map_or("", |c| c.as_str()))
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        };
+        let mut delimiter = self.delimiter.clone();
+        delimiter.resize(width, "---".to_string());
+
+        let separator = format!("{}{}", self.newline, self.prefix);
+        std::iter::once(row(&self.header))
+            .chain(std::iter::once(row(&delimiter)))
+            .chain(self.rows.iter().map(|r| row(r)))
+            .collect::<Vec<_>>()
+            .join(&separator)
+    }
+}
+
+/// Cells of a table row as written; a leading and trailing `|` is optional
+fn split_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut escaped = false;
+    for c in line.chars() {
+        if c == '|' && !escaped {
+            cells.push(cell.trim().to_string());
+            cell.clear();
+        } else {
+            cell.push(c);
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    if !cell.trim().is_empty() {
+        cells.push(cell.trim().to_string());
+    }
+    cells
+}
+
+fn unescape_cell(cell: &str) -> String {
+    cell.replace("\\|", "|")
+}
+
+fn escape_cell(value: &Value) -> String {
+    let text = match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    };
+    text.trim()
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+        .replace('|', "\\|")
+}
+
+fn delimiter_cell(alignment: TableAlignment) -> String {
+    match alignment {
+        TableAlignment::Left => ":---",
+        TableAlignment::Center => ":---:",
+        TableAlignment::Right => "---:",
+        TableAlignment::None => "---",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_row_keeps_escaped_pipes_and_markup() {
+        assert_eq!(
+            split_row("| **A** | b \\| c | [[L\\|alias]] |"),
+            vec!["**A**", "b \\| c", "[[L\\|alias]]"]
+        );
+        assert_eq!(split_row("a | b"), vec!["a", "b"]);
+        assert_eq!(split_row("| a |  |"), vec!["a", ""]);
+    }
+
+    #[test]
+    fn test_find_tables_with_headings_and_callouts() {
+        let content = "# One\n\n| A | B |\n|:-|--:|\n| 1 | 2 |\n\n## Two\n\n> [!note]\n> | X |\n> | --- |\n> | y |\n";
+        let tables = find_tables(content);
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].heading.as_deref(), Some("One"));
+        assert_eq!(
+            tables[0].alignments(),
+            vec![TableAlignment::Left, TableAlignment::Right]
+        );
+        assert_eq!(tables[1].heading.as_deref(), Some("Two"));
+        assert_eq!(tables[1].prefix, "> ");
+        assert_eq!(tables[1].rows, vec![vec!["y".to_string()]]);
+        assert_eq!(tables[1].render(), "| X |\n> | --- |\n> | y |");
+
+        let tables = find_tables("- item\n\n  | A |\n  | - |\n  | b |\n");
+        assert_eq!(tables[0].prefix, "  ");
+        assert_eq!(tables[0].render(), "| A |\n  | - |\n  | b |");
+    }
+
+    #[test]
+    fn test_duplicate_and_empty_headers_get_unique_keys() {
+        let tables = find_tables("| Name | Name |  |\n|---|---|---|\n| a | b | c |\n");
+        assert_eq!(tables[0].keys(), vec!["Name", "Name (2)", "Column 3"]);
+    }
+
+    #[test]
+    fn test_escape_cell() {
+        assert_eq!(escape_cell(&Value::String("a|b\nc".into())), "a\\|b<br>c");
+        assert_eq!(escape_cell(&serde_json::json!(42)), "42");
+        assert_eq!(escape_cell(&Value::Null), "");
+    }
+}
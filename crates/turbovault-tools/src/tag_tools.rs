@@ -0,0 +1,727 @@
+//! Tag management tools: hierarchy listing, rename, merge and delete, plus
+//! co-occurrence analysis (related tags, near-duplicates, bridging notes)
+//!
+//! Tags are read from the frontmatter `tags` property and from inline `#tag`
+//! occurrences. Rewrites edit inline tags by their parsed position, so code
+//! blocks, inline code and wikilink headings are never touched, and all
+//! changed notes are written as one batch, restored if any write fails.
+
+use crate::batch_tools::{BatchTools, NoteRewrite};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+use turbovault_batch::BatchResult;
+use turbovault_core::prelude::*;
+use turbovault_graph::{
+    BridgingNote, CommunityConfig, NearDuplicateConfig, NearDuplicateTags, TagCommunity, TagGraph,
+    TagPair,
+};
+use turbovault_vault::{FrontmatterDocument, VaultManager};
+
+/// Frontmatter keys holding tags
+const TAG_KEYS: [&str; 2] = ["tags", "tag"];
+
+/// A node in the tag hierarchy (`#parent/child` nests `child` under `parent`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagNode {
+    /// Full tag path without `#` (e.g. `project/alpha`)
+    pub tag: String,
+    /// Last path segment
+    pub name: String,
+    /// Occurrences of exactly this tag (frontmatter and inline)
+    pub count: usize,
+    /// Notes using exactly this tag
+    pub note_count: usize,
+    /// Occurrences of this tag and all descendants
+    pub total_count: usize,
+    pub children: Vec<TagNode>,
+}
+
+/// Vault-wide tag hierarchy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagHierarchy {
+    /// Distinct tags, including parents that only exist through children
+    pub total_tags: usize,
+    pub roots: Vec<TagNode>,
+}
+
+/// One tag rewrite in a note
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagOccurrenceChange {
+    /// `frontmatter` or `inline`
+    pub location: String,
+    /// 1-based line for inline occurrences
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub from: String,
+    /// New tag, or `None` when the tag is removed
+    pub to: Option<String>,
+}
+
+/// Tag changes planned for one note
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteTagChanges {
+    /// Note path relative to vault root
+    pub path: String,
+    pub changes: Vec<TagOccurrenceChange>,
+}
+
+/// Result of a tag rename, merge or delete (or its preview)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagChangeReport {
+    pub operation: String,
+    pub dry_run: bool,
+    pub notes_changed: usize,
+    pub occurrences_changed: usize,
+    pub notes: Vec<NoteTagChanges>,
+    /// Batch execution result (absent for dry runs or when nothing changes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch: Option<BatchResult>,
+}
+
+/// Tags co-occurring with one tag
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedTagsReport {
+    pub tag: String,
+    /// Notes carrying exactly this tag
+    pub note_count: usize,
+    /// Parent and direct children in the `#parent/child` hierarchy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    pub children: Vec<String>,
+    /// Co-occurring tags, highest NPMI first
+    pub related: Vec<TagPair>,
+}
+
+/// Tag communities and the notes spanning them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagBridgesReport {
+    pub modularity: f64,
+    pub communities: Vec<TagCommunity>,
+    /// Notes tagged from several communities, most communities first
+    pub notes: Vec<BridgingNote>,
+}
+
+/// What to do with a matched tag
+#[derive(Debug, Clone, PartialEq)]
+enum TagEdit {
+    Replace(String),
+    Remove,
+}
+
+/// Tag management tools context
+pub struct TagTools {
+    pub manager: Arc<VaultManager>,
+}
+
+impl TagTools {
+    /// Create new tag tools
+    pub fn new(manager: Arc<VaultManager>) -> Self {
+        Self { manager }
+    }
+
+    /// List every tag as a hierarchy with usage counts.
+    ///
+    /// Tags are case-insensitive; each is reported under its first spelling.
+    pub async fn list_tags(&self) -> Result<TagHierarchy> {
+        let mut counts: BTreeMap<String, (String, usize, usize)> = BTreeMap::new();
+
+        for (_, content) in self.notes().await? {
+            let mut seen_in_note: Vec<String> = Vec::new();
+            for tag in note_tags(&content) {
+                let key = tag.to_lowercase();
+                let entry = counts.entry(key.clone()).or_insert((tag, 0, 0));
+                entry.1 += 1;
+                if !seen_in_note.contains(&key) {
+                    entry.2 += 1;
+                    seen_in_note.push(key);
+                }
+            }
+        }
+
+        // Make sure every ancestor exists, even if only used through children
+        let keys: Vec<String> = counts.keys().cloned().collect();
+        for key in keys {
+            let spelling = counts[&key].0.clone();
+            let mut parts: Vec<&str> = spelling.split('/').collect();
+            while parts.len() > 1 {
+                parts.pop();
+                let parent = parts.join("/");
+                counts
+                    .entry(parent.to_lowercase())
+                    .or_insert((parent, 0, 0));
+            }
+        }
+
+        let total_tags = counts.len();
+        let roots = build_level(&counts, None);
+        Ok(TagHierarchy { total_tags, roots })
+    }
+
+    /// Rename a tag everywhere; with `include_descendants`, `#old/x` becomes `#new/x`
+    pub async fn rename_tag(
+        &self,
+        from: &str,
+        to: &str,
+        include_descendants: bool,
+        dry_run: bool,
+    ) -> Result<TagChangeReport> {
+        let from = normalize_tag(from)?;
+        let to = validate_new_tag(to)?;
+        let operation = format!("rename #{} -> #{}", from, to);
+
+        self.apply(&operation, dry_run, |tag| {
+            rename_edit(tag, &from, &to, include_descendants)
+        })
+        .await
+    }
+
+    /// Merge several tags into one (exact matches only; descendants are kept)
+    pub async fn merge_tags(
+        &self,
+        sources: &[String],
+        target: &str,
+        dry_run: bool,
+    ) -> Result<TagChangeReport> {
+        if sources.is_empty() {
+            return Err(Error::config_error(
+                "At least one source tag is required".to_string(),
+            ));
+        }
+        let target = validate_new_tag(target)?;
+        let sources = sources
+            .iter()
+            .map(|s| normalize_tag(s).map(|t| t.to_lowercase()))
+            .collect::<Result<Vec<_>>>()?;
+        let operation = format!(
+            "merge {} -> #{}",
+            sources
+                .iter()
+                .map(|s| format!("#{}", s))
+                .collect::<Vec<_>>()
+                .join(", "),
+            target
+        );
+
+        self.apply(&operation, dry_run, |tag| {
+            let lower = tag.to_lowercase();
+            if sources.contains(&lower) && tag != target {
+                Some(TagEdit::Replace(target.clone()))
+            } else {
+                None
+            }
+        })
+        .await
+    }
+
+    /// Remove a tag from frontmatter and body; optionally its descendants too
+    pub async fn delete_tag(
+        &self,
+        tag: &str,
+        include_descendants: bool,
+        dry_run: bool,
+    ) -> Result<TagChangeReport> {
+        let tag = normalize_tag(tag)?.to_lowercase();
+        let operation = format!("delete #{}", tag);
+
+        self.apply(&operation, dry_run, |candidate| {
+            let lower = candidate.to_lowercase();
+            if lower == tag || (include_descendants && lower.starts_with(&format!("{}/", tag))) {
+                Some(TagEdit::Remove)
+            } else {
+                None
+            }
+        })
+        .await
+    }
+
+    /// Rewrite matching tags in every note, then write changed notes as one
+    /// batch that is rolled back if any write fails
+    async fn apply(
+        &self,
+        operation: &str,
+        dry_run: bool,
+        edit_for: impl Fn(&str) -> Option<TagEdit>,
+    ) -> Result<TagChangeReport> {
+        let mut notes = Vec::new();
+        let mut rewrites = Vec::new();
+
+        for (path, content) in self.notes().await? {
+            let (updated, changes) = rewrite_note(&content, &edit_for)
+                .map_err(|e| Error::config_error(format!("{}: {}", path, e)))?;
+            if changes.is_empty() || updated == content {
+                continue;
+            }
+            rewrites.push(NoteRewrite {
+                path: path.clone(),
+                original: content,
+                content: updated,
+            });
+            notes.push(NoteTagChanges { path, changes });
+        }
+
+        let batch = if dry_run || rewrites.is_empty() {
+            None
+        } else {
+            Some(
+                BatchTools::new(self.manager.clone())
+                    .rewrite_notes(rewrites)
+                    .await?,
+            )
+        };
+
+        Ok(TagChangeReport {
+            operation: operation.to_string(),
+            dry_run,
+            notes_changed: notes.len(),
+            occurrences_changed: notes.iter().map(|n| n.changes.len()).sum(),
+            notes,
+            batch,
+        })
+    }
+
+    /// Tags that co-occur with `tag` on at least `min_shared` notes, by NPMI
+    pub async fn related_tags(
+        &self,
+        tag: &str,
+        min_shared: usize,
+        limit: usize,
+    ) -> Result<RelatedTagsReport> {
+        let graph = self.tag_graph().await?;
+        let mut related = graph.related_tags(tag, min_shared)?;
+        related.truncate(limit);
+
+        let tag = tag.trim_start_matches('#').to_lowercase();
+        Ok(RelatedTagsReport {
+            note_count: graph.notes_with(&tag).len(),
+            parent: graph.parent(&tag),
+            children: graph.children(&tag),
+            tag,
+            related,
+        })
+    }
+
+    /// Pairs of tags that look like spellings of the same concept
+    pub async fn find_duplicate_tags(
+        &self,
+        min_score: Option<f64>,
+    ) -> Result<Vec<NearDuplicateTags>> {
+        let defaults = NearDuplicateConfig::default();
+        let config = NearDuplicateConfig {
+            min_score: min_score.unwrap_or(defaults.min_score),
+            ..defaults
+        };
+        Ok(self.tag_graph().await?.near_duplicates(&config))
+    }
+
+    /// Cluster tags by co-occurrence and list notes tagged from several clusters
+    pub async fn find_bridging_notes(
+        &self,
+        min_communities: usize,
+        resolution: Option<f64>,
+    ) -> Result<TagBridgesReport> {
+        let graph = self.tag_graph().await?;
+        let defaults = CommunityConfig::default();
+        let config = CommunityConfig {
+            resolution: resolution.unwrap_or(defaults.resolution),
+            ..defaults
+        };
+        let communities = graph.communities(&config);
+        let notes = graph.bridging_notes(&communities, min_communities);
+
+        Ok(TagBridgesReport {
+            modularity: communities.modularity,
+            communities: communities.communities,
+            notes,
+        })
+    }
+
+    /// Co-occurrence graph over every note's tags
+    async fn tag_graph(&self) -> Result<TagGraph> {
+        let tags: HashMap<PathBuf, Vec<String>> = self
+            .notes()
+            .await?
+            .into_iter()
+            .map(|(path, content)| (PathBuf::from(path), note_tags(&content)))
+            .collect();
+        Ok(TagGraph::new(&tags))
+    }
+
+    /// Raw content of every markdown note, keyed by vault-relative path
+    pub(crate) async fn notes(&self) -> Result<Vec<(String, String)>> {
+        let mut files = self.manager.scan_vault().await?;
+        files.sort();
+
+        let mut notes = Vec::new();
+        for file_path in files {
+            if !file_path.to_string_lossy().to_lowercase().ends_with(".md") {
+                continue;
+            }
+            let content = self.manager.read_file(&file_path).await?;
+            let path = file_path
+                .strip_prefix(self.manager.vault_path())
+                .unwrap_or(&file_path)
+                .to_string_lossy()
+                .to_string();
+            notes.push((path, content));
+        }
+        Ok(notes)
+    }
+}
+
+fn build_level(
+    counts: &BTreeMap<String, (String, usize, usize)>,
+    parent: Option<&str>,
+) -> Vec<TagNode> {
+    counts
+        .iter()
+        .filter(|(key, _)| match parent {
+            None => !key.contains('/'),
+            Some(p) => key
+                .strip_prefix(p)
+                .and_then(|rest| rest.strip_prefix('/'))
+                .is_some_and(|rest| !rest.contains('/')),
+        })
+        .map(|(key, (spelling, count, note_count))| {
+            let children = build_level(counts, Some(key));
+            let total_count = count + children.iter().map(|c| c.total_count).sum::<usize>();
+            TagNode {
+                tag: spelling.clone(),
+                name: spelling.rsplit('/').next().unwrap_or(spelling).to_string(),
+                count: *count,
+                note_count: *note_count,
+                total_count,
+                children,
+            }
+        })
+        .collect()
+}
+
+/// Strip a leading `#` and surrounding whitespace
+fn normalize_tag(tag: &str) -> Result<String> {
+    let tag = tag.trim().trim_start_matches('#').trim_end_matches('/');
+    if tag.is_empty() {
+        return Err(Error::config_error("Tag cannot be empty".to_string()));
+    }
+    Ok(tag.to_string())
+}
+
+/// Ensure a new tag name is something the tag parser recognizes as a whole tag
+fn validate_new_tag(tag: &str) -> Result<String> {
+    let tag = normalize_tag(tag)?;
+    let parsed = turbovault_parser::parse_tags(&format!("#{}", tag));
+    if parsed.len() != 1 || parsed[0].name != tag {
+        return Err(Error::config_error(format!("Invalid tag name: #{}", tag)));
+    }
+    Ok(tag)
+}
+
+/// Tags from frontmatter lists or comma/space separated strings
+fn frontmatter_tag_values(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|s| s.trim().trim_start_matches('#').to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        Value::String(s) => s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .map(|t| t.trim_start_matches('#').to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Inline tags outside wikilinks (`[[#Heading]]` is not a tag)
+fn inline_tags(content: &str) -> Vec<Tag> {
+    let link_ranges: Vec<(usize, usize)> = turbovault_parser::parse_wikilinks(content)
+        .into_iter()
+        .chain(turbovault_parser::parse_embeds(content))
+        .map(|l| (l.position.offset, l.position.offset + l.position.length))
+        .collect();
+
+    turbovault_parser::parse_tags(content)
+        .into_iter()
+        .filter(|t| {
+            !link_ranges
+                .iter()
+                .any(|(start, end)| t.position.offset >= *start && t.position.offset < *end)
+        })
+        .collect()
+}
+
+/// All tag occurrences in a note: frontmatter first, then inline
+pub(crate) fn note_tags(content: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    if let Ok(document) = FrontmatterDocument::parse(content) {
+        for key in TAG_KEYS {
+            if let Some(value) = document.properties().get(key) {
+                tags.extend(frontmatter_tag_values(value));
+            }
+        }
+    }
+    tags.extend(inline_tags(content).into_iter().map(|t| t.name));
+    tags
+}
+
+/// Edit renaming `tag` from `from` to `to` (case-insensitive), if it matches
+fn rename_edit(tag: &str, from: &str, to: &str, include_descendants: bool) -> Option<TagEdit> {
+    let lower = tag.to_lowercase();
+    let from_lower = from.to_lowercase();
+    if tag == to {
+        None
+    } else if lower == from_lower {
+        Some(TagEdit::Replace(to.to_string()))
+    } else if include_descendants && lower.starts_with(&format!("{}/", from_lower)) {
+        // Lowercasing can change byte lengths, so cut the matched tag after
+        // as many segments as `from` has rather than at `from.len()`
+        let segments = from.split('/').count();
+        let (cut, _) = tag.match_indices('/').nth(segments - 1)?;
+        Some(TagEdit::Replace(format!("{}{}", to, &tag[cut..])))
+    } else {
+        None
+    }
+}
+
+/// Apply tag edits to a note's raw content
+fn rewrite_note(
+    content: &str,
+    edit_for: &impl Fn(&str) -> Option<TagEdit>,
+) -> Result<(String, Vec<TagOccurrenceChange>)> {
+    let mut changes = Vec::new();
+
+    // Inline tags first, back to front so earlier offsets stay valid
+    let mut inline: Vec<(Tag, TagEdit)> = inline_tags(content)
+        .into_iter()
+        .filter_map(|t| edit_for(&t.name).map(|e| (t, e)))
+        .collect();
+    inline.sort_by_key(|(t, _)| std::cmp::Reverse(t.position.offset));
+
+    let mut updated = content.to_string();
+    for (tag, edit) in &inline {
+        let start = tag.position.offset;
+        let end = start + tag.position.length;
+        match edit {
+            TagEdit::Replace(new) => updated.replace_range(start..end, &format!("#{}", new)),
+            TagEdit::Remove => {
+                // Take one separating space with the tag so no double space is left
+                let before_space = start > 0 && updated[..start].ends_with(' ');
+                let after_space_or_end = updated[end..]
+                    .chars()
+                    .next()
+                    .is_none_or(|c| c.is_whitespace());
+                let start = if before_space && after_space_or_end {
+                    start - 1
+                } else {
+                    start
+                };
+                updated.replace_range(start..end, "");
+            }
+        }
+    }
+    for (tag, edit) in inline.into_iter().rev() {
+        changes.push(TagOccurrenceChange {
+            location: "inline".to_string(),
+            line: Some(tag.position.line),
+            from: tag.name,
+            to: match edit {
+                TagEdit::Replace(new) => Some(new),
+                TagEdit::Remove => None,
+            },
+        });
+    }
+
+    // Frontmatter tags; the block precedes every inline edit so it is unchanged.
+    // Notes with unparseable frontmatter only get their inline tags rewritten.
+    let Ok(mut document) = FrontmatterDocument::parse(&updated) else {
+        return Ok((updated, changes));
+    };
+    let mut frontmatter_changed = false;
+    for key in TAG_KEYS {
+        let Some(value) = document.properties().get(key).cloned() else {
+            continue;
+        };
+        let tags = frontmatter_tag_values(&value);
+        let mut new_tags: Vec<String> = Vec::new();
+        let mut changed = false;
+        for tag in tags {
+            let replacement = match edit_for(&tag) {
+                Some(TagEdit::Replace(new)) => {
+                    changes.push(TagOccurrenceChange {
+                        location: "frontmatter".to_string(),
+                        line: None,
+                        from: tag,
+                        to: Some(new.clone()),
+                    });
+                    changed = true;
+                    Some(new)
+                }
+                Some(TagEdit::Remove) => {
+                    changes.push(TagOccurrenceChange {
+                        location: "frontmatter".to_string(),
+                        line: None,
+                        from: tag,
+                        to: None,
+                    });
+                    changed = true;
+                    None
+                }
+                None => Some(tag),
+            };
+            if let Some(new) = replacement
+                && !new_tags.iter().any(|t| t.eq_ignore_ascii_case(&new))
+            {
+                new_tags.push(new);
+            }
+        }
+        if !changed {
+            continue;
+        }
+
+        frontmatter_changed = true;
+        let properties = document.properties_mut();
+        if new_tags.is_empty() {
+            properties.shift_remove(key);
+        } else if value.is_string() && new_tags.len() == 1 {
+            properties.insert(key.to_string(), Value::String(new_tags.remove(0)));
+        } else {
+            properties.insert(
+                key.to_string(),
+                Value::Array(new_tags.into_iter().map(Value::String).collect()),
+            );
+        }
+    }
+
+    // Rewrite only the tag properties so comments and formatting elsewhere in
+    // the block survive
+    if frontmatter_changed {
+        updated = document.render_onto(&updated)?;
+    }
+    Ok((updated, changes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename(from: &'static str, to: &'static str) -> impl Fn(&str) -> Option<TagEdit> {
+        move |tag: &str| {
+            if tag.eq_ignore_ascii_case(from) {
+                Some(TagEdit::Replace(to.to_string()))
+            } else {
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn test_rewrite_inline_skips_code() {
+        let content =
+            "Intro #old here\n\n```\n#old in code\n```\n\nAnd `#old` inline, #older too.\n";
+        let (updated, changes) = rewrite_note(content, &rename("old", "new")).unwrap();
+        assert_eq!(
+            updated,
+            "Intro #new here\n\n```\n#old in code\n```\n\nAnd `#old` inline, #older too.\n"
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].line, Some(1));
+    }
+
+    #[test]
+    fn test_rewrite_skips_wikilink_headings() {
+        let content = "See [[#old]] and #old\n";
+        let (updated, _) = rewrite_note(content, &rename("old", "new")).unwrap();
+        assert_eq!(updated, "See [[#old]] and #new\n");
+    }
+
+    #[test]
+    fn test_rewrite_frontmatter_list_and_dedup() {
+        let content = "---\ntags:\n- old\n- new\n- keep\n---\nBody #old\n";
+        let (updated, changes) = rewrite_note(content, &rename("old", "new")).unwrap();
+        assert_eq!(updated, "---\ntags:\n- new\n- keep\n---\nBody #new\n");
+        assert_eq!(changes.len(), 2);
+    }
+
+    #[test]
+    fn test_rewrite_frontmatter_string() {
+        let content = "---\ntags: old\n---\nBody\n";
+        let (updated, _) = rewrite_note(content, &rename("old", "new")).unwrap();
+        assert_eq!(updated, "---\ntags: new\n---\nBody\n");
+    }
+
+    #[test]
+    fn test_remove_inline_and_frontmatter() {
+        let remove = |tag: &str| (tag == "gone").then_some(TagEdit::Remove);
+        let content = "---\ntags: [gone]\ntitle: T\n---\nText #gone more\n#gone\n";
+        let (updated, changes) = rewrite_note(content, &remove).unwrap();
+        assert_eq!(updated, "---\ntitle: T\n---\nText more\n\n");
+        assert_eq!(changes.len(), 3);
+    }
+
+    #[test]
+    fn test_rewrite_keeps_frontmatter_comments() {
+        let content =
+            "---\n# status is manual\nstatus: draft # todo\ntags: [old, keep]\n---\nBody\n";
+        let (updated, _) = rewrite_note(content, &|t| {
+            (t == "old").then(|| TagEdit::Replace("new".to_string()))
+        })
+        .unwrap();
+        assert_eq!(
+            updated,
+            "---\n# status is manual\nstatus: draft # todo\ntags:\n- new\n- keep\n---\nBody\n"
+        );
+    }
+
+    #[test]
+    fn test_unchanged_note_is_identical() {
+        let content = "---\ntags:   [a,b]\n---\nNo match #c\n";
+        let (updated, changes) = rewrite_note(content, &rename("zzz", "y")).unwrap();
+        assert!(changes.is_empty());
+        assert_eq!(updated, content);
+    }
+
+    #[test]
+    fn test_rename_edit_descendants() {
+        let replaced = |tag: &str, from: &str| match rename_edit(tag, from, "new", true) {
+            Some(TagEdit::Replace(tag)) => Some(tag),
+            _ => None,
+        };
+        assert_eq!(replaced("Old/x/y", "old").as_deref(), Some("new/x/y"));
+        assert_eq!(replaced("old/a/b", "old/a").as_deref(), Some("new/b"));
+        // The Kelvin sign lowercases to a one-byte `k`
+        assert_eq!(
+            replaced("\u{212A}ey/alpha", "key").as_deref(),
+            Some("new/alpha")
+        );
+        assert_eq!(replaced("older/x", "old"), None);
+        assert!(rename_edit("old/x", "old", "new", false).is_none());
+    }
+
+    #[test]
+    fn test_tag_validation() {
+        assert_eq!(normalize_tag(" #a/b/ ").unwrap(), "a/b");
+        assert!(normalize_tag("#").is_err());
+        assert_eq!(validate_new_tag("#project/alpha").unwrap(), "project/alpha");
+        assert!(validate_new_tag("two words").is_err());
+        assert!(validate_new_tag("bad!char").is_err());
+    }
+
+    #[test]
+    fn test_build_hierarchy() {
+        let mut counts = BTreeMap::new();
+        counts.insert("a".to_string(), ("a".to_string(), 1, 1));
+        counts.insert("a/b".to_string(), ("a/b".to_string(), 2, 2));
+        counts.insert("a/b/c".to_string(), ("a/b/c".to_string(), 3, 1));
+        counts.insert("z".to_string(), ("Z".to_string(), 1, 1));
+        let roots = build_level(&counts, None);
+        assert_eq!(roots.len(), 2);
+        assert_eq!(roots[0].total_count, 6);
+        assert_eq!(roots[0].children[0].name, "b");
+        assert_eq!(roots[0].children[0].children[0].tag, "a/b/c");
+        assert_eq!(roots[1].tag, "Z");
+    }
+}
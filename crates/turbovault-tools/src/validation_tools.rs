@@ -22,6 +22,9 @@ pub struct ValidationIssueInfo {
     pub message: String,
     pub line: Option<usize>,
     pub suggestion: Option<String>,
+    /// Note the issue was found in (vault-wide validation only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
 }
 
 /// Simplified validation report for JSON serialization
@@ -92,29 +95,25 @@ impl ValidationTools {
         Ok(Self::convert_report(report))
     }
 
-    /// Validate entire vault (batch validation)
+    /// Validate entire vault (batch validation), including note-type schemas
     pub async fn validate_vault(&self) -> Result<ValidationReportInfo> {
-        let files = self.manager.scan_vault().await?;
-
-        let validator = CompositeValidator::default_rules();
-        let mut combined_report = ValidationReport::new();
-
-        for file_path in files {
-            if let Ok(vault_file) = self.manager.parse_file(&file_path).await {
-                let report = validator.validate(&vault_file);
-                combined_report.merge(report);
-            }
-        }
-
-        Ok(Self::convert_report(combined_report))
+        self.validate_files(usize::MAX).await
     }
 
     /// Validate vault with issue limit (for large vaults)
     pub async fn validate_vault_quick(&self, max_issues: usize) -> Result<ValidationReportInfo> {
-        let files = self.manager.scan_vault().await?;
+        self.validate_files(max_issues).await
+    }
+
+    /// Validate every file, tagging issues with their note path
+    async fn validate_files(&self, max_issues: usize) -> Result<ValidationReportInfo> {
+        let mut files = self.manager.scan_vault().await?;
+        files.sort();
+
+        let schemas = self.manager.note_schemas().await;
 
-        let validator = CompositeValidator::default_rules();
         let mut combined_report = ValidationReport::new();
+        let mut issue_files = Vec::new();
 
         for file_path in files {
             // Stop if we've hit the max issues
@@ -123,12 +122,28 @@ impl ValidationTools {
             }
 
             if let Ok(vault_file) = self.manager.parse_file(&file_path).await {
+                // Validators aren't Send, so build them outside of any await
+                let mut validator = CompositeValidator::default_rules();
+                if let Some(schemas) = &schemas {
+                    validator = validator
+                        .add_validator(Box::new(schemas.validator(self.manager.vault_path())));
+                }
                 let report = validator.validate(&vault_file);
+                let relative = file_path
+                    .strip_prefix(self.manager.vault_path())
+                    .unwrap_or(&file_path)
+                    .to_string_lossy()
+                    .to_string();
+                issue_files.extend(std::iter::repeat_n(relative, report.total_issues()));
                 combined_report.merge(report);
             }
         }
 
-        Ok(Self::convert_report(combined_report))
+        let mut info = Self::convert_report(combined_report);
+        for (issue, file) in info.issues.iter_mut().zip(issue_files) {
+            issue.file = Some(file);
+        }
+        Ok(info)
     }
 
     /// Convert ValidationReport to serializable format
@@ -149,6 +164,7 @@ impl ValidationTools {
                     message: issue.message,
                     line: issue.line,
                     suggestion: issue.suggestion,
+                    file: None,
                 })
                 .collect(),
         }
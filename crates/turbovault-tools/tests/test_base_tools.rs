@@ -0,0 +1,177 @@
+//! Unit tests for BaseTools
+
+use std::sync::Arc;
+use tempfile::TempDir;
+use turbovault_core::{ConfigProfile, VaultConfig};
+use turbovault_tools::BaseTools;
+use turbovault_vault::VaultManager;
+
+const BOOKS: &str = r#"
+filters:
+  and:
+    - file.hasTag("book")
+    - file.inFolder("Library")
+properties:
+  note.author:
+    displayName: Author
+views:
+  - type: table
+    name: Unfinished
+    filters:
+      not:
+        - status == "done"
+    order:
+      - file.name
+      - note.author
+      - pages
+      - formula.left
+    sort:
+      - property: pages
+        direction: DESC
+    limit: 2
+  - type: cards
+    name: Long reads
+    filters: pages >= 400 && file.hasLink("Reading list")
+  - type: table
+    name: Computed
+    filters: formula.left > 0
+"#;
+
+async fn setup_base_vault() -> (TempDir, Arc<VaultManager>) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let vault_path = temp_dir.path();
+
+    tokio::fs::create_dir_all(vault_path.join("Library/Fiction"))
+        .await
+        .unwrap();
+    let files = [
+        (
+            "Library/Earthsea.md",
+            "---\nauthor: Ursula K. Le Guin\npages: 183\nstatus: done\ntags: [book]\n---\n# Earthsea\n",
+        ),
+        (
+            "Library/Fiction/Dune.md",
+            "---\nauthor: Frank Herbert\npages: 412\nstatus: reading\n---\n# Dune\n#book/scifi, on the [[Reading list]]\n",
+        ),
+        (
+            "Library/Fiction/Hyperion.md",
+            "---\nauthor: Dan Simmons\npages: 482\ntags: [book]\n---\n# Hyperion\n",
+        ),
+        (
+            "Library/Short.md",
+            "---\npages: 90\ntags: [book]\n---\n# Short\n",
+        ),
+        (
+            "Elsewhere.md",
+            "---\npages: 999\ntags: [book]\n---\n# Elsewhere\n",
+        ),
+        ("Reading list.md", "# Reading list\n"),
+        ("Books.base", BOOKS),
+        ("Broken.base", "views: [unclosed"),
+    ];
+    for (name, content) in files {
+        tokio::fs::write(vault_path.join(name), content)
+            .await
+            .unwrap();
+    }
+
+    let mut config = ConfigProfile::Development.create_config();
+    let vault_config = VaultConfig::builder("test", vault_path).build().unwrap();
+    config.vaults.push(vault_config);
+
+    let manager = VaultManager::new(config).unwrap();
+    manager.initialize().await.unwrap();
+
+    (temp_dir, Arc::new(manager))
+}
+
+#[tokio::test]
+async fn test_list_bases() {
+    let (_temp, manager) = setup_base_vault().await;
+    let tools = BaseTools::new(manager);
+
+    let bases = tools.list_bases().await.unwrap();
+    assert_eq!(bases.len(), 2);
+
+    assert_eq!(bases[0].path, "Books.base");
+    assert!(bases[0].error.is_none());
+    let views = &bases[0].views;
+    assert_eq!(views.len(), 3);
+    assert_eq!(views[0].name, "Unfinished");
+    assert_eq!(views[0].unsupported, vec!["formula.left"]);
+    assert_eq!(views[1].view_type, "cards");
+    assert_eq!(views[1].columns, vec!["file.name"]);
+    assert!(views[1].unsupported.is_empty());
+    assert_eq!(views[2].unsupported, vec!["formula.left > 0"]);
+
+    assert_eq!(bases[1].path, "Broken.base");
+    assert!(bases[1].error.is_some());
+}
+
+#[tokio::test]
+async fn test_bases_are_not_notes() {
+    let (_temp, manager) = setup_base_vault().await;
+
+    let is_base = |p: &std::path::PathBuf| p.extension().is_some_and(|e| e == "base");
+    assert!(!manager.scan_vault().await.unwrap().iter().any(is_base));
+    assert!(
+        !manager
+            .get_orphaned_notes()
+            .await
+            .unwrap()
+            .iter()
+            .any(is_base)
+    );
+    assert_eq!(manager.get_stats().await.unwrap().total_files, 6);
+}
+
+#[tokio::test]
+async fn test_query_base_view() {
+    let (_temp, manager) = setup_base_vault().await;
+    let tools = BaseTools::new(manager);
+
+    let result = tools.query_base("Books.base", None).await.unwrap();
+    assert_eq!(result.view, "Unfinished");
+    assert_eq!(result.total, 3);
+    let paths: Vec<&str> = result.rows.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec!["Library/Fiction/Hyperion.md", "Library/Fiction/Dune.md"]
+    );
+    assert_eq!(result.columns[1].name, "Author");
+    assert_eq!(result.columns[2].name, "pages");
+    let first = &result.rows[0].values;
+    assert_eq!(first["file.name"], "Hyperion.md");
+    assert_eq!(first["note.author"], "Dan Simmons");
+    assert_eq!(first["pages"], 482);
+    assert!(first["formula.left"].is_null());
+    assert_eq!(result.unsupported_columns, vec!["formula.left"]);
+
+    let long = tools
+        .query_base("Books.base", Some("long reads"))
+        .await
+        .unwrap();
+    assert_eq!(long.total, 1);
+    assert_eq!(long.rows[0].path, "Library/Fiction/Dune.md");
+}
+
+#[tokio::test]
+async fn test_query_base_errors() {
+    let (_temp, manager) = setup_base_vault().await;
+    let tools = BaseTools::new(manager);
+
+    assert!(
+        tools
+            .query_base("Books.base", Some("Computed"))
+            .await
+            .is_err()
+    );
+    assert!(
+        tools
+            .query_base("Books.base", Some("Missing"))
+            .await
+            .is_err()
+    );
+    assert!(tools.query_base("Broken.base", None).await.is_err());
+    assert!(tools.query_base("Reading list.md", None).await.is_err());
+}
@@ -276,3 +276,57 @@ async fn test_async_error_path_concurrent_batch_operations() {
         assert!(result.is_ok());
     }
 }
+
+async fn setup_schema_vault(mode: &str) -> (TempDir, Arc<VaultManager>) {
+    let (temp_dir, manager) = setup_test_vault().await;
+    manager
+        .write_file(
+            std::path::Path::new(".turbovault/schemas.yaml"),
+            &format!(
+                "mode: {}\ntypes:\n  task:\n    required:\n      due: date\n",
+                mode
+            ),
+        )
+        .await
+        .unwrap();
+    (temp_dir, manager)
+}
+
+#[tokio::test]
+async fn test_batch_rejects_schema_violation_before_writing() {
+    let (_temp_dir, manager) = setup_schema_vault("reject").await;
+    let tools = BatchTools::new(manager.clone());
+
+    let ops = vec![
+        BatchOperation::WriteNote {
+            path: "ok.md".to_string(),
+            content: "---\ntype: task\ndue: 2024-01-01\n---\n".to_string(),
+        },
+        BatchOperation::WriteNote {
+            path: "bad.md".to_string(),
+            content: "---\ntype: task\n---\n".to_string(),
+        },
+    ];
+
+    let result = tools.batch_execute(ops).await.unwrap();
+    assert!(!result.success);
+    assert_eq!(result.executed, 0);
+    assert!(result.errors[0].contains("bad.md"));
+    assert!(!manager.vault_path().join("ok.md").exists());
+}
+
+#[tokio::test]
+async fn test_batch_warns_on_schema_violation() {
+    let (_temp_dir, manager) = setup_schema_vault("warn").await;
+    let tools = BatchTools::new(manager.clone());
+
+    let ops = vec![BatchOperation::CreateNote {
+        path: "bad.md".to_string(),
+        content: "---\ntype: task\ndue: someday\n---\n".to_string(),
+    }];
+
+    let result = tools.batch_execute(ops).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.warnings.len(), 1);
+    assert!(manager.vault_path().join("bad.md").exists());
+}
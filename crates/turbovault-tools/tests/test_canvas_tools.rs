@@ -0,0 +1,206 @@
+//! Unit tests for CanvasTools and canvas files in the vault
+
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+use turbovault_core::{ConfigProfile, VaultConfig};
+use turbovault_parser::{Canvas, CanvasNodeType, CanvasSide};
+use turbovault_tools::{CanvasTools, NewCanvasEdge, NewCanvasNode, SearchEngine};
+use turbovault_vault::VaultManager;
+
+const BOARD: &str = r#"{
+	"nodes":[
+		{"id":"t1","type":"text","text":"Kickoff notes, see [[Plan]] #sprint","x":0,"y":0,"width":250,"height":60},
+		{"id":"f1","type":"file","file":"notes/Spec.md","x":300,"y":-40,"width":400,"height":400}
+	],
+	"edges":[],
+	"customTopLevel":{"kept":true}
+}"#;
+
+async fn setup_canvas_vault() -> (TempDir, Arc<VaultManager>) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let vault_path = temp_dir.path();
+
+    tokio::fs::create_dir_all(vault_path.join("notes"))
+        .await
+        .unwrap();
+    tokio::fs::write(vault_path.join("Plan.md"), "# Plan\n")
+        .await
+        .unwrap();
+    tokio::fs::write(vault_path.join("notes/Spec.md"), "# Spec\n")
+        .await
+        .unwrap();
+    tokio::fs::write(vault_path.join("board.canvas"), BOARD)
+        .await
+        .unwrap();
+
+    let mut config = ConfigProfile::Development.create_config();
+    let vault_config = VaultConfig::builder("test", vault_path).build().unwrap();
+    config.vaults.push(vault_config);
+
+    let manager = VaultManager::new(config).unwrap();
+    manager.initialize().await.unwrap();
+
+    (temp_dir, Arc::new(manager))
+}
+
+fn node(node_type: CanvasNodeType, content: &str) -> NewCanvasNode {
+    NewCanvasNode {
+        node_type,
+        content: content.to_string(),
+        subpath: None,
+        x: None,
+        y: None,
+        width: None,
+        height: None,
+        color: None,
+    }
+}
+
+fn edge(from: &str, to: &str) -> NewCanvasEdge {
+    NewCanvasEdge {
+        from: from.to_string(),
+        to: to.to_string(),
+        label: None,
+        from_side: None,
+        to_side: None,
+        color: None,
+    }
+}
+
+#[tokio::test]
+async fn test_canvas_links_in_graph() {
+    let (temp, manager) = setup_canvas_vault().await;
+
+    let mut forward = manager
+        .get_forward_links(Path::new("board.canvas"))
+        .await
+        .unwrap();
+    forward.sort();
+    assert_eq!(
+        forward,
+        vec![
+            temp.path().join("Plan.md"),
+            temp.path().join("notes/Spec.md")
+        ]
+    );
+
+    let backlinks = manager
+        .get_backlinks(Path::new("notes/Spec.md"))
+        .await
+        .unwrap();
+    assert_eq!(backlinks, vec![temp.path().join("board.canvas")]);
+}
+
+#[tokio::test]
+async fn test_canvas_text_is_searchable() {
+    let (_temp, manager) = setup_canvas_vault().await;
+    let engine = SearchEngine::new(manager).await.unwrap();
+
+    let results = engine.search("kickoff").await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert!(results[0].path.ends_with("board.canvas"));
+    assert!(results[0].tags.contains(&"sprint".to_string()));
+}
+
+#[tokio::test]
+async fn test_add_and_connect_nodes() {
+    let (temp, manager) = setup_canvas_vault().await;
+    let tools = CanvasTools::new(manager.clone());
+
+    let added = tools
+        .add_node("board.canvas", node(CanvasNodeType::File, "Plan"))
+        .await
+        .unwrap();
+    assert_eq!(added.file.as_deref(), Some("Plan.md"));
+    assert_eq!((added.x, added.y), (750, -40));
+    assert_eq!((added.width, added.height), (400, 400));
+    assert_eq!(added.id.len(), 16);
+
+    let mut connection = edge("Plan", "f1");
+    connection.label = Some("details".to_string());
+    connection.from_side = Some(CanvasSide::Left);
+    let created = tools
+        .connect_nodes("board.canvas", connection)
+        .await
+        .unwrap();
+    assert_eq!(created.from_node, added.id);
+    assert_eq!(created.to_node, "f1");
+
+    let content = tokio::fs::read_to_string(temp.path().join("board.canvas"))
+        .await
+        .unwrap();
+    assert!(content.starts_with("{\n\t\"nodes\": ["));
+    let canvas = Canvas::parse(&content).unwrap();
+    assert_eq!(canvas.nodes.len(), 3);
+    assert_eq!(canvas.edges.len(), 1);
+    assert_eq!(canvas.edges[0].label.as_deref(), Some("details"));
+    assert!(canvas.extra.contains_key("customTopLevel"));
+    assert!(canvas.validate().is_empty());
+
+    // The graph sees the canvas's new file node
+    let backlinks = manager.get_backlinks(Path::new("Plan.md")).await.unwrap();
+    assert_eq!(backlinks, vec![temp.path().join("board.canvas")]);
+}
+
+#[tokio::test]
+async fn test_invalid_edits_are_rejected() {
+    let (_temp, manager) = setup_canvas_vault().await;
+    let tools = CanvasTools::new(manager);
+
+    assert!(
+        tools
+            .add_node("board.canvas", node(CanvasNodeType::File, "Missing"))
+            .await
+            .is_err()
+    );
+    let mut colored = node(CanvasNodeType::Text, "hi");
+    colored.color = Some("red".to_string());
+    assert!(tools.add_node("board.canvas", colored).await.is_err());
+    assert!(
+        tools
+            .connect_nodes("board.canvas", edge("t1", "nope"))
+            .await
+            .is_err()
+    );
+    assert!(
+        tools
+            .add_node("Plan.md", node(CanvasNodeType::Text, "hi"))
+            .await
+            .is_err()
+    );
+
+    let info = tools.read_canvas("board.canvas").await.unwrap();
+    assert_eq!(info.canvas.nodes.len(), 2);
+    assert!(info.issues.is_empty());
+}
+
+#[tokio::test]
+async fn test_create_canvas() {
+    let (temp, manager) = setup_canvas_vault().await;
+    let tools = CanvasTools::new(manager);
+
+    let canvas = Canvas::parse(
+        r#"{"nodes":[{"id":"a","type":"file","file":"Spec","x":0,"y":0,"width":400,"height":400}]}"#,
+    )
+    .unwrap();
+    let created = tools
+        .create_canvas("boards/new.canvas", canvas)
+        .await
+        .unwrap();
+    assert_eq!(created.nodes[0].file.as_deref(), Some("notes/Spec.md"));
+    assert!(temp.path().join("boards/new.canvas").exists());
+
+    assert!(
+        tools
+            .create_canvas("board.canvas", Canvas::default())
+            .await
+            .is_err()
+    );
+    assert!(
+        tools
+            .create_canvas("notes.md", Canvas::default())
+            .await
+            .is_err()
+    );
+}
@@ -3,7 +3,10 @@
 use std::sync::Arc;
 use tempfile::TempDir;
 use turbovault_core::{ConfigProfile, VaultConfig};
-use turbovault_tools::GraphTools;
+use turbovault_tools::{
+    BrokenLinkKind, CentralityMetric, GraphTools, NeighborRelation, PathDirection,
+    RelationshipTools,
+};
 use turbovault_vault::VaultManager;
 
 async fn setup_test_vault_with_graph() -> (TempDir, Arc<VaultManager>) {
@@ -69,10 +72,109 @@ async fn test_get_broken_links() {
     let (_temp_dir, manager) = setup_test_vault_with_graph().await;
     let tools = GraphTools::new(manager);
 
-    let broken = tools.get_broken_links().await;
-    assert!(broken.is_ok());
-    // Broken links detection works (may or may not find links depending on graph resolution)
-    let _links = broken.unwrap();
+    let broken = tools.get_broken_links().await.unwrap();
+    assert_eq!(broken.len(), 1);
+    assert_eq!(broken[0].target, "nonexistent");
+    assert!(broken[0].source_file.ends_with("e.md"));
+    assert_eq!(broken[0].line, 2);
+}
+
+#[tokio::test]
+async fn test_broken_links_in_broken_vault() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let vault_path = temp_dir.path();
+
+    tokio::fs::create_dir_all(vault_path.join("notes/attachments"))
+        .await
+        .unwrap();
+    tokio::fs::write(vault_path.join("notes/attachments/photo.jpg"), [0u8; 4])
+        .await
+        .unwrap();
+    tokio::fs::write(vault_path.join("Project Plan.md"), "# Project Plan")
+        .await
+        .unwrap();
+    tokio::fs::write(
+        vault_path.join("notes/daily.md"),
+        "# Daily\n\
+         [[Project Plan]] [[Project Plann]]\n\
+         ![[photo.jpg]] ![[missing.jpg]]\n\
+         [plan](../Project%20Plan.md) [old](../archive/plan.md)\n\
+         [site](https://example.com) [top](#daily)\n",
+    )
+    .await
+    .unwrap();
+
+    let mut config = ConfigProfile::Development.create_config();
+    let vault_config = VaultConfig::builder("broken", vault_path).build().unwrap();
+    config.vaults.push(vault_config);
+    let manager = VaultManager::new(config).unwrap();
+    manager.initialize().await.unwrap();
+    let tools = GraphTools::new(Arc::new(manager));
+
+    let broken = tools.get_broken_links().await.unwrap();
+    let targets: Vec<_> = broken.iter().map(|b| b.target.as_str()).collect();
+    assert_eq!(
+        targets,
+        vec!["Project Plann", "missing.jpg", "../archive/plan.md"]
+    );
+    assert_eq!(
+        broken.iter().map(|b| b.line).collect::<Vec<_>>(),
+        vec![2, 3, 4]
+    );
+    assert!(broken[0].suggestions.contains(&"Project Plan".to_string()));
+
+    let health = tools.full_health_analysis().await.unwrap();
+    assert_eq!(health.broken_links_count, 3);
+    let quick = tools.quick_health_check().await.unwrap();
+    assert_eq!(quick.broken_links_count, 3);
+}
+
+#[tokio::test]
+async fn test_broken_heading_and_block_refs() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let vault_path = temp_dir.path();
+
+    tokio::fs::write(
+        vault_path.join("guide.md"),
+        "# Guide\n\n## Installation\n\nRun the installer. ^install-step\n\n## Usage\n",
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        vault_path.join("notes.md"),
+        "# Notes\n\
+         [[guide#Installation]] [[guide#Instalation]]\n\
+         [[guide#^install-step]] [[guide#^removed]]\n\
+         [usage](guide.md#usage) [[missing#Anything]]\n",
+    )
+    .await
+    .unwrap();
+
+    let mut config = ConfigProfile::Development.create_config();
+    let vault_config = VaultConfig::builder("fragments", vault_path)
+        .build()
+        .unwrap();
+    config.vaults.push(vault_config);
+    let manager = VaultManager::new(config).unwrap();
+    manager.initialize().await.unwrap();
+    let tools = GraphTools::new(Arc::new(manager));
+
+    let broken = tools.get_broken_links().await.unwrap();
+    let summary: Vec<_> = broken.iter().map(|b| (b.target.as_str(), b.kind)).collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("missing#Anything", BrokenLinkKind::MissingTarget),
+            ("guide#Instalation", BrokenLinkKind::MissingHeading),
+            ("guide#^removed", BrokenLinkKind::MissingBlock),
+        ]
+    );
+    assert_eq!(broken[1].suggestions[0], "guide#Installation");
+    assert_eq!(broken[2].suggestions, vec!["guide#^install-step"]);
+
+    let health = tools.full_health_analysis().await.unwrap();
+    assert_eq!(health.broken_links_count, 1);
+    assert_eq!(health.broken_fragments_count, 2);
 }
 
 #[tokio::test]
@@ -123,6 +225,164 @@ async fn test_get_hub_notes_with_limit() {
     assert!(notes.len() <= 2);
 }
 
+#[tokio::test]
+async fn test_centrality_ranking() {
+    let (_temp_dir, manager) = setup_test_vault_with_graph().await;
+    let tools = RelationshipTools::new(manager);
+
+    let ranking = tools.get_centrality_ranking(&[], None).await.unwrap();
+    assert_eq!(ranking["total_files"], 10);
+    assert_eq!(ranking["sort_by"], "pagerank");
+    assert_eq!(ranking["betweenness_sampled"], false);
+    let rankings = ranking["rankings"].as_array().unwrap();
+    assert_eq!(rankings.len(), 10);
+    let pagerank = |name: &str| {
+        rankings
+            .iter()
+            .find(|r| r["file"].as_str().unwrap().ends_with(name))
+            .and_then(|r| r["pagerank"].as_f64())
+            .unwrap()
+    };
+    assert!(pagerank("hub.md") > pagerank("a.md"));
+    assert!(pagerank("a.md") > pagerank("orphan.md"));
+    let total: f64 = rankings
+        .iter()
+        .map(|r| r["pagerank"].as_f64().unwrap())
+        .sum();
+    assert!((total - 1.0).abs() < 1e-6);
+
+    // Every path between a, b and c goes through the hub
+    let ranking = tools
+        .get_centrality_ranking(&[CentralityMetric::Betweenness], Some(3))
+        .await
+        .unwrap();
+    let rankings = ranking["rankings"].as_array().unwrap();
+    assert_eq!(rankings.len(), 3);
+    assert!(rankings[0]["file"].as_str().unwrap().ends_with("hub.md"));
+    assert_eq!(rankings[0]["interpretation"], "Bridge between clusters");
+    assert!(rankings[0].get("pagerank").is_none());
+    assert_eq!(rankings[0]["score"], rankings[0]["betweenness"]);
+}
+
+#[tokio::test]
+async fn test_communities_and_folder_mismatches() {
+    let temp_dir = TempDir::new().unwrap();
+    let vault_path = temp_dir.path();
+    for folder in ["rust", "cooking"] {
+        tokio::fs::create_dir_all(vault_path.join(folder))
+            .await
+            .unwrap();
+    }
+
+    let notes = [
+        (
+            "rust/ownership.md",
+            "---\ntags: [rust]\n---\n[[borrowing]] [[lifetimes]] [[traits]]",
+        ),
+        ("rust/borrowing.md", "#rust\n[[ownership]] [[lifetimes]]"),
+        ("rust/lifetimes.md", "#rust\n[[ownership]] [[borrowing]]"),
+        ("cooking/traits.md", "#rust\n[[ownership]] [[generics]]"),
+        ("rust/generics.md", "[[traits]] [[ownership]]"),
+        ("cooking/bread.md", "#baking\n[[yeast]] [[flour]] [[oven]]"),
+        ("cooking/yeast.md", "#baking\n[[bread]] [[flour]]"),
+        ("cooking/flour.md", "[[bread]] [[yeast]] [[oven]]"),
+        ("cooking/oven.md", "[[bread]] [[flour]] [[ownership]]"),
+    ];
+    for (path, content) in notes {
+        tokio::fs::write(vault_path.join(path), content)
+            .await
+            .unwrap();
+    }
+
+    let mut config = ConfigProfile::Development.create_config();
+    config
+        .vaults
+        .push(VaultConfig::builder("test", vault_path).build().unwrap());
+    let manager = VaultManager::new(config).unwrap();
+    manager.initialize().await.unwrap();
+    let tools = GraphTools::new(Arc::new(manager));
+
+    let report = tools.get_communities(None, None).await.unwrap();
+    assert_eq!(report.note_count, 9);
+    assert_eq!(report.communities.len(), 2);
+    assert!(report.modularity > 0.3);
+
+    let rust = report
+        .communities
+        .iter()
+        .find(|c| c.notes.contains(&"rust/ownership.md".to_string()))
+        .unwrap();
+    assert_eq!(rust.size, 5);
+    assert!(rust.notes.contains(&"cooking/traits.md".to_string()));
+    assert_eq!(rust.central_notes[0], "rust/ownership.md");
+    assert_eq!(rust.dominant_tags[0].tag, "rust");
+    assert_eq!(rust.dominant_tags[0].count, 4);
+    assert_eq!(rust.folders[0].folder, "rust");
+
+    let mismatches = tools.find_folder_mismatches(0.6, None).await.unwrap();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "cooking/traits.md");
+    assert_eq!(mismatches[0].expected_folder, "rust");
+    assert_eq!(
+        mismatches[0].community_central_notes[0],
+        "rust/ownership.md"
+    );
+}
+
+#[tokio::test]
+async fn test_shortest_paths_and_neighborhoods() {
+    let (_temp_dir, manager) = setup_test_vault_with_graph().await;
+    let tools = GraphTools::new(manager);
+
+    let paths = tools
+        .find_shortest_paths("a.md", "d.md", PathDirection::Directed, &[], 3)
+        .await
+        .unwrap();
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].length, 2);
+    assert_eq!(paths[0].notes, vec!["a.md", "hub.md", "d.md"]);
+    assert_eq!(paths[0].hops[0].link_text, "hub");
+    assert_eq!(paths[0].hops[1].line, 2);
+
+    // Nothing links out of d
+    let back = tools
+        .find_shortest_paths("d.md", "a.md", PathDirection::Directed, &[], 3)
+        .await
+        .unwrap();
+    assert!(back.is_empty());
+    let back = tools
+        .find_shortest_paths("d.md", "a.md", PathDirection::Undirected, &[], 3)
+        .await
+        .unwrap();
+    assert_eq!(back[0].notes, vec!["d.md", "hub.md", "a.md"]);
+    assert!(!back[0].hops[0].forward);
+
+    let ego = tools
+        .get_ego_network("d.md", 2, PathDirection::Undirected, &[])
+        .await
+        .unwrap();
+    assert_eq!(ego.center, "d.md");
+    assert_eq!(ego.nodes[0].path, "hub.md");
+    assert_eq!(ego.nodes[0].distance, 1);
+    assert_eq!(ego.nodes.len(), 5);
+    assert!(ego.nodes[1..].iter().all(|n| n.distance == 2));
+
+    let common = tools
+        .find_common_neighbors("a.md", "b.md", &[])
+        .await
+        .unwrap();
+    assert_eq!(common.len(), 1);
+    assert_eq!(common[0].path, "hub.md");
+    assert_eq!(common[0].first, NeighborRelation::Mutual);
+
+    assert!(
+        tools
+            .find_shortest_paths("a.md", "missing.md", PathDirection::Directed, &[], 3)
+            .await
+            .is_err()
+    );
+}
+
 #[tokio::test]
 async fn test_get_dead_end_notes() {
     let (_temp_dir, manager) = setup_test_vault_with_graph().await;
@@ -230,3 +490,90 @@ async fn test_health_score_boundary_conditions() {
         assert!(!health.is_healthy);
     }
 }
+
+#[tokio::test]
+async fn test_suggest_links_for_unlinked_note() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let vault_path = temp_dir.path();
+    let notes = [
+        (
+            "new.md",
+            "# Ownership\nRust ownership and borrowing rules keep memory safe. #rust",
+        ),
+        (
+            "borrowing.md",
+            "# Borrowing\nThe borrow checker enforces ownership and borrowing rules. #rust\n[[index]]",
+        ),
+        (
+            "memory.md",
+            "# Memory\nManual memory management without ownership is error prone.",
+        ),
+        (
+            "bread.md",
+            "# Bread\nSourdough bread needs flour, water and patience.",
+        ),
+        (
+            "index.md",
+            "# Index\n[[borrowing|Rules]] [[memory|Heap]] [[bread|Baking]]",
+        ),
+    ];
+    for (name, content) in notes {
+        tokio::fs::write(vault_path.join(name), content)
+            .await
+            .unwrap();
+    }
+
+    let mut config = ConfigProfile::Development.create_config();
+    config.link_similarity_threshold = 0.1;
+    config.max_link_suggestions = 5;
+    config
+        .vaults
+        .push(VaultConfig::builder("test", vault_path).build().unwrap());
+    let manager = Arc::new(VaultManager::new(config).unwrap());
+    manager.initialize().await.unwrap();
+    let tools = RelationshipTools::new(manager);
+
+    let result = tools.suggest_links("new.md", None).await.unwrap();
+    assert_eq!(result["file"], "new.md");
+    assert_eq!(result["limit"], 5);
+    let suggestions = result["suggestions"].as_array().unwrap();
+    let targets: Vec<&str> = suggestions
+        .iter()
+        .map(|s| s["target"].as_str().unwrap())
+        .collect();
+    // Ranked on content and tags alone; unrelated notes fall below the threshold
+    assert_eq!(targets, vec!["borrowing.md", "memory.md"]);
+
+    let top = &suggestions[0];
+    assert!(top["signals"]["content"].as_f64().unwrap() > 0.0);
+    assert_eq!(top["signals"]["tags"], 1.0);
+    assert!(top["signals"]["outgoing_links"].is_null());
+    assert!(top["signals"]["backlinks"].is_null());
+    let reasons: Vec<&str> = top["reasons"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r.as_str().unwrap())
+        .collect();
+    assert!(reasons[0].starts_with("Similar content") && reasons[0].contains("ownership"));
+    assert_eq!(reasons[1], "Shared tags (1.00): #rust");
+    assert!(suggestions[0]["strength"].as_f64() > suggestions[1]["strength"].as_f64());
+
+    // Limit is capped by max_link_suggestions
+    let result = tools.suggest_links("new.md", Some(1)).await.unwrap();
+    assert_eq!(result["suggestions"].as_array().unwrap().len(), 1);
+    let result = tools.suggest_links("new.md", Some(50)).await.unwrap();
+    assert_eq!(result["limit"], 5);
+
+    // Existing links are not suggested; shared neighbours are explained
+    let result = tools.suggest_links("index.md", None).await.unwrap();
+    assert!(
+        result["suggestions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|s| s["target"] != "borrowing.md" && s["target"] != "bread.md")
+    );
+
+    assert!(tools.suggest_links("missing.md", None).await.is_err());
+}
@@ -0,0 +1,164 @@
+//! Unit tests for MentionTools
+
+use std::sync::Arc;
+use tempfile::TempDir;
+use turbovault_core::{ConfigProfile, VaultConfig};
+use turbovault_tools::MentionTools;
+use turbovault_tools::mention_tools::DEFAULT_MIN_NAME_LENGTH;
+use turbovault_vault::VaultManager;
+
+async fn setup_mention_vault() -> (TempDir, Arc<VaultManager>) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let vault_path = temp_dir.path();
+
+    tokio::fs::create_dir_all(vault_path.join("topics"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        vault_path.join("topics/Machine Learning.md"),
+        "---\naliases: [ML]\n---\n# Machine Learning\n",
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        vault_path.join("journal.md"),
+        "# Journal\nRead about machine learning today.\nML is fun, see [[Machine Learning]].\n`machine learning` in code\n",
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        vault_path.join("ideas.md"),
+        "# Ideas\nApply Machine Learning to the journal.\n",
+    )
+    .await
+    .unwrap();
+
+    let mut config = ConfigProfile::Development.create_config();
+    let vault_config = VaultConfig::builder("test", vault_path).build().unwrap();
+    config.vaults.push(vault_config);
+
+    let manager = VaultManager::new(config).unwrap();
+    manager.initialize().await.unwrap();
+
+    (temp_dir, Arc::new(manager))
+}
+
+#[tokio::test]
+async fn test_find_unlinked_mentions() {
+    let (_temp_dir, manager) = setup_mention_vault().await;
+    let tools = MentionTools::new(manager);
+
+    let report = tools
+        .find_unlinked_mentions(None, DEFAULT_MIN_NAME_LENGTH)
+        .await
+        .unwrap();
+    // The ML alias is too short by default; ideas.md also mentions the journal
+    let ml = report
+        .notes
+        .iter()
+        .find(|n| n.target == "topics/Machine Learning.md")
+        .unwrap();
+    assert_eq!(ml.names, vec!["Machine Learning"]);
+    let found: Vec<(&str, usize, &str)> = ml
+        .mentions
+        .iter()
+        .map(|m| (m.source.as_str(), m.line, m.text.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("ideas.md", 2, "Machine Learning"),
+            ("journal.md", 2, "machine learning"),
+        ]
+    );
+    assert_eq!(ml.mentions[1].id, "journal.md:2:12");
+    assert_eq!(ml.mentions[1].context, "Read about machine learning today.");
+
+    let journal = report
+        .notes
+        .iter()
+        .find(|n| n.target == "journal.md")
+        .unwrap();
+    assert_eq!(journal.mentions.len(), 1);
+    assert_eq!(report.total_mentions, 3);
+
+    // A two-letter alias counts once the minimum allows it
+    let short = tools
+        .find_unlinked_mentions(Some("Machine Learning"), 2)
+        .await
+        .unwrap();
+    assert_eq!(short.notes.len(), 1);
+    assert_eq!(short.notes[0].mentions.len(), 3);
+}
+
+#[tokio::test]
+async fn test_link_mentions_preview_then_apply() {
+    let (temp_dir, manager) = setup_mention_vault().await;
+    let tools = MentionTools::new(manager);
+
+    let preview = tools
+        .link_mentions(
+            "topics/Machine Learning.md",
+            &["journal.md:2:12".to_string(), "gone.md:1:1".to_string()],
+            DEFAULT_MIN_NAME_LENGTH,
+            true,
+        )
+        .await
+        .unwrap();
+    assert!(preview.dry_run);
+    assert_eq!(preview.mentions_linked, 1);
+    assert_eq!(
+        preview.notes[0].changes[0].replacement,
+        "[[Machine Learning|machine learning]]"
+    );
+    assert_eq!(preview.not_found, vec!["gone.md:1:1"]);
+    let journal = std::fs::read_to_string(temp_dir.path().join("journal.md")).unwrap();
+    assert!(journal.contains("Read about machine learning today."));
+
+    let applied = tools
+        .link_mentions("Machine Learning", &[], DEFAULT_MIN_NAME_LENGTH, false)
+        .await
+        .unwrap();
+    assert_eq!(applied.notes_changed, 2);
+    assert!(applied.batch.unwrap().success);
+
+    let journal = std::fs::read_to_string(temp_dir.path().join("journal.md")).unwrap();
+    assert_eq!(
+        journal,
+        "# Journal\nRead about [[Machine Learning|machine learning]] today.\nML is fun, see [[Machine Learning]].\n`machine learning` in code\n"
+    );
+    let ideas = std::fs::read_to_string(temp_dir.path().join("ideas.md")).unwrap();
+    assert_eq!(
+        ideas,
+        "# Ideas\nApply [[Machine Learning]] to the journal.\n"
+    );
+
+    let after = tools
+        .find_unlinked_mentions(Some("Machine Learning"), DEFAULT_MIN_NAME_LENGTH)
+        .await
+        .unwrap();
+    assert_eq!(after.total_mentions, 0);
+}
+
+#[tokio::test]
+async fn test_link_mentions_escapes_alias_pipe_in_tables() {
+    let (temp_dir, manager) = setup_mention_vault().await;
+    tokio::fs::write(
+        temp_dir.path().join("table.md"),
+        "| Topic | Notes |\n| --- | --- |\n| machine learning | todo |\n",
+    )
+    .await
+    .unwrap();
+    let tools = MentionTools::new(manager);
+
+    tools
+        .link_mentions("Machine Learning", &[], DEFAULT_MIN_NAME_LENGTH, false)
+        .await
+        .unwrap();
+
+    let table = std::fs::read_to_string(temp_dir.path().join("table.md")).unwrap();
+    assert_eq!(
+        table,
+        "| Topic | Notes |\n| --- | --- |\n| [[Machine Learning\\|machine learning]] | todo |\n"
+    );
+}
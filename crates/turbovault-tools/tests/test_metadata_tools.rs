@@ -1,9 +1,13 @@
 //! Unit tests for MetadataTools
 
+use serde_json::json;
 use std::sync::Arc;
 use tempfile::TempDir;
 use turbovault_core::{ConfigProfile, VaultConfig};
-use turbovault_tools::MetadataTools;
+use turbovault_tools::{
+    MetadataTools, ObsidianPropertyType, PropertyMutation, PropertySchemaTools, QueryFilter,
+    SearchEngine, SearchQuery,
+};
 use turbovault_vault::VaultManager;
 
 async fn setup_test_vault_with_metadata() -> (TempDir, Arc<VaultManager>) {
@@ -208,6 +212,180 @@ async fn test_query_metadata_invalid_syntax() {
     assert!(result.is_err());
 }
 
+async fn matched_paths(tools: &MetadataTools, pattern: &str) -> Vec<String> {
+    let response = tools.query_metadata(pattern).await.unwrap();
+    let mut paths: Vec<String> = response["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["path"].as_str().unwrap().to_string())
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[tokio::test]
+async fn test_query_metadata_returns_matching_files() {
+    let (_temp_dir, manager) = setup_test_vault_with_metadata().await;
+    let tools = MetadataTools::new(manager);
+
+    assert_eq!(
+        matched_paths(&tools, r#"status: "draft""#).await,
+        ["note1.md"]
+    );
+    assert_eq!(
+        matched_paths(&tools, "priority >= 3").await,
+        ["note1.md", "note2.md"]
+    );
+    assert_eq!(
+        matched_paths(&tools, "tags contains urgent").await,
+        ["note1.md"]
+    );
+}
+
+#[tokio::test]
+async fn test_query_metadata_nested_keys_and_existence() {
+    let (_temp_dir, manager) = setup_test_vault_with_metadata().await;
+    let tools = MetadataTools::new(manager);
+
+    assert_eq!(
+        matched_paths(&tools, "nested.count > 40").await,
+        ["note3.md"]
+    );
+    assert_eq!(
+        matched_paths(&tools, r#"nested.field = "value""#).await,
+        ["note3.md"]
+    );
+    assert_eq!(
+        matched_paths(&tools, "NOT exists(author)").await,
+        ["note3.md"]
+    );
+}
+
+#[tokio::test]
+async fn test_query_metadata_boolean_composition() {
+    let (_temp_dir, manager) = setup_test_vault_with_metadata().await;
+    let tools = MetadataTools::new(manager);
+
+    let paths = matched_paths(
+        &tools,
+        r#"(status in ("draft", "archived") OR author = Bob) AND NOT tags contains urgent"#,
+    )
+    .await;
+    assert_eq!(paths, ["note2.md", "note3.md"]);
+
+    let paths = matched_paths(&tools, r#"title matches "^(First|Third)""#).await;
+    assert_eq!(paths, ["note1.md", "note3.md"]);
+}
+
+#[tokio::test]
+async fn test_advanced_search_uses_metadata_filter() {
+    let (_temp_dir, manager) = setup_test_vault_with_metadata().await;
+    let engine = SearchEngine::new(manager).await.unwrap();
+
+    let filter = QueryFilter::parse("priority > 4 OR nested.count >= 42").unwrap();
+    let query = SearchQuery::new("content OR text")
+        .with_metadata_filter(filter)
+        .limit(10);
+    let mut titles: Vec<String> = engine
+        .advanced_search(query)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| r.title)
+        .collect();
+    titles.sort();
+    assert_eq!(titles, ["First Note", "Third Note"]);
+
+    // Legacy key/value filter now matches list elements too
+    let query = SearchQuery::new("content")
+        .with_frontmatter("tags".to_string(), "reference".to_string())
+        .limit(10);
+    let results = engine.advanced_search(query).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].title, "Second Note");
+}
+
+#[tokio::test]
+async fn test_bulk_update_properties_dry_run_then_apply() {
+    let (temp_dir, manager) = setup_test_vault_with_metadata().await;
+    let tools = MetadataTools::new(manager);
+    let mutations = vec![
+        PropertyMutation::Set {
+            key: "status".to_string(),
+            value: json!("review"),
+        },
+        PropertyMutation::AddToList {
+            key: "tags".to_string(),
+            value: json!("reviewed"),
+        },
+    ];
+
+    let preview = tools
+        .bulk_update_properties("priority >= 3", &mutations, true)
+        .await
+        .unwrap();
+    assert!(preview.dry_run);
+    assert_eq!(preview.matched, 2);
+    assert_eq!(preview.updated, 2);
+    assert!(preview.batch.is_none());
+    let note1 = preview.notes.iter().find(|n| n.path == "note1.md").unwrap();
+    let status = note1.changes.iter().find(|c| c.key == "status").unwrap();
+    assert_eq!(status.before, Some(json!("draft")));
+    assert_eq!(status.after, Some(json!("review")));
+
+    // Dry run leaves files untouched
+    let raw = tokio::fs::read_to_string(temp_dir.path().join("note1.md"))
+        .await
+        .unwrap();
+    assert!(raw.contains("status: \"draft\""));
+
+    let applied = tools
+        .bulk_update_properties("priority >= 3", &mutations, false)
+        .await
+        .unwrap();
+    assert!(applied.batch.as_ref().unwrap().success);
+
+    let raw = tokio::fs::read_to_string(temp_dir.path().join("note1.md"))
+        .await
+        .unwrap();
+    // Only the changed properties are rewritten; quoting elsewhere is kept
+    assert!(raw.starts_with("---\ntitle: \"First Note\"\nauthor: \"Alice\"\nstatus: review\n"));
+    assert!(raw.ends_with("# Note 1\nContent here"));
+    assert_eq!(
+        matched_paths(&tools, "status = review AND tags contains reviewed").await,
+        ["note1.md", "note2.md"]
+    );
+
+    // Re-running is a no-op for list adds and sets
+    let rerun = tools
+        .bulk_update_properties("priority >= 3", &mutations, false)
+        .await
+        .unwrap();
+    assert_eq!(rerun.matched, 2);
+    assert_eq!(rerun.updated, 0);
+}
+
+#[tokio::test]
+async fn test_bulk_update_properties_rename_conflict_aborts() {
+    let (temp_dir, manager) = setup_test_vault_with_metadata().await;
+    let tools = MetadataTools::new(manager);
+    let mutations = vec![PropertyMutation::RenameKey {
+        from: "title".to_string(),
+        to: "status".to_string(),
+    }];
+
+    let result = tools
+        .bulk_update_properties("exists(title)", &mutations, false)
+        .await;
+    assert!(result.is_err());
+
+    let raw = tokio::fs::read_to_string(temp_dir.path().join("note2.md"))
+        .await
+        .unwrap();
+    assert!(raw.contains("title: \"Second Note\""));
+}
+
 #[tokio::test]
 async fn test_async_error_nonexistent_file() {
     let (_temp_dir, manager) = setup_test_vault_with_metadata().await;
@@ -240,3 +418,140 @@ async fn test_concurrent_metadata_reads() {
         assert!(result.is_ok());
     }
 }
+
+#[tokio::test]
+async fn test_analyze_property_schema() {
+    let temp_dir = TempDir::new().unwrap();
+    let vault_path = temp_dir.path();
+
+    tokio::fs::write(
+        vault_path.join("a.md"),
+        "---\nstatus: draft\ndue: 2024-03-01\ntags: [work]\n---\nA",
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        vault_path.join("b.md"),
+        "---\nStatus: done\ndue: someday\ntags: work\n---\nB",
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(vault_path.join("c.md"), "---\n[broken\n---\nC")
+        .await
+        .unwrap();
+    tokio::fs::create_dir_all(vault_path.join(".obsidian"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        vault_path.join(".obsidian/types.json"),
+        r#"{"types": {"due": "date"}}"#,
+    )
+    .await
+    .unwrap();
+
+    let mut config = ConfigProfile::Development.create_config();
+    config
+        .vaults
+        .push(VaultConfig::builder("test", vault_path).build().unwrap());
+    let manager = Arc::new(VaultManager::new(config).unwrap());
+    manager.initialize().await.unwrap();
+
+    let report = PropertySchemaTools::new(manager)
+        .analyze_properties()
+        .await
+        .unwrap();
+
+    assert_eq!(report.total_notes, 3);
+    assert_eq!(report.unparseable_notes, ["c.md"]);
+    assert_eq!(report.inconsistent_properties, 3);
+
+    let due = report.properties.iter().find(|p| p.key == "due").unwrap();
+    assert_eq!(due.declared_type, Some(ObsidianPropertyType::Date));
+    assert_eq!(due.inconsistencies.len(), 2);
+
+    let status = report
+        .properties
+        .iter()
+        .find(|p| p.key == "status")
+        .unwrap();
+    assert_eq!(status.variants.len(), 2);
+
+    assert_eq!(
+        report.obsidian_types_json(),
+        json!({"types": {"due": "date", "status": "text", "tags": "tags"}})
+    );
+}
+
+#[tokio::test]
+async fn test_list_callouts_by_type() {
+    let temp_dir = TempDir::new().unwrap();
+    let vault_path = temp_dir.path();
+    tokio::fs::create_dir_all(vault_path.join("projects"))
+        .await
+        .unwrap();
+
+    tokio::fs::write(
+        vault_path.join("projects/plan.md"),
+        "---\nstatus: active\n---\n> [!TODO] Launch\n> - [ ] Ship\n> - [x] Test\n\n> [!tldr]\n> Short\n",
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        vault_path.join("log.md"),
+        "> [!decision]- Use Rust\n> Fast\n> > [!todo] Follow-up\n> > - [ ] Benchmark\n",
+    )
+    .await
+    .unwrap();
+
+    let mut config = ConfigProfile::Development.create_config();
+    config
+        .vaults
+        .push(VaultConfig::builder("test", vault_path).build().unwrap());
+    let manager = Arc::new(VaultManager::new(config).unwrap());
+    manager.initialize().await.unwrap();
+    let tools = MetadataTools::new(manager);
+
+    let todos = tools.list_callouts(Some("todo"), None).await.unwrap();
+    assert_eq!(todos.total, 2);
+    assert_eq!(todos.by_type["todo"], 2);
+    assert_eq!(todos.by_type["abstract"], 1);
+    assert_eq!(todos.by_type["decision"], 1);
+
+    let nested = todos.callouts.iter().find(|c| c.path == "log.md").unwrap();
+    assert_eq!(nested.depth, 2);
+    assert_eq!(nested.line, 3);
+    assert_eq!(nested.open_tasks, 1);
+
+    let launch = todos
+        .callouts
+        .iter()
+        .find(|c| c.path == "projects/plan.md")
+        .unwrap();
+    assert_eq!(launch.identifier, "TODO");
+    assert_eq!(launch.line, 4);
+    assert_eq!(launch.open_tasks, 1);
+
+    tokio::fs::create_dir_all(vault_path.join("projects-archive"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        vault_path.join("projects-archive/old.md"),
+        "> [!summary]\n> Archived\n",
+    )
+    .await
+    .unwrap();
+    let summaries = tools
+        .list_callouts(Some("summary"), Some("projects"))
+        .await
+        .unwrap();
+    assert_eq!(summaries.callout_type.as_deref(), Some("abstract"));
+    assert_eq!(summaries.total, 1);
+
+    let decisions = tools.list_callouts(Some("Decision"), None).await.unwrap();
+    assert_eq!(decisions.total, 1);
+    assert!(decisions.callouts[0].is_collapsed);
+    assert_eq!(
+        decisions.callouts[0].content,
+        "Fast\n> [!todo] Follow-up\n> - [ ] Benchmark"
+    );
+}
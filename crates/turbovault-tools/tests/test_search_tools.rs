@@ -3,7 +3,7 @@
 use std::sync::Arc;
 use tempfile::TempDir;
 use turbovault_core::{ConfigProfile, VaultConfig};
-use turbovault_tools::SearchTools;
+use turbovault_tools::{EdgeKind, SearchEngine, SearchTools};
 use turbovault_vault::VaultManager;
 
 async fn setup_test_vault_with_links() -> (TempDir, Arc<VaultManager>) {
@@ -212,3 +212,155 @@ async fn test_concurrent_searches() {
         assert!(result.is_ok());
     }
 }
+
+#[tokio::test]
+async fn test_find_link_edges_by_kind() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let vault_path = temp_dir.path();
+    tokio::fs::create_dir_all(vault_path.join("projects"))
+        .await
+        .expect("Failed to create folder");
+
+    tokio::fs::write(vault_path.join("projects/plan.md"), "# Plan\n## Goals\n")
+        .await
+        .expect("Failed to write plan");
+    tokio::fs::write(
+        vault_path.join("daily.md"),
+        "---\nproject: \"[[plan]]\"\n---\n# Daily\nSee [plan](projects/plan.md) and [[plan#Goals]].\n",
+    )
+    .await
+    .expect("Failed to write daily");
+
+    let mut config = ConfigProfile::Development.create_config();
+    let vault_config = VaultConfig::builder("test", vault_path)
+        .build()
+        .expect("Failed to create vault config");
+    config.vaults.push(vault_config);
+    let manager = VaultManager::new(config).expect("Failed to create vault manager");
+    manager
+        .initialize()
+        .await
+        .expect("Failed to initialize vault");
+    let tools = SearchTools::new(Arc::new(manager));
+
+    let edges = tools
+        .find_backlink_edges("projects/plan.md", &[])
+        .await
+        .expect("backlink edges");
+    let mut kinds: Vec<_> = edges.iter().map(|e| e.kind.as_str()).collect();
+    kinds.sort();
+    assert_eq!(kinds, vec!["heading_ref", "markdown_link", "property"]);
+    assert!(edges.iter().all(|e| e.path.ends_with("daily.md")));
+
+    let property = tools
+        .find_backlink_edges("projects/plan.md", &[EdgeKind::Property])
+        .await
+        .expect("property edges");
+    assert_eq!(property.len(), 1);
+    assert_eq!(property[0].property.as_deref(), Some("project"));
+
+    let forward = tools
+        .find_forward_link_edges("daily.md", &[EdgeKind::WikiLink])
+        .await
+        .expect("forward edges");
+    assert!(forward.is_empty());
+}
+
+#[tokio::test]
+async fn test_search_by_unicode_inline_tags() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let vault_path = temp_dir.path();
+    let notes = [
+        (
+            "menu.md",
+            "# Menu\nCroissants at the #café, see #日本語 notes",
+        ),
+        ("launch.md", "# Launch\nShipping soon #🚀launch #Überblick"),
+        (
+            "plain.md",
+            "---\ntags: [cafe]\n---\n# Plain\nNo unicode here",
+        ),
+    ];
+    for (name, content) in notes {
+        tokio::fs::write(vault_path.join(name), content)
+            .await
+            .unwrap();
+    }
+
+    let mut config = ConfigProfile::Development.create_config();
+    config
+        .vaults
+        .push(VaultConfig::builder("test", vault_path).build().unwrap());
+    let manager = Arc::new(VaultManager::new(config).unwrap());
+    manager.initialize().await.unwrap();
+    let engine = SearchEngine::new(manager).await.unwrap();
+
+    let paths = |results: Vec<turbovault_tools::SearchResultInfo>| {
+        let mut paths: Vec<String> = results
+            .into_iter()
+            .map(|r| r.path.rsplit('/').next().unwrap().to_string())
+            .collect();
+        paths.sort();
+        paths
+    };
+
+    let cafe = engine
+        .search_by_tags(vec!["café".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(paths(cafe), vec!["menu.md"]);
+
+    let launch = engine
+        .search_by_tags(vec!["#🚀launch".to_string(), "日本語".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(paths(launch), vec!["launch.md", "menu.md"]);
+
+    let plain = engine
+        .search_by_tags(vec!["cafe".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(paths(plain), vec!["plain.md"]);
+}
+
+#[tokio::test]
+async fn test_excalidraw_links_and_search_skip_scene() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let vault_path = temp_dir.path();
+    let drawing = "---\nexcalidraw-plugin: parsed\n---\n==⚠  Switch to EXCALIDRAW VIEW in the MORE OPTIONS menu of this document. ⚠==\n\n# Excalidraw Data\n\n## Text Elements\nArchitecture sketch, see [[Roadmap]] ^a1b2c3d4\n\n## Embedded Files\n0f1e2d3c: [[Spec]]\n\n%%\n## Drawing\n```json\n{\"elements\":[{\"strokeColor\":\"#1e1e1e\",\"text\":\"scenepayload [[Hidden]]\"}]}\n```\n%%";
+    let files = [
+        ("Board.excalidraw.md", drawing),
+        ("Roadmap.md", "# Roadmap\n"),
+        ("Spec.md", "# Spec\n"),
+        ("Hidden.md", "# Hidden\n"),
+    ];
+    for (name, content) in files {
+        tokio::fs::write(vault_path.join(name), content)
+            .await
+            .unwrap();
+    }
+
+    let mut config = ConfigProfile::Development.create_config();
+    config
+        .vaults
+        .push(VaultConfig::builder("test", vault_path).build().unwrap());
+    let manager = Arc::new(VaultManager::new(config).unwrap());
+    manager.initialize().await.unwrap();
+
+    let mut forward = manager
+        .get_forward_links(std::path::Path::new("Board.excalidraw.md"))
+        .await
+        .unwrap();
+    forward.sort();
+    assert_eq!(
+        forward,
+        vec![vault_path.join("Roadmap.md"), vault_path.join("Spec.md")]
+    );
+
+    let engine = SearchEngine::new(manager).await.unwrap();
+    let sketch = engine.search("architecture").await.unwrap();
+    assert_eq!(sketch.len(), 1);
+    assert!(sketch[0].path.ends_with("Board.excalidraw.md"));
+    assert!(engine.search("scenepayload").await.unwrap().is_empty());
+    assert!(engine.search("1e1e1e").await.unwrap().is_empty());
+}
@@ -0,0 +1,178 @@
+//! Unit tests for TableTools
+
+use serde_json::json;
+use std::sync::Arc;
+use tempfile::TempDir;
+use turbovault_core::{ConfigProfile, TableAlignment, VaultConfig};
+use turbovault_tools::{RowSelector, TableOperation, TableTools};
+use turbovault_vault::VaultManager;
+
+const TASKS: &str = "# Tasks\n\nIntro text.\n\n## Active\n\n| Task | Owner | Est |\n|:-----|-------|----:|\n| **Ship** [[Release]] | sam | 3 |\n| Fix a\\|b | ana | 1 |\n\n## Backlog\n\n> [!note] Later\n> | Task | Owner |\n> | --- | --- |\n> | Docs | sam |\n\nFooter.\n";
+
+async fn setup_table_vault() -> (TempDir, Arc<VaultManager>) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let vault_path = temp_dir.path();
+
+    tokio::fs::write(vault_path.join("tasks.md"), TASKS)
+        .await
+        .unwrap();
+
+    let mut config = ConfigProfile::Development.create_config();
+    let vault_config = VaultConfig::builder("test", vault_path).build().unwrap();
+    config.vaults.push(vault_config);
+
+    let manager = VaultManager::new(config).unwrap();
+    manager.initialize().await.unwrap();
+
+    (temp_dir, Arc::new(manager))
+}
+
+async fn read_note(temp_dir: &TempDir) -> String {
+    tokio::fs::read_to_string(temp_dir.path().join("tasks.md"))
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_list_tables() {
+    let (_temp, manager) = setup_table_vault().await;
+    let tools = TableTools::new(manager);
+
+    let tables = tools.list_tables("tasks.md").await.unwrap();
+    assert_eq!(tables.len(), 2);
+    assert_eq!(tables[0].heading.as_deref(), Some("Active"));
+    assert_eq!(tables[0].line, 7);
+    assert_eq!(tables[0].headers, vec!["Task", "Owner", "Est"]);
+    assert_eq!(tables[0].row_count, 2);
+    assert_eq!(tables[1].heading.as_deref(), Some("Backlog"));
+    assert_eq!(tables[1].row_count, 1);
+}
+
+#[tokio::test]
+async fn test_read_table_as_records() {
+    let (_temp, manager) = setup_table_vault().await;
+    let tools = TableTools::new(manager);
+
+    let table = tools.read_table("tasks.md", None, None).await.unwrap();
+    assert_eq!(
+        table.alignments,
+        vec![
+            TableAlignment::Left,
+            TableAlignment::None,
+            TableAlignment::Right
+        ]
+    );
+    assert_eq!(table.records.len(), 2);
+    assert_eq!(table.records[0]["Task"], json!("**Ship** [[Release]]"));
+    assert_eq!(table.records[1]["Task"], json!("Fix a|b"));
+    assert_eq!(table.records[1]["Est"], json!("1"));
+
+    let backlog = tools
+        .read_table("tasks.md", None, Some("## backlog"))
+        .await
+        .unwrap();
+    assert_eq!(backlog.index, 1);
+    assert_eq!(backlog.records[0]["Task"], json!("Docs"));
+
+    assert!(tools.read_table("tasks.md", Some(2), None).await.is_err());
+    assert!(
+        tools
+            .read_table("tasks.md", Some(1), Some("Active"))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_update_rows_preserves_rest_of_note() {
+    let (temp, manager) = setup_table_vault().await;
+    let tools = TableTools::new(manager);
+
+    let operations = vec![TableOperation::UpdateRows {
+        rows: RowSelector::Match {
+            column: "owner".to_string(),
+            equals: "ana".to_string(),
+        },
+        set: json!({"Est": 2, "Task": "Fix a|b\nand c"})
+            .as_object()
+            .unwrap()
+            .clone(),
+    }];
+    let report = tools
+        .update_table("tasks.md", None, None, &operations, false)
+        .await
+        .unwrap();
+
+    assert_eq!(report.rows_affected, 1);
+    assert_eq!(report.after.records[1]["Task"], json!("Fix a|b<br>and c"));
+    assert_eq!(
+        read_note(&temp).await,
+        TASKS.replace(
+            "| Task | Owner | Est |\n|:-----|-------|----:|\n| **Ship** [[Release]] | sam | 3 |\n| Fix a\\|b | ana | 1 |",
+            "| Task | Owner | Est |\n| :----- | ------- | ----: |\n| **Ship** [[Release]] | sam | 3 |\n| Fix a\\|b<br>and c | ana | 2 |"
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_row_and_column_operations_in_callout() {
+    let (temp, manager) = setup_table_vault().await;
+    let tools = TableTools::new(manager);
+
+    let operations: Vec<TableOperation> = serde_json::from_value(json!([
+        {"op": "insert_row", "values": {"Task": "Tests"}, "position": 0},
+        {"op": "insert_column", "name": "Due", "value": "soon", "alignment": "center"},
+        {"op": "rename_column", "from": "Owner", "to": "Who"},
+        {"op": "delete_rows", "rows": {"column": "Task", "equals": "Docs"}},
+        {"op": "delete_column", "name": "Task"}
+    ]))
+    .unwrap();
+    let report = tools
+        .update_table("tasks.md", None, Some("Backlog"), &operations, false)
+        .await
+        .unwrap();
+
+    assert_eq!(report.rows_affected, 2);
+    assert_eq!(
+        report.markdown,
+        "| Who | Due |\n> | --- | :---: |\n> |  | soon |"
+    );
+    let content = read_note(&temp).await;
+    assert!(content.contains(
+        "> [!note] Later\n> | Who | Due |\n> | --- | :---: |\n> |  | soon |\n\nFooter.\n"
+    ));
+    assert!(content.contains("| **Ship** [[Release]] | sam | 3 |"));
+}
+
+#[tokio::test]
+async fn test_dry_run_and_invalid_operations_do_not_write() {
+    let (temp, manager) = setup_table_vault().await;
+    let tools = TableTools::new(manager);
+
+    let operations = vec![TableOperation::DeleteRows {
+        rows: RowSelector::Index(0),
+    }];
+    let report = tools
+        .update_table("tasks.md", None, None, &operations, true)
+        .await
+        .unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.after.records.len(), 1);
+    assert_eq!(read_note(&temp).await, TASKS);
+
+    let operations = vec![
+        TableOperation::DeleteRows {
+            rows: RowSelector::Index(0),
+        },
+        TableOperation::DeleteColumn {
+            name: "Missing".to_string(),
+        },
+    ];
+    assert!(
+        tools
+            .update_table("tasks.md", None, None, &operations, false)
+            .await
+            .is_err()
+    );
+    assert_eq!(read_note(&temp).await, TASKS);
+}
@@ -0,0 +1,155 @@
+//! Unit tests for TagTools
+
+use std::sync::Arc;
+use tempfile::TempDir;
+use turbovault_core::{ConfigProfile, VaultConfig};
+use turbovault_tools::TagTools;
+use turbovault_vault::VaultManager;
+
+async fn setup_tagged_vault() -> (TempDir, Arc<VaultManager>) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let vault_path = temp_dir.path();
+
+    tokio::fs::write(
+        vault_path.join("alpha.md"),
+        "---\ntags: [project/alpha, todo]\n---\n# Alpha\nWork item #todo and #project/alpha/design\n\n```\n#todo stays in code\n```\n",
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        vault_path.join("beta.md"),
+        "# Beta\nTracking #to-do and #ToDos for #project/beta\n",
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(vault_path.join("plain.md"), "# Plain\nNo tags\n")
+        .await
+        .unwrap();
+
+    let mut config = ConfigProfile::Development.create_config();
+    let vault_config = VaultConfig::builder("test", vault_path).build().unwrap();
+    config.vaults.push(vault_config);
+
+    let manager = VaultManager::new(config).unwrap();
+    manager.initialize().await.unwrap();
+
+    (temp_dir, Arc::new(manager))
+}
+
+#[tokio::test]
+async fn test_list_tags_hierarchy() {
+    let (_temp_dir, manager) = setup_tagged_vault().await;
+    let tools = TagTools::new(manager);
+
+    let hierarchy = tools.list_tags().await.unwrap();
+    let project = hierarchy.roots.iter().find(|n| n.tag == "project").unwrap();
+    assert_eq!(project.count, 0);
+    assert_eq!(project.total_count, 3);
+    assert_eq!(project.children.len(), 2);
+
+    let todo = hierarchy.roots.iter().find(|n| n.tag == "todo").unwrap();
+    // Frontmatter + inline in alpha; the code block is ignored
+    assert_eq!(todo.count, 2);
+    assert_eq!(todo.note_count, 1);
+}
+
+#[tokio::test]
+async fn test_rename_with_descendants_preview_then_apply() {
+    let (temp_dir, manager) = setup_tagged_vault().await;
+    let tools = TagTools::new(manager);
+
+    let preview = tools
+        .rename_tag("project", "work", true, true)
+        .await
+        .unwrap();
+    assert!(preview.dry_run);
+    assert_eq!(preview.notes_changed, 2);
+    assert_eq!(preview.occurrences_changed, 3);
+    let untouched = std::fs::read_to_string(temp_dir.path().join("beta.md")).unwrap();
+    assert!(untouched.contains("#project/beta"));
+
+    let report = tools
+        .rename_tag("project", "work", true, false)
+        .await
+        .unwrap();
+    assert!(report.batch.unwrap().success);
+
+    let alpha = std::fs::read_to_string(temp_dir.path().join("alpha.md")).unwrap();
+    assert!(alpha.contains("- work/alpha"));
+    assert!(alpha.contains("#work/alpha/design"));
+    let beta = std::fs::read_to_string(temp_dir.path().join("beta.md")).unwrap();
+    assert!(beta.contains("for #work/beta"));
+}
+
+#[tokio::test]
+async fn test_merge_and_delete_tags() {
+    let (temp_dir, manager) = setup_tagged_vault().await;
+    let tools = TagTools::new(manager);
+
+    let report = tools
+        .merge_tags(&["to-do".to_string(), "todos".to_string()], "todo", false)
+        .await
+        .unwrap();
+    assert_eq!(report.notes_changed, 1);
+    let beta = std::fs::read_to_string(temp_dir.path().join("beta.md")).unwrap();
+    assert_eq!(beta, "# Beta\nTracking #todo and #todo for #project/beta\n");
+
+    tools.delete_tag("todo", false, false).await.unwrap();
+    let alpha = std::fs::read_to_string(temp_dir.path().join("alpha.md")).unwrap();
+    assert!(alpha.starts_with("---\ntags:\n- project/alpha\n---\n"));
+    assert!(alpha.contains("Work item and #project/alpha/design"));
+    assert!(alpha.contains("#todo stays in code"));
+    let beta = std::fs::read_to_string(temp_dir.path().join("beta.md")).unwrap();
+    assert_eq!(beta, "# Beta\nTracking and for #project/beta\n");
+}
+
+#[tokio::test]
+async fn test_rename_rejects_invalid_target() {
+    let (_temp_dir, manager) = setup_tagged_vault().await;
+    let tools = TagTools::new(manager);
+    assert!(
+        tools
+            .rename_tag("todo", "two words", false, true)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_tag_cooccurrence_analysis() {
+    let (_temp_dir, manager) = setup_tagged_vault().await;
+    let tools = TagTools::new(manager);
+
+    let report = tools.related_tags("#TODO", 1, 10).await.unwrap();
+    assert_eq!(report.tag, "todo");
+    assert_eq!(report.note_count, 1);
+    let related: Vec<&str> = report.related.iter().map(|p| p.other.as_str()).collect();
+    assert_eq!(related, vec!["project/alpha", "project/alpha/design"]);
+    assert_eq!(report.related[0].npmi, 1.0);
+
+    let project = tools.related_tags("project/alpha", 1, 10).await.unwrap();
+    assert_eq!(project.parent.as_deref(), Some("project"));
+    assert_eq!(project.children, vec!["project/alpha/design"]);
+    assert_eq!(project.related.len(), 1);
+    assert!(tools.related_tags("missing", 1, 10).await.is_err());
+
+    let duplicates = tools.find_duplicate_tags(None).await.unwrap();
+    let pairs: Vec<(&str, &str)> = duplicates
+        .iter()
+        .map(|d| (d.keep.as_str(), d.merge.as_str()))
+        .collect();
+    assert_eq!(duplicates.len(), 3);
+    assert!(pairs.contains(&("to-do", "todo")));
+    assert!(pairs.contains(&("todo", "todos")));
+    // Used together on beta, with the same other tags
+    assert_eq!(
+        (duplicates[0].keep.as_str(), duplicates[0].merge.as_str()),
+        ("to-do", "todos")
+    );
+    assert_eq!(duplicates[0].shared_notes, 1);
+    assert_eq!(duplicates[0].context_similarity, 1.0);
+
+    let bridges = tools.find_bridging_notes(2, None).await.unwrap();
+    assert_eq!(bridges.communities.len(), 2);
+    assert!(bridges.notes.is_empty());
+}
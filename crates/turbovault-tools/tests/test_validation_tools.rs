@@ -0,0 +1,97 @@
+//! Unit tests for ValidationTools
+
+use std::sync::Arc;
+use tempfile::TempDir;
+use turbovault_core::{ConfigProfile, VaultConfig};
+use turbovault_tools::ValidationTools;
+use turbovault_vault::VaultManager;
+
+async fn setup_schema_vault() -> (TempDir, Arc<VaultManager>) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let vault_path = temp_dir.path();
+
+    tokio::fs::create_dir_all(vault_path.join(".turbovault"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        vault_path.join(".turbovault/schemas.yaml"),
+        r#"
+types:
+  book:
+    folders: [Books]
+    required:
+      author: text
+      rating: number
+    optional:
+      status:
+        enum: [reading, finished]
+"#,
+    )
+    .await
+    .unwrap();
+
+    tokio::fs::create_dir_all(vault_path.join("Books"))
+        .await
+        .unwrap();
+    tokio::fs::write(
+        vault_path.join("Books/good.md"),
+        "---\nauthor: Le Guin\nrating: 5\nstatus: finished\n---\n# Good\n",
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        vault_path.join("Books/bad.md"),
+        "---\nauthor: Herbert\nstatus: abandoned\n---\n# Bad\n",
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(vault_path.join("free.md"), "# Free\nNo schema here\n")
+        .await
+        .unwrap();
+
+    let mut config = ConfigProfile::Development.create_config();
+    let vault_config = VaultConfig::builder("test", vault_path).build().unwrap();
+    config.vaults.push(vault_config);
+
+    let manager = VaultManager::new(config).unwrap();
+    manager.initialize().await.unwrap();
+
+    (temp_dir, Arc::new(manager))
+}
+
+#[tokio::test]
+async fn test_validate_vault_reports_schema_violations() {
+    let (_temp_dir, manager) = setup_schema_vault().await;
+    let tools = ValidationTools::new(manager);
+
+    let report = tools.validate_vault().await.unwrap();
+    assert!(!report.passed);
+
+    let schema_issues: Vec<_> = report
+        .issues
+        .iter()
+        .filter(|i| i.category == "schema")
+        .collect();
+    assert_eq!(schema_issues.len(), 2, "{:?}", schema_issues);
+    assert!(
+        schema_issues
+            .iter()
+            .all(|i| i.file.as_deref() == Some("Books/bad.md"))
+    );
+    assert!(schema_issues.iter().any(|i| i.message.contains("'rating'")));
+    assert!(
+        schema_issues
+            .iter()
+            .any(|i| i.message.contains("abandoned"))
+    );
+}
+
+#[tokio::test]
+async fn test_validate_vault_quick_respects_limit() {
+    let (_temp_dir, manager) = setup_schema_vault().await;
+    let tools = ValidationTools::new(manager);
+
+    let report = tools.validate_vault_quick(1).await.unwrap();
+    assert!(report.total_issues >= 1);
+    assert!(report.issues.iter().all(|i| i.file.is_some()));
+}
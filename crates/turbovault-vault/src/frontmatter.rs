@@ -0,0 +1,376 @@
+//! Frontmatter rewriting for property edits
+//!
+//! Splits a note into its YAML frontmatter and body so properties can be
+//! modified and written back without touching the note body. Property order
+//! is preserved; YAML comments and quoting style inside the frontmatter block
+//! are normalized on render. [`FrontmatterDocument::render_onto`] and
+//! [`replace_property`] rewrite only the changed properties in place and leave
+//! the rest of the block, comments included, as written.
+
+use serde_json::{Map, Value};
+use turbovault_core::{Error, Result};
+
+/// A note split into editable frontmatter properties and an untouched body
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontmatterDocument {
+    properties: Map<String, Value>,
+    body: String,
+    had_frontmatter: bool,
+}
+
+impl FrontmatterDocument {
+    /// Split note content into frontmatter properties and body.
+    ///
+    /// Content without a leading `---` block yields empty properties and the
+    /// whole content as body. Invalid YAML in the block is a parse error.
+    pub fn parse(content: &str) -> Result<Self> {
+        let Some((yaml, body)) = split_frontmatter(content) else {
+            return Ok(Self {
+                properties: Map::new(),
+                body: content.to_string(),
+                had_frontmatter: false,
+            });
+        };
+
+        let properties = if yaml.trim().is_empty() {
+            Map::new()
+        } else {
+            match serde_yaml::from_str::<Value>(yaml) {
+                Ok(Value::Object(map)) => map,
+                Ok(Value::Null) => Map::new(),
+                Ok(_) => {
+                    return Err(Error::parse_error(
+                        "Frontmatter is not a YAML mapping".to_string(),
+                    ));
+                }
+                Err(e) => {
+                    return Err(Error::parse_error(format!(
+                        "Invalid frontmatter YAML: {}",
+                        e
+                    )));
+                }
+            }
+        };
+
+        Ok(Self {
+            properties,
+            body: body.to_string(),
+            had_frontmatter: true,
+        })
+    }
+
+    /// Frontmatter properties in document order
+    pub fn properties(&self) -> &Map<String, Value> {
+        &self.properties
+    }
+
+    /// Mutable access to frontmatter properties
+    pub fn properties_mut(&mut self) -> &mut Map<String, Value> {
+        &mut self.properties
+    }
+
+    /// Note body after the frontmatter block
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Whether the original content had a frontmatter block
+    pub fn had_frontmatter(&self) -> bool {
+        self.had_frontmatter
+    }
+
+    /// Render the note back to markdown.
+    ///
+    /// An empty property set drops the frontmatter block entirely.
+    pub fn render(&self) -> Result<String> {
+        if self.properties.is_empty() {
+            return Ok(self.body.clone());
+        }
+
+        let yaml = serde_yaml::to_string(&self.properties)
+            .map_err(|e| Error::other(format!("Failed to serialize frontmatter: {}", e)))?;
+
+        let mut output = String::with_capacity(yaml.len() + self.body.len() + 8);
+        output.push_str("---\n");
+        output.push_str(&yaml);
+        if !yaml.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str("---\n");
+        output.push_str(&self.body);
+        Ok(output)
+    }
+
+    /// Render the note back onto `original`, the content it was parsed from,
+    /// rewriting only the properties that changed.
+    ///
+    /// Comments, quoting and unchanged properties in the block are kept as
+    /// written; new properties go at the end of the block. Falls back to
+    /// [`Self::render`] when the block is emptied or a changed key is not
+    /// written as a plain `key:` line.
+    pub fn render_onto(&self, original: &str) -> Result<String> {
+        let before = Self::parse(original)?;
+        if !before.had_frontmatter || self.properties.is_empty() {
+            return self.render();
+        }
+
+        let mut output = original.to_string();
+        for (key, old) in &before.properties {
+            let new = self.properties.get(key);
+            if new == Some(old) {
+                continue;
+            }
+            match replace_property(&output, key, new)? {
+                Some(updated) => output = updated,
+                None => return self.render(),
+            }
+        }
+        for (key, new) in &self.properties {
+            if before.properties.contains_key(key) {
+                continue;
+            }
+            match insert_property(&output, key, new)? {
+                Some(updated) => output = updated,
+                None => return self.render(),
+            }
+        }
+        Ok(output)
+    }
+}
+
+/// Split content into `(yaml, body)` if it starts with a `---` frontmatter block
+pub fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+    let yaml_start = content.len() - rest.len();
+
+    let mut offset = yaml_start;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if trimmed == "---" || trimmed == "..." {
+            return Some((
+                &content[yaml_start..offset],
+                &content[offset + line.len()..],
+            ));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Rewrite one top-level property in place, or remove it when `value` is `None`.
+///
+/// Only the `key:` line and its indented or `-` continuation lines (blank
+/// lines between them included) are replaced, using the key line's line
+/// ending; every other line of the block is kept verbatim. Returns `None`
+/// when the content has no frontmatter block or the key is not written as a
+/// plain `key:` line.
+pub fn replace_property(content: &str, key: &str, value: Option<&Value>) -> Result<Option<String>> {
+    let Some((yaml, _)) = split_frontmatter(content) else {
+        return Ok(None);
+    };
+    let yaml_start = yaml.as_ptr() as usize - content.as_ptr() as usize;
+
+    let mut offset = yaml_start;
+    let mut span: Option<(usize, usize)> = None;
+    let mut newline = "\n";
+    for line in yaml.split_inclusive('\n') {
+        match span {
+            None => {
+                let is_key = line
+                    .strip_prefix(key)
+                    .is_some_and(|rest| rest.starts_with(':'));
+                if is_key {
+                    span = Some((offset, offset + line.len()));
+                    if line.ends_with("\r\n") {
+                        newline = "\r\n";
+                    }
+                }
+            }
+            // Blank lines only belong to the value if it continues after them
+            Some(_) if line.trim().is_empty() => {}
+            Some((start, _)) => {
+                if !line.starts_with([' ', '\t', '-']) {
+                    break;
+                }
+                span = Some((start, offset + line.len()));
+            }
+        }
+        offset += line.len();
+    }
+    let Some((start, end)) = span else {
+        return Ok(None);
+    };
+
+    let replacement = match value {
+        Some(value) => property_yaml(key, value, newline)?,
+        None => String::new(),
+    };
+
+    let mut output = content.to_string();
+    output.replace_range(start..end, &replacement);
+    Ok(Some(output))
+}
+
+/// Add a property at the end of the frontmatter block; `None` without a block
+fn insert_property(content: &str, key: &str, value: &Value) -> Result<Option<String>> {
+    let Some((yaml, _)) = split_frontmatter(content) else {
+        return Ok(None);
+    };
+    let end = yaml.as_ptr() as usize - content.as_ptr() as usize + yaml.len();
+    let newline = if content.starts_with("---\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+
+    let mut output = content.to_string();
+    output.insert_str(end, &property_yaml(key, value, newline)?);
+    Ok(Some(output))
+}
+
+/// `key: value` as YAML lines ending in `newline`
+fn property_yaml(key: &str, value: &Value, newline: &str) -> Result<String> {
+    let mut entry = Map::new();
+    entry.insert(key.to_string(), value.clone());
+    let mut yaml = serde_yaml::to_string(&entry)
+        .map_err(|e| Error::other(format!("Failed to serialize frontmatter: {}", e)))?;
+    if !yaml.ends_with('\n') {
+        yaml.push('\n');
+    }
+    Ok(yaml.replace('\n', newline))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_and_render_roundtrip() {
+        let content = "---\ntitle: Test\ntags:\n- a\n- b\n---\n# Body\n\ntext\n";
+        let doc = FrontmatterDocument::parse(content).unwrap();
+        assert_eq!(doc.properties()["title"], json!("Test"));
+        assert_eq!(doc.body(), "# Body\n\ntext\n");
+        assert_eq!(doc.render().unwrap(), content);
+    }
+
+    #[test]
+    fn test_preserves_property_order() {
+        let content = "---\nzeta: 1\nalpha: 2\n---\nbody";
+        let mut doc = FrontmatterDocument::parse(content).unwrap();
+        doc.properties_mut().insert("middle".to_string(), json!(3));
+        let keys: Vec<_> = doc.properties().keys().cloned().collect();
+        assert_eq!(keys, ["zeta", "alpha", "middle"]);
+        assert!(
+            doc.render()
+                .unwrap()
+                .starts_with("---\nzeta: 1\nalpha: 2\nmiddle: 3\n---\n")
+        );
+    }
+
+    #[test]
+    fn test_no_frontmatter() {
+        let mut doc = FrontmatterDocument::parse("# Just body").unwrap();
+        assert!(!doc.had_frontmatter());
+        assert_eq!(doc.render().unwrap(), "# Just body");
+
+        doc.properties_mut()
+            .insert("status".to_string(), json!("draft"));
+        assert_eq!(
+            doc.render().unwrap(),
+            "---\nstatus: draft\n---\n# Just body"
+        );
+    }
+
+    #[test]
+    fn test_empty_properties_drop_block() {
+        let mut doc = FrontmatterDocument::parse("---\na: 1\n---\nbody").unwrap();
+        doc.properties_mut().clear();
+        assert_eq!(doc.render().unwrap(), "body");
+    }
+
+    #[test]
+    fn test_unclosed_frontmatter_is_body() {
+        let content = "---\ntitle: open\nno close";
+        let doc = FrontmatterDocument::parse(content).unwrap();
+        assert!(!doc.had_frontmatter());
+        assert_eq!(doc.body(), content);
+    }
+
+    #[test]
+    fn test_replace_property_keeps_comments() {
+        let content =
+            "---\n# reviewed\ntitle: T # inline\ntags:\n  - a\n  - b\n# trailing\n---\nbody";
+        let replaced = replace_property(content, "tags", Some(&json!(["c"])))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            replaced,
+            "---\n# reviewed\ntitle: T # inline\ntags:\n- c\n# trailing\n---\nbody"
+        );
+
+        let removed = replace_property(content, "tags", None).unwrap().unwrap();
+        assert_eq!(
+            removed,
+            "---\n# reviewed\ntitle: T # inline\n# trailing\n---\nbody"
+        );
+    }
+
+    #[test]
+    fn test_replace_property_spans_blank_lines_and_keeps_crlf() {
+        let content = "---\ntags:\n  - a\n\n  - b\n\ntitle: T\n---\nbody";
+        let replaced = replace_property(content, "tags", Some(&json!(["c"])))
+            .unwrap()
+            .unwrap();
+        assert_eq!(replaced, "---\ntags:\n- c\n\ntitle: T\n---\nbody");
+
+        let crlf = "---\r\ntags:\r\n- a\r\ntitle: T\r\n---\r\nbody";
+        let replaced = replace_property(crlf, "tags", Some(&json!(["b", "c"])))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            replaced,
+            "---\r\ntags:\r\n- b\r\n- c\r\ntitle: T\r\n---\r\nbody"
+        );
+    }
+
+    #[test]
+    fn test_render_onto_keeps_untouched_lines() {
+        let content = "---\n# owner: me\ntitle: 'Quoted'\nstatus: draft # wip\nold: x\n---\nbody";
+        let mut doc = FrontmatterDocument::parse(content).unwrap();
+        doc.properties_mut()
+            .insert("status".to_string(), json!("done"));
+        doc.properties_mut().shift_remove("old");
+        doc.properties_mut()
+            .insert("reviewed".to_string(), json!(true));
+        assert_eq!(
+            doc.render_onto(content).unwrap(),
+            "---\n# owner: me\ntitle: 'Quoted'\nstatus: done\nreviewed: true\n---\nbody"
+        );
+
+        doc.properties_mut().clear();
+        assert_eq!(doc.render_onto(content).unwrap(), "body");
+    }
+
+    #[test]
+    fn test_replace_property_missing_key() {
+        assert!(
+            replace_property("---\ntagsx: 1\n---\n", "tags", None)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            replace_property("no block", "tags", None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_invalid_yaml_is_error() {
+        assert!(FrontmatterDocument::parse("---\n[unclosed\n---\nbody").is_err());
+        assert!(FrontmatterDocument::parse("---\n- a list\n---\nbody").is_err());
+    }
+}
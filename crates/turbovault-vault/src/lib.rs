@@ -39,6 +39,12 @@
 //! - List files
 //! - Traverse directory structure
 //!
+//! ### Transclusion
+//!
+//! [`VaultManager::read_expanded`] returns a note with its note, heading and
+//! block embeds expanded inline, with cycle detection, a depth limit and a
+//! size budget ([`transclusion::TransclusionOptions`]).
+//!
 //! ### File Watching
 //!
 //! [`watcher::VaultWatcher`] monitors the vault for changes:
@@ -78,6 +84,11 @@
 //! // Use edit_engine for advanced file modifications
 //! ```
 //!
+//! ### Frontmatter
+//!
+//! [`frontmatter::FrontmatterDocument`] splits a note into properties and body
+//! for property edits that leave the body untouched.
+//!
 //! ## Thread Safety
 //!
 //! All components are thread-safe:
@@ -96,19 +107,25 @@
 
 pub mod atomic;
 pub mod edit;
+pub mod frontmatter;
 pub mod manager;
+pub mod transclusion;
 pub mod watcher;
 
 pub use atomic::{AtomicFileOps, FileOp, TransactionResult};
 pub use edit::{EditEngine, EditResult, SearchReplaceBlock, compute_hash};
+pub use frontmatter::{FrontmatterDocument, replace_property, split_frontmatter};
 pub use manager::VaultManager;
+pub use transclusion::{EmbedExpansion, EmbedStatus, ExpandedNote, TransclusionOptions};
 pub use turbovault_core::prelude::*;
 pub use watcher::{VaultEvent, VaultWatcher, WatcherConfig};
 
 pub mod prelude {
     pub use crate::atomic::*;
     pub use crate::edit::*;
+    pub use crate::frontmatter::*;
     pub use crate::manager::*;
+    pub use crate::transclusion::*;
     pub use crate::watcher::*;
     pub use turbovault_core::prelude::*;
 }
@@ -1,23 +1,123 @@
 //! Vault manager implementation with file watching and caching
 
 use path_trav::PathTrav;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 use tracing::instrument;
 use turbovault_core::prelude::*;
-use turbovault_graph::LinkGraph;
-use turbovault_parser::Parser;
+use turbovault_core::{NOTE_SCHEMA_FILE, SchemaMode};
+use turbovault_graph::{
+    AnchorIndex, CommonNeighbor, EdgeKind, EgoNetwork, LinkEdge, LinkGraph, LinkResolver,
+    LinkValidation, NoteAnchors, NotePath, PathDirection,
+};
+use turbovault_parser::{IncrementalParse, Parser, TextEdit, excalidraw};
+
+use crate::frontmatter::{FrontmatterDocument, split_frontmatter};
+use crate::transclusion::{
+    EmbedExpansion, EmbedStatus, ExpandedNote, TransclusionOptions, block_text, expanded_marker,
+    heading_section, reference_marker,
+};
+
+/// Notes at least this large keep their parse between writes, so a write
+/// reparses only the blocks it changed
+const INCREMENTAL_PARSE_MIN_BYTES: usize = 64 * 1024;
+
+/// How many large-note parses are kept; the least recently written go first
+const INCREMENTAL_PARSE_MAX_NOTES: usize = 32;
 
 /// File cache entry with timestamp
 #[derive(Debug, Clone)]
 struct CacheEntry {
-    file: VaultFile,
+    /// Raw file content, including frontmatter
+    content: String,
     cached_at: f64,
 }
 
+/// Progress of one [`VaultManager::read_expanded`] call
+struct Expansion {
+    options: TransclusionOptions,
+    /// Bytes of the expanded note so far, markers included
+    used: usize,
+    embeds: Vec<EmbedExpansion>,
+    /// Notes (and fragments, empty for the whole note) being expanded
+    stack: Vec<(PathBuf, String)>,
+    /// Every file in the vault, walked the first time an embed does not
+    /// resolve to a note in the link graph
+    all_files: Option<Vec<PathBuf>>,
+}
+
+/// Parses of large notes written so far, by absolute path
+#[derive(Default)]
+struct IncrementalParses {
+    parses: HashMap<PathBuf, IncrementalParse>,
+    /// Paths from least to most recently written
+    order: VecDeque<PathBuf>,
+}
+
+impl IncrementalParses {
+    fn contains(&self, path: &Path) -> bool {
+        self.parses.contains_key(path)
+    }
+
+    /// The parse of `path`, marked as most recently written
+    fn touch(&mut self, path: &Path) -> Option<&mut IncrementalParse> {
+        let parse = self.parses.get_mut(path)?;
+        if let Some(i) = self.order.iter().position(|p| p == path)
+            && let Some(path) = self.order.remove(i)
+        {
+            self.order.push_back(path);
+        }
+        Some(parse)
+    }
+
+    fn insert(&mut self, parse: IncrementalParse) -> &mut IncrementalParse {
+        let path = parse.path().to_path_buf();
+        self.remove(&path);
+        while self.order.len() >= INCREMENTAL_PARSE_MAX_NOTES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.parses.remove(&oldest);
+            }
+        }
+        self.order.push_back(path.clone());
+        self.parses.entry(path).or_insert(parse)
+    }
+
+    fn remove(&mut self, path: &Path) {
+        if self.parses.remove(path).is_some() {
+            self.order.retain(|p| p != path);
+        }
+    }
+}
+
+/// Links and anchors of every parsed note, kept current on write so link
+/// validation needs no reparse of the vault
+#[derive(Default)]
+struct LinkIndex {
+    links: HashMap<PathBuf, Vec<Link>>,
+    anchors: AnchorIndex,
+}
+
+impl LinkIndex {
+    fn insert(&mut self, file: &VaultFile) {
+        if is_link_source(&file.path) {
+            self.links.insert(file.path.clone(), file.links.clone());
+            self.anchors.insert(file);
+        } else {
+            self.remove(&file.path);
+        }
+    }
+
+    fn remove(&mut self, path: &Path) {
+        self.links.remove(path);
+        self.anchors.remove(path);
+    }
+}
+
 /// Main vault manager with file operations and watching
 pub struct VaultManager {
     config: ServerConfig,
@@ -25,6 +125,9 @@ pub struct VaultManager {
     parser: Parser,
     link_graph: Arc<RwLock<LinkGraph>>,
     file_cache: Arc<RwLock<HashMap<PathBuf, CacheEntry>>>,
+    note_schemas: Arc<RwLock<Option<NoteSchemas>>>,
+    incremental_parses: Arc<RwLock<IncrementalParses>>,
+    link_index: Arc<RwLock<LinkIndex>>,
 }
 
 impl VaultManager {
@@ -39,6 +142,9 @@ impl VaultManager {
             parser,
             link_graph: Arc::new(RwLock::new(LinkGraph::new())),
             file_cache: Arc::new(RwLock::new(HashMap::new())),
+            note_schemas: Arc::new(RwLock::new(None)),
+            incremental_parses: Arc::new(RwLock::new(IncrementalParses::default())),
+            link_index: Arc::new(RwLock::new(LinkIndex::default())),
         })
     }
 
@@ -47,11 +153,20 @@ impl VaultManager {
         &self.vault_path
     }
 
+    /// Get server configuration
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
     /// Initialize vault by scanning all files
     #[instrument(skip(self), name = "vault_initialize")]
     pub async fn initialize(&self) -> Result<()> {
         log::info!("Starting vault initialization for: {:?}", self.vault_path);
 
+        if let Err(e) = self.reload_note_schemas().await {
+            log::warn!("Note schemas not loaded: {}", e);
+        }
+
         let mut cache = self.file_cache.write().await;
         let mut graph = self.link_graph.write().await;
 
@@ -59,6 +174,7 @@ impl VaultManager {
         let md_files = self.scan_files()?;
         log::info!("Found {} markdown files", md_files.len());
 
+        let mut parsed = Vec::with_capacity(md_files.len());
         for file_path in md_files {
             log::debug!("Processing file: {:?}", file_path);
             if let Ok(content) = tokio::fs::read_to_string(&file_path).await {
@@ -76,14 +192,12 @@ impl VaultManager {
                         cache.insert(
                             file_path.clone(),
                             CacheEntry {
-                                file: vault_file.clone(),
+                                content: content.clone(),
                                 cached_at: now,
                             },
                         );
 
-                        // Add to graph
-                        let _ = graph.add_file(&vault_file);
-                        let _ = graph.update_links(&vault_file);
+                        parsed.push(vault_file);
                     }
                     Err(e) => {
                         log::warn!("Failed to parse {}: {}", file_path.display(), e);
@@ -94,6 +208,19 @@ impl VaultManager {
             }
         }
 
+        // Add every node before linking, so links to files scanned later resolve
+        for vault_file in &parsed {
+            let _ = graph.add_file(vault_file);
+        }
+        for vault_file in &parsed {
+            let _ = graph.update_links(vault_file);
+        }
+        let mut index = self.link_index.write().await;
+        *index = LinkIndex::default();
+        for vault_file in &parsed {
+            index.insert(vault_file);
+        }
+
         log::info!(
             "Vault initialization complete. Graph now has {} files, {} links",
             graph.node_count(),
@@ -113,7 +240,7 @@ impl VaultManager {
         if let Some(entry) = cache.get(&vault_path)
             && !self.is_cache_expired(entry.cached_at)
         {
-            return Ok(entry.file.content.clone());
+            return Ok(entry.content.clone());
         }
         drop(cache);
 
@@ -125,11 +252,222 @@ impl VaultManager {
         Ok(content)
     }
 
+    /// Read a note with its note, `#Heading` and `#^block` embeds expanded
+    /// inline, recursively, between markers naming their origin.
+    ///
+    /// Attachments stay as references with their resolved path; cyclic,
+    /// unresolved, too deep or over-budget embeds stay as written. See
+    /// [`crate::transclusion`] for the markers.
+    #[instrument(skip(self, options), fields(file = ?path), name = "vault_read_expanded")]
+    pub async fn read_expanded(
+        &self,
+        path: &Path,
+        options: &TransclusionOptions,
+    ) -> Result<ExpandedNote> {
+        let full_path = self.resolve_path(path)?;
+        let content = self.read_file(&full_path).await?;
+
+        let mut state = Expansion {
+            options: *options,
+            used: content.len(),
+            embeds: Vec::new(),
+            stack: vec![(full_path.clone(), String::new())],
+            all_files: None,
+        };
+        let content = self
+            .expand_embeds(&full_path, &content, 1, &mut state)
+            .await?;
+
+        Ok(ExpandedNote {
+            path: self.relative_path(&full_path),
+            content,
+            embeds: state.embeds,
+        })
+    }
+
+    /// Expand the embeds in `text`, part of `file`, at nesting `depth`
+    fn expand_embeds<'a>(
+        &'a self,
+        file: &'a Path,
+        text: &'a str,
+        depth: usize,
+        state: &'a mut Expansion,
+    ) -> Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>> {
+        Box::pin(async move {
+            let parsed = self
+                .parser
+                .parse_file(file, text)
+                .map_err(|e| Error::parse_error(e.to_string()))?;
+            let mut embeds: Vec<&Link> = parsed
+                .links
+                .iter()
+                .filter(|link| link.type_ == LinkType::Embed)
+                .collect();
+            embeds.sort_by_key(|link| link.position.offset);
+            let targets = self.resolve_embeds(&embeds, state).await?;
+
+            let mut output = String::with_capacity(text.len());
+            let mut cursor = 0;
+            for (link, resolved) in embeds.into_iter().zip(targets) {
+                let start = link.position.offset;
+                let end = start + link.position.length;
+                if start < cursor || end > text.len() {
+                    continue;
+                }
+                output.push_str(&text[cursor..start]);
+                cursor = end;
+                let raw = &text[start..end];
+
+                let fragment = link
+                    .target
+                    .split_once('#')
+                    .map(|(_, fragment)| fragment.trim().to_string())
+                    .unwrap_or_default();
+                let mut record = EmbedExpansion {
+                    target: link.target.clone(),
+                    source: self.relative_path(file),
+                    resolved: resolved.as_deref().map(|p| self.relative_path(p)),
+                    depth,
+                    status: EmbedStatus::Expanded,
+                };
+
+                let piece = match &resolved {
+                    None => Err(EmbedStatus::Unresolved),
+                    Some(target) if !is_transcludable(target) => Err(EmbedStatus::Attachment),
+                    // A whole note contains every section; a section only itself
+                    Some(target)
+                        if state.stack.iter().any(|(p, f)| {
+                            p == target && (fragment.is_empty() || *f == fragment)
+                        }) =>
+                    {
+                        Err(EmbedStatus::Cycle)
+                    }
+                    Some(_) if depth > state.options.max_depth => Err(EmbedStatus::DepthLimit),
+                    Some(target) => self
+                        .embedded_piece(target, &fragment)
+                        .await
+                        .ok_or(EmbedStatus::MissingFragment)
+                        .and_then(|piece| {
+                            // The piece and its markers replace the embed
+                            let path = self.relative_path(target);
+                            let expanded =
+                                expanded_marker(&link.target, &path.to_string_lossy(), &piece);
+                            let used = (state.used + expanded.len()).saturating_sub(raw.len());
+                            if used > state.options.max_bytes {
+                                Err(EmbedStatus::BudgetExceeded)
+                            } else {
+                                Ok((piece, used))
+                            }
+                        }),
+                };
+
+                match (piece, resolved) {
+                    (Ok((piece, used)), Some(target)) => {
+                        state.used = used;
+                        state.embeds.push(record);
+                        state.stack.push((target.clone(), fragment));
+                        let inner = self.expand_embeds(&target, &piece, depth + 1, state).await;
+                        state.stack.pop();
+                        let path = self.relative_path(&target);
+                        output.push_str(&expanded_marker(
+                            &link.target,
+                            &path.to_string_lossy(),
+                            &inner?,
+                        ));
+                    }
+                    (piece, _) => {
+                        record.status = piece.err().unwrap_or(EmbedStatus::Unresolved);
+                        let path = record
+                            .resolved
+                            .as_ref()
+                            .map(|p| p.to_string_lossy().to_string());
+                        let marker = reference_marker(record.status, path.as_deref());
+                        state.used += marker.len();
+                        output.push_str(raw);
+                        output.push_str(&marker);
+                        state.embeds.push(record);
+                    }
+                }
+            }
+            output.push_str(&text[cursor..]);
+            Ok(output)
+        })
+    }
+
+    /// Resolve embeds, holding the link graph only for the lookup
+    async fn resolve_embeds(
+        &self,
+        embeds: &[&Link],
+        state: &mut Expansion,
+    ) -> Result<Vec<Option<PathBuf>>> {
+        let graph = self.link_graph.read().await;
+        let notes = LinkResolver::new(&graph, &self.vault_path, Vec::new());
+        let mut resolved: Vec<Option<PathBuf>> =
+            embeds.iter().map(|link| notes.resolve(link)).collect();
+        if resolved.iter().all(Option::is_some) {
+            return Ok(resolved);
+        }
+
+        // Attachments are not in the graph
+        if state.all_files.is_none() {
+            state.all_files = Some(self.walk_vault(|_| true)?);
+        }
+        let files = state.all_files.iter().flatten().cloned();
+        let all = LinkResolver::new(&graph, &self.vault_path, files);
+        for (target, link) in resolved.iter_mut().zip(embeds) {
+            if target.is_none() {
+                *target = all.resolve(link);
+            }
+        }
+        Ok(resolved)
+    }
+
+    /// Part of a note an embed shows: the body, a heading's section or a
+    /// block; `None` if the note cannot be read or lacks the fragment
+    async fn embedded_piece(&self, target: &Path, fragment: &str) -> Option<String> {
+        let content = self.read_file(target).await.ok()?;
+        if fragment.is_empty() {
+            let body = split_frontmatter(&content).map_or(content.as_str(), |(_, body)| body);
+            return Some(body.to_string());
+        }
+
+        let note = self.parser.parse_file(target, &content).ok()?;
+        match fragment.strip_prefix('^') {
+            Some(id) => note
+                .blocks
+                .iter()
+                .find(|b| {
+                    b.block_id
+                        .as_deref()
+                        .is_some_and(|b| b.eq_ignore_ascii_case(id))
+                })
+                .map(|block| block_text(&content, block)),
+            None => NoteAnchors::from_file(&note)
+                .heading(fragment)
+                .map(|heading| heading_section(&content, &note.headings, heading).to_string()),
+        }
+    }
+
+    fn relative_path(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.vault_path)
+            .unwrap_or(path)
+            .to_path_buf()
+    }
+
     /// Write file to disk atomically
     #[instrument(skip(self, content), fields(file = ?path, size = content.len()), name = "vault_write_file")]
     pub async fn write_file(&self, path: &Path, content: &str) -> Result<()> {
         let vault_path = self.resolve_path(path)?;
 
+        // Refuse invalid schema files and, in reject mode, notes violating them
+        let is_schema_file = vault_path == self.vault_path.join(NOTE_SCHEMA_FILE);
+        let new_schemas = if is_schema_file {
+            Some(NoteSchemas::from_yaml(content)?)
+        } else {
+            self.enforce_note_schema(&vault_path, content).await?;
+            None
+        };
+
         // Ensure parent directory exists
         if let Some(parent) = vault_path.parent() {
             tokio::fs::create_dir_all(parent).await.map_err(Error::io)?;
@@ -151,8 +489,13 @@ impl VaultManager {
         cache.remove(&vault_path);
         drop(cache); // Release write lock before parsing
 
+        if let Some(schemas) = new_schemas {
+            *self.note_schemas.write().await = Some(schemas);
+            return Ok(());
+        }
+
         // Parse file and update graph
-        match self.parser.parse_file(&vault_path, content) {
+        match self.parse_written(&vault_path, content).await {
             Ok(vault_file) => {
                 log::debug!(
                     "Parsed {}: {} links extracted",
@@ -164,6 +507,7 @@ impl VaultManager {
                 let mut graph = self.link_graph.write().await;
                 let _ = graph.add_file(&vault_file);
                 let _ = graph.update_links(&vault_file);
+                self.link_index.write().await.insert(&vault_file);
                 log::debug!("Graph updated for {}", vault_path.display());
             }
             Err(e) => {
@@ -179,6 +523,51 @@ impl VaultManager {
         Ok(())
     }
 
+    /// Parse a file just written. Large notes are reparsed incrementally
+    /// from their previous write: only the blocks that changed are parsed.
+    async fn parse_written(&self, vault_path: &Path, content: &str) -> Result<VaultFile> {
+        let is_note = vault_path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+        if !is_note || content.len() < INCREMENTAL_PARSE_MIN_BYTES {
+            // Only a note that shrank below the threshold needs the write lock
+            if self.incremental_parses.read().await.contains(vault_path) {
+                self.incremental_parses.write().await.remove(vault_path);
+            }
+            return self.parser.parse_file(vault_path, content);
+        }
+
+        let mut parses = self.incremental_parses.write().await;
+        let parse = match parses.touch(vault_path) {
+            Some(parse) => {
+                let edit = TextEdit::between(parse.content(), content);
+                let incremental = parse.apply_edit(&edit)?;
+                log::debug!(
+                    "Reparsed {} {}",
+                    vault_path.display(),
+                    if incremental {
+                        "incrementally"
+                    } else {
+                        "in full"
+                    }
+                );
+                parse
+            }
+            None => parses.insert(IncrementalParse::new(vault_path, content)),
+        };
+        Ok(parse.vault_file())
+    }
+
+    /// Drop what is cached for a file deleted or moved away outside
+    /// [`Self::write_file`]
+    pub async fn forget_file(&self, path: &Path) -> Result<()> {
+        let vault_path = self.resolve_path(path)?;
+        self.file_cache.write().await.remove(&vault_path);
+        self.incremental_parses.write().await.remove(&vault_path);
+        self.link_index.write().await.remove(&vault_path);
+        Ok(())
+    }
+
     /// Edit file using SEARCH/REPLACE blocks (LLM-optimized)
     ///
     /// This method applies edits using the aider-inspired format that reduces
@@ -264,6 +653,28 @@ impl VaultManager {
         Ok(forward_links.into_iter().map(|(p, _)| p).collect())
     }
 
+    /// Get backlinks as typed edges, keeping only the given kinds (empty = all)
+    pub async fn get_backlink_edges(
+        &self,
+        path: &Path,
+        kinds: &[EdgeKind],
+    ) -> Result<Vec<(PathBuf, Vec<LinkEdge>)>> {
+        let vault_path = self.resolve_path(path)?;
+        let graph = self.link_graph.read().await;
+        graph.backlinks_by_kind(&vault_path, kinds)
+    }
+
+    /// Get forward links as typed edges, keeping only the given kinds (empty = all)
+    pub async fn get_forward_link_edges(
+        &self,
+        path: &Path,
+        kinds: &[EdgeKind],
+    ) -> Result<Vec<(PathBuf, Vec<LinkEdge>)>> {
+        let vault_path = self.resolve_path(path)?;
+        let graph = self.link_graph.read().await;
+        graph.forward_links_by_kind(&vault_path, kinds)
+    }
+
     /// Get orphaned notes
     pub async fn get_orphaned_notes(&self) -> Result<Vec<PathBuf>> {
         let graph = self.link_graph.read().await;
@@ -277,6 +688,59 @@ impl VaultManager {
         graph.related_notes(&vault_path, max_hops)
     }
 
+    /// Get related notes, following only the given edge kinds (empty = all)
+    pub async fn get_related_notes_by_kind(
+        &self,
+        path: &Path,
+        max_hops: usize,
+        kinds: &[EdgeKind],
+    ) -> Result<Vec<PathBuf>> {
+        let vault_path = self.resolve_path(path)?;
+        let graph = self.link_graph.read().await;
+        graph.related_notes_by_kind(&vault_path, max_hops, kinds)
+    }
+
+    /// Get up to `max_paths` shortest paths between two notes
+    pub async fn get_shortest_paths(
+        &self,
+        from: &Path,
+        to: &Path,
+        direction: PathDirection,
+        kinds: &[EdgeKind],
+        max_paths: usize,
+    ) -> Result<Vec<NotePath>> {
+        let from = self.resolve_path(from)?;
+        let to = self.resolve_path(to)?;
+        let graph = self.link_graph.read().await;
+        graph.shortest_paths(&from, &to, direction, kinds, max_paths)
+    }
+
+    /// Get the notes within `max_hops` of a note, with distances
+    pub async fn get_ego_network(
+        &self,
+        path: &Path,
+        max_hops: usize,
+        direction: PathDirection,
+        kinds: &[EdgeKind],
+    ) -> Result<EgoNetwork> {
+        let vault_path = self.resolve_path(path)?;
+        let graph = self.link_graph.read().await;
+        graph.ego_network(&vault_path, max_hops, direction, kinds)
+    }
+
+    /// Get notes linked with both of two notes
+    pub async fn get_common_neighbors(
+        &self,
+        first: &Path,
+        second: &Path,
+        kinds: &[EdgeKind],
+    ) -> Result<Vec<CommonNeighbor>> {
+        let first = self.resolve_path(first)?;
+        let second = self.resolve_path(second)?;
+        let graph = self.link_graph.read().await;
+        graph.common_neighbors(&first, &second, kinds)
+    }
+
     /// Get graph statistics
     pub async fn get_stats(&self) -> Result<turbovault_graph::GraphStats> {
         let graph = self.link_graph.read().await;
@@ -344,6 +808,21 @@ impl VaultManager {
 
     /// Scan for markdown files in vault
     fn scan_files(&self) -> Result<Vec<PathBuf>> {
+        self.walk_vault(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|ext| {
+                    self.config
+                        .allowed_extensions
+                        .iter()
+                        .any(|allowed| allowed.eq_ignore_ascii_case(&format!(".{}", ext)))
+                })
+                && path.metadata().map(|m| m.len()).unwrap_or(0) <= self.config.max_file_size
+        })
+    }
+
+    /// Walk the vault, skipping excluded paths, and collect files accepted by `filter`
+    fn walk_vault(&self, filter: impl Fn(&Path) -> bool) -> Result<Vec<PathBuf>> {
         use std::fs;
 
         let mut files = Vec::new();
@@ -366,13 +845,7 @@ impl VaultManager {
 
                 if path.is_dir() {
                     stack.push(path);
-                } else if let Some(ext) = path.extension().and_then(|e| e.to_str())
-                    && self
-                        .config
-                        .allowed_extensions
-                        .contains(&format!(".{}", ext))
-                    && path.metadata().map(|m| m.len()).unwrap_or(0) <= self.config.max_file_size
-                {
+                } else if filter(&path) {
                     files.push(path);
                 }
             }
@@ -395,6 +868,93 @@ impl VaultManager {
         now - cached_at > self.config.cache_ttl as f64
     }
 
+    /// Load note-type schemas from the vault's schema file.
+    ///
+    /// A missing file clears any loaded schemas.
+    pub async fn reload_note_schemas(&self) -> Result<()> {
+        let schema_path = self.vault_path.join(NOTE_SCHEMA_FILE);
+        let schemas = match tokio::fs::read_to_string(&schema_path).await {
+            Ok(yaml) => Some(NoteSchemas::from_yaml(&yaml)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(Error::io(e)),
+        };
+        *self.note_schemas.write().await = schemas;
+        Ok(())
+    }
+
+    /// Currently loaded note-type schemas
+    pub async fn note_schemas(&self) -> Option<NoteSchemas> {
+        self.note_schemas.read().await.clone()
+    }
+
+    /// Validate note content against the vault's note-type schemas.
+    ///
+    /// Returns an empty report for non-markdown files or when no schemas are loaded.
+    pub async fn validate_note_schema(
+        &self,
+        path: &Path,
+        content: &str,
+    ) -> Result<ValidationReport> {
+        let vault_path = self.resolve_path(path)?;
+        let schemas = self.note_schemas.read().await;
+        let Some(schemas) = schemas.as_ref() else {
+            return Ok(ValidationReport::new());
+        };
+        if !vault_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("md"))
+        {
+            return Ok(ValidationReport::new());
+        }
+
+        let relative = vault_path
+            .strip_prefix(&self.vault_path)
+            .unwrap_or(&vault_path)
+            .to_string_lossy();
+        let properties: Option<HashMap<String, serde_json::Value>> =
+            FrontmatterDocument::parse(content)
+                .ok()
+                .filter(|doc| doc.had_frontmatter())
+                .map(|doc| doc.properties().clone().into_iter().collect());
+
+        Ok(schemas.validate(&relative, properties.as_ref()))
+    }
+
+    /// Fail in reject mode if the content violates its note schema; log in warn mode
+    async fn enforce_note_schema(&self, vault_path: &Path, content: &str) -> Result<()> {
+        let report = self.validate_note_schema(vault_path, content).await?;
+        if report.passed {
+            return Ok(());
+        }
+
+        let messages: Vec<&str> = report
+            .issues
+            .iter()
+            .filter(|i| i.severity.is_failure())
+            .map(|i| i.message.as_str())
+            .collect();
+        let relative = vault_path
+            .strip_prefix(&self.vault_path)
+            .unwrap_or(vault_path)
+            .display();
+
+        let mode = self.note_schemas.read().await.as_ref().map(|s| s.mode);
+        if mode == Some(SchemaMode::Reject) {
+            return Err(Error::validation_error(format!(
+                "{} violates its note schema: {}",
+                relative,
+                messages.join("; ")
+            )));
+        }
+        log::warn!(
+            "{} violates its note schema: {}",
+            relative,
+            messages.join("; ")
+        );
+        Ok(())
+    }
+
     /// Get a reference to the link graph (read-only access)
     pub fn link_graph(&self) -> Arc<RwLock<LinkGraph>> {
         Arc::clone(&self.link_graph)
@@ -417,6 +977,58 @@ impl VaultManager {
     pub async fn scan_vault(&self) -> Result<Vec<PathBuf>> {
         self.scan_files()
     }
+
+    /// Scan the vault for files with `extension` (e.g. `"base"`), whether or
+    /// not it is an allowed extension.
+    ///
+    /// Use this for files read on demand, such as `.base` views, that must
+    /// not join the link graph or the note counts.
+    #[instrument(skip(self), name = "vault_scan_extension")]
+    pub async fn scan_files_with_extension(&self, extension: &str) -> Result<Vec<PathBuf>> {
+        self.walk_vault(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+                && path.metadata().map(|m| m.len()).unwrap_or(0) <= self.config.max_file_size
+        })
+    }
+
+    /// Resolve every link in the vault's notes and canvases against the files on disk.
+    ///
+    /// Returns each note's links with `resolved_target` and `is_valid`
+    /// populated, keyed by absolute path. Attachments outside the allowed
+    /// extensions still count as link targets, and `#Heading` / `#^block`
+    /// fragments must exist in the target note.
+    #[instrument(skip(self), name = "vault_validate_links")]
+    pub async fn validate_links(&self) -> Result<LinkValidation> {
+        let all_files = self.walk_vault(|_| true)?;
+        let graph = self.link_graph.read().await;
+        let index = self.link_index.read().await;
+        let resolver =
+            LinkResolver::new(&graph, &self.vault_path, all_files).with_anchors(&index.anchors);
+        let links = index
+            .links
+            .iter()
+            .map(|(path, links)| (path.clone(), resolver.validate_links(path, links)))
+            .collect();
+        Ok(LinkValidation {
+            links,
+            anchors: index.anchors.clone(),
+        })
+    }
+}
+
+/// Whether links in this file are validated: notes and canvases
+fn is_link_source(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("canvas"))
+}
+
+/// Whether an embed of this file shows note content rather than a file
+/// (images, PDFs, canvases, Excalidraw drawings)
+fn is_transcludable(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+        && !excalidraw::is_excalidraw(path, None)
 }
 
 #[cfg(test)]
@@ -452,6 +1064,86 @@ mod tests {
         assert_eq!(manager.vault_path(), temp_dir.path());
     }
 
+    #[tokio::test]
+    async fn test_cached_read_keeps_frontmatter() {
+        let temp_dir = TempDir::new().unwrap();
+        let content = "---\ntitle: Cached\n---\n# Body";
+        std::fs::write(temp_dir.path().join("note.md"), content).unwrap();
+
+        let config = create_test_config(temp_dir.path());
+        let manager = VaultManager::new(config).unwrap();
+        manager.initialize().await.unwrap();
+
+        let read_content = manager.read_file(Path::new("note.md")).await.unwrap();
+        assert_eq!(read_content, content);
+    }
+
+    #[tokio::test]
+    async fn test_note_schema_reject_mode() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".turbovault")).unwrap();
+        std::fs::write(
+            temp_dir.path().join(NOTE_SCHEMA_FILE),
+            "mode: reject\ntypes:\n  task:\n    folders: [Tasks]\n    required:\n      due: date\n",
+        )
+        .unwrap();
+
+        let config = create_test_config(temp_dir.path());
+        let manager = VaultManager::new(config).unwrap();
+        manager.initialize().await.unwrap();
+
+        let err = manager
+            .write_file(Path::new("Tasks/a.md"), "---\ndue: soon\n---\nbody")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("'due'"));
+        assert!(!temp_dir.path().join("Tasks/a.md").exists());
+
+        manager
+            .write_file(Path::new("Tasks/a.md"), "---\ndue: 2024-05-01\n---\nbody")
+            .await
+            .unwrap();
+        // Untyped notes are unaffected
+        manager
+            .write_file(Path::new("inbox.md"), "# no schema")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_note_schema_warn_mode_and_reload_on_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(temp_dir.path());
+        let manager = VaultManager::new(config).unwrap();
+
+        let schema_path = Path::new(NOTE_SCHEMA_FILE);
+        assert!(
+            manager
+                .write_file(schema_path, "types: [broken]")
+                .await
+                .is_err()
+        );
+        manager
+            .write_file(
+                schema_path,
+                "types:\n  task:\n    folders: [Tasks]\n    required:\n      due: date\n",
+            )
+            .await
+            .unwrap();
+        assert!(manager.note_schemas().await.is_some());
+
+        let content = "# missing due";
+        manager
+            .write_file(Path::new("Tasks/b.md"), content)
+            .await
+            .unwrap();
+        let report = manager
+            .validate_note_schema(Path::new("Tasks/b.md"), content)
+            .await
+            .unwrap();
+        assert_eq!(report.summary.error_count, 1);
+    }
+
     #[tokio::test]
     async fn test_write_and_read_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -656,6 +1348,56 @@ mod tests {
         assert_eq!(orphans.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_validate_links_finds_broken_targets() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = create_test_config(temp_dir.path());
+        let manager = VaultManager::new(config).unwrap();
+
+        std::fs::create_dir(temp_dir.path().join("assets")).unwrap();
+        std::fs::write(temp_dir.path().join("assets/diagram.png"), [0u8; 4]).unwrap();
+        std::fs::write(temp_dir.path().join("target.md"), "# Target").unwrap();
+        std::fs::write(
+            temp_dir.path().join("source.md"),
+            "# Source\n[[target]] [[missing]]\n![[diagram.png]] ![[gone.png]]\n\
+             [ok](target.md) [bad](nowhere.md) [web](https://example.com)",
+        )
+        .unwrap();
+
+        manager.initialize().await.unwrap();
+
+        let validation = manager.validate_links().await.unwrap();
+        let source = validation
+            .links
+            .get(&temp_dir.path().join("source.md"))
+            .unwrap();
+        let broken: Vec<_> = source
+            .iter()
+            .filter(|l| !l.is_valid)
+            .map(|l| l.target.as_str())
+            .collect();
+        assert_eq!(broken.len(), 3);
+        assert!(broken.contains(&"missing"));
+        assert!(broken.contains(&"gone.png"));
+        assert!(broken.contains(&"nowhere.md"));
+
+        let embed = source.iter().find(|l| l.target == "diagram.png").unwrap();
+        assert_eq!(
+            embed.resolved_target,
+            Some(temp_dir.path().join("assets/diagram.png"))
+        );
+
+        // Validation reads the index kept current by writes, including `.MD` notes
+        manager
+            .write_file(Path::new("Upper.MD"), "[[target#Nope]] [[target#Target]]")
+            .await
+            .unwrap();
+        let validation = manager.validate_links().await.unwrap();
+        let upper = &validation.links[&temp_dir.path().join("Upper.MD")];
+        let valid: Vec<bool> = upper.iter().map(|l| l.is_valid).collect();
+        assert_eq!(valid, [false, true]);
+    }
+
     #[tokio::test]
     async fn test_get_stats() {
         let temp_dir = TempDir::new().unwrap();
@@ -734,4 +1476,196 @@ mod tests {
         let result2 = manager.resolve_path(Path::new("../../../etc/passwd"));
         assert!(result2.is_err(), "Path traversal should be prevented");
     }
+
+    #[tokio::test]
+    async fn test_large_note_write_reparses_incrementally() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("A.md"), "# A").unwrap();
+        std::fs::write(temp_dir.path().join("B.md"), "# B").unwrap();
+        let manager = VaultManager::new(create_test_config(temp_dir.path())).unwrap();
+        manager.initialize().await.unwrap();
+
+        let mut content = String::from("---\ntitle: Big\n---\n# Big\n\n");
+        for i in 0..2000 {
+            content.push_str(&format!("Paragraph {} about [[A]] and #topic.\n\n", i));
+        }
+        assert!(content.len() >= INCREMENTAL_PARSE_MIN_BYTES);
+        let path = Path::new("Big.md");
+        manager.write_file(path, &content).await.unwrap();
+
+        let content = content.replace("Paragraph 1500 about [[A]]", "Paragraph 1500 about [[B]]");
+        manager.write_file(path, &content).await.unwrap();
+
+        let mut links = manager.get_forward_links(path).await.unwrap();
+        links.sort();
+        assert_eq!(
+            links,
+            vec![temp_dir.path().join("A.md"), temp_dir.path().join("B.md")]
+        );
+
+        let vault_path = manager.resolve_path(path).unwrap();
+        let parses = manager.incremental_parses.read().await;
+        let incremental = parses.parses[&vault_path].vault_file();
+        let full = manager.parser.parse_file(&vault_path, &content).unwrap();
+        assert_eq!(
+            serde_json::to_value(&incremental.links).unwrap(),
+            serde_json::to_value(&full.links).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(&incremental.tags).unwrap(),
+            serde_json::to_value(&full.tags).unwrap()
+        );
+        assert_eq!(incremental.content, full.content);
+    }
+
+    #[tokio::test]
+    async fn test_incremental_parses_are_capped_and_forgotten() {
+        let mut parses = IncrementalParses::default();
+        for i in 0..=INCREMENTAL_PARSE_MAX_NOTES {
+            let path = PathBuf::from(format!("/vault/{}.md", i));
+            parses.insert(IncrementalParse::new(&path, "# Note\n"));
+            if i == 0 {
+                // Rewritten notes are kept over older ones
+                continue;
+            }
+            parses.touch(Path::new("/vault/0.md"));
+        }
+        assert_eq!(parses.parses.len(), INCREMENTAL_PARSE_MAX_NOTES);
+        assert!(parses.contains(Path::new("/vault/0.md")));
+        assert!(!parses.contains(Path::new("/vault/1.md")));
+
+        let temp_dir = TempDir::new().unwrap();
+        let manager = VaultManager::new(create_test_config(temp_dir.path())).unwrap();
+        let content = "Big paragraph.\n\n".repeat(INCREMENTAL_PARSE_MIN_BYTES / 16 + 1);
+        let path = Path::new("Big.md");
+        manager.write_file(path, &content).await.unwrap();
+        assert_eq!(manager.incremental_parses.read().await.parses.len(), 1);
+
+        std::fs::remove_file(temp_dir.path().join(path)).unwrap();
+        manager.forget_file(path).await.unwrap();
+        assert!(manager.incremental_parses.read().await.parses.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_expanded() {
+        let temp_dir = TempDir::new().unwrap();
+        let files = [
+            (
+                "Daily.md",
+                "# Daily\n![[Plan#Goals]]\n\nQuote: ![[Plan#^risk]]\n\n![[chart.png]]\n![[Missing]]\n![[Loop]]\n![[#Later]]\n## Later\nSoon\n",
+            ),
+            (
+                "Plan.md",
+                "---\nstatus: draft\n---\n# Plan\n## Goals\nShip v2\n![[Detail]]\n## Risks\nScope creep ^risk\n",
+            ),
+            ("Detail.md", "---\ntags: [x]\n---\nFine print\n"),
+            ("Loop.md", "Loop start\n![[Loop]]\n"),
+            ("chart.png", "png"),
+        ];
+        for (name, content) in files {
+            std::fs::write(temp_dir.path().join(name), content).unwrap();
+        }
+
+        let manager = VaultManager::new(create_test_config(temp_dir.path())).unwrap();
+        manager.initialize().await.unwrap();
+
+        let expanded = manager
+            .read_expanded(Path::new("Daily.md"), &TransclusionOptions::default())
+            .await
+            .unwrap();
+        assert!(expanded.content.contains(
+            "<!-- embed: Plan#Goals (Plan.md) -->\n## Goals\nShip v2\n<!-- embed: Detail (Detail.md) -->\nFine print\n<!-- /embed: Detail -->\n<!-- /embed: Plan#Goals -->"
+        ));
+        assert!(
+            expanded
+                .content
+                .contains("Quote: <!-- embed: Plan#^risk (Plan.md) -->\nScope creep\n")
+        );
+        assert!(
+            expanded
+                .content
+                .contains("![[chart.png]]<!-- attachment: chart.png -->")
+        );
+        assert!(
+            expanded
+                .content
+                .contains("![[Missing]]<!-- embed not expanded: unresolved -->")
+        );
+        assert!(
+            expanded
+                .content
+                .contains("Loop start\n![[Loop]]<!-- embed not expanded: cycle -->")
+        );
+        // A section of the note being read is not a cycle
+        assert!(expanded.content.contains(
+            "<!-- embed: #Later (Daily.md) -->\n## Later\nSoon\n<!-- /embed: #Later -->"
+        ));
+
+        let statuses: Vec<(&str, usize, EmbedStatus)> = expanded
+            .embeds
+            .iter()
+            .map(|e| (e.target.as_str(), e.depth, e.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("Plan#Goals", 1, EmbedStatus::Expanded),
+                ("Detail", 2, EmbedStatus::Expanded),
+                ("Plan#^risk", 1, EmbedStatus::Expanded),
+                ("chart.png", 1, EmbedStatus::Attachment),
+                ("Missing", 1, EmbedStatus::Unresolved),
+                ("Loop", 1, EmbedStatus::Expanded),
+                ("Loop", 2, EmbedStatus::Cycle),
+                ("#Later", 1, EmbedStatus::Expanded),
+            ]
+        );
+
+        let shallow = manager
+            .read_expanded(
+                Path::new("Daily.md"),
+                &TransclusionOptions {
+                    max_depth: 1,
+                    max_bytes: 100_000,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(shallow.embeds[1].status, EmbedStatus::DepthLimit);
+
+        let tight = manager
+            .read_expanded(
+                Path::new("Daily.md"),
+                &TransclusionOptions {
+                    max_depth: 3,
+                    max_bytes: 100,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(
+            tight
+                .embeds
+                .iter()
+                .any(|e| e.status == EmbedStatus::BudgetExceeded)
+        );
+
+        // Markers count towards the budget: an exact budget fits, one byte less does not
+        std::fs::write(temp_dir.path().join("Small.md"), "![[Detail]]\n").unwrap();
+        let small = Path::new("Small.md");
+        let full = manager
+            .read_expanded(small, &TransclusionOptions::default())
+            .await
+            .unwrap();
+        for (max_bytes, status) in [
+            (full.content.len(), EmbedStatus::Expanded),
+            (full.content.len() - 1, EmbedStatus::BudgetExceeded),
+        ] {
+            let options = TransclusionOptions {
+                max_depth: 3,
+                max_bytes,
+            };
+            let expanded = manager.read_expanded(small, &options).await.unwrap();
+            assert_eq!(expanded.embeds[0].status, status);
+        }
+    }
 }
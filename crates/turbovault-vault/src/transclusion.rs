@@ -0,0 +1,245 @@
+//! Transclusion: notes with their `![[embeds]]` expanded inline
+//!
+//! [`VaultManager::read_expanded`](crate::VaultManager::read_expanded)
+//! replaces note, `#Heading` and `#^block` embeds with the content they
+//! show, recursively, between `<!-- embed: ... -->` and
+//! `<!-- /embed: ... -->` markers naming the target and its vault path.
+//! Attachments (images, PDFs, canvases, drawings) stay as references,
+//! followed by an `<!-- attachment: ... -->` marker with the resolved path.
+//! Embeds that are not expanded (unresolved, cyclic, too deep, over the size
+//! budget) are kept as written, followed by an `<!-- embed not expanded: ... -->`
+//! marker. Every embed met is reported in [`ExpandedNote::embeds`].
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use turbovault_core::{Block, Heading};
+
+/// Limits for [`VaultManager::read_expanded`](crate::VaultManager::read_expanded)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransclusionOptions {
+    /// How many levels of embeds inside embeds to expand
+    pub max_depth: usize,
+    /// Size budget of the expanded note, in bytes; embeds that would exceed
+    /// it are left unexpanded
+    pub max_bytes: usize,
+}
+
+impl Default for TransclusionOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_bytes: 100_000,
+        }
+    }
+}
+
+/// A note with its embeds expanded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpandedNote {
+    /// Vault-relative path of the note
+    pub path: PathBuf,
+    pub content: String,
+    /// Every embed met, in document order (nested embeds after their parent)
+    pub embeds: Vec<EmbedExpansion>,
+}
+
+/// What happened to one embed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbedExpansion {
+    /// Target as written, with any `#Heading` or `#^block`
+    pub target: String,
+    /// Vault-relative path of the note containing the embed
+    pub source: PathBuf,
+    /// Vault-relative path the embed resolved to
+    pub resolved: Option<PathBuf>,
+    /// 1 for embeds in the note itself, 2 for embeds inside those, ...
+    pub depth: usize,
+    pub status: EmbedStatus,
+}
+
+/// Outcome of expanding an embed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbedStatus {
+    /// Content inserted inline
+    Expanded,
+    /// Image, PDF or other non-note file, left as a reference
+    Attachment,
+    /// No file matches the target
+    Unresolved,
+    /// The note exists but the heading or block does not
+    MissingFragment,
+    /// The embed is already being expanded further up
+    Cycle,
+    /// Deeper than [`TransclusionOptions::max_depth`]
+    DepthLimit,
+    /// Would exceed [`TransclusionOptions::max_bytes`]
+    BudgetExceeded,
+}
+
+impl EmbedStatus {
+    /// Reason shown in the `embed not expanded` marker
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EmbedStatus::Expanded => "expanded",
+            EmbedStatus::Attachment => "attachment",
+            EmbedStatus::Unresolved => "unresolved",
+            EmbedStatus::MissingFragment => "missing heading or block",
+            EmbedStatus::Cycle => "cycle",
+            EmbedStatus::DepthLimit => "depth limit",
+            EmbedStatus::BudgetExceeded => "size budget",
+        }
+    }
+}
+
+/// Text inserted in place of an expanded embed
+pub(crate) fn expanded_marker(target: &str, path: &str, content: &str) -> String {
+    format!(
+        "<!-- embed: {} ({}) -->\n{}\n<!-- /embed: {} -->",
+        target,
+        path,
+        content.trim_matches('\n'),
+        target
+    )
+}
+
+/// Marker placed after an embed that stays a reference
+pub(crate) fn reference_marker(status: EmbedStatus, path: Option<&str>) -> String {
+    match (status, path) {
+        (EmbedStatus::Attachment, Some(path)) => format!("<!-- attachment: {} -->", path),
+        _ => format!("<!-- embed not expanded: {} -->", status.as_str()),
+    }
+}
+
+/// Start of the line containing `offset`
+fn line_start(content: &str, offset: usize) -> usize {
+    content[..offset].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// A heading's section: the heading line up to the next heading of the
+/// same or a higher level
+pub(crate) fn heading_section<'a>(
+    content: &'a str,
+    headings: &[Heading],
+    heading: &Heading,
+) -> &'a str {
+    let start = line_start(content, heading.position.offset.min(content.len()));
+    let end = headings
+        .iter()
+        .filter(|h| h.position.offset > heading.position.offset && h.level <= heading.level)
+        .map(|h| line_start(content, h.position.offset.min(content.len())))
+        .min()
+        .unwrap_or(content.len());
+    &content[start..end.max(start)]
+}
+
+/// Text of the block a `^id` marks, without the marker: the list item or
+/// paragraph it ends, or for an id on its own line the block above it
+pub(crate) fn block_text(content: &str, block: &Block) -> String {
+    let marker = block.position.offset.min(content.len());
+    let lines: Vec<&str> = content[..line_start(content, marker)].lines().collect();
+    let marker_line = content[line_start(content, marker)..marker].trim_end();
+
+    let mut above: Vec<&str> = match block.type_.as_str() {
+        "list_item" => Vec::new(),
+        "standalone" => {
+            let end = lines
+                .iter()
+                .rposition(|line| !line.trim().is_empty())
+                .map_or(0, |i| i + 1);
+            paragraph_lines(&lines[..end])
+        }
+        _ => paragraph_lines(&lines),
+    };
+    if !marker_line.is_empty() {
+        above.push(marker_line);
+    }
+    above.join("\n")
+}
+
+/// Trailing run of non-blank lines, stopping after a heading
+fn paragraph_lines<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    let start = lines
+        .iter()
+        .rposition(|line| line.trim().is_empty() || line.trim_start().starts_with('#'))
+        .map_or(0, |i| i + 1);
+    lines[start..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use turbovault_core::SourcePosition;
+
+    fn block(content: &str, id: &str, type_: &str) -> Block {
+        let offset = content.find(&format!("^{}", id)).unwrap();
+        Block {
+            content: String::new(),
+            block_id: Some(id.to_string()),
+            position: SourcePosition::new(0, 0, offset, id.len() + 1),
+            type_: type_.to_string(),
+        }
+    }
+
+    fn heading(content: &str, text: &str, level: u8) -> Heading {
+        let marker = format!("{} {}", "#".repeat(level as usize), text);
+        Heading {
+            text: text.to_string(),
+            level,
+            position: SourcePosition::new(0, 0, content.find(&marker).unwrap(), marker.len()),
+            anchor: None,
+        }
+    }
+
+    #[test]
+    fn test_heading_section() {
+        let content = "# Title\nintro\n## Goals\nship it\n### Detail\nmore\n## Risks\nnone\n";
+        let headings = vec![
+            heading(content, "Title", 1),
+            heading(content, "Goals", 2),
+            heading(content, "Detail", 3),
+            heading(content, "Risks", 2),
+        ];
+        assert_eq!(
+            heading_section(content, &headings, &headings[1]),
+            "## Goals\nship it\n### Detail\nmore\n"
+        );
+        assert_eq!(
+            heading_section(content, &headings, &headings[3]),
+            "## Risks\nnone\n"
+        );
+    }
+
+    #[test]
+    fn test_block_text() {
+        let content = "# Notes\nfirst line\nsecond line ^para\n\n- one\n- two ^item\n\n| a |\n| - |\n\n^table\n";
+        assert_eq!(
+            block_text(content, &block(content, "para", "paragraph")),
+            "first line\nsecond line"
+        );
+        assert_eq!(
+            block_text(content, &block(content, "item", "list_item")),
+            "- two"
+        );
+        assert_eq!(
+            block_text(content, &block(content, "table", "standalone")),
+            "| a |\n| - |"
+        );
+    }
+
+    #[test]
+    fn test_markers() {
+        assert_eq!(
+            expanded_marker("Plan#Goals", "Plan.md", "## Goals\nship\n"),
+            "<!-- embed: Plan#Goals (Plan.md) -->\n## Goals\nship\n<!-- /embed: Plan#Goals -->"
+        );
+        assert_eq!(
+            reference_marker(EmbedStatus::Attachment, Some("img/a.png")),
+            "<!-- attachment: img/a.png -->"
+        );
+        assert_eq!(
+            reference_marker(EmbedStatus::Cycle, Some("Plan.md")),
+            "<!-- embed not expanded: cycle -->"
+        );
+    }
+}
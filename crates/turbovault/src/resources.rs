@@ -84,7 +84,7 @@ Create highlighted information boxes with callouts:
 > - **Formatting works too**
 ```
 
-Supported callout types: `note`, `abstract`, `info`, `tip`, `success`, `question`, `warning`, `failure`, `danger`, `bug`, `example`, `quote`. Unknown identifiers render as standard blockquotes.
+Supported callout types: `note`, `abstract`, `info`, `todo`, `tip`, `success`, `question`, `warning`, `failure`, `danger`, `bug`, `example`, `quote`. Aliases: `summary`/`tldr` (abstract), `hint`/`important` (tip), `check`/`done` (success), `help`/`faq` (question), `caution`/`attention` (warning), `fail`/`missing` (failure), `error` (danger), `cite` (quote). Other identifiers (e.g. `[!decision]`) are custom types, styled by CSS snippets and otherwise shown like `note`. Add `+` or `-` after the type to make a callout foldable (expanded or collapsed), and nest callouts with `> > [!type]`.
 
 ### Tables
 
@@ -10,10 +10,14 @@ use turbovault_core::ServerConfig;
 use turbovault_core::error::Error;
 use turbovault_core::prelude::MultiVaultManager;
 use turbovault_tools::{
-    AnalysisTools, BatchOperation, BatchTools, ExportTools, FileTools, GraphTools, MetadataTools,
-    RelationshipTools, SearchEngine, SearchQuery, SearchTools, TemplateEngine, VaultLifecycleTools,
+    AnalysisTools, BaseTools, BatchOperation, BatchTools, BrokenLinkKind, CanvasTools,
+    CentralityMetric, EdgeKind, ExportTools, FileTools, GraphTools, MentionTools, MetadataTools,
+    NewCanvasEdge, NewCanvasNode, PathDirection, PropertyMutation, PropertySchemaTools,
+    QueryFilter, RelationshipTools, SearchEngine, SearchQuery, SearchTools, SubgraphSelection,
+    TableOperation, TableTools, TagTools, TemplateEngine, ValidationTools, VaultLifecycleTools,
+    mention_tools::DEFAULT_MIN_NAME_LENGTH,
 };
-use turbovault_vault::VaultManager;
+use turbovault_vault::{TransclusionOptions, VaultManager};
 
 /// Helper to convert internal Error to McpError
 fn to_mcp_error(e: Error) -> McpError {
@@ -196,6 +200,78 @@ impl ObsidianMcpServer {
         }
         Ok(())
     }
+
+    /// Parse optional edge kind names for graph queries (none = every kind)
+    fn parse_edge_kinds(names: Option<Vec<String>>) -> McpResult<Vec<EdgeKind>> {
+        names
+            .unwrap_or_default()
+            .iter()
+            .map(|name| {
+                EdgeKind::from_name(name).ok_or_else(|| {
+                    let valid: Vec<_> = EdgeKind::ALL.iter().map(|k| k.as_str()).collect();
+                    McpError::invalid_request(format!(
+                        "Unknown edge kind '{}'. Use one of: {}",
+                        name,
+                        valid.join(", ")
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Path queries follow links both ways unless `directed` is set
+    fn path_direction(directed: Option<bool>) -> PathDirection {
+        if directed.unwrap_or(false) {
+            PathDirection::Directed
+        } else {
+            PathDirection::Undirected
+        }
+    }
+
+    /// Shared response for get_backlinks and get_forward_links
+    fn link_edges_response(
+        vault_name: String,
+        operation: &str,
+        edges: Vec<turbovault_tools::LinkEdgeInfo>,
+        kinds: &[EdgeKind],
+    ) -> StandardResponse<serde_json::Value> {
+        let mut paths: Vec<&str> = Vec::new();
+        for edge in &edges {
+            if !paths.contains(&edge.path.as_str()) {
+                paths.push(&edge.path);
+            }
+        }
+
+        let mut response = StandardResponse::new(vault_name, operation, serde_json::json!(paths))
+            .with_count(paths.len())
+            .with_meta("edges", serde_json::json!(edges));
+        if !kinds.is_empty() {
+            response = response.with_meta("edge_kinds", serde_json::json!(kinds));
+        }
+        response
+    }
+
+    /// Shared response for rename_tag, merge_tags and delete_tag
+    fn tag_change_response(
+        vault_name: String,
+        operation: &str,
+        report: turbovault_tools::TagChangeReport,
+    ) -> McpResult<serde_json::Value> {
+        let count = report.notes_changed;
+        let dry_run = report.dry_run;
+        let result_data =
+            serde_json::to_value(&report).map_err(|e| McpError::internal(e.to_string()))?;
+
+        let response = StandardResponse::new(vault_name, operation, result_data)
+            .with_count(count)
+            .with_meta("dry_run", serde_json::json!(dry_run));
+
+        if dry_run {
+            response.with_next_step(operation).to_json()
+        } else {
+            response.with_next_step("list_tags").to_json()
+        }
+    }
 }
 
 impl Default for ObsidianMcpServer {
@@ -343,12 +419,23 @@ impl ObsidianMcpServer {
             "tools": {
                 "file_operations": ["read_note", "write_note", "delete_note", "move_note"],
                 "search": ["search", "advanced_search", "recommend_related", "find_notes_from_template"],
-                "link_analysis": ["get_backlinks", "get_forward_links", "get_related_notes", "get_hub_notes", "get_dead_end_notes"],
+                "link_analysis": ["get_backlinks", "get_forward_links", "get_related_notes", "get_hub_notes", "get_dead_end_notes", "find_shortest_path", "get_ego_network", "get_common_neighbors"],
                 "analysis": ["quick_health_check", "full_health_analysis", "get_broken_links", "detect_cycles"],
                 "vault_management": ["add_vault", "list_vaults", "set_active_vault", "get_active_vault"],
                 "templates": ["list_templates", "get_template", "create_from_template", "find_notes_from_template"],
-                "metadata": ["get_metadata_value", "query_metadata"],
+                "metadata": [
+                    "get_metadata_value",
+                    "query_metadata",
+                    "bulk_update_properties",
+                    "analyze_property_schema",
+                    "list_callouts"
+                ],
+                "tables": ["list_tables", "read_table", "update_table"],
+                "canvas": ["read_canvas", "create_canvas", "add_canvas_node", "connect_canvas_nodes"],
+                "bases": ["list_bases", "query_base"],
+                "tags": ["list_tags", "rename_tag", "merge_tags", "delete_tag"],
                 "batch": ["batch_execute"],
+                "validation": ["validate_vault"],
             }
         });
 
@@ -384,25 +471,61 @@ impl ObsidianMcpServer {
 
     /// Read the contents of a note
     #[tool(
-        description = "Read complete markdown content of a note from active vault",
-        usage = "Use before editing, analyzing, or displaying notes. Supports all Obsidian Flavored Markdown syntax including wikilinks [[note]], embeds ![[image.png]], and block references ^block-id",
-        performance = "Fast (<10ms typical). Returns path, content, and content hash for conflict detection",
+        description = "Read complete markdown content of a note from active vault, optionally with its note, heading and block embeds expanded inline",
+        usage = "Use before editing, analyzing, or displaying notes. Supports all Obsidian Flavored Markdown syntax including wikilinks [[note]], embeds ![[image.png]], and block references ^block-id. With expand_embeds, ![[Note]], ![[Note#Section]] and ![[Note#^block]] are replaced (recursively, up to max_depth levels, default 3, and max_bytes in total, default 100000) by their content between <!-- embed: ... --> markers; image/PDF embeds stay as references followed by their resolved path, and cyclic or unresolved embeds are kept as written. Expanded content is for reading; edit the raw note (the hash is always of the raw file)",
+        performance = "Fast (<10ms typical). Returns path, content, and content hash for conflict detection; expanding embeds reads each embedded note",
         related = ["write_note", "edit_note", "get_backlinks"],
-        examples = ["daily/2024-01-15.md", "projects/website-redesign.md"]
+        examples = [
+            "daily/2024-01-15.md",
+            "projects/website-redesign.md",
+            "path: projects/website-redesign.md, expand_embeds: true, max_depth: 2"
+        ]
     )]
-    async fn read_note(&self, path: String) -> McpResult<serde_json::Value> {
+    async fn read_note(
+        &self,
+        path: String,
+        expand_embeds: Option<bool>,
+        max_depth: Option<usize>,
+        max_bytes: Option<usize>,
+    ) -> McpResult<serde_json::Value> {
         let (vault_name, manager) = self.get_vault_pair().await?;
-        let tools = FileTools::new(manager);
+        let tools = FileTools::new(manager.clone());
         let content = tools.read_file(&path).await.map_err(to_mcp_error)?;
 
         // Compute hash for use with edit_file
         let hash = turbovault_vault::compute_hash(&content);
 
+        if !expand_embeds.unwrap_or(false) {
+            return StandardResponse::new(
+                vault_name,
+                "read_note",
+                serde_json::json!({"path": path, "content": content, "hash": hash}),
+            )
+            .with_read_next_steps()
+            .to_json();
+        }
+
+        let defaults = TransclusionOptions::default();
+        let options = TransclusionOptions {
+            max_depth: max_depth.unwrap_or(defaults.max_depth),
+            max_bytes: max_bytes.unwrap_or(defaults.max_bytes),
+        };
+        let expanded = manager
+            .read_expanded(Path::new(&path), &options)
+            .await
+            .map_err(to_mcp_error)?;
+
         StandardResponse::new(
             vault_name,
             "read_note",
-            serde_json::json!({"path": path, "content": content, "hash": hash}),
+            serde_json::json!({
+                "path": path,
+                "content": expanded.content,
+                "hash": hash,
+                "embeds": expanded.embeds,
+            }),
         )
+        .with_count(expanded.embeds.len())
         .with_read_next_steps()
         .to_json()
     }
@@ -410,7 +533,7 @@ impl ObsidianMcpServer {
     /// Write or update a note
     #[tool(
         description = "Write or overwrite a note in active vault (creates if missing, replaces if exists)",
-        usage = "Use for creating new notes or completely replacing existing ones. Accepts full markdown content with Obsidian Flavored Markdown syntax (wikilinks, callouts, block refs). Automatically creates parent directories and triggers link graph rebuild. For targeted edits, use edit_note instead",
+        usage = "Use for creating new notes or completely replacing existing ones. Accepts full markdown content with Obsidian Flavored Markdown syntax (wikilinks, callouts, block refs). Automatically creates parent directories and triggers link graph rebuild. Notes are checked against note-type schemas in .turbovault/schemas.yaml: violations are rejected in reject mode and returned as schema_warnings in warn mode. For targeted edits, use edit_note instead",
         performance = "Moderate (<50ms typical). Includes filesystem write and link graph update",
         related = ["read_note", "edit_note", "create_from_template"],
         examples = ["meeting-notes/2024-01-15.md", "references/api-documentation.md"]
@@ -423,19 +546,25 @@ impl ObsidianMcpServer {
             .await
             .map_err(to_mcp_error)?;
 
-        StandardResponse::new(
-            vault_name,
-            "write_note",
-            serde_json::json!({"path": path, "status": "written", "bytes": content.len()}),
-        )
-        .with_write_next_steps()
-        .to_json()
+        let mut data =
+            serde_json::json!({"path": path, "status": "written", "bytes": content.len()});
+        let schema_warnings = tools
+            .schema_issues(&path, &content)
+            .await
+            .map_err(to_mcp_error)?;
+        if !schema_warnings.is_empty() {
+            data["schema_warnings"] = serde_json::json!(schema_warnings);
+        }
+
+        StandardResponse::new(vault_name, "write_note", data)
+            .with_write_next_steps()
+            .to_json()
     }
 
     /// Edit note using SEARCH/REPLACE blocks
     #[tool(
         description = "Apply targeted edits using SEARCH/REPLACE blocks (safer than full overwrite)",
-        usage = "Use for precise modifications without reading/writing entire file. Requires exact match of search text. Supports optional content hash for conflict detection and dry_run mode for preview. Returns applied changes, rejected changes, and new hash",
+        usage = "Use for precise modifications without reading/writing entire file. Requires exact match of search text. Supports optional content hash for conflict detection and dry_run mode for preview. Returns applied changes, rejected changes, and new hash. Edited notes are checked against note-type schemas like write_note",
         performance = "Fast (<30ms typical). More efficient than read+write cycle for small edits",
         related = ["read_note", "write_note"],
         examples = []
@@ -455,13 +584,22 @@ impl ObsidianMcpServer {
             .await
             .map_err(to_mcp_error)?;
 
-        StandardResponse::new(
-            vault_name,
-            "edit_note",
-            serde_json::to_value(&result).map_err(|e| McpError::internal(e.to_string()))?,
-        )
-        .with_next_steps(&["read_note", "write_note"])
-        .to_json()
+        let mut data =
+            serde_json::to_value(&result).map_err(|e| McpError::internal(e.to_string()))?;
+        if !dry_run {
+            let content = tools.read_file(&path).await.map_err(to_mcp_error)?;
+            let schema_warnings = tools
+                .schema_issues(&path, &content)
+                .await
+                .map_err(to_mcp_error)?;
+            if !schema_warnings.is_empty() {
+                data["schema_warnings"] = serde_json::json!(schema_warnings);
+            }
+        }
+
+        StandardResponse::new(vault_name, "edit_note", data)
+            .with_next_steps(&["read_note", "write_note"])
+            .to_json()
     }
 
     /// Delete a note
@@ -512,23 +650,29 @@ impl ObsidianMcpServer {
 
     /// Find all notes that link to this note
     #[tool(
-        description = "Find all notes that link TO this note (incoming links)",
-        usage = "Use to understand note importance in knowledge graph, discover related content, and analyze impact before deletion. Essential for bidirectional link analysis.",
+        description = "Find all notes that link TO this note (incoming links). Links include wikilinks, embeds, relative markdown links, heading/block references and frontmatter properties holding [[Note]]; meta.edges lists each link with its kind (and property name for property links)",
+        usage = "Use to understand note importance in knowledge graph, discover related content, and analyze impact before deletion. Essential for bidirectional link analysis. Pass edge_kinds (wiki_link, embed, markdown_link, heading_ref, block_ref, property) to count only some kinds of links.",
         performance = "Fast retrieval from pre-built link graph (<50ms typical)",
         related = ["get_forward_links", "get_related_notes", "get_hub_notes"],
-        examples = []
+        examples = ["path: Projects/Roadmap.md", "path: People/Ada.md, edge_kinds: [property]"]
     )]
-    async fn get_backlinks(&self, path: String) -> McpResult<serde_json::Value> {
+    async fn get_backlinks(
+        &self,
+        path: String,
+        edge_kinds: Option<Vec<String>>,
+    ) -> McpResult<serde_json::Value> {
+        let kinds = Self::parse_edge_kinds(edge_kinds)?;
         let (vault_name, manager) = self.get_vault_pair().await?;
         let tools = SearchTools::new(manager);
-        let backlinks = tools.find_backlinks(&path).await.map_err(to_mcp_error)?;
+        let edges = tools
+            .find_backlink_edges(&path, &kinds)
+            .await
+            .map_err(to_mcp_error)?;
 
-        let count = backlinks.len();
-        let response =
-            StandardResponse::new(vault_name, "get_backlinks", serde_json::json!(backlinks))
-                .with_count(count)
-                .with_next_step("get_forward_links")
-                .with_next_step("get_related_notes");
+        let count = edges.len();
+        let response = Self::link_edges_response(vault_name, "get_backlinks", edges, &kinds)
+            .with_next_step("get_forward_links")
+            .with_next_step("get_related_notes");
 
         if count == 0 {
             let response = response.with_warning("Note has no incoming links".to_string());
@@ -541,56 +685,199 @@ impl ObsidianMcpServer {
 
     /// Find all notes that this note links to
     #[tool(
-        description = "Find all notes that this note links TO (outgoing links)",
-        usage = "Use to understand note dependencies, validate link integrity, and explore connection patterns. Pair with get_backlinks for bidirectional link analysis.",
+        description = "Find all notes that this note links TO (outgoing links). Links include wikilinks, embeds, relative markdown links, heading/block references and frontmatter properties holding [[Note]]; meta.edges lists each link with its kind (and property name for property links)",
+        usage = "Use to understand note dependencies, validate link integrity, and explore connection patterns. Pair with get_backlinks for bidirectional link analysis. Pass edge_kinds (wiki_link, embed, markdown_link, heading_ref, block_ref, property) to follow only some kinds of links.",
         performance = "Fast retrieval from pre-built link graph (<50ms typical)",
         related = ["get_backlinks", "get_related_notes", "get_broken_links"],
-        examples = []
+        examples = ["path: Projects/Roadmap.md", "path: Daily/2024-05-01.md, edge_kinds: [wiki_link, markdown_link]"]
     )]
-    async fn get_forward_links(&self, path: String) -> McpResult<serde_json::Value> {
+    async fn get_forward_links(
+        &self,
+        path: String,
+        edge_kinds: Option<Vec<String>>,
+    ) -> McpResult<serde_json::Value> {
+        let kinds = Self::parse_edge_kinds(edge_kinds)?;
         let (vault_name, manager) = self.get_vault_pair().await?;
         let tools = SearchTools::new(manager);
-        let links = tools
-            .find_forward_links(&path)
+        let edges = tools
+            .find_forward_link_edges(&path, &kinds)
             .await
             .map_err(to_mcp_error)?;
 
-        let count = links.len();
-        let response =
-            StandardResponse::new(vault_name, "get_forward_links", serde_json::json!(links))
-                .with_count(count)
-                .with_next_step("get_backlinks")
-                .with_next_step("get_related_notes");
+        let response = Self::link_edges_response(vault_name, "get_forward_links", edges, &kinds)
+            .with_next_step("get_backlinks")
+            .with_next_step("get_related_notes");
 
         response.to_json()
     }
 
     /// Find related notes (by link proximity)
     #[tool(
-        description = "Find notes connected within N hops in the link graph (default 2 hops)",
-        usage = "Use to discover non-obvious relationships through graph traversal. Ideal for recommendations, cluster analysis, and exploring knowledge neighborhoods. Configurable max_hops parameter.",
+        description = "Find notes connected within N hops in the link graph (default 2 hops), nearest first",
+        usage = "Use to discover non-obvious relationships through graph traversal. Ideal for recommendations, cluster analysis, and exploring knowledge neighborhoods. Configurable max_hops parameter; edge_kinds (wiki_link, embed, markdown_link, heading_ref, block_ref, property) restricts which links are followed.",
         performance = "Graph traversal speed varies by depth: 2 hops <100ms typical, 3+ hops may take longer on large vaults",
-        related = ["recommend_related", "get_hub_notes", "suggest_links"],
+        related = ["get_ego_network", "recommend_related", "get_hub_notes", "suggest_links"],
         examples = []
     )]
     async fn get_related_notes(
         &self,
         path: String,
         max_hops: Option<usize>,
+        edge_kinds: Option<Vec<String>>,
     ) -> McpResult<serde_json::Value> {
+        let kinds = Self::parse_edge_kinds(edge_kinds)?;
         let (vault_name, manager) = self.get_vault_pair().await?;
         let tools = SearchTools::new(manager);
         let max_hops = max_hops.unwrap_or(2);
         let related = tools
-            .find_related_notes(&path, max_hops)
+            .find_related_notes_by_kind(&path, max_hops, &kinds)
             .await
             .map_err(to_mcp_error)?;
 
         let count = related.len();
-        let response =
+        let mut response =
             StandardResponse::new(vault_name, "get_related_notes", serde_json::json!(related))
                 .with_count(count)
                 .with_meta("max_hops", serde_json::json!(max_hops));
+        if !kinds.is_empty() {
+            response = response.with_meta("edge_kinds", serde_json::json!(kinds));
+        }
+
+        response.to_json()
+    }
+
+    /// Find shortest paths between two notes
+    #[tool(
+        description = "Show how two notes are connected: the shortest path(s) between them, with the link (kind, target, display text, line) followed at each hop",
+        usage = "Answer 'how is A related to B'. By default links are followed both ways and hops with forward=false walk a link backwards; directed: true only follows links as written. edge_kinds restricts which links count.",
+        performance = "BFS, <50ms typical",
+        related = ["get_ego_network", "get_common_neighbors", "get_related_notes"],
+        examples = [
+            "from: projects/alpha.md, to: people/alice.md",
+            "from: index.md, to: archive/old.md, directed: true",
+            "from: a.md, to: b.md, max_paths: 5, edge_kinds: [\"wiki_link\"]"
+        ]
+    )]
+    async fn find_shortest_path(
+        &self,
+        from: String,
+        to: String,
+        directed: Option<bool>,
+        max_paths: Option<usize>,
+        edge_kinds: Option<Vec<String>>,
+    ) -> McpResult<serde_json::Value> {
+        let kinds = Self::parse_edge_kinds(edge_kinds)?;
+        let direction = Self::path_direction(directed);
+        let max_paths = max_paths.unwrap_or(3).max(1);
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = GraphTools::new(manager);
+        let paths = tools
+            .find_shortest_paths(&from, &to, direction, &kinds, max_paths)
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = paths.len();
+        let distance = paths.first().map(|p| p.length);
+        let mut response = StandardResponse::new(
+            vault_name,
+            "find_shortest_path",
+            serde_json::to_value(&paths).map_err(|e| McpError::internal(e.to_string()))?,
+        )
+        .with_count(count)
+        .with_meta("direction", serde_json::json!(direction))
+        .with_meta("distance", serde_json::json!(distance));
+        if !kinds.is_empty() {
+            response = response.with_meta("edge_kinds", serde_json::json!(kinds));
+        }
+        if paths.is_empty() {
+            response = response.with_warning(match direction {
+                PathDirection::Directed => {
+                    "No directed path; try directed: false to follow links both ways"
+                }
+                PathDirection::Undirected => "The notes are not connected",
+            });
+        }
+
+        response.to_json()
+    }
+
+    /// Get the k-hop neighborhood of a note
+    #[tool(
+        description = "Get the ego network of a note: every note within max_hops (default 2) with its distance, plus the links among them. Unlike get_related_notes, results carry distances and are ordered nearest first.",
+        usage = "Explore a note's neighborhood, build a local graph view, find what is 1 vs 2 hops away. directed: true only follows outgoing links.",
+        performance = "BFS, <100ms for 2 hops on typical vaults",
+        related = ["find_shortest_path", "get_related_notes", "get_common_neighbors"],
+        examples = [
+            "path: projects/alpha.md",
+            "path: index.md, max_hops: 1",
+            "path: daily/2024-01-15.md, max_hops: 3, directed: true"
+        ]
+    )]
+    async fn get_ego_network(
+        &self,
+        path: String,
+        max_hops: Option<usize>,
+        directed: Option<bool>,
+        edge_kinds: Option<Vec<String>>,
+    ) -> McpResult<serde_json::Value> {
+        let kinds = Self::parse_edge_kinds(edge_kinds)?;
+        let direction = Self::path_direction(directed);
+        let max_hops = max_hops.unwrap_or(2);
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = GraphTools::new(manager);
+        let ego = tools
+            .get_ego_network(&path, max_hops, direction, &kinds)
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = ego.nodes.len();
+        let mut response = StandardResponse::new(
+            vault_name,
+            "get_ego_network",
+            serde_json::to_value(&ego).map_err(|e| McpError::internal(e.to_string()))?,
+        )
+        .with_count(count)
+        .with_meta("max_hops", serde_json::json!(max_hops))
+        .with_meta("direction", serde_json::json!(direction));
+        if !kinds.is_empty() {
+            response = response.with_meta("edge_kinds", serde_json::json!(kinds));
+        }
+
+        response.to_json()
+    }
+
+    /// List notes linked with both of two notes
+    #[tool(
+        description = "List notes linked with both of two notes (in either direction), with how each is linked to each note: links_to, linked_from or mutual",
+        usage = "Find shared context between two notes, explain why they are related, or pick a bridge note to link them through",
+        performance = "O(degree), <10ms",
+        related = ["find_shortest_path", "get_ego_network", "get_link_strength"],
+        examples = ["first: projects/alpha.md, second: projects/beta.md"]
+    )]
+    async fn get_common_neighbors(
+        &self,
+        first: String,
+        second: String,
+        edge_kinds: Option<Vec<String>>,
+    ) -> McpResult<serde_json::Value> {
+        let kinds = Self::parse_edge_kinds(edge_kinds)?;
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = GraphTools::new(manager);
+        let common = tools
+            .find_common_neighbors(&first, &second, &kinds)
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = common.len();
+        let mut response = StandardResponse::new(
+            vault_name,
+            "get_common_neighbors",
+            serde_json::to_value(&common).map_err(|e| McpError::internal(e.to_string()))?,
+        )
+        .with_count(count);
+        if !kinds.is_empty() {
+            response = response.with_meta("edge_kinds", serde_json::json!(kinds));
+        }
 
         response.to_json()
     }
@@ -651,7 +938,7 @@ impl ObsidianMcpServer {
         description = "Find disconnected groups of notes (subgraphs with no connections to main graph). Returns clusters as arrays of paths. Isolated clusters may represent separate projects, orphaned content, or incomplete knowledge areas.",
         usage = "Improve vault connectivity, discover orphaned content, validate vault structure",
         performance = "<200ms typical, uses union-find algorithm O(N)",
-        related = ["suggest_links", "get_dead_end_notes", "full_health_analysis"],
+        related = ["get_communities", "suggest_links", "get_dead_end_notes", "full_health_analysis"],
         examples = []
     )]
     async fn get_isolated_clusters(&self) -> McpResult<serde_json::Value> {
@@ -670,6 +957,104 @@ impl ObsidianMcpServer {
         response.to_json()
     }
 
+    /// Detect topic communities
+    #[tool(
+        description = "Detect topic clusters with modularity-based (Louvain) community detection. Returns each community's notes, most central notes (PageRank), dominant tags and folder distribution, plus the partition's modularity. Works on well-linked vaults where get_isolated_clusters finds a single component.",
+        usage = "Discover the vault's actual topical structure, name clusters by their central notes and tags, compare link structure against folder organization",
+        performance = "<500ms for 1000 notes; reads every note once for tags",
+        related = ["find_folder_mismatches", "get_isolated_clusters", "get_centrality_ranking"],
+        examples = [
+            "(no args) - default resolution 1.0, communities of 2+ notes",
+            "resolution: 2.0 - smaller, tighter communities",
+            "resolution: 0.5, min_size: 5 - broad topics only"
+        ]
+    )]
+    async fn get_communities(
+        &self,
+        resolution: Option<f64>,
+        min_size: Option<usize>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        if resolution.is_some_and(|r| r <= 0.0) {
+            return Err(McpError::invalid_request(
+                "resolution must be greater than 0".to_string(),
+            ));
+        }
+
+        let tools = GraphTools::new(manager);
+        let report = tools
+            .get_communities(resolution, min_size)
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = report.communities.len();
+        let modularity = report.modularity;
+        let unclustered = report.unclustered.len();
+        let mut response = StandardResponse::new(
+            vault_name,
+            "get_communities",
+            serde_json::to_value(&report).map_err(|e| McpError::internal(e.to_string()))?,
+        )
+        .with_count(count)
+        .with_meta("modularity", serde_json::json!(modularity))
+        .with_meta("unclustered", serde_json::json!(unclustered))
+        .with_next_step("find_folder_mismatches");
+        if count > 1 && modularity < 0.3 {
+            response = response.with_warning(
+                "Low modularity: the vault has no strong topical structure at this resolution",
+            );
+        }
+
+        response.to_json()
+    }
+
+    /// Find notes filed outside their community's folder
+    #[tool(
+        description = "Flag notes whose folder disagrees with their link community: e.g. a note in cooking/ that only links to and from notes in rust/. Each mismatch names the note's folder, the community's dominant folder and its share.",
+        usage = "Find misfiled notes and candidates for moving, or folders that no longer match how notes are actually linked",
+        performance = "Same as get_communities",
+        related = ["get_communities", "move_note", "get_isolated_clusters"],
+        examples = [
+            "(no args) - communities where 60%+ of notes share a folder",
+            "min_share: 0.8 - only clearly folder-aligned communities"
+        ]
+    )]
+    async fn find_folder_mismatches(
+        &self,
+        min_share: Option<f64>,
+        resolution: Option<f64>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let min_share = min_share.unwrap_or(0.6);
+        if !(0.0..=1.0).contains(&min_share) {
+            return Err(McpError::invalid_request(
+                "min_share must be between 0.0 and 1.0".to_string(),
+            ));
+        }
+        if resolution.is_some_and(|r| r <= 0.0) {
+            return Err(McpError::invalid_request(
+                "resolution must be greater than 0".to_string(),
+            ));
+        }
+
+        let tools = GraphTools::new(manager);
+        let mismatches = tools
+            .find_folder_mismatches(min_share, resolution)
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = mismatches.len();
+        let response = StandardResponse::new(
+            vault_name,
+            "find_folder_mismatches",
+            serde_json::to_value(&mismatches).map_err(|e| McpError::internal(e.to_string()))?,
+        )
+        .with_count(count)
+        .with_meta("min_share", serde_json::json!(min_share));
+
+        response.to_json()
+    }
+
     // ==================== Health & Validation ====================
 
     /// Quick health check (0-100 score)
@@ -723,7 +1108,7 @@ impl ObsidianMcpServer {
         response = response.with_meta("analysis_type", serde_json::json!("comprehensive"));
 
         // Suggest next actions based on health status
-        if health.broken_links_count > 0 {
+        if health.broken_links_count > 0 || health.broken_fragments_count > 0 {
             response = response.with_next_step("get_broken_links");
         }
         if health.orphaned_notes_count > 0 {
@@ -735,8 +1120,8 @@ impl ObsidianMcpServer {
 
     /// Get all broken links in vault
     #[tool(
-        description = "Find all links pointing to non-existent notes with source path, target path, link text, and line number for each broken link",
-        usage = "Use to identify notes to create or links to fix. Broken links harm navigation and indicate incomplete knowledge graph",
+        description = "Find all links pointing to non-existent notes, or to headings (#Heading) and blocks (#^id) that no longer exist, with source path, target, line number, kind (missing_target, missing_heading, missing_block) and suggested fixes for each broken link",
+        usage = "Use to identify notes to create or links to fix. Broken links harm navigation and indicate incomplete knowledge graph. Heading and block breakage usually means a heading was renamed; suggestions list the closest existing headings",
         performance = "Moderate - scans all notes and validates link targets, scales with vault size",
         related = ["suggest_links", "full_health_analysis", "export_broken_links"],
         examples = ["find broken links", "which links are broken?", "show missing note targets"]
@@ -747,9 +1132,22 @@ impl ObsidianMcpServer {
         let broken = tools.get_broken_links().await.map_err(to_mcp_error)?;
 
         let count = broken.len();
+        let count_kind = |kind: BrokenLinkKind| broken.iter().filter(|b| b.kind == kind).count();
         let response =
             StandardResponse::new(vault_name, "get_broken_links", serde_json::json!(broken))
-                .with_count(count);
+                .with_count(count)
+                .with_meta(
+                    "missing_targets",
+                    serde_json::json!(count_kind(BrokenLinkKind::MissingTarget)),
+                )
+                .with_meta(
+                    "missing_headings",
+                    serde_json::json!(count_kind(BrokenLinkKind::MissingHeading)),
+                )
+                .with_meta(
+                    "missing_blocks",
+                    serde_json::json!(count_kind(BrokenLinkKind::MissingBlock)),
+                );
 
         let response = if count > 0 {
             response
@@ -941,21 +1339,25 @@ impl ObsidianMcpServer {
         usage = "Use when search() returns too many results or you need tag-based filtering. Supports compound queries for precise targeting",
         performance = "Fast to Moderate - uses Tantivy search engine with BM25 ranking, additional filtering adds minimal overhead",
         related = ["search", "query_metadata", "find_notes_from_template"],
-        examples = ["search 'project' tags:['work', 'active']", "find notes tagged 'important'", "query with metadata filters"]
+        examples = ["search 'project' tags:['work', 'active']", "find notes tagged 'important'", "search 'roadmap' metadata:'status in (draft, review) AND NOT archived exists'"]
     )]
     async fn advanced_search(
         &self,
         query: String,
         tags: Option<Vec<String>>,
+        metadata: Option<String>,
     ) -> McpResult<serde_json::Value> {
         let (vault_name, manager) = self.get_vault_pair().await?;
         let engine = SearchEngine::new(manager).await.map_err(to_mcp_error)?;
 
-        let search_query = if let Some(tags) = tags {
-            SearchQuery::new(query).with_tags(tags).limit(10)
-        } else {
-            SearchQuery::new(query).limit(10)
-        };
+        let mut search_query = SearchQuery::new(query).limit(10);
+        if let Some(tags) = tags {
+            search_query = search_query.with_tags(tags);
+        }
+        if let Some(pattern) = metadata {
+            let filter = QueryFilter::parse(&pattern).map_err(to_mcp_error)?;
+            search_query = search_query.with_metadata_filter(filter);
+        }
 
         let results = engine
             .advanced_search(search_query)
@@ -1401,6 +1803,43 @@ impl ObsidianMcpServer {
             .map_err(|e| McpError::internal(format!("Failed to serialize batch result: {}", e)))
     }
 
+    // ==================== Validation Operations ====================
+
+    /// Validate all notes, including note-type schemas
+    #[tool(
+        description = "Validate every note in the vault: frontmatter format, link syntax, content rules and note-type schemas from .turbovault/schemas.yaml",
+        usage = "Use to audit a vault against its note-type schemas (required/optional properties, types, enums, patterns) after adding or changing .turbovault/schemas.yaml, or before a cleanup. Each issue names its note. Pass max_issues on large vaults.",
+        performance = "Full vault scan, parses every note. Stops early once max_issues is reached.",
+        related = ["analyze_property_schema", "bulk_update_properties", "quick_health_check"],
+        examples = ["(no parameters)", "max_issues: 100"]
+    )]
+    async fn validate_vault(&self, max_issues: Option<usize>) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = ValidationTools::new(manager);
+        let report = match max_issues {
+            Some(max) => tools.validate_vault_quick(max).await,
+            None => tools.validate_vault().await,
+        }
+        .map_err(to_mcp_error)?;
+
+        let count = report.total_issues;
+        let passed = report.passed;
+        let result_data =
+            serde_json::to_value(&report).map_err(|e| McpError::internal(e.to_string()))?;
+
+        let response = StandardResponse::new(vault_name, "validate_vault", result_data)
+            .with_count(count)
+            .with_meta("passed", serde_json::json!(passed));
+
+        if passed {
+            response.to_json()
+        } else {
+            response
+                .with_next_steps(&["read_note", "bulk_update_properties"])
+                .to_json()
+        }
+    }
+
     // ==================== Export Operations ====================
 
     /// Export health report as JSON or CSV
@@ -1507,12 +1946,74 @@ impl ObsidianMcpServer {
         response.to_json()
     }
 
+    /// Export the link graph for Gephi, Graphviz, Mermaid or JSON Graph tools
+    #[tool(
+        description = "Export the link graph as graphml, gexf, dot, mermaid, json_graph (JSON Graph Format), json or csv (edge list). Nodes carry folder, tags, word count, PageRank, betweenness and closeness; edges carry their kind and link count. Optionally export only notes with a tag, notes in a folder, or the ego network around a note.",
+        usage = "Use for visualising the vault in Gephi/yEd (graphml, gexf), Graphviz (dot), docs and READMEs (mermaid), or other tooling (json_graph). Mermaid keeps the max_nodes (default 50) notes with the highest PageRank.",
+        performance = "Reads every note for tags and word counts; 1-3s on 10k+ notes",
+        related = ["get_ego_network", "get_communities", "get_centrality_ranking", "export_vault_stats"],
+        examples = [
+            "format: graphml",
+            "format: mermaid, folder: projects, max_nodes: 30",
+            "format: dot, center: index.md, hops: 2",
+            "format: gexf, tag: research"
+        ]
+    )]
+    async fn export_graph(
+        &self,
+        format: String,
+        tag: Option<String>,
+        folder: Option<String>,
+        center: Option<String>,
+        hops: Option<usize>,
+        max_nodes: Option<usize>,
+    ) -> McpResult<serde_json::Value> {
+        let selection = match (tag, folder, center) {
+            (None, None, None) => SubgraphSelection::All,
+            (Some(tag), None, None) => SubgraphSelection::Tag(tag),
+            (None, Some(folder), None) => SubgraphSelection::Folder(folder),
+            (None, None, Some(center)) => SubgraphSelection::Ego {
+                center: center.into(),
+                hops: hops.unwrap_or(2),
+            },
+            _ => {
+                return Err(McpError::invalid_request(
+                    "Use at most one of tag, folder or center".to_string(),
+                ));
+            }
+        };
+
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = ExportTools::new(manager);
+        let export = tools
+            .export_graph(&format, selection, Vec::new(), max_nodes)
+            .await
+            .map_err(to_mcp_error)?;
+
+        let mut response = StandardResponse::new(
+            vault_name,
+            "export_graph",
+            serde_json::to_value(&export).map_err(|e| McpError::internal(e.to_string()))?,
+        )
+        .with_count(export.node_count)
+        .with_meta("format", serde_json::json!(export.format));
+        if export.omitted_nodes > 0 {
+            response = response.with_warning(format!(
+                "Mermaid output shows {} of {} notes; raise max_nodes or narrow the selection",
+                export.node_count - export.omitted_nodes,
+                export.node_count
+            ));
+        }
+
+        response.to_json()
+    }
+
     // ==================== Metadata Operations ====================
 
     /// Query files by metadata pattern
     #[tool(
-        description = "Query notes by frontmatter metadata pattern (equality, comparison, existence checks)",
-        usage = "Use for tag-based organization, status tracking, or property-based filtering. Searches frontmatter YAML fields.",
+        description = "Query notes by frontmatter metadata pattern (equality, comparison, dates, list membership, regex, existence checks, AND/OR/NOT with parentheses)",
+        usage = "Use for tag-based organization, status tracking, or property-based filtering. Searches frontmatter YAML fields; nested keys use dot notation.",
         performance = "Fast on indexed fields (<100ms typical). Full vault scan for complex queries.",
         related = ["get_metadata_value", "advanced_search"],
         examples = [
@@ -1520,7 +2021,9 @@ impl ObsidianMcpServer {
             "priority > 3",
             "tags contains 'project'",
             "author.name = 'Alice'",
-            "created_at > '2024-01-01'"
+            "created_at > '2024-01-01'",
+            "status in (draft, review) AND NOT exists(archived)",
+            "(priority >= 3 OR tags contains urgent) AND title matches '^Meeting'"
         ]
     )]
     async fn query_metadata(&self, pattern: String) -> McpResult<serde_json::Value> {
@@ -1539,30 +2042,128 @@ impl ObsidianMcpServer {
         response.to_json()
     }
 
-    /// Get metadata value from a file
+    /// Bulk-update frontmatter of notes matching a metadata query
     #[tool(
-        description = "Extract specific metadata value from a note's frontmatter (supports dot notation for nested keys)",
-        usage = "Use to read properties without parsing full note content. Faster than read_note when you only need metadata.",
-        performance = "Very fast (<10ms typical), only parses frontmatter section.",
-        related = ["query_metadata", "read_note"],
+        description = "Apply frontmatter mutations (set, unset, add_to_list, remove_from_list, rename_key) to every note matching a query_metadata pattern as one atomic batch",
+        usage = "Use for re-tagging, archiving or renaming properties across many notes. Run with dry_run=true first to preview affected notes with before/after values. Note bodies are left untouched.",
+        performance = "Full vault scan plus one batch write of changed notes. Dry runs only read.",
+        related = ["query_metadata", "get_metadata_value", "batch_execute"],
         examples = [
-            "key: author",
-            "key: tags",
-            "key: author.name",
-            "key: metadata.priority",
-            "key: custom.nested.field"
+            r#"query: 'status: "draft"', mutations: [{"op":"set","key":"status","value":"review"}], dry_run: true"#,
+            r#"query: 'tags contains old', mutations: [{"op":"remove_from_list","key":"tags","value":"old"},{"op":"add_to_list","key":"tags","value":"new"}]"#,
+            r#"query: 'exists(Status)', mutations: [{"op":"rename_key","from":"Status","to":"status"}]"#
         ]
     )]
-    async fn get_metadata_value(&self, file: String, key: String) -> McpResult<serde_json::Value> {
+    async fn bulk_update_properties(
+        &self,
+        query: String,
+        mutations: Vec<serde_json::Value>,
+        dry_run: Option<bool>,
+    ) -> McpResult<serde_json::Value> {
         let (vault_name, manager) = self.get_vault_pair().await?;
+
+        let mut parsed = Vec::new();
+        for mutation in mutations {
+            match serde_json::from_value::<PropertyMutation>(mutation) {
+                Ok(m) => parsed.push(m),
+                Err(e) => {
+                    return Err(McpError::internal(format!(
+                        "Invalid property mutation: {}",
+                        e
+                    )));
+                }
+            }
+        }
+
+        let dry_run = dry_run.unwrap_or(false);
         let tools = MetadataTools::new(manager);
-        let value = tools
-            .get_metadata_value(&file, &key)
+        let report = tools
+            .bulk_update_properties(&query, &parsed, dry_run)
             .await
             .map_err(to_mcp_error)?;
 
-        let response = StandardResponse::new(
-            vault_name,
+        let count = report.updated;
+        let result_data =
+            serde_json::to_value(&report).map_err(|e| McpError::internal(e.to_string()))?;
+
+        let response = StandardResponse::new(vault_name, "bulk_update_properties", result_data)
+            .with_count(count)
+            .with_meta("dry_run", serde_json::json!(dry_run));
+
+        if dry_run {
+            response.with_next_step("bulk_update_properties").to_json()
+        } else {
+            response.with_next_step("query_metadata").to_json()
+        }
+    }
+
+    /// Infer frontmatter property schema and flag inconsistencies
+    #[tool(
+        description = "Profile every frontmatter property across the vault (observed types, value distributions, casing variants, usage counts), flag inconsistent properties and suggest an .obsidian/types.json schema",
+        usage = "Use before cleaning up metadata: find keys like `due` stored as both dates and free text, `tags` as string vs list, or `Status` vs `status`. Fix the flagged notes with bulk_update_properties. Set inconsistent_only=true to list only drifting properties.",
+        performance = "Full vault scan reading frontmatter only. Linear in note count.",
+        related = ["bulk_update_properties", "query_metadata", "get_metadata_value"],
+        examples = [
+            "(no parameters)",
+            "inconsistent_only: true"
+        ]
+    )]
+    async fn analyze_property_schema(
+        &self,
+        inconsistent_only: Option<bool>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = PropertySchemaTools::new(manager);
+        let mut report = tools.analyze_properties().await.map_err(to_mcp_error)?;
+
+        let suggested_schema = report.obsidian_types_json();
+        let inconsistent_only = inconsistent_only.unwrap_or(false);
+        if inconsistent_only {
+            report.properties.retain(|p| !p.is_consistent());
+        }
+
+        let count = report.properties.len();
+        let mut result_data =
+            serde_json::to_value(&report).map_err(|e| McpError::internal(e.to_string()))?;
+        result_data["suggested_schema"] = suggested_schema;
+
+        let response = StandardResponse::new(vault_name, "analyze_property_schema", result_data)
+            .with_count(count)
+            .with_meta("inconsistent_only", serde_json::json!(inconsistent_only));
+
+        if report.inconsistent_properties > 0 {
+            response
+                .with_next_steps(&["bulk_update_properties", "query_metadata"])
+                .to_json()
+        } else {
+            response.to_json()
+        }
+    }
+
+    /// Get metadata value from a file
+    #[tool(
+        description = "Extract specific metadata value from a note's frontmatter (supports dot notation for nested keys)",
+        usage = "Use to read properties without parsing full note content. Faster than read_note when you only need metadata.",
+        performance = "Very fast (<10ms typical), only parses frontmatter section.",
+        related = ["query_metadata", "read_note"],
+        examples = [
+            "key: author",
+            "key: tags",
+            "key: author.name",
+            "key: metadata.priority",
+            "key: custom.nested.field"
+        ]
+    )]
+    async fn get_metadata_value(&self, file: String, key: String) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = MetadataTools::new(manager);
+        let value = tools
+            .get_metadata_value(&file, &key)
+            .await
+            .map_err(to_mcp_error)?;
+
+        let response = StandardResponse::new(
+            vault_name,
             "get_metadata_value",
             serde_json::json!({"file": file, "key": key, "value": value}),
         )
@@ -1571,18 +2172,668 @@ impl ObsidianMcpServer {
         response.to_json()
     }
 
+    /// List callouts across the vault
+    #[tool(
+        description = "List callouts (> [!type]) across the vault with their note, line, title, content, nesting depth, fold state and open task count. Optionally filter by type (aliases like tldr, hint, faq and custom types like decision are accepted) and folder.",
+        usage = "Use to collect all [!todo] or [!question] callouts, audit custom callout types, or find decisions recorded as [!decision] callouts. by_type summarises every callout in the scanned notes so unknown or misspelled types stand out.",
+        performance = "Full vault scan (<500ms for 1k notes).",
+        related = ["query_metadata", "read_note", "advanced_search"],
+        examples = [
+            "callout_type: todo",
+            "callout_type: tldr",
+            "callout_type: decision, folder: projects",
+            "(no parameters)"
+        ]
+    )]
+    async fn list_callouts(
+        &self,
+        callout_type: Option<String>,
+        folder: Option<String>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = MetadataTools::new(manager);
+        let listing = tools
+            .list_callouts(callout_type.as_deref(), folder.as_deref())
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = listing.total;
+        let result_data =
+            serde_json::to_value(&listing).map_err(|e| McpError::internal(e.to_string()))?;
+
+        StandardResponse::new(vault_name, "list_callouts", result_data)
+            .with_count(count)
+            .with_next_step("read_note")
+            .to_json()
+    }
+
+    // ==================== Table Operations ====================
+
+    /// List the tables in a note
+    #[tool(
+        description = "List the Markdown tables in a note with their index, nearest preceding heading, line, headers and row count",
+        usage = "Use to find which table to read or edit. Tables inside callouts, lists and <details> are included. Address a table in read_table/update_table by index, or by heading plus an index among the tables under that heading.",
+        performance = "Very fast (<10ms typical), parses one note.",
+        related = ["read_table", "update_table", "read_note"],
+        examples = ["path: projects/roadmap.md"]
+    )]
+    async fn list_tables(&self, path: String) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = TableTools::new(manager);
+        let tables = tools.list_tables(&path).await.map_err(to_mcp_error)?;
+
+        let count = tables.len();
+        StandardResponse::new(
+            vault_name,
+            "list_tables",
+            serde_json::json!({"path": path, "tables": tables}),
+        )
+        .with_count(count)
+        .with_next_steps(&["read_table", "update_table"])
+        .to_json()
+    }
+
+    /// Read a table as JSON records
+    #[tool(
+        description = "Read one Markdown table of a note as JSON records keyed by header, with column alignments. Cells keep their inline Markdown; escaped pipes are unescaped.",
+        usage = "Select the table by index (default 0) and/or heading; with a heading, index counts only the tables under it. Repeated or empty headers are keyed as 'Name (2)' or 'Column 3'.",
+        performance = "Very fast (<10ms typical), parses one note.",
+        related = ["list_tables", "update_table"],
+        examples = [
+            "path: projects/roadmap.md",
+            "path: projects/roadmap.md, heading: Milestones",
+            "path: projects/roadmap.md, index: 2"
+        ]
+    )]
+    async fn read_table(
+        &self,
+        path: String,
+        index: Option<usize>,
+        heading: Option<String>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = TableTools::new(manager);
+        let table = tools
+            .read_table(&path, index, heading.as_deref())
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = table.records.len();
+        let mut result_data =
+            serde_json::to_value(&table).map_err(|e| McpError::internal(e.to_string()))?;
+        result_data["path"] = serde_json::json!(path);
+
+        StandardResponse::new(vault_name, "read_table", result_data)
+            .with_count(count)
+            .with_next_step("update_table")
+            .to_json()
+    }
+
+    /// Edit rows and columns of a table
+    #[tool(
+        description = "Edit one Markdown table of a note by header name: update_rows, insert_row, delete_rows, insert_column, delete_column, rename_column. The table is re-rendered with its alignment row kept and pipes in values escaped; the rest of the note is unchanged.",
+        usage = "Select the table like read_table. Rows are chosen by index or {column, equals}. Operations apply in order and nothing is written if one fails. Run with dry_run=true to preview the before/after records and rendered table.",
+        performance = "Parses and rewrites one note.",
+        related = ["read_table", "list_tables", "edit_note"],
+        examples = [
+            r#"path: tasks.md, operations: [{"op":"update_rows","rows":{"column":"Task","equals":"Ship"},"set":{"Status":"done"}}]"#,
+            r#"path: tasks.md, heading: Backlog, operations: [{"op":"insert_row","values":{"Task":"Review","Owner":"sam"}}]"#,
+            r#"path: tasks.md, operations: [{"op":"insert_column","name":"Due","alignment":"right"},{"op":"delete_rows","rows":0}], dry_run: true"#
+        ]
+    )]
+    async fn update_table(
+        &self,
+        path: String,
+        operations: Vec<serde_json::Value>,
+        index: Option<usize>,
+        heading: Option<String>,
+        dry_run: Option<bool>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+
+        let mut parsed = Vec::new();
+        for operation in operations {
+            match serde_json::from_value::<TableOperation>(operation) {
+                Ok(op) => parsed.push(op),
+                Err(e) => {
+                    return Err(McpError::internal(format!(
+                        "Invalid table operation: {}",
+                        e
+                    )));
+                }
+            }
+        }
+
+        let dry_run = dry_run.unwrap_or(false);
+        let tools = TableTools::new(manager);
+        let report = tools
+            .update_table(&path, index, heading.as_deref(), &parsed, dry_run)
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = report.rows_affected;
+        let result_data =
+            serde_json::to_value(&report).map_err(|e| McpError::internal(e.to_string()))?;
+
+        let response = StandardResponse::new(vault_name, "update_table", result_data)
+            .with_count(count)
+            .with_meta("dry_run", serde_json::json!(dry_run));
+
+        if dry_run {
+            response.with_next_step("update_table").to_json()
+        } else {
+            response.with_next_step("read_table").to_json()
+        }
+    }
+
+    // ==================== Canvas Operations ====================
+
+    /// Read a JSON Canvas file
+    #[tool(
+        description = "Read a .canvas file (JSON Canvas): its text, file, link and group nodes with positions, the edges between them, and any spec violations",
+        usage = "Use to see what a canvas shows and how its cards connect. File nodes name vault files; get_forward_links on the canvas lists the notes it embeds or links from text cards.",
+        performance = "Very fast (<10ms typical), parses one file.",
+        related = ["add_canvas_node", "connect_canvas_nodes", "get_forward_links"],
+        examples = ["path: boards/roadmap.canvas"]
+    )]
+    async fn read_canvas(&self, path: String) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = CanvasTools::new(manager);
+        let info = tools.read_canvas(&path).await.map_err(to_mcp_error)?;
+
+        let count = info.canvas.nodes.len();
+        let result_data =
+            serde_json::to_value(&info).map_err(|e| McpError::internal(e.to_string()))?;
+
+        StandardResponse::new(vault_name, "read_canvas", result_data)
+            .with_count(count)
+            .with_next_steps(&["add_canvas_node", "connect_canvas_nodes"])
+            .to_json()
+    }
+
+    /// Create a JSON Canvas file
+    #[tool(
+        description = "Create a .canvas file from JSON Canvas nodes and edges (both optional), validated against the spec before writing",
+        usage = "Nodes need id, type (text, file, link, group), x, y, width and height plus text/file/url; edges need id, fromNode and toNode. File nodes may name a note ('Plan') and are stored with its vault path. Fails if the file exists.",
+        performance = "Writes one file.",
+        related = ["add_canvas_node", "connect_canvas_nodes", "read_canvas"],
+        examples = [
+            "path: boards/empty.canvas",
+            r#"path: boards/plan.canvas, nodes: [{"id":"n1","type":"file","file":"Plan","x":0,"y":0,"width":400,"height":400}]"#
+        ]
+    )]
+    async fn create_canvas(
+        &self,
+        path: String,
+        nodes: Option<Vec<serde_json::Value>>,
+        edges: Option<Vec<serde_json::Value>>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+
+        let canvas = serde_json::from_value(serde_json::json!({
+            "nodes": nodes.unwrap_or_default(),
+            "edges": edges.unwrap_or_default(),
+        }))
+        .map_err(|e| McpError::internal(format!("Invalid canvas: {}", e)))?;
+
+        let tools = CanvasTools::new(manager);
+        let canvas = tools
+            .create_canvas(&path, canvas)
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = canvas.nodes.len();
+        StandardResponse::new(
+            vault_name,
+            "create_canvas",
+            serde_json::json!({"path": path, "nodes": canvas.nodes, "edges": canvas.edges}),
+        )
+        .with_count(count)
+        .with_next_step("add_canvas_node")
+        .to_json()
+    }
+
+    /// Add a node to a canvas
+    #[tool(
+        description = "Add a node to a .canvas file: a text card, a file node showing a vault note or attachment, a link node for a URL, or a group. Returns the node with its generated id.",
+        usage = "node has type (text, file, link, group) and content: the card text, the file (vault path or note name), the URL or the group label. Optional: subpath (#Heading for file nodes), x, y, width, height, color. Without x/y the node is placed to the right of the existing nodes; width/height default to Obsidian's sizes.",
+        performance = "Parses and rewrites one file.",
+        related = ["connect_canvas_nodes", "read_canvas", "create_canvas"],
+        examples = [
+            r#"path: boards/roadmap.canvas, node: {"type":"file","content":"Plan"}"#,
+            r#"path: boards/roadmap.canvas, node: {"type":"text","content":"Open risks","x":0,"y":500,"color":"1"}"#,
+            r##"path: boards/roadmap.canvas, node: {"type":"file","content":"notes/Spec.md","subpath":"#Scope"}"##
+        ]
+    )]
+    async fn add_canvas_node(
+        &self,
+        path: String,
+        node: serde_json::Value,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+
+        let new = serde_json::from_value::<NewCanvasNode>(node)
+            .map_err(|e| McpError::internal(format!("Invalid canvas node: {}", e)))?;
+
+        let tools = CanvasTools::new(manager);
+        let node = tools.add_node(&path, new).await.map_err(to_mcp_error)?;
+
+        StandardResponse::new(
+            vault_name,
+            "add_canvas_node",
+            serde_json::json!({"path": path, "node": node}),
+        )
+        .with_next_step("connect_canvas_nodes")
+        .to_json()
+    }
+
+    /// Connect two canvas nodes
+    #[tool(
+        description = "Add an edge between two nodes of a .canvas file, optionally labelled and with the sides it attaches to",
+        usage = "from/to are node ids (see read_canvas) or the file shown by a file node ('Plan' or 'notes/Plan.md'), as long as exactly one node shows it. Sides are top, right, bottom or left.",
+        performance = "Parses and rewrites one file.",
+        related = ["add_canvas_node", "read_canvas"],
+        examples = [
+            "path: boards/roadmap.canvas, from: Plan, to: Spec, label: details",
+            "path: boards/roadmap.canvas, from: 6f1c0a2b9d3e4f57, to: Spec, from_side: right, to_side: left"
+        ]
+    )]
+    async fn connect_canvas_nodes(
+        &self,
+        path: String,
+        from: String,
+        to: String,
+        label: Option<String>,
+        from_side: Option<String>,
+        to_side: Option<String>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+
+        let side = |side: Option<String>| {
+            side.map(|s| {
+                serde_json::from_value(serde_json::json!(s.to_lowercase())).map_err(|_| {
+                    McpError::invalid_request(format!(
+                        "Invalid side '{}': expected top, right, bottom or left",
+                        s
+                    ))
+                })
+            })
+            .transpose()
+        };
+        let new = NewCanvasEdge {
+            from,
+            to,
+            label,
+            from_side: side(from_side)?,
+            to_side: side(to_side)?,
+            color: None,
+        };
+
+        let tools = CanvasTools::new(manager);
+        let edge = tools
+            .connect_nodes(&path, new)
+            .await
+            .map_err(to_mcp_error)?;
+
+        StandardResponse::new(
+            vault_name,
+            "connect_canvas_nodes",
+            serde_json::json!({"path": path, "edge": edge}),
+        )
+        .with_next_step("read_canvas")
+        .to_json()
+    }
+
+    // ==================== Bases Operations ====================
+
+    /// List Obsidian Bases files
+    #[tool(
+        description = "List every .base file (Obsidian Bases) with its views: name, type, columns, limit, and any filters or columns that cannot be evaluated",
+        usage = "Use to discover the saved views of a vault before evaluating one with query_base. Views listing unsupported filters (formulas, arithmetic) cannot be evaluated.",
+        performance = "Fast, reads each .base file once.",
+        related = ["query_base", "query_metadata"],
+        examples = []
+    )]
+    async fn list_bases(&self) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = BaseTools::new(manager);
+        let bases = tools.list_bases().await.map_err(to_mcp_error)?;
+
+        let count = bases.len();
+        let result_data =
+            serde_json::to_value(&bases).map_err(|e| McpError::internal(e.to_string()))?;
+
+        StandardResponse::new(vault_name, "list_bases", result_data)
+            .with_count(count)
+            .with_next_step("query_base")
+            .to_json()
+    }
+
+    /// Evaluate a view of an Obsidian Bases file
+    #[tool(
+        description = "Evaluate a view of a .base file against the vault's notes: apply the base and view filters, sort and limit, and return each matching note with the view's columns",
+        usage = "Omit view for the first one. Filters support file.hasTag, file.inFolder, file.hasLink, file.hasProperty, comparisons against literals and today()/now()/date(), contains/startsWith/endsWith/isEmpty, and && || ! ; views filtering on formulas are refused. Formula columns come back null.",
+        performance = "Parses every note once; O(n) in vault size.",
+        related = ["list_bases", "query_metadata"],
+        examples = ["path: Books.base", "path: Books.base, view: Reading"]
+    )]
+    async fn query_base(&self, path: String, view: Option<String>) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = BaseTools::new(manager);
+        let result = tools
+            .query_base(&path, view.as_deref())
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = result.rows.len();
+        let result_data =
+            serde_json::to_value(&result).map_err(|e| McpError::internal(e.to_string()))?;
+
+        StandardResponse::new(vault_name, "query_base", result_data)
+            .with_count(count)
+            .with_next_step("read_note")
+            .to_json()
+    }
+
+    // ==================== Tag Operations ====================
+
+    /// List the vault's tag hierarchy
+    #[tool(
+        description = "List every tag (frontmatter and inline) as a #parent/child hierarchy with occurrence and note counts",
+        usage = "Use to survey tagging before renaming, merging or deleting tags, or to find rarely used and near-duplicate tags. total_count on a node includes all descendants.",
+        performance = "Full vault scan (<500ms for 1k notes).",
+        related = ["rename_tag", "merge_tags", "delete_tag", "query_metadata"],
+        examples = ["(no parameters)"]
+    )]
+    async fn list_tags(&self) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = TagTools::new(manager);
+        let hierarchy = tools.list_tags().await.map_err(to_mcp_error)?;
+
+        let count = hierarchy.total_tags;
+        let result_data =
+            serde_json::to_value(&hierarchy).map_err(|e| McpError::internal(e.to_string()))?;
+
+        StandardResponse::new(vault_name, "list_tags", result_data)
+            .with_count(count)
+            .with_next_steps(&["rename_tag", "merge_tags"])
+            .to_json()
+    }
+
+    /// Rename a tag across the vault
+    #[tool(
+        description = "Rename a tag in frontmatter and note bodies, optionally including descendants (#old/x -> #new/x), as one atomic batch",
+        usage = "Run with dry_run=true first to preview every occurrence per note. Inline tags are rewritten by position; code blocks, inline code and [[#heading]] links are left untouched.",
+        performance = "Full vault scan plus one batch write of changed notes. Dry runs only read.",
+        related = ["list_tags", "merge_tags", "delete_tag"],
+        examples = [
+            "from: todo, to: task, dry_run: true",
+            "from: project, to: projects, include_descendants: true"
+        ]
+    )]
+    async fn rename_tag(
+        &self,
+        from: String,
+        to: String,
+        include_descendants: Option<bool>,
+        dry_run: Option<bool>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = TagTools::new(manager);
+        let dry_run = dry_run.unwrap_or(false);
+        let report = tools
+            .rename_tag(&from, &to, include_descendants.unwrap_or(false), dry_run)
+            .await
+            .map_err(to_mcp_error)?;
+
+        Self::tag_change_response(vault_name, "rename_tag", report)
+    }
+
+    /// Merge several tags into one
+    #[tool(
+        description = "Merge several tags into one target tag in frontmatter and note bodies as one atomic batch (duplicates collapse)",
+        usage = "Use to consolidate spelling variants like #todo, #to-do and #ToDo. Descendants of the source tags are not moved. Preview with dry_run=true.",
+        performance = "Full vault scan plus one batch write of changed notes. Dry runs only read.",
+        related = ["list_tags", "rename_tag", "delete_tag"],
+        examples = [r#"sources: ["to-do", "todos"], target: todo, dry_run: true"#]
+    )]
+    async fn merge_tags(
+        &self,
+        sources: Vec<String>,
+        target: String,
+        dry_run: Option<bool>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = TagTools::new(manager);
+        let report = tools
+            .merge_tags(&sources, &target, dry_run.unwrap_or(false))
+            .await
+            .map_err(to_mcp_error)?;
+
+        Self::tag_change_response(vault_name, "merge_tags", report)
+    }
+
+    /// Delete a tag from the vault
+    #[tool(
+        description = "Remove a tag from frontmatter and note bodies, optionally including descendants, as one atomic batch",
+        usage = "Preview with dry_run=true. Empty tag lists are removed from frontmatter; the surrounding note text is kept.",
+        performance = "Full vault scan plus one batch write of changed notes. Dry runs only read.",
+        related = ["list_tags", "rename_tag", "merge_tags"],
+        examples = ["tag: obsolete, dry_run: true", "tag: archive, include_descendants: true"]
+    )]
+    async fn delete_tag(
+        &self,
+        tag: String,
+        include_descendants: Option<bool>,
+        dry_run: Option<bool>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = TagTools::new(manager);
+        let report = tools
+            .delete_tag(
+                &tag,
+                include_descendants.unwrap_or(false),
+                dry_run.unwrap_or(false),
+            )
+            .await
+            .map_err(to_mcp_error)?;
+
+        Self::tag_change_response(vault_name, "delete_tag", report)
+    }
+
+    /// Tags that co-occur with a tag
+    #[tool(
+        description = "List tags that appear on the same notes as a tag, scored by shared notes, Jaccard similarity and (normalized) pointwise mutual information. Also returns the tag's parent and children in the #parent/child hierarchy.",
+        usage = "Use to discover how tags relate, choose tags for a new note, or spot tags that are always used together. npmi close to 1 means the tags almost only appear together; ancestors and descendants are left out.",
+        performance = "Full vault scan (<500ms for 1k notes).",
+        related = ["list_tags", "find_duplicate_tags", "find_bridging_notes"],
+        examples = ["tag: machine-learning", "tag: project, min_shared: 3, limit: 5"]
+    )]
+    async fn get_related_tags(
+        &self,
+        tag: String,
+        min_shared: Option<usize>,
+        limit: Option<usize>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = TagTools::new(manager);
+        let report = tools
+            .related_tags(&tag, min_shared.unwrap_or(1), limit.unwrap_or(10))
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = report.related.len();
+        StandardResponse::new(
+            vault_name,
+            "get_related_tags",
+            serde_json::to_value(&report).map_err(|e| McpError::internal(e.to_string()))?,
+        )
+        .with_count(count)
+        .to_json()
+    }
+
+    /// Tags that probably mean the same thing
+    #[tool(
+        description = "Find near-duplicate tags (#ml vs #machine-learning, #recipe vs #recipes) by name similarity (separators, plurals, acronyms, edit distance) and by how similar the tags they co-occur with are. Each pair suggests the more used tag to keep.",
+        usage = "Review candidates, then merge them with merge_tags. score = 0.6 × string_similarity + 0.4 × context_similarity; raise min_score (default 0.5) for fewer, surer pairs.",
+        performance = "Full vault scan plus a pairwise tag comparison; <1s for a few thousand tags.",
+        related = ["merge_tags", "list_tags", "get_related_tags"],
+        examples = ["(no parameters)", "min_score: 0.7"]
+    )]
+    async fn find_duplicate_tags(&self, min_score: Option<f64>) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = TagTools::new(manager);
+        let duplicates = tools
+            .find_duplicate_tags(min_score)
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = duplicates.len();
+        let mut response = StandardResponse::new(
+            vault_name,
+            "find_duplicate_tags",
+            serde_json::json!({ "duplicates": duplicates }),
+        )
+        .with_count(count);
+        if count > 0 {
+            response = response.with_next_step("merge_tags");
+        }
+        response.to_json()
+    }
+
+    /// Notes spanning several tag clusters
+    #[tool(
+        description = "Cluster tags by co-occurrence (Louvain) and list notes whose tags span several clusters. These notes connect otherwise separate topics.",
+        usage = "Use to find cross-topic notes worth linking from both sides, or to see which tag groups exist. min_communities (default 2) sets how many clusters a note must touch; resolution (default 1.0) > 1 gives smaller clusters.",
+        performance = "Full vault scan plus Louvain on the tag graph; <1s typical.",
+        related = ["get_related_tags", "get_communities", "list_tags"],
+        examples = ["(no parameters)", "min_communities: 3", "resolution: 1.5"]
+    )]
+    async fn find_bridging_notes(
+        &self,
+        min_communities: Option<usize>,
+        resolution: Option<f64>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = TagTools::new(manager);
+        let report = tools
+            .find_bridging_notes(min_communities.unwrap_or(2), resolution)
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = report.notes.len();
+        StandardResponse::new(
+            vault_name,
+            "find_bridging_notes",
+            serde_json::to_value(&report).map_err(|e| McpError::internal(e.to_string()))?,
+        )
+        .with_count(count)
+        .with_meta(
+            "tag_communities",
+            serde_json::json!(report.communities.len()),
+        )
+        .to_json()
+    }
+
+    // ==================== Unlinked Mentions ====================
+
+    /// Find plain-text mentions of notes
+    #[tool(
+        description = "Find unlinked mentions: whole-word, case-insensitive occurrences of note titles and frontmatter aliases in other notes, with line, column and surrounding text. Frontmatter, code, HTML, tags and existing links are skipped.",
+        usage = "Omit path to scan for every note (most mentioned first), or pass a note to see only its mentions. Names shorter than min_length (default 3) are ignored. Pass mention ids to link_unlinked_mentions to convert them.",
+        performance = "Full vault scan with one combined matcher; <1s for 1k notes.",
+        related = ["link_unlinked_mentions", "suggest_links", "get_backlinks"],
+        examples = ["(no parameters)", "path: projects/Alpha.md", "path: Rust, min_length: 4"]
+    )]
+    async fn find_unlinked_mentions(
+        &self,
+        path: Option<String>,
+        min_length: Option<usize>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = MentionTools::new(manager);
+        let report = tools
+            .find_unlinked_mentions(
+                path.as_deref(),
+                min_length.unwrap_or(DEFAULT_MIN_NAME_LENGTH),
+            )
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = report.total_mentions;
+        let mut response = StandardResponse::new(
+            vault_name,
+            "find_unlinked_mentions",
+            serde_json::to_value(&report).map_err(|e| McpError::internal(e.to_string()))?,
+        )
+        .with_count(count);
+        if count > 0 {
+            response = response.with_next_step("link_unlinked_mentions");
+        }
+        response.to_json()
+    }
+
+    /// Convert unlinked mentions into wikilinks
+    #[tool(
+        description = "Turn unlinked mentions of a note into [[Note|original text]] wikilinks, as one atomic batch. Links every mention, or only the given mention ids from find_unlinked_mentions.",
+        usage = "Run with dry_run=true first to preview each replacement. Ids are located again in the current content; ids that no longer match are listed in not_found.",
+        performance = "Full vault scan plus one batch write of changed notes. Dry runs only read.",
+        related = ["find_unlinked_mentions", "get_backlinks"],
+        examples = [
+            "path: Rust, dry_run: true",
+            "path: projects/Alpha.md, mention_ids: [\"daily/2024-01-15.md:3:12\"]"
+        ]
+    )]
+    async fn link_unlinked_mentions(
+        &self,
+        path: String,
+        mention_ids: Option<Vec<String>>,
+        min_length: Option<usize>,
+        dry_run: Option<bool>,
+    ) -> McpResult<serde_json::Value> {
+        let (vault_name, manager) = self.get_vault_pair().await?;
+        let tools = MentionTools::new(manager);
+        let ids = mention_ids.unwrap_or_default();
+        let dry_run = dry_run.unwrap_or(false);
+        let report = tools
+            .link_mentions(
+                &path,
+                &ids,
+                min_length.unwrap_or(DEFAULT_MIN_NAME_LENGTH),
+                dry_run,
+            )
+            .await
+            .map_err(to_mcp_error)?;
+
+        let count = report.mentions_linked;
+        let mut response = StandardResponse::new(
+            vault_name,
+            "link_unlinked_mentions",
+            serde_json::to_value(&report).map_err(|e| McpError::internal(e.to_string()))?,
+        )
+        .with_count(count)
+        .with_meta("dry_run", serde_json::json!(dry_run));
+        if !report.not_found.is_empty() {
+            response = response.with_warning(format!(
+                "{} mention id(s) no longer match; run find_unlinked_mentions again",
+                report.not_found.len()
+            ));
+        }
+        response.to_json()
+    }
+
     // ==================== Relationship Operations ====================
 
     /// Suggest files to link
     #[tool(
-        description = "AI-powered link suggestions for a note (returns top N candidates with reasoning)",
-        usage = "Use to improve vault connectivity and discover non-obvious relationships. Analyzes content similarity, link patterns, and graph structure. ML-based, slower than simple queries.",
-        performance = "200ms-2s depending on vault size. Uses TF-IDF + graph features. Consider limit parameter for faster results.",
-        related = ["recommend_related", "get_dead_end_notes", "get_related_notes"],
+        description = "Suggest notes to link from a note, scored by content similarity (TF-IDF), tag overlap, shared outgoing links and shared backlinks, with per-signal explanations",
+        usage = "Use to improve vault connectivity, including for new notes without links yet. Each suggestion has a combined strength, the individual signals and human-readable reasons. Respects link_similarity_threshold and max_link_suggestions from the server config.",
+        performance = "O(n) over the vault: parses every note to build TF-IDF vectors. 50-500ms for typical vaults.",
+        related = ["recommend_related", "get_dead_end_notes", "get_related_notes", "find_unlinked_mentions"],
         examples = [
             "file: daily/2024-01-15.md, limit: 5",
             "file: projects/research.md, limit: 10",
-            "file: index.md (default limit: 5)"
+            "file: index.md (default limit: max_link_suggestions)"
         ]
     )]
     async fn suggest_links(
@@ -1592,22 +2843,28 @@ impl ObsidianMcpServer {
     ) -> McpResult<serde_json::Value> {
         let (vault_name, manager) = self.get_vault_pair().await?;
         let tools = RelationshipTools::new(manager);
-        let limit = limit.unwrap_or(5) as usize;
-        let suggestions = tools
-            .suggest_links(&file, limit)
+        let result_data = tools
+            .suggest_links(&file, limit.map(|l| l.max(0) as usize))
             .await
             .map_err(to_mcp_error)?;
 
-        let result_data =
-            serde_json::to_value(&suggestions).map_err(|e| McpError::internal(e.to_string()))?;
-        let count = extract_count(&result_data);
-
-        let response = StandardResponse::new(vault_name, "suggest_links", result_data)
+        let count = result_data["suggestions"]
+            .as_array()
+            .map(|s| s.len())
+            .unwrap_or(0);
+        let mut response = StandardResponse::new(vault_name, "suggest_links", result_data.clone())
             .with_count(count)
-            .with_meta("limit", serde_json::json!(limit));
+            .with_meta("limit", result_data["limit"].clone())
+            .with_meta("threshold", result_data["threshold"].clone());
+        if count == 0 {
+            response = response
+                .with_next_step("find_unlinked_mentions")
+                .with_next_step("recommend_related");
+        } else {
+            response = response.with_next_step("get_link_strength");
+        }
 
-        serde_json::to_value(response)
-            .map_err(|e| McpError::internal(format!("Failed to serialize suggestions: {}", e)))
+        response.to_json()
     }
 
     /// Get link strength between two files
@@ -1646,34 +2903,61 @@ impl ObsidianMcpServer {
 
     /// Get centrality ranking
     #[tool(
-        description = "Rank all notes by graph centrality metrics (betweenness, closeness, eigenvector)",
-        usage = "Use for identifying key notes beyond simple link counts. Betweenness identifies bridge notes, closeness finds accessible notes, eigenvector reveals influence. More sophisticated than get_hub_notes.",
-        performance = "Computationally expensive on large vaults. O(V³) for betweenness. May take several seconds for >1000 notes.",
+        description = "Rank notes by graph centrality: PageRank (influence), betweenness (bridge notes) and harmonic closeness (reachability)",
+        usage = "Use for identifying key notes beyond simple link counts. PageRank finds notes that important notes link to, betweenness finds bridges between topic clusters, closeness finds notes reachable from most of the vault. The first metric listed orders the ranking. More sophisticated than get_hub_notes.",
+        performance = "First call is O(V·(V+E)); scores are cached until the vault's links change. Betweenness is estimated from 256 sampled notes on vaults over 2000 notes.",
         related = ["get_hub_notes", "explain_vault", "get_link_strength"],
         examples = [
-            "Returns all notes ranked by betweenness (bridge importance)",
-            "Returns all notes ranked by closeness (accessibility)",
-            "Returns all notes ranked by eigenvector (influence)"
+            "(no args) - all metrics, ranked by PageRank",
+            "metrics: [\"betweenness\"], top_n: 10 - top 10 bridge notes",
+            "metrics: [\"closeness\", \"pagerank\"], top_n: 20"
         ]
     )]
-    async fn get_centrality_ranking(&self) -> McpResult<serde_json::Value> {
+    async fn get_centrality_ranking(
+        &self,
+        metrics: Option<Vec<String>>,
+        top_n: Option<usize>,
+    ) -> McpResult<serde_json::Value> {
         let (vault_name, manager) = self.get_vault_pair().await?;
+        let metrics = metrics
+            .unwrap_or_default()
+            .iter()
+            .map(|name| {
+                CentralityMetric::from_name(name).ok_or_else(|| {
+                    let valid: Vec<_> = CentralityMetric::ALL.iter().map(|m| m.as_str()).collect();
+                    McpError::invalid_request(format!(
+                        "Unknown centrality metric '{}'. Use one of: {}",
+                        name,
+                        valid.join(", ")
+                    ))
+                })
+            })
+            .collect::<McpResult<Vec<_>>>()?;
+
         let tools = RelationshipTools::new(manager);
-        let ranking = tools.get_centrality_ranking().await.map_err(to_mcp_error)?;
+        let ranking = tools
+            .get_centrality_ranking(&metrics, top_n)
+            .await
+            .map_err(to_mcp_error)?;
 
-        let result_data =
-            serde_json::to_value(&ranking).map_err(|e| McpError::internal(e.to_string()))?;
-        let count = extract_count(&result_data);
+        let count = ranking["rankings"].as_array().map_or(0, Vec::len);
+        let metrics = ranking["metrics"].clone();
+        let sort_by = ranking["sort_by"].clone();
+        let sampled = (ranking["betweenness_sampled"].as_bool() == Some(true))
+            .then(|| ranking["betweenness_sources"].clone());
 
-        let response = StandardResponse::new(vault_name, "get_centrality_ranking", result_data)
+        let mut response = StandardResponse::new(vault_name, "get_centrality_ranking", ranking)
             .with_count(count)
-            .with_meta(
-                "metrics",
-                serde_json::json!(["betweenness", "closeness", "eigenvector"]),
-            );
+            .with_meta("metrics", metrics)
+            .with_meta("sort_by", sort_by);
+        if let Some(sources) = sampled {
+            response = response.with_warning(format!(
+                "Betweenness estimated from {} sampled notes",
+                sources
+            ));
+        }
 
-        serde_json::to_value(response)
-            .map_err(|e| McpError::internal(format!("Failed to serialize ranking: {}", e)))
+        response.to_json()
     }
 
     // ==================== Resources (OFM Knowledge Injection) ====================
@@ -141,4 +141,51 @@ mod tests {
         assert!(report.contains("\"vault_name\""));
         assert!(report.contains("\"recommendations\""));
     }
+
+    #[tokio::test]
+    async fn test_export_graph() {
+        use turbovault_tools::SubgraphSelection;
+
+        let (_temp, manager) = create_test_vault().await;
+        let tools = ExportTools::new(Arc::new(manager));
+
+        let graphml = tools
+            .export_graph("graphml", SubgraphSelection::All, vec![], None)
+            .await
+            .unwrap();
+        assert_eq!(graphml.format, "graphml");
+        assert_eq!(graphml.node_count, 3);
+        assert_eq!(graphml.edge_count, 5);
+        assert!(graphml.content.contains("<node id=\"note1.md\">"));
+        assert!(graphml.content.contains("<data key=\"word_count\">"));
+
+        let ego = tools
+            .export_graph(
+                "dot",
+                SubgraphSelection::Ego {
+                    center: PathBuf::from("note1.md"),
+                    hops: 1,
+                },
+                vec![],
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(ego.node_count, 3);
+        assert!(ego.content.contains("\"note2.md\" -> \"index.md\""));
+
+        let mermaid = tools
+            .export_graph("mermaid", SubgraphSelection::All, vec![], Some(2))
+            .await
+            .unwrap();
+        assert_eq!(mermaid.omitted_nodes, 1);
+        assert!(mermaid.content.contains("%% Showing 2 of 3 notes"));
+
+        assert!(
+            tools
+                .export_graph("svg", SubgraphSelection::All, vec![], None)
+                .await
+                .is_err()
+        );
+    }
 }